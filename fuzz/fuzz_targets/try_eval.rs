@@ -0,0 +1,11 @@
+#![no_main]
+
+// 运行：cargo +nightly fuzz run try_eval -- -timeout=5
+// try_eval 承诺对任意输入都不会 panic；死循环由 fuzzer 的超时发现。
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(source) = std::str::from_utf8(data) {
+        let _ = rox::try_eval(source);
+    }
+});
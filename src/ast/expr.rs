@@ -4,22 +4,49 @@ use crate::{
     ast::{helper::generate_token, stmt::Stmt},
     tokenizer::{Token, TokenType},
 };
+use serde::{Deserialize, Deserializer, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 #[derive(Debug)]
 pub struct Ast {
     pub body: Vec<Stmt>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// 表达式节点 ID，Resolver 的侧表 (`locals`) 以它为键
+///
+/// ID 在整个进程内唯一：模块、REPL 的每一行、`eval` 的代码共用同一个解释器侧表，
+/// 如果每次解析都从 0 开始编号，不同代码片段的节点会互相覆盖作用域距离。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
 pub struct ExprId(pub usize);
 
+static NEXT_EXPR_ID: AtomicUsize = AtomicUsize::new(0);
+
+impl ExprId {
+    /// 分配一个新的唯一 ID
+    pub fn fresh() -> Self {
+        ExprId(NEXT_EXPR_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// 反序列化（编译缓存 / bundle）时重新分配 ID，避免与本进程中已解析的节点冲突
+impl<'de> Deserialize<'de> for ExprId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        usize::deserialize(deserializer)?;
+        Ok(ExprId::fresh())
+    }
+}
+
 /// Expr：表达式节点，程序中所有可能的表达式类型。
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Expr {
     // literals
     Number {
         value: String,
     },
+    /// 大整数字面值，value 为十进制数字
+    BigInt {
+        value: String,
+    },
     String {
         value: String,
     },
@@ -52,6 +79,13 @@ pub enum Expr {
         expr: Box<Expr>,
     },
 
+    // destructuring assignment `(a, b) = (b, a)`
+    // pattern 是只包含 Expr::Variable 的 Expr::Tuple 或 Expr::List
+    AssignDestructure {
+        pattern: Box<Expr>,
+        value: Box<Expr>,
+    },
+
     // compound assignment
     AssignOp {
         id: ExprId,
@@ -86,11 +120,15 @@ pub enum Expr {
         id: ExprId, // 函数名本质上也是变量引用
         callee: Box<Expr>,
         args: Vec<Expr>,
+        // 关键字参数 `f(x = 1)`：调用时按形参名匹配，总是位于位置参数之后
+        named: Vec<(Token, Expr)>,
     },
 
     Get {
         object: Box<Expr>,
         name: Token,
+        // 可选链 `obj?.name`：对象为 nil 时整条调用链返回 nil
+        optional: bool,
     },
 
     Set {
@@ -112,7 +150,11 @@ pub enum Expr {
 
     Lambda {
         id: ExprId, // 供 Resolver 解析闭包
+        // 按值捕获列表 `fun [x, y]() {...}`，元素均为 Expr::Variable
+        captures: Vec<Expr>,
         params: Vec<Token>,
+        // 最后一个参数是否为剩余参数 `...args`
+        rest: bool,
         body: Vec<Stmt>,
     },
 
@@ -121,6 +163,7 @@ pub enum Expr {
         object: Box<Expr>,
         bracket: Token,   // '[' token，用于报错定位
         index: Box<Expr>, // 下标表达式 (0, "key", i+1)
+        optional: bool,   // 可选下标 `obj?[key]`
     },
 
     SetIndex {
@@ -133,6 +176,14 @@ pub enum Expr {
 }
 
 impl Expr {
+    /// 解构模式中的目标（Tuple / List 模式的元素），其他表达式没有目标
+    pub fn pattern_targets(&self) -> &[Expr] {
+        match self {
+            Expr::Tuple { elements } | Expr::List { elements } => elements,
+            _ => &[],
+        }
+    }
+
     pub fn number(value: impl Into<String>) -> Expr {
         Expr::Number {
             value: value.into(),
@@ -235,6 +286,7 @@ impl Expr {
             // 当 callee 类型限制为 Token 时，这将意味着只支持通过变量名来调用
             callee: Box::new(callee),
             args,
+            named: Vec::new(),
         }
     }
 
@@ -244,6 +296,7 @@ impl Expr {
             id: ExprId(0),
             callee: Box::new(Expr::variable_str(name)),
             args,
+            named: Vec::new(),
         }
     }
 
@@ -251,6 +304,7 @@ impl Expr {
         Expr::Get {
             object: Box::new(object),
             name,
+            optional: false,
         }
     }
 
@@ -279,7 +333,9 @@ impl Expr {
     pub fn lambda(params: Vec<Token>, body: Vec<Stmt>) -> Expr {
         Expr::Lambda {
             id: ExprId(0),
+            captures: Vec::new(),
             params,
+            rest: false,
             body,
         }
     }
@@ -290,6 +346,7 @@ impl Expr {
             object: Box::new(object),
             bracket: generate_token(TokenType::LeftBracket, "["),
             index: Box::new(index),
+            optional: false,
         }
     }
 
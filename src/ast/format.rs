@@ -1,11 +1,13 @@
 #![allow(dead_code)]
 use super::Operator;
 use crate::ast::{Expr, Stmt};
+use crate::tokenizer::Token;
 
 /// 格式化表达式 (返回值)
 pub fn format_expr(expr: &Expr) -> String {
     match expr {
         Expr::Number { value } => value.clone(), // number: String, not f64
+        Expr::BigInt { value } => format!("{}n", value),
         Expr::String { value } => format!("\"{}\"", value),
         Expr::Boolean { value } => value.to_string(),
         Expr::Nil => "nil".to_string(), // 做为语言类型系统的一部分，可以被赋值给变量、作为参数传递、被打印。不同于 Stmt::Nil 后者是语法结构，用于满足语法要求，但什么都不做。
@@ -32,6 +34,10 @@ pub fn format_expr(expr: &Expr) -> String {
             format!("{} = {}", name.lexeme, format_expr(expr))
         }
 
+        Expr::AssignDestructure { pattern, value } => {
+            format!("{} = {}", format_expr(pattern), format_expr(value))
+        }
+
         Expr::AssignOp { op, name, expr, .. } => {
             format!(
                 "{} {} {}",
@@ -69,16 +75,37 @@ pub fn format_expr(expr: &Expr) -> String {
 
         Expr::Super { method, .. } => format!("super.{}", method.lexeme),
 
-        Expr::Call { callee, args, .. } => {
-            let args_str: Vec<String> = args.iter().map(format_expr).collect();
+        Expr::Call {
+            callee,
+            args,
+            named,
+            ..
+        } => {
+            let mut args_str: Vec<String> = args.iter().map(format_expr).collect();
+            args_str.extend(
+                named
+                    .iter()
+                    .map(|(name, value)| format!("{} = {}", name.lexeme, format_expr(value))),
+            );
             format!("{}({})", format_expr(callee), args_str.join(", "))
         }
 
-        Expr::Get { object, name, .. } => {
-            format!("{}.{}", format_expr(object), name.lexeme)
+        Expr::Get {
+            object,
+            name,
+            optional,
+        } => {
+            let dot = if *optional { "?." } else { "." };
+            format!("{}{}{}", format_expr(object), dot, name.lexeme)
         }
-        Expr::GetIndex { object, index, .. } => {
-            format!("{}[{}]", format_expr(object), format_expr(index))
+        Expr::GetIndex {
+            object,
+            index,
+            optional,
+            ..
+        } => {
+            let bracket = if *optional { "?[" } else { "[" };
+            format!("{}{}{}]", format_expr(object), bracket, format_expr(index))
         }
         Expr::SetIndex {
             object,
@@ -111,17 +138,38 @@ pub fn format_expr(expr: &Expr) -> String {
 
         Expr::Lambda {
             id: _,
+            captures,
             params,
+            rest,
             body,
         } => {
-            let params_str: Vec<String> = params.iter().map(|t| t.lexeme.clone()).collect();
+            let params_str = format_params(params, *rest);
             let body_str = body.iter().map(format_stmt).collect::<Vec<_>>().join(" ");
 
-            format!("fun ({}) {{{}}}", params_str.join(", "), body_str)
+            if captures.is_empty() {
+                format!("fun ({}) {{{}}}", params_str.join(", "), body_str)
+            } else {
+                let captures_str: Vec<String> = captures.iter().map(format_expr).collect();
+                format!(
+                    "fun [{}] ({}) {{{}}}",
+                    captures_str.join(", "),
+                    params_str.join(", "),
+                    body_str
+                )
+            }
         }
     }
 }
 
+/// 格式化参数列表，剩余参数带上 `...` 前缀
+fn format_params(params: &[Token], rest: bool) -> Vec<String> {
+    let mut params_str: Vec<String> = params.iter().map(|t| t.lexeme.clone()).collect();
+    if rest && let Some(last) = params_str.last_mut() {
+        last.insert_str(0, "...");
+    }
+    params_str
+}
+
 /// 格式化语句 (执行动作)
 pub fn format_stmt(stmt: &Stmt) -> String {
     match stmt {
@@ -132,8 +180,21 @@ pub fn format_stmt(stmt: &Stmt) -> String {
             Some(expr) => format!("var {} = {};", name.lexeme, format_expr(expr)),
             None => format!("var {};", name.lexeme),
         },
-        Stmt::Function { name, params, body } => {
-            let params_str: Vec<String> = params.iter().map(|t| t.lexeme.clone()).collect();
+        Stmt::VarDestructure {
+            pattern,
+            initializer,
+        } => format!(
+            "var {} = {};",
+            format_expr(pattern),
+            format_expr(initializer)
+        ),
+        Stmt::Function {
+            name,
+            params,
+            rest,
+            body,
+        } => {
+            let params_str = format_params(params, *rest);
 
             let body_str = body.iter().map(format_stmt).collect::<Vec<_>>().join("\n");
             format!(
@@ -147,23 +208,54 @@ pub fn format_stmt(stmt: &Stmt) -> String {
             name,
             superclass,
             methods,
+            static_methods,
+            traits,
         } => {
             let super_class = match superclass {
                 Some(super_expr) => format!("< {}", format_expr(super_expr)),
                 None => "".to_string(),
             };
+            let implements = if traits.is_empty() {
+                "".to_string()
+            } else {
+                let names: Vec<_> = traits.iter().map(|t| t.lexeme.as_str()).collect();
+                format!(" implements {}", names.join(", "))
+            };
 
             format!(
-                "class {} {} {{ {} }}",
+                "class {} {}{} {{ {} }}",
                 name.lexeme,
                 super_class,
-                methods
+                implements,
+                static_methods
                     .iter()
-                    .map(format_stmt)
+                    .map(|m| format!("static {}", format_stmt(m)))
+                    .chain(methods.iter().map(format_stmt))
                     .collect::<Vec<_>>()
                     .join("\n")
             )
         }
+        Stmt::Trait { name, methods } => format!(
+            "trait {} {{ {} }}",
+            name.lexeme,
+            methods
+                .iter()
+                .map(|(method, params)| {
+                    let params: Vec<_> = params.iter().map(|p| p.lexeme.as_str()).collect();
+                    format!("{}({});", method.lexeme, params.join(", "))
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        ),
+        Stmt::Extend { target, methods } => format!(
+            "extend {} {{ {} }}",
+            target.lexeme,
+            methods
+                .iter()
+                .map(format_stmt)
+                .collect::<Vec<_>>()
+                .join("\n")
+        ),
         Stmt::Block { body } => {
             let parts: Vec<String> = body.iter().map(format_stmt).collect();
 
@@ -186,15 +278,21 @@ pub fn format_stmt(stmt: &Stmt) -> String {
         }
         Stmt::Try {
             try_branch,
-            catch_var,
-            catch_branch,
+            catch,
+            finally_branch,
         } => {
-            format!(
-                "try {} catch ({}) {}",
-                format_stmt(try_branch),
-                catch_var.lexeme,
-                format_stmt(catch_branch)
-            )
+            let mut out = format!("try {}", format_stmt(try_branch));
+            if let Some((catch_var, catch_branch)) = catch {
+                out.push_str(&format!(
+                    " catch ({}) {}",
+                    catch_var.lexeme,
+                    format_stmt(catch_branch)
+                ));
+            }
+            if let Some(finally_branch) = finally_branch {
+                out.push_str(&format!(" finally {}", format_stmt(finally_branch)));
+            }
+            out
         }
         Stmt::Throw { keyword: _, value } => {
             format!("throw {}", format_expr(value))
@@ -229,6 +327,19 @@ pub fn format_stmt(stmt: &Stmt) -> String {
                 format_stmt(body)
             )
         }
+        Stmt::ForIn {
+            vars,
+            iterable,
+            body,
+        } => format!(
+            "for ({} in {}) {}",
+            vars.iter()
+                .map(|v| v.lexeme.clone())
+                .collect::<Vec<_>>()
+                .join(", "),
+            format_expr(iterable),
+            format_stmt(body)
+        ),
         Stmt::Print { expr } => {
             format!("print {};", format_expr(expr))
         }
@@ -236,8 +347,20 @@ pub fn format_stmt(stmt: &Stmt) -> String {
             Some(expr) => format!("return {};", format_expr(expr)),
             None => "return;".to_string(),
         },
-        Stmt::Break => "break;".to_string(),
-        Stmt::Continue => "continue;".to_string(),
+        Stmt::Defer { expr, .. } => format!("defer {};", format_expr(expr)),
+        Stmt::Yield { value, .. } => match value {
+            Some(expr) => format!("yield {};", format_expr(expr)),
+            None => "yield;".to_string(),
+        },
+        Stmt::Break { label } => match label {
+            Some(label) => format!("break {};", label.lexeme),
+            None => "break;".to_string(),
+        },
+        Stmt::Continue { label } => match label {
+            Some(label) => format!("continue {};", label.lexeme),
+            None => "continue;".to_string(),
+        },
+        Stmt::Labeled { label, body } => format!("{}: {}", label.lexeme, format_stmt(body)),
         Stmt::Export { stmt } => {
             format!("export {};", format_stmt(stmt))
         }
@@ -252,6 +375,7 @@ pub fn format_operator(op: &Operator) -> &'static str {
         Operator::Sub => "-",
         Operator::Mul => "*",
         Operator::Div => "/",
+        Operator::FloorDiv => "~/",
         Operator::Mod => "%",
         Operator::Assign => "=",
         Operator::AddAssign => "+=",
@@ -264,6 +388,7 @@ pub fn format_operator(op: &Operator) -> &'static str {
         Operator::LessEqual => "<=",
         Operator::LogicalAnd => "&&",
         Operator::LogicalOr => "||",
+        Operator::NullCoalesce => "??",
         Operator::AndKeyword => "&&",
         Operator::OrKeyword => "||",
         Operator::BitwiseXor => "^",
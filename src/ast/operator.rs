@@ -1,10 +1,13 @@
-#[derive(Debug, PartialEq, Clone)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub enum Operator {
     Add,
     Sub,
     Mul,
     Div,
+    FloorDiv,
     Mod,
     Less,
     LessEqual,
@@ -18,6 +21,7 @@ pub enum Operator {
     BitwiseXor,
     LogicalOr,
     LogicalAnd,
+    NullCoalesce,
     AndKeyword,
     OrKeyword,
     BitwiseAnd,
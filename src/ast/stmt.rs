@@ -1,12 +1,14 @@
 #![allow(dead_code)]
 
+use serde::{Deserialize, Serialize};
+
 use crate::{
     ast::{Expr, helper::generate_token},
     tokenizer::{Token, TokenType},
 };
 
 /// 语句（Statement）：执行动作和控制流的结构（语句用来执行操作，而不是产生值）
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Stmt {
     // eg："1+1;"、"func();"
     Expression {
@@ -23,9 +25,17 @@ pub enum Stmt {
         initializer: Option<Expr>,
     },
 
+    // 解构声明 `var (a, b) = pair;` / `var [x, y] = list;`
+    // pattern 是只包含 Expr::Variable 的 Expr::Tuple 或 Expr::List
+    VarDestructure {
+        pattern: Expr,
+        initializer: Expr,
+    },
+
     Function {
         name: Token,
         params: Vec<Token>, // Token： 参数本质是定义在函数作用域中声明的局部变量，每一个参数名需要被当作变量标识符来处理
+        rest: bool,         // 最后一个参数是否为剩余参数 `...args`
         body: Vec<Stmt>,    // 函数体是一组语句
     },
 
@@ -33,6 +43,20 @@ pub enum Stmt {
         name: Token,
         superclass: Option<Expr>,
         methods: Vec<Stmt>,
+        static_methods: Vec<Stmt>, // `static name() {}`：通过类本身调用，不绑定 this
+        traits: Vec<Token>,        // `implements A, B` 声明遵循的 trait
+    },
+
+    // trait 声明：只列出方法签名 (方法名, 参数列表)，类声明执行时检查 `implements` 的实现
+    Trait {
+        name: Token,
+        methods: Vec<(Token, Vec<Token>)>,
+    },
+
+    // 为内置类型扩展方法：extend String { shout() { ... } }
+    Extend {
+        target: Token, // 被扩展的内置类型名
+        methods: Vec<Stmt>,
     },
 
     // control flow
@@ -47,9 +71,9 @@ pub enum Stmt {
     },
 
     Try {
-        try_branch: Box<Stmt>,   // 通常是 Block
-        catch_var: Token,        // catch (e) 中的 e
-        catch_branch: Box<Stmt>, // Block
+        try_branch: Box<Stmt>,             // 通常是 Block
+        catch: Option<(Token, Box<Stmt>)>, // catch (e) 中的 e 与 catch 块，有 finally 时可以省略
+        finally_branch: Option<Box<Stmt>>, // 无论如何离开 try / catch 都会执行的 Block
     },
 
     Throw {
@@ -69,6 +93,13 @@ pub enum Stmt {
         body: Box<Stmt>,
     },
 
+    // for (k in iterable) / for (k, v in iterable)
+    ForIn {
+        vars: Vec<Token>, // 循环变量，每次迭代都会在新作用域中重新定义
+        iterable: Expr,
+        body: Box<Stmt>,
+    },
+
     Print {
         expr: Expr,
     },
@@ -78,8 +109,32 @@ pub enum Stmt {
         value: Option<Expr>,
     },
 
-    Break,
-    Continue,
+    // 函数体中出现 yield 时，调用函数得到一个生成器，每次 yield 暂停并产出一个值
+    Yield {
+        keyword: Token,
+        value: Option<Expr>,
+    },
+
+    // 登记一个表达式，在所在函数退出时（正常结束、return 或出错）按登记的逆序求值
+    Defer {
+        keyword: Token,
+        expr: Expr,
+    },
+
+    // 可选的标签指向外层带标签的循环：`break outer;`
+    Break {
+        label: Option<Token>,
+    },
+    Continue {
+        label: Option<Token>,
+    },
+
+    // 带标签的循环 `outer: while (...) {...}`，body 是 While / For / ForIn
+    Labeled {
+        label: Token,
+        body: Box<Stmt>,
+    },
+
     // export 语句：包裹一个声明 (Var, Function, Class)
     Export {
         stmt: Box<Stmt>,
@@ -87,6 +142,55 @@ pub enum Stmt {
 }
 
 impl Stmt {
+    /// 查找语句中（不进入嵌套函数）的第一个 `yield`
+    pub fn find_yield(&self) -> Option<&Token> {
+        self.find_keyword(&|stmt| match stmt {
+            Stmt::Yield { keyword, .. } => Some(keyword),
+            _ => None,
+        })
+    }
+
+    /// 查找语句中（不进入嵌套函数）的第一个 `defer`
+    pub fn find_defer(&self) -> Option<&Token> {
+        self.find_keyword(&|stmt| match stmt {
+            Stmt::Defer { keyword, .. } => Some(keyword),
+            _ => None,
+        })
+    }
+
+    /// 深度优先查找第一个满足 `f` 的语句，不进入嵌套函数
+    fn find_keyword<'a, F>(&'a self, f: &F) -> Option<&'a Token>
+    where
+        F: Fn(&'a Stmt) -> Option<&'a Token>,
+    {
+        if let Some(keyword) = f(self) {
+            return Some(keyword);
+        }
+        match self {
+            Stmt::Block { body } => body.iter().find_map(|stmt| stmt.find_keyword(f)),
+            Stmt::If {
+                then_branch,
+                else_branch,
+                ..
+            } => then_branch
+                .find_keyword(f)
+                .or_else(|| else_branch.as_ref()?.find_keyword(f)),
+            Stmt::Try {
+                try_branch,
+                catch,
+                finally_branch,
+            } => try_branch
+                .find_keyword(f)
+                .or_else(|| catch.as_ref()?.1.find_keyword(f))
+                .or_else(|| finally_branch.as_ref()?.find_keyword(f)),
+            Stmt::While { body, .. }
+            | Stmt::For { body, .. }
+            | Stmt::ForIn { body, .. }
+            | Stmt::Labeled { body, .. } => body.find_keyword(f),
+            _ => None,
+        }
+    }
+
     pub fn expression(expr: Expr) -> Stmt {
         Stmt::Expression { expr }
     }
@@ -111,6 +215,7 @@ impl Stmt {
         Stmt::Function {
             name: generate_token(TokenType::Identifier, name),
             params: param_tokens,
+            rest: false,
             body,
         }
     }
@@ -120,6 +225,15 @@ impl Stmt {
             name: generate_token(TokenType::Identifier, name),
             superclass,
             methods,
+            static_methods: Vec::new(),
+            traits: Vec::new(),
+        }
+    }
+
+    pub fn extend(target: &str, methods: Vec<Stmt>) -> Stmt {
+        Stmt::Extend {
+            target: generate_token(TokenType::Identifier, target),
+            methods,
         }
     }
 
@@ -156,6 +270,17 @@ impl Stmt {
         }
     }
 
+    pub fn for_in(vars: Vec<&str>, iterable: Expr, body: Stmt) -> Stmt {
+        Stmt::ForIn {
+            vars: vars
+                .into_iter()
+                .map(|v| generate_token(TokenType::Identifier, v))
+                .collect(),
+            iterable,
+            body: Box::new(body),
+        }
+    }
+
     pub fn print(expr: Expr) -> Stmt {
         Stmt::Print { expr }
     }
@@ -168,11 +293,11 @@ impl Stmt {
     }
 
     pub fn break_() -> Stmt {
-        Stmt::Break
+        Stmt::Break { label: None }
     }
 
     pub fn continue_() -> Stmt {
-        Stmt::Continue
+        Stmt::Continue { label: None }
     }
 
     pub fn export(stmt: Stmt) -> Stmt {
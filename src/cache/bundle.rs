@@ -0,0 +1,284 @@
+use crate::{
+    ast::Stmt,
+    cache::parse_source,
+    evaluate::Interpreter,
+    reader::Source,
+    tokenizer::{Literal, TokenType, tokenize},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// 编译产物扩展名
+pub const BUNDLE_EXTENSION: &str = "roxb";
+
+/// 编译产物 (Bundle)
+///
+/// 把入口脚本及其（静态可知的）导入模块解析后的语法树一起序列化（JSON）成单个文件，
+/// `rox run app.roxb` 可以在没有源码的环境中直接执行，加载时跳过词法和语法分析。
+///
+/// Note：产物不是字节码。rox 是树遍历解释器，加载后仍然对语法树做作用域解析 (Resolve) 并逐节点求值；
+///       作用域解析依赖运行时状态，因此不保存在产物中。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Bundle {
+    pub version: String,
+    /// 入口模块的键
+    pub entry: String,
+    /// 模块键 -> 模块
+    pub modules: BTreeMap<String, BundledModule>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BundledModule {
+    pub body: Vec<Stmt>,
+    /// 模块中出现的导入路径 -> 目标模块键
+    pub imports: BTreeMap<String, String>,
+}
+
+impl Bundle {
+    /// 编译入口脚本及其导入的模块
+    ///
+    /// 只有参数为字符串字面量的 `import("...")` / `include("...")` 会被收集，导入路径的解析规则与运行时一致
+    /// （`interpreter` 提供模块查找路径与内置模块注册表）。
+    pub fn compile(entry: &Path, interpreter: &Interpreter) -> Result<Self, String> {
+        let entry = fs::canonicalize(entry)
+            .map_err(|e| format!("Cannot read '{}': {}", entry.display(), e))?;
+        let root = entry.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        let mut modules = BTreeMap::new();
+        let mut queue = VecDeque::from([entry.clone()]);
+
+        while let Some(path) = queue.pop_front() {
+            let key = module_key(&root, &path);
+            if modules.contains_key(&key) {
+                continue;
+            }
+
+            let source = fs::read_to_string(&path)
+                .map_err(|e| format!("Cannot read '{}': {}", path.display(), e))?;
+            let specifiers = import_specifiers(&source);
+            let body = parse_source(source).map_err(|e| format!("{} (in '{}')", e, key))?;
+
+            let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+            let mut imports = BTreeMap::new();
+            for specifier in specifiers {
+                // 内置模块在运行时由注册表提供
+                if interpreter.registry.is_enabled(&specifier) {
+                    continue;
+                }
+                let target = interpreter
+                    .resolve_import(&dir, &specifier)
+                    .map_err(|e| format!("{} (in '{}')", e, key))?;
+                imports.insert(specifier, module_key(&root, &target));
+                queue.push_back(target);
+            }
+
+            modules.insert(key, BundledModule { body, imports });
+        }
+
+        Ok(Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            entry: module_key(&root, &entry),
+            modules,
+        })
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let bytes = serde_json::to_vec(self).map_err(|e| e.to_string())?;
+        fs::write(path, bytes).map_err(|e| format!("Cannot write '{}': {}", path.display(), e))
+    }
+
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let bytes =
+            fs::read(path).map_err(|e| format!("Cannot read '{}': {}", path.display(), e))?;
+        let bundle: Bundle = serde_json::from_slice(&bytes)
+            .map_err(|e| format!("Invalid bundle '{}': {}", path.display(), e))?;
+
+        if bundle.version != env!("CARGO_PKG_VERSION") {
+            return Err(format!(
+                "Bundle '{}' was compiled by rox {}, but this is rox {}. Please recompile it.",
+                path.display(),
+                bundle.version,
+                env!("CARGO_PKG_VERSION")
+            ));
+        }
+        bundle
+            .validate()
+            .map_err(|e| format!("Invalid bundle '{}': {}", path.display(), e))?;
+        Ok(bundle)
+    }
+
+    /// 检查入口模块以及每个导入指向的模块都在产物中（文件可能被截断或手工修改过）
+    fn validate(&self) -> Result<(), String> {
+        if !self.modules.contains_key(&self.entry) {
+            return Err(format!("entry module '{}' is missing.", self.entry));
+        }
+        for (key, module) in &self.modules {
+            if let Some(target) = module
+                .imports
+                .values()
+                .find(|target| !self.modules.contains_key(*target))
+            {
+                return Err(format!(
+                    "module '{}' imported by '{}' is missing.",
+                    target, key
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// 入口模块的语法树
+    pub fn entry_body(&self) -> Result<Vec<Stmt>, String> {
+        self.modules
+            .get(&self.entry)
+            .map(|module| module.body.clone())
+            .ok_or_else(|| format!("Entry module '{}' is not in the bundle.", self.entry))
+    }
+}
+
+/// 模块键：位于入口目录下的模块使用相对路径，其余使用绝对路径
+fn module_key(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| path.to_path_buf())
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// 收集源码中 `import("literal")` / `include("literal")` 形式的导入路径，
+/// 以及导入语句 `import name;` / `import "literal";` 中的模块
+fn import_specifiers(source: &str) -> Vec<String> {
+    let Ok(tokens) = tokenize(Source {
+        contents: source.to_string(),
+    }) else {
+        return Vec::new(); // 词法错误交给 parse_source 报告
+    };
+
+    let statements = tokens
+        .tokens
+        .windows(2)
+        .filter_map(|w| match (&w[0], &w[1]) {
+            (import, target)
+                if import.token_type == TokenType::Identifier && import.lexeme == "import" =>
+            {
+                match (&target.token_type, &target.literal) {
+                    (TokenType::String, Literal::String(s)) => Some(s.clone()),
+                    (TokenType::Identifier, _) => Some(target.lexeme.clone()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        });
+
+    tokens
+        .tokens
+        .windows(4)
+        .filter_map(|w| match (&w[0], &w[1], &w[2], &w[3]) {
+            (import, open, path, close)
+                if import.token_type == TokenType::Identifier
+                    && (import.lexeme == "import" || import.lexeme == "include")
+                    && open.token_type == TokenType::LeftParen
+                    && close.token_type == TokenType::RightParen =>
+            {
+                match &path.literal {
+                    Literal::String(s) => Some(s.clone()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .chain(statements)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Bundle;
+    use crate::{
+        ast::Ast,
+        evaluate::{Interpreter, Value},
+        resolver::Resolver,
+    };
+    use pretty_assertions::assert_eq;
+    use std::{fs, rc::Rc};
+
+    #[test]
+    fn test_compile_and_run_bundle() {
+        let dir = std::env::temp_dir().join(format!("rox_bundle_{}", std::process::id()));
+        fs::create_dir_all(dir.join("lib")).unwrap();
+        fs::write(
+            dir.join("main.rox"),
+            r#"var m = import("math"); var u = import("./lib/util.rox"); var res = u.four * m.abs(-2);"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("lib/util.rox"),
+            r#"var h = import("helper"); export var four = h.add(2, 2);"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("lib/helper.rox"),
+            "export fun add(a, b) { return a + b; }",
+        )
+        .unwrap();
+
+        let bundle = Bundle::compile(&dir.join("main.rox"), &Interpreter::default());
+        fs::remove_dir_all(&dir).unwrap();
+        let bundle = bundle.unwrap();
+
+        assert_eq!(
+            bundle.modules.keys().cloned().collect::<Vec<_>>(),
+            vec!["lib/helper.rox", "lib/util.rox", "main.rox"]
+        );
+
+        // 源码已经删除，只依赖 bundle 执行
+        let mut interpreter = Interpreter::default();
+        let body = bundle.entry_body().unwrap();
+        interpreter.bundle = Some(Rc::new(bundle));
+        Resolver::new(&mut interpreter)
+            .resolve_stmts(&body)
+            .unwrap();
+        interpreter.interpret(Ast { body }).unwrap();
+
+        assert_eq!(
+            interpreter.get_global_value("res").unwrap(),
+            Value::Number(8.0)
+        );
+    }
+
+    #[test]
+    fn test_read_rejects_bundle_with_missing_modules() {
+        let path =
+            std::env::temp_dir().join(format!("rox_bundle_missing_{}.roxb", std::process::id()));
+        let version = env!("CARGO_PKG_VERSION");
+
+        let missing_entry = format!(
+            r#"{{"version":"{}","entry":"main.rox","modules":{{}}}}"#,
+            version
+        );
+        fs::write(&path, missing_entry).unwrap();
+        let error = Bundle::read(&path).unwrap_err();
+        assert!(
+            error.contains("entry module 'main.rox' is missing"),
+            "{}",
+            error
+        );
+
+        let missing_import = format!(
+            r#"{{"version":"{}","entry":"main.rox","modules":{{"main.rox":{{"body":[],"imports":{{"./util":"util.rox"}}}}}}}}"#,
+            version
+        );
+        fs::write(&path, missing_import).unwrap();
+        let error = Bundle::read(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert!(
+            error.contains("module 'util.rox' imported by 'main.rox' is missing"),
+            "{}",
+            error
+        );
+    }
+}
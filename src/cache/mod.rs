@@ -0,0 +1,176 @@
+pub mod bundle;
+
+use crate::{ast::Stmt, reader::Source};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+/// 缓存文件扩展名
+pub const CACHE_EXTENSION: &str = "roxc";
+
+/// 语法树的序列化格式版本
+///
+/// 修改 `ast` 中节点的结构（增删字段或变体）时递增，使旧的缓存失效；
+/// 仅靠 crate 版本号不够，开发过程中语法树经常变化而版本号不变。
+pub const AST_FORMAT_VERSION: u32 = 1;
+
+/// 编译模块缓存 (.roxc)
+///
+/// 以源码内容的哈希为键，把解析好的 AST 序列化到磁盘。
+/// 模块内容未变化时直接反序列化，跳过词法与语法分析。
+///
+/// - Resolve 的结果 (locals 侧表) 依赖运行时状态，不参与缓存，每次加载后重新解析作用域。
+/// - 缓存是尽力而为的：读写失败或格式不兼容都会回退为重新解析，不会影响执行。
+/// - 设置环境变量 `ROX_NO_CACHE` 可以禁用缓存。
+#[derive(Debug, Clone)]
+pub struct ModuleCache {
+    dir: PathBuf,
+}
+
+/// 缓存文件内容：带上解释器版本与语法树格式版本，任一变化后旧缓存自动失效
+#[derive(Serialize, Deserialize)]
+struct CachedModule {
+    version: String,
+    ast_version: u32,
+    hash: u64,
+    body: Vec<Stmt>,
+}
+
+impl ModuleCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// 默认缓存目录 `~/.rox/cache`；设置了 `ROX_NO_CACHE` 时返回 `None`
+    pub fn from_env() -> Option<Self> {
+        if std::env::var_os("ROX_NO_CACHE").is_some() {
+            return None;
+        }
+        dirs::home_dir().map(|home| Self::new(home.join(".rox").join("cache")))
+    }
+
+    /// 读取缓存：哈希与两个版本都匹配时返回 AST
+    pub fn load(&self, source: &str) -> Option<Vec<Stmt>> {
+        let hash = content_hash(source);
+        let bytes = fs::read(self.path_for(hash)).ok()?;
+        let cached: CachedModule = serde_json::from_slice(&bytes).ok()?;
+
+        (cached.version == env!("CARGO_PKG_VERSION")
+            && cached.ast_version == AST_FORMAT_VERSION
+            && cached.hash == hash)
+            .then_some(cached.body)
+    }
+
+    /// 写入缓存，失败时静默忽略
+    pub fn store(&self, source: &str, body: &[Stmt]) {
+        let hash = content_hash(source);
+        let cached = CachedModule {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            ast_version: AST_FORMAT_VERSION,
+            hash,
+            body: body.to_vec(),
+        };
+
+        if fs::create_dir_all(&self.dir).is_err() {
+            return;
+        }
+        if let Ok(bytes) = serde_json::to_vec(&cached) {
+            // 先写临时文件再重命名，避免并发进程读到写了一半的缓存
+            let path = self.path_for(hash);
+            let tmp = path.with_extension(format!("{}.{}", CACHE_EXTENSION, std::process::id()));
+            if fs::write(&tmp, bytes).is_ok() && fs::rename(&tmp, &path).is_err() {
+                let _ = fs::remove_file(&tmp);
+            }
+        }
+    }
+
+    /// 解析源码，优先使用缓存
+    ///
+    /// # 返回值
+    /// * `Ok(Vec<Stmt>)` - 语法树
+    /// * `Err(String)` - 词法或语法错误信息
+    pub fn parse(&self, source: String) -> Result<Vec<Stmt>, String> {
+        if let Some(body) = self.load(&source) {
+            return Ok(body);
+        }
+
+        let body = parse_source(source.clone())?;
+        self.store(&source, &body);
+        Ok(body)
+    }
+
+    fn path_for(&self, hash: u64) -> PathBuf {
+        self.dir.join(format!("{:016x}.{}", hash, CACHE_EXTENSION))
+    }
+}
+
+/// 不经过缓存直接解析源码
+pub fn parse_source(source: String) -> Result<Vec<Stmt>, String> {
+    let tokens = crate::tokenizer::tokenize(Source { contents: source })
+        .map_err(|e| format!("Scan error: {}", e))?;
+    let ast = crate::parser::parse(tokens).map_err(|e| format!("Parse error: {}", e))?;
+    Ok(ast.body)
+}
+
+/// 源码内容哈希 (FNV-1a 64)
+///
+/// 不使用 `DefaultHasher`：它的算法不保证跨 Rust 版本稳定，而缓存需要跨进程复用。
+pub fn content_hash(source: &str) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    source.bytes().fold(OFFSET, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AST_FORMAT_VERSION, ModuleCache, content_hash};
+    use crate::ast::{Stmt, format::format_stmt};
+    use pretty_assertions::assert_eq;
+    use std::fs;
+
+    #[test]
+    fn test_cache_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("rox_cache_{}", std::process::id()));
+        let cache = ModuleCache::new(dir.clone());
+        let source = "export fun add(a, b) { return a + b; }".to_string();
+
+        assert!(cache.load(&source).is_none());
+        let parsed = cache.parse(source.clone()).unwrap();
+        let cached = cache.load(&source);
+        fs::remove_dir_all(&dir).unwrap();
+
+        // 加载时节点 ID 会重新分配，按源码形式比较
+        let format = |body: &[Stmt]| body.iter().map(format_stmt).collect::<Vec<_>>();
+        let cached = cached.unwrap();
+        assert_eq!(format(&cached), format(&parsed));
+        assert_ne!(cached, parsed);
+    }
+
+    #[test]
+    fn test_cache_rejects_other_ast_format() {
+        let dir = std::env::temp_dir().join(format!("rox_cache_ast_{}", std::process::id()));
+        let cache = ModuleCache::new(dir.clone());
+        let source = "print 1;".to_string();
+        cache.parse(source.clone()).unwrap();
+
+        // 把缓存文件改写为另一个语法树格式版本
+        let path = cache.path_for(content_hash(&source));
+        let stale = fs::read_to_string(&path).unwrap().replace(
+            &format!("\"ast_version\":{}", AST_FORMAT_VERSION),
+            &format!("\"ast_version\":{}", AST_FORMAT_VERSION + 1),
+        );
+        fs::write(&path, stale).unwrap();
+        let loaded = cache.load(&source);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(loaded.is_none());
+    }
+
+    #[test]
+    fn test_content_hash_changes_with_source() {
+        assert_eq!(content_hash("var a = 1;"), content_hash("var a = 1;"));
+        assert!(content_hash("var a = 1;") != content_hash("var a = 2;"));
+    }
+}
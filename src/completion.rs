@@ -0,0 +1,199 @@
+//! REPL 的 Tab 补全
+//!
+//! 补全来源是解释器的全局环境：全局名（含内置名）、关键字，以及模块值的导出成员
+//! (eg. `math.<TAB>`、`std.math.<TAB>`)。
+
+use crate::{
+    evaluate::{Value, environment::Environment},
+    std_lib::registry::Registry,
+    tokenizer::KEYWORDS,
+};
+use rustyline::{
+    Context, Helper, completion::Completer, highlight::Highlighter, hint::Hinter,
+    validate::Validator,
+};
+use std::{cell::RefCell, collections::BTreeSet, rc::Rc};
+
+/// rustyline 的辅助对象，持有解释器的全局环境与模块注册表
+///
+/// `:clear` 等操作原地替换全局绑定，因此这里持有的引用始终看到最新的状态。
+pub struct RoxHelper {
+    globals: Rc<RefCell<Environment>>,
+    registry: Registry,
+}
+
+impl RoxHelper {
+    pub fn new(globals: Rc<RefCell<Environment>>, registry: Registry) -> Self {
+        Self { globals, registry }
+    }
+}
+
+/// 计算光标处的补全
+///
+/// # 返回值
+/// `(start, candidates)`：被替换部分在 `line` 中的起始位置，以及按字典序排列的候选项
+/// 原生模块按需构建，补全成员前先通过 `registry` 加载。
+pub fn complete(
+    globals: &Environment,
+    registry: &Registry,
+    line: &str,
+    pos: usize,
+) -> (usize, Vec<String>) {
+    let before = &line[..pos];
+    // 光标前由标识符字符和 `.` 组成的一段，eg. `print std.ma` 中的 `std.ma`
+    let path_start = before
+        .rfind(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+        .map_or(0, |i| {
+            i + before[i..].chars().next().map_or(1, char::len_utf8)
+        });
+    let path = &before[path_start..];
+
+    let (start, prefix, names) = match path.rsplit_once('.') {
+        Some((object, prefix)) => {
+            let names = match resolve(globals, registry, object) {
+                Some(Value::Module(module)) => {
+                    registry.load(&module);
+                    module.borrow().exports.keys().cloned().collect()
+                }
+                _ => BTreeSet::new(),
+            };
+            (pos - prefix.len(), prefix, names)
+        }
+        None => {
+            let mut names = global_names(globals);
+            names.extend(KEYWORDS.iter().map(|k| k.to_string()));
+            (path_start, path, names)
+        }
+    };
+
+    let candidates = names
+        .into_iter()
+        .filter(|name| name.starts_with(prefix))
+        .collect();
+    (start, candidates)
+}
+
+/// 全局环境及其外层 (预置环境) 中的全部名字
+fn global_names(globals: &Environment) -> BTreeSet<String> {
+    let mut names = globals.values.keys().cloned().collect::<BTreeSet<_>>();
+    let mut enclosing = globals.enclosing.clone();
+    while let Some(env) = enclosing {
+        let env = env.borrow();
+        names.extend(env.values.keys().cloned());
+        enclosing = env.enclosing.clone();
+    }
+    names
+}
+
+/// 按 `a.b.c` 的路径查找值：首段在全局环境中查找，其余各段依次取模块的导出
+fn resolve(globals: &Environment, registry: &Registry, path: &str) -> Option<Value> {
+    let mut segments = path.split('.');
+    let mut value = lookup(globals, segments.next()?)?;
+    for segment in segments {
+        value = match value {
+            Value::Module(module) => {
+                registry.load(&module);
+                module.borrow().exports.get(segment)?.clone()
+            }
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+fn lookup(globals: &Environment, name: &str) -> Option<Value> {
+    if let Some(value) = globals.values.get(name) {
+        return Some(value.clone());
+    }
+    let mut enclosing = globals.enclosing.clone();
+    while let Some(env) = enclosing {
+        let env = env.borrow();
+        if let Some(value) = env.values.get(name) {
+            return Some(value.clone());
+        }
+        enclosing = env.enclosing.clone();
+    }
+    None
+}
+
+impl Completer for RoxHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(complete(&self.globals.borrow(), &self.registry, line, pos))
+    }
+}
+
+impl Hinter for RoxHelper {
+    type Hint = String;
+}
+
+impl Highlighter for RoxHelper {}
+
+impl Validator for RoxHelper {}
+
+impl Helper for RoxHelper {}
+
+#[cfg(test)]
+mod tests {
+    use super::complete;
+    use crate::{evaluate::Interpreter, reader::Source, resolver::Resolver, tokenizer};
+    use pretty_assertions::assert_eq;
+
+    fn interpreter_with(code: &str) -> Interpreter {
+        let mut interpreter = Interpreter::new();
+        let tokens = tokenizer::tokenize(Source {
+            contents: code.to_string(),
+        })
+        .unwrap();
+        let ast = crate::parser::parse(tokens).unwrap();
+        Resolver::new(&mut interpreter)
+            .resolve_stmts(&ast.body)
+            .unwrap();
+        interpreter.interpret(ast).unwrap();
+        interpreter
+    }
+
+    #[test]
+    fn test_complete_globals_and_keywords() {
+        let interpreter = interpreter_with("var counter = 1; fun compute() {}");
+        let globals = interpreter.globals.borrow();
+
+        let (start, candidates) = complete(&globals, &interpreter.registry, "print co", 8);
+        assert_eq!(start, 6);
+        assert_eq!(candidates, vec!["compute", "continue", "counter"]);
+
+        // 内置名来自预置环境
+        let (_, candidates) = complete(&globals, &interpreter.registry, "deep_c", 6);
+        assert_eq!(candidates, vec!["deep_copy"]);
+    }
+
+    #[test]
+    fn test_complete_module_members() {
+        let interpreter = interpreter_with("");
+        let globals = interpreter.globals.borrow();
+
+        let (start, candidates) = complete(&globals, &interpreter.registry, "math.sq", 7);
+        assert_eq!(start, 5);
+        assert_eq!(candidates, vec!["sqrt"]);
+
+        let (start, candidates) = complete(&globals, &interpreter.registry, "x = std.math.fl", 15);
+        assert_eq!(start, 13);
+        assert_eq!(candidates, vec!["floor"]);
+
+        // 非模块的值没有可补全的成员
+        assert_eq!(
+            complete(&globals, &interpreter.registry, "clock.", 6),
+            (6, vec![])
+        );
+        assert_eq!(
+            complete(&globals, &interpreter.registry, "nope.a", 6),
+            (5, vec![])
+        );
+    }
+}
@@ -86,6 +86,9 @@ fn map_scan_error(file_id: usize, source: &str, error: &ScanError) -> Diagnostic
             SingleScanError::UnterminatedString { line } => {
                 ("Unterminated string".to_string(), *line)
             }
+            SingleScanError::InvalidNumber { lexeme, line } => {
+                (format!("Invalid number '{}'", lexeme), *line)
+            }
         };
 
         let range = line_range(source, line);
@@ -2,11 +2,13 @@ use std::fmt;
 
 use crate::evaluate;
 use crate::parser;
+use crate::project;
 use crate::reader;
 use crate::tokenizer;
 
 type Read = reader::Error;
 type Parse = parser::Error;
+type Project = project::Error;
 type Tokenize = tokenizer::Error;
 type Evaluate = evaluate::error::RuntimeError;
 type Readline = rustyline::error::ReadlineError;
@@ -19,6 +21,7 @@ pub enum RoxError {
     Evaluate(Evaluate),
     Tokenize(Tokenize),
     Readline(Readline),
+    Project(Project),
 }
 
 impl fmt::Display for RoxError {
@@ -29,6 +32,7 @@ impl fmt::Display for RoxError {
             RoxError::Evaluate(error) => write!(f, "{}", error),
             RoxError::Tokenize(error) => write!(f, "{}", error),
             RoxError::Readline(e) => write!(f, "Readline Error: {}", e),
+            RoxError::Project(e) => write!(f, "{}", e),
         }
     }
 }
@@ -45,4 +49,4 @@ macro_rules! impl_from_error {
     };
 }
 
-impl_from_error!(RoxError, Read, Parse, Evaluate, Tokenize, Readline);
+impl_from_error!(RoxError, Read, Parse, Evaluate, Tokenize, Readline, Project);
@@ -0,0 +1,143 @@
+use crate::{
+    ast::Operator,
+    evaluate::{error::RuntimeError, value::Value},
+};
+use num_bigint::BigInt;
+use num_traits::{FromPrimitive, Signed, ToPrimitive, Zero};
+use std::cmp::Ordering;
+
+/// 大整数 (BigInt) 运算
+///
+/// - 两侧都是 BigInt 时结果为 BigInt；另一侧是整数值的 Number 时自动提升为 BigInt，
+///   非整数的 Number 不能参与算术运算（精度无法保证）。
+/// - `/` 是整除，与 `%` 配套满足 `a == (a / b) * b + a % b`，余数总是非负（与 Number 的 `%` 一致）。
+/// - 比较运算可以混用 BigInt 与任意 Number。
+///
+/// # 返回值
+/// * `None` - 两侧都不是 BigInt，或者运算符不由这里处理（eg. `==` 与字符串拼接）
+pub fn binary(op: &Operator, left: &Value, right: &Value) -> Option<Result<Value, RuntimeError>> {
+    if !matches!(left, Value::BigInt(_)) && !matches!(right, Value::BigInt(_)) {
+        return None;
+    }
+
+    match op {
+        Operator::Less | Operator::LessEqual | Operator::Greater | Operator::GreaterEqual => {
+            let Some(ordering) = compare(left, right) else {
+                // 与 NaN 比较总是 false；其余组合交给通用的类型错误处理
+                let numeric = |v: &Value| matches!(v, Value::Number(_) | Value::BigInt(_));
+                return (numeric(left) && numeric(right)).then_some(Ok(Value::Boolean(false)));
+            };
+            let result = match op {
+                Operator::Less => ordering == Ordering::Less,
+                Operator::LessEqual => ordering != Ordering::Greater,
+                Operator::Greater => ordering == Ordering::Greater,
+                _ => ordering != Ordering::Less,
+            };
+            Some(Ok(Value::Boolean(result)))
+        }
+        Operator::Add
+        | Operator::Sub
+        | Operator::Mul
+        | Operator::Div
+        | Operator::FloorDiv
+        | Operator::Mod => {
+            let (a, b) = match (promote(left)?, promote(right)?) {
+                (Ok(a), Ok(b)) => (a, b),
+                (Err(e), _) | (_, Err(e)) => return Some(Err(e)),
+            };
+            Some(arithmetic(op, a, b).map(Value::BigInt))
+        }
+        _ => None,
+    }
+}
+
+fn arithmetic(op: &Operator, a: BigInt, b: BigInt) -> Result<BigInt, RuntimeError> {
+    match op {
+        Operator::Add => Ok(a + b),
+        Operator::Sub => Ok(a - b),
+        Operator::Mul => Ok(a * b),
+        Operator::Div | Operator::FloorDiv | Operator::Mod => {
+            if b.is_zero() {
+                return Err(RuntimeError::DivisionByZero);
+            }
+            if *op == Operator::FloorDiv {
+                // 向下取整：截断除法的余数与除数异号时商减一
+                let quotient = &a / &b;
+                let adjust = !(&a % &b).is_zero() && a.is_negative() != b.is_negative();
+                return Ok(if adjust { quotient - 1 } else { quotient });
+            }
+            // 欧几里得除法：余数非负
+            let mut remainder = &a % &b;
+            if remainder.is_negative() {
+                remainder += b.abs();
+            }
+            match op {
+                Operator::Mod => Ok(remainder),
+                _ => Ok((a - remainder) / b),
+            }
+        }
+        _ => unreachable!("filtered by binary()"),
+    }
+}
+
+/// 把参与运算的值转换为 BigInt
+///
+/// # 返回值
+/// * `None` - 值不是数字，交给通用的类型错误处理
+fn promote(value: &Value) -> Option<Result<BigInt, RuntimeError>> {
+    match value {
+        Value::BigInt(n) => Some(Ok(n.clone())),
+        Value::Number(n) => Some(from_integral(*n).ok_or_else(|| {
+            RuntimeError::TypeError(format!("Cannot mix BigInt with non-integer Number {}.", n))
+        })),
+        _ => None,
+    }
+}
+
+fn compare(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::BigInt(a), Value::BigInt(b)) => Some(a.cmp(b)),
+        (Value::BigInt(a), Value::Number(b)) => compare_with_number(a, *b),
+        (Value::Number(a), Value::BigInt(b)) => compare_with_number(b, *a).map(Ordering::reverse),
+        _ => None,
+    }
+}
+
+fn compare_with_number(big: &BigInt, n: f64) -> Option<Ordering> {
+    match from_integral(n) {
+        Some(n) => Some(big.cmp(&n)),
+        // 非整数：与其向下取整的结果比较，相等时 BigInt 更小
+        None if n.is_finite() => {
+            let floor = BigInt::from_f64(n.floor())?;
+            Some(big.cmp(&floor).then(Ordering::Less))
+        }
+        None if n.is_nan() => None,
+        None if n > 0.0 => Some(Ordering::Less),
+        None => Some(Ordering::Greater),
+    }
+}
+
+/// 整数值的 Number 转换为 BigInt（非整数、NaN、无穷返回 `None`）
+pub fn from_integral(n: f64) -> Option<BigInt> {
+    if n.is_finite() && n.fract() == 0.0 {
+        BigInt::from_f64(n)
+    } else {
+        None
+    }
+}
+
+/// BigInt 与 Number 是否表示同一个整数
+pub fn equals_number(big: &BigInt, n: f64) -> bool {
+    from_integral(n).is_some_and(|n| *big == n)
+}
+
+/// 能够被 f64 精确表示的 BigInt 转换为 Number（用于哈希：`1n` 与 `1` 是同一个字典键）
+pub fn to_exact_f64(big: &BigInt) -> Option<f64> {
+    let n = big.to_f64()?;
+    from_integral(n).filter(|back| back == big).map(|_| n)
+}
+
+/// 解析 `bigint("123")` 的字符串参数
+pub fn parse(text: &str) -> Option<BigInt> {
+    text.trim().parse().ok()
+}
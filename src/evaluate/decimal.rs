@@ -0,0 +1,136 @@
+use crate::{
+    ast::Operator,
+    evaluate::{error::RuntimeError, value::Value},
+};
+use num_bigint::BigInt;
+use rust_decimal::Decimal;
+use std::{cmp::Ordering, str::FromStr};
+
+/// 十进制小数 (Decimal) 运算
+///
+/// - Decimal 以十进制存储（最多 28 位有效数字），`decimal("0.1") + decimal("0.2")` 精确等于 `decimal("0.3")`。
+/// - 另一侧是整数值的 Number 或 BigInt 时自动提升为 Decimal；
+///   非整数的 Number 本身已经带有二进制误差，不能隐式参与运算，需要先用 `decimal()` 显式转换。
+/// - `%` 的余数总是非负（与 Number 的 `%` 一致）；溢出、除以零报错而不是静默丢失精度。
+///
+/// # 返回值
+/// * `None` - 两侧都不是 Decimal，或者运算符不由这里处理（eg. `==` 与字符串拼接）
+pub fn binary(op: &Operator, left: &Value, right: &Value) -> Option<Result<Value, RuntimeError>> {
+    if !matches!(left, Value::Decimal(_)) && !matches!(right, Value::Decimal(_)) {
+        return None;
+    }
+
+    let (a, b) = match (promote(left)?, promote(right)?) {
+        (Ok(a), Ok(b)) => (a, b),
+        (Err(e), _) | (_, Err(e)) => return Some(Err(e)),
+    };
+
+    match op {
+        Operator::Less => Some(Ok(Value::Boolean(a < b))),
+        Operator::LessEqual => Some(Ok(Value::Boolean(a <= b))),
+        Operator::Greater => Some(Ok(Value::Boolean(a > b))),
+        Operator::GreaterEqual => Some(Ok(Value::Boolean(a >= b))),
+        Operator::Add
+        | Operator::Sub
+        | Operator::Mul
+        | Operator::Div
+        | Operator::FloorDiv
+        | Operator::Mod => Some(arithmetic(op, a, b).map(Value::Decimal)),
+        _ => None,
+    }
+}
+
+fn arithmetic(op: &Operator, a: Decimal, b: Decimal) -> Result<Decimal, RuntimeError> {
+    if matches!(op, Operator::Div | Operator::FloorDiv | Operator::Mod) && b.is_zero() {
+        return Err(RuntimeError::DivisionByZero);
+    }
+
+    let result = match op {
+        Operator::Add => a.checked_add(b),
+        Operator::Sub => a.checked_sub(b),
+        Operator::Mul => a.checked_mul(b),
+        Operator::Div => a.checked_div(b),
+        Operator::FloorDiv => a.checked_div(b).map(|q| q.floor()),
+        Operator::Mod => a.checked_rem(b).map(|r| {
+            if r.is_sign_negative() && !r.is_zero() {
+                r + b.abs()
+            } else {
+                r
+            }
+        }),
+        _ => unreachable!("filtered by binary()"),
+    };
+
+    result.ok_or_else(|| RuntimeError::Generic("Decimal overflow.".into()))
+}
+
+/// 把参与运算的值转换为 Decimal
+///
+/// # 返回值
+/// * `None` - 值不是数字，交给通用的类型错误处理
+fn promote(value: &Value) -> Option<Result<Decimal, RuntimeError>> {
+    match value {
+        Value::Decimal(d) => Some(Ok(*d)),
+        Value::Number(n) if n.is_finite() && n.fract() == 0.0 => Some(from_number(*n)),
+        Value::Number(n) => Some(Err(RuntimeError::TypeError(format!(
+            "Cannot mix Decimal with non-integer Number {}; use decimal() to convert it explicitly.",
+            n
+        )))),
+        Value::BigInt(n) => Some(
+            Decimal::from_str(&n.to_string())
+                .map_err(|_| RuntimeError::Generic("Decimal overflow.".into())),
+        ),
+        _ => None,
+    }
+}
+
+/// Number 转换为 Decimal：按照 Number 的最短十进制表示转换，`decimal(0.1)` 得到的就是 0.1
+pub fn from_number(n: f64) -> Result<Decimal, RuntimeError> {
+    if !n.is_finite() {
+        return Err(RuntimeError::Generic(format!(
+            "Cannot convert {} to Decimal.",
+            n
+        )));
+    }
+
+    // 极大 / 极小的数字使用科学计数法表示
+    Decimal::from_str(&n.to_string())
+        .or_else(|_| Decimal::from_scientific(&format!("{:e}", n)))
+        .map_err(|_| RuntimeError::Generic(format!("Cannot convert {} to Decimal.", n)))
+}
+
+/// 解析 `decimal("1.25")` 的字符串参数（支持 `1.5e3` 形式）
+pub fn parse(text: &str) -> Option<Decimal> {
+    let text = text.trim();
+    Decimal::from_str(text)
+        .or_else(|_| Decimal::from_scientific(text))
+        .ok()
+}
+
+/// Decimal 与 Number 是否表示同一个数：只有整数值的 Number 与 Decimal 相等，
+/// 与哈希键保持一致（`decimal("1.0")` 与 `1` 是同一个字典键）
+pub fn equals_number(decimal: &Decimal, n: f64) -> bool {
+    n.is_finite()
+        && n.fract() == 0.0
+        && from_number(n).is_ok_and(|n| decimal.cmp(&n) == Ordering::Equal)
+}
+
+/// 整数值的 Decimal 转换为 BigInt（用于与 BigInt 判等及哈希），非整数返回 `None`
+pub fn to_bigint(decimal: &Decimal) -> Option<BigInt> {
+    if !decimal.fract().is_zero() {
+        return None;
+    }
+    super::bigint::parse(&decimal.trunc().normalize().to_string())
+}
+
+/// 整数值的 Decimal 转换为 Number（用于哈希），非整数或超出精确范围返回 `None`
+pub fn to_exact_integer(decimal: &Decimal) -> Option<f64> {
+    if !decimal.fract().is_zero() {
+        return None;
+    }
+    let n: f64 = decimal.to_string().parse().ok()?;
+    from_number(n)
+        .ok()
+        .filter(|back| back == decimal)
+        .map(|_| n)
+}
@@ -122,21 +122,16 @@ impl Environment {
     /// * `distance` - 变量定义距离当前环境的层数（0 表示当前环境）
     /// * `name` - 变量名
     ///
-    /// # Panics
-    /// 如果 `distance` 大于实际环境链的深度，或者在目标环境找不到变量，
-    /// 代码会 Panic。理论上不会发生，因为 Resolver 阶段已经保证了变量的存在性。
+    /// # 返回值
+    /// * `None` - `distance` 超出了环境链的深度，或者目标环境中没有该变量。
+    ///   Resolver 保证了这种情况不会发生，这里仍然返回 `None` 交给调用方报错，而不是 Panic。
     pub fn get_at(&self, distance: usize, name: &str) -> Option<Value> {
         if distance == 0 {
             // 就在当前环境
             return self.values.get(name).cloned();
         }
         // 递归去父环境找 (distance - 1)
-        // unwrap 是安全的，因为 Resolver 保证了 distance 是有效的，且父环境一定存在
-        self.enclosing
-            .as_ref()
-            .unwrap()
-            .borrow()
-            .get_at(distance - 1, name)
+        self.enclosing.as_ref()?.borrow().get_at(distance - 1, name)
     }
 
     /// 在指定的距离（深度）赋值 (Static Assignment)
@@ -147,17 +142,19 @@ impl Environment {
     /// * `distance` - 目标环境距离当前环境的层数
     /// * `name` - 变量名
     /// * `value` - 新值
-    pub fn assign_at(&mut self, distance: usize, name: &str, value: Value) {
+    ///
+    /// # 返回值
+    /// * `false` - `distance` 超出了环境链的深度
+    pub fn assign_at(&mut self, distance: usize, name: &str, value: Value) -> bool {
         if distance == 0 {
             self.values.insert(name.to_string(), value);
-            return;
+            return true;
         }
         // 递归向上传递赋值操作
-        self.enclosing
-            .as_ref()
-            .unwrap()
-            .borrow_mut()
-            .assign_at(distance - 1, name, value);
+        match &self.enclosing {
+            Some(enclosing) => enclosing.borrow_mut().assign_at(distance - 1, name, value),
+            None => false,
+        }
     }
 }
 
@@ -15,8 +15,26 @@ pub enum RuntimeError {
     DivisionByZero,
     Return(super::Value),
     Print(String),
-    Break,
-    Continue,
+    Break(Option<String>),    // 可选的目标循环标签
+    Continue(Option<String>), // 可选的目标循环标签
+    Exit(i32),                // exit(code)：一路冒泡到 main，以该状态码结束进程
+}
+
+impl RuntimeError {
+    /// 转换为 catch 可以捕获的异常值
+    ///
+    /// `throw` 抛出的值原样交给 catch；其他运行时错误以错误信息字符串的形式被捕获；
+    /// return / break / continue / exit 是控制流信号，不能被捕获，原样返回。
+    pub fn into_exception(self) -> Result<super::Value, RuntimeError> {
+        match self {
+            RuntimeError::Catchable(value) => Ok(value),
+            RuntimeError::Return(_)
+            | RuntimeError::Break(_)
+            | RuntimeError::Continue(_)
+            | RuntimeError::Exit(_) => Err(self),
+            other => Ok(super::Value::String(other.to_string())),
+        }
+    }
 }
 
 impl fmt::Display for RuntimeError {
@@ -30,9 +48,10 @@ impl fmt::Display for RuntimeError {
             RuntimeError::ArgumentError(msg) => write!(f, "Argument error: {}", msg),
             RuntimeError::DivisionByZero => write!(f, "Division by zero."),
             RuntimeError::Return(_) => write!(f, "Cannot 'return' from top-level code."),
-            RuntimeError::Break => write!(f, "Cannot use 'break' outside of a loop."),
-            RuntimeError::Continue => write!(f, "Cannot use 'continue' outside of a loop."),
+            RuntimeError::Break(_) => write!(f, "Cannot use 'break' outside of a loop."),
+            RuntimeError::Continue(_) => write!(f, "Cannot use 'continue' outside of a loop."),
             RuntimeError::Print(s) => write!(f, "{}", s),
+            RuntimeError::Exit(code) => write!(f, "Script exited with code {}.", code),
         }
     }
 }
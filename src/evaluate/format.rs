@@ -0,0 +1,184 @@
+use crate::evaluate::{Interpreter, error::RuntimeError, value::Value};
+
+/// 格式化字符串 (`format` / `printf`)
+///
+/// 占位符写作 `{}`（依次取下一个参数）或 `{n}`（取第 n 个参数），冒号后可以跟格式说明：
+/// `[[fill]align][0][width][.precision]`，eg. `{:>8}`、`{:*^9}`、`{:06.2}`。
+/// - align：`<` 左对齐、`>` 右对齐、`^` 居中；数字默认右对齐，其余默认左对齐。
+/// - `0`：数字用 0 补齐到 width，符号保留在最前面。
+/// - precision：数字保留的小数位数；其余值截断到该字符数。
+///
+/// 值先按 `print` 的规则转为文本（实例会调用 `__str__`），`{{` / `}}` 输出字面的花括号。
+impl Interpreter {
+    pub fn format_template(
+        &mut self,
+        template: &str,
+        args: &[Value],
+    ) -> Result<String, RuntimeError> {
+        let mut out = String::new();
+        let mut next_arg = 0;
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    out.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    out.push('}');
+                }
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => placeholder.push(c),
+                            None => {
+                                return Err(RuntimeError::Generic(
+                                    "Unclosed '{' in format string.".into(),
+                                ));
+                            }
+                        }
+                    }
+
+                    let (index, spec) = placeholder
+                        .split_once(':')
+                        .unwrap_or((placeholder.as_str(), ""));
+                    let index = if index.is_empty() {
+                        next_arg += 1;
+                        next_arg - 1
+                    } else {
+                        index.parse::<usize>().map_err(|_| {
+                            RuntimeError::Generic(format!(
+                                "Invalid placeholder '{{{}}}' in format string.",
+                                placeholder
+                            ))
+                        })?
+                    };
+                    let Some(value) = args.get(index) else {
+                        return Err(RuntimeError::Generic(format!(
+                            "Format string refers to argument {} but only {} were given.",
+                            index,
+                            args.len()
+                        )));
+                    };
+
+                    let spec = Spec::parse(spec)?;
+                    let text = self.format_value(value, &spec)?;
+                    out.push_str(&text);
+                }
+                '}' => {
+                    return Err(RuntimeError::Generic(
+                        "Unmatched '}' in format string.".into(),
+                    ));
+                }
+                _ => out.push(c),
+            }
+        }
+        Ok(out)
+    }
+
+    fn format_value(&mut self, value: &Value, spec: &Spec) -> Result<String, RuntimeError> {
+        let numeric = matches!(value, Value::Number(_));
+        let mut text = match (value, spec.precision) {
+            (Value::Number(n), Some(precision)) => format!("{:.*}", precision, n),
+            (_, Some(precision)) => self.stringify(value)?.chars().take(precision).collect(),
+            _ => self.stringify(value)?,
+        };
+
+        let len = text.chars().count();
+        if len >= spec.width {
+            return Ok(text);
+        }
+        let padding = spec.width - len;
+
+        if spec.zero && numeric {
+            let sign = if text.starts_with(['-', '+']) {
+                text.remove(0).to_string()
+            } else {
+                String::new()
+            };
+            return Ok(format!("{}{}{}", sign, "0".repeat(padding), text));
+        }
+
+        let align = spec.align.unwrap_or(if numeric { '>' } else { '<' });
+        let (left, right) = match align {
+            '>' => (padding, 0),
+            '^' => (padding / 2, padding - padding / 2),
+            _ => (0, padding),
+        };
+        let fill = spec.fill.to_string();
+        Ok(format!(
+            "{}{}{}",
+            fill.repeat(left),
+            text,
+            fill.repeat(right)
+        ))
+    }
+}
+
+/// 冒号之后的格式说明
+struct Spec {
+    fill: char,
+    align: Option<char>,
+    zero: bool,
+    width: usize,
+    precision: Option<usize>,
+}
+
+impl Spec {
+    fn parse(spec: &str) -> Result<Spec, RuntimeError> {
+        let invalid = || RuntimeError::Generic(format!("Invalid format spec '{}'.", spec));
+        let chars: Vec<char> = spec.chars().collect();
+        let mut parsed = Spec {
+            fill: ' ',
+            align: None,
+            zero: false,
+            width: 0,
+            precision: None,
+        };
+
+        // 对齐符号前可以带一个填充字符
+        let mut i = 0;
+        if chars.len() >= 2 && matches!(chars[1], '<' | '>' | '^') {
+            parsed.fill = chars[0];
+            parsed.align = Some(chars[1]);
+            i = 2;
+        } else if chars.first().is_some_and(|c| matches!(c, '<' | '>' | '^')) {
+            parsed.align = Some(chars[0]);
+            i = 1;
+        }
+
+        if chars.get(i) == Some(&'0') {
+            parsed.zero = true;
+            i += 1;
+        }
+
+        let rest: String = chars[i..].iter().collect();
+        let (width, precision) = match rest.split_once('.') {
+            Some((width, precision)) => (width, Some(precision)),
+            None => (rest.as_str(), None),
+        };
+        // width 与 precision 不超过 u16::MAX：过大的精度会让 `format!` panic，过大的宽度会耗尽内存
+        let bounded = |text: &str| -> Result<usize, RuntimeError> {
+            let n: usize = text.parse().map_err(|_| invalid())?;
+            if n > usize::from(u16::MAX) {
+                return Err(RuntimeError::Generic(format!(
+                    "Format width and precision must not exceed {}, got '{}'.",
+                    u16::MAX,
+                    spec
+                )));
+            }
+            Ok(n)
+        };
+        if !width.is_empty() {
+            parsed.width = bounded(width)?;
+        }
+        if let Some(precision) = precision {
+            parsed.precision = Some(bounded(precision)?);
+        }
+        Ok(parsed)
+    }
+}
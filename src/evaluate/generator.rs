@@ -0,0 +1,572 @@
+use crate::{
+    ast::Stmt,
+    evaluate::{
+        Interpreter, Value, environment::Environment, error::RuntimeError, interpreter::LoopSignal,
+    },
+    std_lib::{io::file_system::lines::LineReader, iter::Adapter, lookup_method},
+    tokenizer::Token,
+};
+use std::{cell::RefCell, mem, rc::Rc, vec};
+
+/*
+ * 生成器 (Generator)
+ *
+ * 树遍历解释器的执行状态保存在 Rust 调用栈上，无法在 yield 处直接“挂起”。
+ * 因此生成器不走 `execute`，而是用一个显式的帧栈重新进入解释器：
+ *
+ * - 每个帧记录一段正在执行的代码以及它所在的环境（代码块执行到第几条语句、循环是否已开始、for-in 的迭代状态）。
+ * - 不包含 yield 的语句直接交给 `execute` 整体执行；包含 yield 的语句被拆成帧压栈。
+ * - 遇到 yield 时停止推进，帧栈留在生成器中，下次恢复时从栈顶继续。
+ * - break / continue 沿帧栈向下寻找对应的循环帧；return 或执行完毕时生成器结束。
+ */
+
+/// 生成器对象：保存暂停时的帧栈
+#[derive(Debug, Clone)]
+pub struct Generator {
+    pub name: String,
+    frames: Vec<Frame>,
+    /// 正在执行中（生成器在自身内部被再次恢复时报错）
+    running: bool,
+}
+
+impl Generator {
+    /// 以函数体和绑定好参数的环境创建生成器，第一次恢复时才开始执行
+    pub fn new(name: String, body: Vec<Stmt>, env: Rc<RefCell<Environment>>) -> Self {
+        Self {
+            name,
+            frames: vec![Frame::Block {
+                stmts: body,
+                index: 0,
+                env,
+            }],
+            running: false,
+        }
+    }
+}
+
+/// 生成器按身份比较：只有同一个生成器对象才相等
+impl PartialEq for Generator {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Frame {
+    /// 顺序执行的一组语句
+    Block {
+        stmts: Vec<Stmt>,
+        index: usize,
+        env: Rc<RefCell<Environment>>,
+    },
+    /// while / for 循环语句本身；`started` 之后每轮开始前先求值 increment
+    Loop {
+        label: Option<String>,
+        stmt: Box<Stmt>,
+        env: Rc<RefCell<Environment>>,
+        started: bool,
+    },
+    /// for-in 循环
+    ForIn {
+        label: Option<String>,
+        vars: Vec<Token>,
+        iteration: Iteration,
+        body: Box<Stmt>,
+        env: Rc<RefCell<Environment>>,
+    },
+}
+
+/// 推进一步的结果
+enum Step {
+    /// 语句已执行完
+    Next,
+    /// 进入新的帧
+    Push(Frame),
+    /// 当前帧执行完毕
+    Pop,
+    /// 暂停并产出一个值
+    Yield(Value),
+}
+
+/// 迭代状态，for-in 与 `iter()` 返回的迭代器共用
+///
+/// 集合在迭代开始时拍下快照；生成器与用户定义的迭代器则每轮取一次，按需产出。
+#[derive(Debug, Clone)]
+pub enum Iteration {
+    /// (键 / 下标, 值) 序列；单个循环变量时字典取键，其余取值
+    Entries {
+        entries: vec::IntoIter<(Value, Value)>,
+        single_is_key: bool,
+    },
+    Generator {
+        generator: Rc<RefCell<Generator>>,
+        index: usize,
+    },
+    /// 迭代器协议：反复调用实例的 `next()`，返回 nil 时结束
+    Protocol { iterator: Value, index: usize },
+    /// `iter()` 返回的原生迭代器，与其他持有者共享进度
+    Shared(Rc<RefCell<Iteration>>),
+    /// `iter` 模块组合出的惰性迭代器
+    Adapter { adapter: Box<Adapter>, index: usize },
+    /// `fs.readLines` 返回的逐行读取器
+    Lines {
+        reader: Box<LineReader>,
+        index: usize,
+    },
+}
+
+/// 迭代器按身份比较
+impl PartialEq for Iteration {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Iteration {
+    /// 取下一组 (键, 值)，迭代结束时返回 None
+    pub fn next(
+        &mut self,
+        interpreter: &mut Interpreter,
+    ) -> Result<Option<(Value, Value)>, RuntimeError> {
+        let index = |i: &mut usize| {
+            *i += 1;
+            Value::Number((*i - 1) as f64)
+        };
+        match self {
+            Iteration::Entries { entries, .. } => Ok(entries.next()),
+            Iteration::Generator {
+                generator,
+                index: i,
+            } => {
+                let generator = generator.clone();
+                let Some(value) = interpreter.resume_generator(&generator)? else {
+                    return Ok(None);
+                };
+                Ok(Some((index(i), value)))
+            }
+            Iteration::Protocol { iterator, index: i } => {
+                let next = interpreter.invoke_method(&iterator.clone(), "next", Vec::new())?;
+                if next == Value::Nil {
+                    return Ok(None);
+                }
+                Ok(Some((index(i), next)))
+            }
+            Iteration::Adapter { adapter, index: i } => {
+                let Some(value) = adapter.next(interpreter)? else {
+                    return Ok(None);
+                };
+                Ok(Some((index(i), value)))
+            }
+            Iteration::Lines { reader, index: i } => {
+                let Some(line) = reader.next(interpreter)? else {
+                    return Ok(None);
+                };
+                Ok(Some((index(i), line)))
+            }
+            Iteration::Shared(shared) => {
+                let Ok(mut shared) = shared.try_borrow_mut() else {
+                    return Err(RuntimeError::Generic("Iterator is already running.".into()));
+                };
+                shared.next(interpreter)
+            }
+        }
+    }
+
+    /// 取下一个元素：单个循环变量时得到的值（字典为键）
+    pub fn next_item(
+        &mut self,
+        interpreter: &mut Interpreter,
+    ) -> Result<Option<Value>, RuntimeError> {
+        let single_is_key = self.single_is_key();
+        Ok(self
+            .next(interpreter)?
+            .map(|(key, value)| if single_is_key { key } else { value }))
+    }
+
+    fn single_is_key(&self) -> bool {
+        match self {
+            Iteration::Entries { single_is_key, .. } => *single_is_key,
+            Iteration::Shared(shared) => shared.try_borrow().is_ok_and(|s| s.single_is_key()),
+            _ => false,
+        }
+    }
+
+    /// 在新的作用域中定义本轮的循环变量
+    pub fn bind(
+        &self,
+        vars: &[Token],
+        key: Value,
+        value: Value,
+        enclosing: Rc<RefCell<Environment>>,
+    ) -> Rc<RefCell<Environment>> {
+        let single_is_key = self.single_is_key();
+
+        let mut env = Environment::with_enclosing(enclosing);
+        match vars {
+            [item] => env.define(item.lexeme.clone(), if single_is_key { key } else { value }),
+            [k, v] => {
+                env.define(k.lexeme.clone(), key);
+                env.define(v.lexeme.clone(), value);
+            }
+            _ => unreachable!("for-in accepts one or two loop variables"),
+        }
+        Rc::new(RefCell::new(env))
+    }
+}
+
+impl Interpreter {
+    /// 为 for-in 准备迭代状态
+    pub fn iterate(&mut self, iterable: Value) -> Result<Iteration, RuntimeError> {
+        let index = |i: usize| Value::Number(i as f64);
+        let (entries, single_is_key): (Vec<_>, bool) = match iterable {
+            Value::Generator(generator) => {
+                return Ok(Iteration::Generator {
+                    generator,
+                    index: 0,
+                });
+            }
+            Value::Iterator(iteration) => return Ok(Iteration::Shared(iteration)),
+            // 用户定义的迭代器：定义了 next() 的实例直接迭代，只定义了 iter() 的先取得迭代器
+            Value::Instance(ref instance) => {
+                let class = instance.borrow().class.clone();
+                let (has_next, has_iter) = {
+                    let class = class.borrow();
+                    (
+                        class.bind_method("next", iterable.clone()).is_some(),
+                        class.bind_method("iter", iterable.clone()).is_some(),
+                    )
+                };
+                if has_next {
+                    return Ok(Iteration::Protocol {
+                        iterator: iterable,
+                        index: 0,
+                    });
+                }
+                if has_iter {
+                    let iterator = self.invoke_method(&iterable, "iter", Vec::new())?;
+                    if iterator == iterable {
+                        return Err(RuntimeError::TypeError(format!(
+                            "iter() of '{}' must return an iterator with next().",
+                            class.borrow().name
+                        )));
+                    }
+                    return self.iterate(iterator);
+                }
+                return Err(RuntimeError::TypeError(format!(
+                    "Instance of '{}' is not iterable: define iter() or next().",
+                    class.borrow().name
+                )));
+            }
+            // 先拍下快照：循环体内修改集合不会影响本次迭代
+            Value::Dict(dict) => (dict.borrow().values().cloned().collect(), true),
+            Value::Bytes(bytes) => (
+                bytes
+                    .iter()
+                    .enumerate()
+                    .map(|(i, b)| (index(i), Value::Number(*b as f64)))
+                    .collect(),
+                false,
+            ),
+            Value::Set(set) => (
+                set.borrow()
+                    .values()
+                    .enumerate()
+                    .map(|(i, (k, _))| (index(i), k.clone()))
+                    .collect(),
+                false,
+            ),
+            Value::List(list) => (
+                list.borrow()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| (index(i), v.clone()))
+                    .collect(),
+                false,
+            ),
+            Value::Tuple(items) => (
+                items
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, v)| (index(i), v))
+                    .collect(),
+                false,
+            ),
+            // 按字符迭代
+            Value::String(s) => (
+                s.chars()
+                    .enumerate()
+                    .map(|(i, c)| (index(i), Value::String(c.to_string())))
+                    .collect(),
+                false,
+            ),
+            other => {
+                return Err(RuntimeError::TypeError(format!(
+                    "Can only iterate over a List, Dict, Set, Tuple, Bytes, String, Generator or Iterator, got {}.",
+                    other.type_name()
+                )));
+            }
+        };
+
+        Ok(Iteration::Entries {
+            entries: entries.into_iter(),
+            single_is_key,
+        })
+    }
+
+    /// 按名字调用值上的方法（实例方法、原生方法或扩展方法）
+    pub fn invoke_method(
+        &mut self,
+        receiver: &Value,
+        name: &str,
+        args: Vec<Value>,
+    ) -> Result<Value, RuntimeError> {
+        let method = match receiver {
+            Value::Instance(instance) => {
+                let class = instance.borrow().class.clone();
+                class.borrow().bind_method(name, receiver.clone())
+            }
+            _ => lookup_method(self, receiver, name),
+        };
+        match method {
+            Some(method) => self.call_value(&method, args),
+            None => Err(RuntimeError::TypeError(format!(
+                "{} has no method '{}'.",
+                receiver.type_name(),
+                name
+            ))),
+        }
+    }
+
+    /// 恢复生成器，执行到下一个 yield
+    ///
+    /// 返回 `None` 表示生成器已经结束（执行完毕或遇到 return）。出错后生成器同样结束。
+    pub fn resume_generator(
+        &mut self,
+        generator: &Rc<RefCell<Generator>>,
+    ) -> Result<Option<Value>, RuntimeError> {
+        let mut frames = {
+            let mut generator = generator.borrow_mut();
+            if generator.running {
+                return Err(RuntimeError::Generic(format!(
+                    "Generator '{}' is already running.",
+                    generator.name
+                )));
+            }
+            generator.running = true;
+            mem::take(&mut generator.frames)
+        };
+
+        let previous = self.environment.clone();
+        let result = self.run_frames(&mut frames);
+        self.environment = previous;
+
+        let mut generator = generator.borrow_mut();
+        generator.running = false;
+        match result {
+            Ok(Some(value)) => {
+                generator.frames = frames;
+                Ok(Some(value))
+            }
+            Ok(None) | Err(RuntimeError::Return(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 推进帧栈直到遇到 yield（返回产出的值）或帧栈清空（返回 None）
+    fn run_frames(&mut self, frames: &mut Vec<Frame>) -> Result<Option<Value>, RuntimeError> {
+        while let Some(frame) = frames.last_mut() {
+            match self.advance(frame) {
+                Ok(Step::Next) => {}
+                Ok(Step::Push(frame)) => frames.push(frame),
+                Ok(Step::Pop) => {
+                    frames.pop();
+                }
+                Ok(Step::Yield(value)) => return Ok(Some(value)),
+                Err(e) => Self::unwind(frames, e)?,
+            }
+        }
+        Ok(None)
+    }
+
+    /// 执行栈顶帧的下一步
+    fn advance(&mut self, frame: &mut Frame) -> Result<Step, RuntimeError> {
+        match frame {
+            Frame::Block { stmts, index, env } => {
+                let Some(stmt) = stmts.get(*index) else {
+                    return Ok(Step::Pop);
+                };
+                *index += 1;
+                self.environment = env.clone();
+                self.enter(stmt)
+            }
+            Frame::Loop {
+                stmt, env, started, ..
+            } => {
+                let (condition, increment, body) = match &**stmt {
+                    Stmt::While { condition, body } => (Some(condition), None, body),
+                    Stmt::For {
+                        condition,
+                        increment,
+                        body,
+                        ..
+                    } => (condition.as_ref(), increment.as_ref(), body),
+                    _ => unreachable!("loop frames hold while / for statements"),
+                };
+
+                self.environment = env.clone();
+                // Note：continue 也要执行 increment
+                if *started && let Some(incr) = increment {
+                    self.evaluate(incr)?;
+                }
+                *started = true;
+
+                if let Some(cond) = condition
+                    && !self.evaluate(cond)?.is_truthy()
+                {
+                    return Ok(Step::Pop);
+                }
+                Ok(Step::Push(Frame::Block {
+                    stmts: vec![(**body).clone()],
+                    index: 0,
+                    env: env.clone(),
+                }))
+            }
+            Frame::ForIn {
+                vars,
+                iteration,
+                body,
+                env,
+                ..
+            } => {
+                let Some((key, value)) = iteration.next(self)? else {
+                    return Ok(Step::Pop);
+                };
+                // 每次迭代使用新的作用域，闭包捕获到的是当次的循环变量
+                let loop_env = iteration.bind(vars, key, value, env.clone());
+                Ok(Step::Push(Frame::Block {
+                    stmts: vec![(**body).clone()],
+                    index: 0,
+                    env: loop_env,
+                }))
+            }
+        }
+    }
+
+    /// 执行一条语句：不含 yield 的语句整体执行，其余拆成帧
+    fn enter(&mut self, stmt: &Stmt) -> Result<Step, RuntimeError> {
+        if stmt.find_yield().is_none() {
+            self.execute(stmt)?;
+            return Ok(Step::Next);
+        }
+
+        match stmt {
+            Stmt::Yield { value, .. } => {
+                let value = match value {
+                    Some(expr) => self.evaluate(expr)?,
+                    None => Value::Nil,
+                };
+                Ok(Step::Yield(value))
+            }
+            Stmt::Block { body } => Ok(Step::Push(Frame::Block {
+                stmts: body.clone(),
+                index: 0,
+                env: Rc::new(RefCell::new(Environment::with_enclosing(
+                    self.environment.clone(),
+                ))),
+            })),
+            Stmt::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                let branch = if self.evaluate(condition)?.is_truthy() {
+                    Some(then_branch)
+                } else {
+                    else_branch.as_ref()
+                };
+                Ok(match branch {
+                    Some(branch) => Step::Push(Frame::Block {
+                        stmts: vec![(**branch).clone()],
+                        index: 0,
+                        env: self.environment.clone(),
+                    }),
+                    None => Step::Next,
+                })
+            }
+            Stmt::Labeled { label, body } => self.enter_loop(body, Some(label.lexeme.clone())),
+            _ => self.enter_loop(stmt, None),
+        }
+    }
+
+    /// 为包含 yield 的循环创建循环帧
+    fn enter_loop(&mut self, stmt: &Stmt, label: Option<String>) -> Result<Step, RuntimeError> {
+        match stmt {
+            Stmt::While { .. } => Ok(Step::Push(Frame::Loop {
+                label,
+                stmt: Box::new(stmt.clone()),
+                env: self.environment.clone(),
+                started: false,
+            })),
+            Stmt::For { initializer, .. } => {
+                // 创建新作用域 (init 变量)
+                let env = Rc::new(RefCell::new(Environment::with_enclosing(
+                    self.environment.clone(),
+                )));
+                self.environment = env.clone();
+                if let Some(init) = initializer {
+                    self.execute(init)?;
+                }
+                Ok(Step::Push(Frame::Loop {
+                    label,
+                    stmt: Box::new(stmt.clone()),
+                    env,
+                    started: false,
+                }))
+            }
+            Stmt::ForIn {
+                vars,
+                iterable,
+                body,
+            } => {
+                let iterable = self.evaluate(iterable)?;
+                Ok(Step::Push(Frame::ForIn {
+                    label,
+                    vars: vars.clone(),
+                    iteration: self.iterate(iterable)?,
+                    body: body.clone(),
+                    env: self.environment.clone(),
+                }))
+            }
+            // Resolver 保证 try 中不会出现 yield
+            _ => Err(RuntimeError::Generic(
+                "Can't yield inside this statement.".into(),
+            )),
+        }
+    }
+
+    /// 错误沿帧栈向下传播：break / continue 交给对应的循环帧处理，其余错误结束生成器
+    fn unwind(frames: &mut Vec<Frame>, mut error: RuntimeError) -> Result<(), RuntimeError> {
+        while let Some(frame) = frames.last() {
+            let label = match frame {
+                Frame::Block { .. } => {
+                    frames.pop();
+                    continue;
+                }
+                Frame::Loop { label, .. } | Frame::ForIn { label, .. } => label.as_deref(),
+            };
+
+            match Self::loop_signal(error, label) {
+                Ok(LoopSignal::Break) => {
+                    frames.pop();
+                    return Ok(());
+                }
+                Ok(LoopSignal::Continue) => return Ok(()),
+                Err(e) => {
+                    error = e;
+                    frames.pop();
+                }
+            }
+        }
+        Err(error)
+    }
+}
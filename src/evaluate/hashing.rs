@@ -0,0 +1,296 @@
+use crate::evaluate::{
+    Interpreter, bigint, decimal,
+    error::RuntimeError,
+    value::{DictMap, HashKey, Value},
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// 字典键的哈希与判等
+///
+/// 基础类型（Nil / Boolean / Number / BigInt / Decimal / String / Bytes）以及由它们组成的元组可以直接规范化为 `HashKey`。
+/// 类实例需要定义 `__hash__`（返回 Number），判等时优先调用 `__eq__`，
+/// 因此包含实例的键不能只靠 `HashKey` 判等，需要在 `Probe` 槽位中逐个比较。
+impl Interpreter {
+    /// 计算值的哈希键
+    ///
+    /// # 返回值
+    /// * `Ok((HashKey, bool))` - 哈希键，以及该键是否需要调用 `__eq__` 判等
+    /// * `Err(RuntimeError)` - 值不可哈希（List / Dict / 函数 / 未定义 `__hash__` 的实例等）
+    pub fn hash_key(&mut self, value: &Value) -> Result<(HashKey, bool), RuntimeError> {
+        match value {
+            Value::Nil => Ok((HashKey::Nil, false)),
+            Value::Boolean(b) => Ok((HashKey::Boolean(*b), false)),
+            Value::Number(n) => Ok((HashKey::number(*n), false)),
+            Value::BigInt(n) => match bigint::to_exact_f64(n) {
+                Some(n) => Ok((HashKey::number(n), false)),
+                None => Ok((HashKey::BigInt(n.clone()), false)),
+            },
+            // 整数值的 Decimal 与同值的 Number / BigInt 使用同一个键
+            Value::Decimal(d) => match (decimal::to_exact_integer(d), decimal::to_bigint(d)) {
+                (Some(n), _) => Ok((HashKey::number(n), false)),
+                (None, Some(n)) => Ok((HashKey::BigInt(n), false)),
+                (None, None) => Ok((HashKey::Decimal(d.normalize()), false)),
+            },
+            Value::String(s) => Ok((HashKey::String(s.clone()), false)),
+            Value::Bytes(bytes) => Ok((HashKey::Bytes(bytes.to_vec()), false)),
+            Value::DateTime(seconds) => {
+                Ok((HashKey::DateTime(HashKey::number_bits(*seconds)), false))
+            }
+            Value::Tuple(elements) => {
+                let mut keys = Vec::with_capacity(elements.len());
+                let mut needs_eq = false;
+                for element in elements {
+                    let (key, eq) = self.hash_key(element)?;
+                    keys.push(key);
+                    needs_eq |= eq;
+                }
+                Ok((HashKey::Tuple(keys), needs_eq))
+            }
+            Value::Instance(instance_rc) => {
+                let method = {
+                    let klass = instance_rc.borrow().class.clone();
+                    klass.borrow().bind_method("__hash__", value.clone())
+                };
+
+                let Some(method) = method else {
+                    return Err(RuntimeError::TypeError(format!(
+                        "Unhashable type: instance of '{}' does not define __hash__.",
+                        instance_rc.borrow().class.borrow().name
+                    )));
+                };
+
+                match self.call_value(&method, vec![])? {
+                    Value::Number(n) => Ok((HashKey::Object(HashKey::number_bits(n)), true)),
+                    other => Err(RuntimeError::TypeError(format!(
+                        "__hash__ must return a Number, got {}.",
+                        other.type_name()
+                    ))),
+                }
+            }
+            other => Err(RuntimeError::TypeError(format!(
+                "Unhashable type: {}.",
+                other.type_name()
+            ))),
+        }
+    }
+
+    /// 判断两个值是否相等
+    ///
+    /// 实例如果定义了 `__eq__` 则调用它；元组、列表、字典逐个元素递归比较，集合按元素比较，其余情况使用结构相等。
+    pub fn values_equal(&mut self, left: &Value, right: &Value) -> Result<bool, RuntimeError> {
+        self.values_equal_in(left, right, &mut Vec::new())
+    }
+
+    /// `comparing` 记录正在比较的容器对：自引用的容器再次遇到同一对时视为相等，避免无限递归
+    fn values_equal_in(
+        &mut self,
+        left: &Value,
+        right: &Value,
+        comparing: &mut Vec<(usize, usize)>,
+    ) -> Result<bool, RuntimeError> {
+        match (left, right) {
+            (Value::BigInt(a), Value::Number(b)) | (Value::Number(b), Value::BigInt(a)) => {
+                Ok(bigint::equals_number(a, *b))
+            }
+            (Value::Decimal(a), Value::Number(b)) | (Value::Number(b), Value::Decimal(a)) => {
+                Ok(decimal::equals_number(a, *b))
+            }
+            (Value::Decimal(a), Value::BigInt(b)) | (Value::BigInt(b), Value::Decimal(a)) => {
+                Ok(decimal::to_bigint(a).is_some_and(|a| a == *b))
+            }
+            (Value::Instance(instance_rc), _) => {
+                let method = {
+                    let klass = instance_rc.borrow().class.clone();
+                    klass.borrow().bind_method("__eq__", left.clone())
+                };
+
+                match method {
+                    Some(method) => Ok(self.call_value(&method, vec![right.clone()])?.is_truthy()),
+                    None => Ok(left == right),
+                }
+            }
+            (Value::Tuple(a), Value::Tuple(b)) => {
+                if a.len() != b.len() {
+                    return Ok(false);
+                }
+                for (x, y) in a.iter().zip(b.iter()) {
+                    if !self.values_equal_in(x, y, comparing)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            (Value::List(a), Value::List(b)) => {
+                let pair = (Rc::as_ptr(a) as usize, Rc::as_ptr(b) as usize);
+                if Rc::ptr_eq(a, b) || comparing.contains(&pair) {
+                    return Ok(true);
+                }
+                // 复制元素后再比较：`__eq__` 可能访问这两个列表
+                let (a, b) = (a.borrow().clone(), b.borrow().clone());
+                if a.len() != b.len() {
+                    return Ok(false);
+                }
+
+                comparing.push(pair);
+                let mut equal = true;
+                for (x, y) in a.iter().zip(b.iter()) {
+                    if !self.values_equal_in(x, y, comparing)? {
+                        equal = false;
+                        break;
+                    }
+                }
+                comparing.pop();
+                Ok(equal)
+            }
+            // 集合：元素个数相同，且 a 的每个元素都在 b 中
+            (Value::Set(a), Value::Set(b)) => {
+                if Rc::ptr_eq(a, b) {
+                    return Ok(true);
+                }
+                if a.borrow().len() != b.borrow().len() {
+                    return Ok(false);
+                }
+                let keys = a
+                    .borrow()
+                    .values()
+                    .map(|(k, _)| k.clone())
+                    .collect::<Vec<_>>();
+                for key in keys {
+                    if !self.dict_contains(b, &key)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            (Value::Dict(a), Value::Dict(b)) => {
+                let pair = (Rc::as_ptr(a) as usize, Rc::as_ptr(b) as usize);
+                if Rc::ptr_eq(a, b) || comparing.contains(&pair) {
+                    return Ok(true);
+                }
+                if a.borrow().len() != b.borrow().len() {
+                    return Ok(false);
+                }
+
+                comparing.push(pair);
+                let entries = a.borrow().values().cloned().collect::<Vec<_>>();
+                let mut equal = true;
+                for (key, x) in entries {
+                    let matched = match self.dict_get(b, &key)? {
+                        Some(y) => self.values_equal_in(&x, &y, comparing)?,
+                        None => false,
+                    };
+                    if !matched {
+                        equal = false;
+                        break;
+                    }
+                }
+                comparing.pop();
+                Ok(equal)
+            }
+            _ => Ok(left == right),
+        }
+    }
+
+    /// 定位键在字典中的存储位置
+    ///
+    /// # 返回值
+    /// * `(HashKey, bool)` - 键对应（或应当插入）的存储位置，以及该位置是否已存在
+    fn dict_locate(
+        &mut self,
+        dict: &RefCell<DictMap>,
+        key: &Value,
+    ) -> Result<(HashKey, bool), RuntimeError> {
+        let (hash, needs_eq) = self.hash_key(key)?;
+        if !needs_eq {
+            let found = dict.borrow().contains_key(&hash);
+            return Ok((hash, found));
+        }
+
+        let mut slot = 0;
+        loop {
+            let probe = HashKey::Probe(Box::new(hash.clone()), slot);
+            // 先克隆出已存储的键，避免调用 __eq__ 时字典仍处于借用状态
+            let stored = dict.borrow().get(&probe).map(|(k, _)| k.clone());
+            match stored {
+                None => return Ok((probe, false)),
+                Some(stored) => {
+                    if self.values_equal(key, &stored)? {
+                        return Ok((probe, true));
+                    }
+                }
+            }
+            slot += 1;
+        }
+    }
+
+    /// 读取字典中的值，不存在时返回 `None`
+    pub fn dict_get(
+        &mut self,
+        dict: &RefCell<DictMap>,
+        key: &Value,
+    ) -> Result<Option<Value>, RuntimeError> {
+        let (slot, found) = self.dict_locate(dict, key)?;
+        if !found {
+            return Ok(None);
+        }
+        Ok(dict.borrow().get(&slot).map(|(_, v)| v.clone()))
+    }
+
+    /// 判断字典是否包含指定键
+    pub fn dict_contains(
+        &mut self,
+        dict: &RefCell<DictMap>,
+        key: &Value,
+    ) -> Result<bool, RuntimeError> {
+        Ok(self.dict_locate(dict, key)?.1)
+    }
+
+    /// 插入或更新字典条目（已存在的键保留原始键对象）
+    pub fn dict_insert(
+        &mut self,
+        dict: &RefCell<DictMap>,
+        key: Value,
+        value: Value,
+    ) -> Result<(), RuntimeError> {
+        let (slot, found) = self.dict_locate(dict, &key)?;
+        let mut dict = dict.borrow_mut();
+        if found {
+            if let Some(entry) = dict.get_mut(&slot) {
+                entry.1 = value;
+            }
+        } else {
+            dict.insert(slot, (key, value));
+        }
+        Ok(())
+    }
+
+    /// 删除字典条目，返回被删除的值
+    pub fn dict_remove(
+        &mut self,
+        dict: &RefCell<DictMap>,
+        key: &Value,
+    ) -> Result<Option<Value>, RuntimeError> {
+        let (slot, found) = self.dict_locate(dict, key)?;
+        if !found {
+            return Ok(None);
+        }
+
+        let mut dict = dict.borrow_mut();
+        let removed = dict.shift_remove(&slot).map(|(_, v)| v);
+
+        // 探测链不能出现空洞：把同一哈希下后续槽位的编号依次前移（保持插入顺序）
+        if let HashKey::Probe(hash, index) = slot {
+            *dict = std::mem::take(&mut *dict)
+                .into_iter()
+                .map(|(key, entry)| match key {
+                    HashKey::Probe(h, i) if h == hash && i > index => {
+                        (HashKey::Probe(h, i - 1), entry)
+                    }
+                    key => (key, entry),
+                })
+                .collect();
+        }
+
+        Ok(removed)
+    }
+}
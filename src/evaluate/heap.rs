@@ -0,0 +1,518 @@
+use crate::evaluate::{
+    Interpreter,
+    environment::Environment,
+    value::{DictMap, RoxClass, RoxInstance, Value},
+};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap, HashSet},
+    mem::{self, size_of},
+    rc::{Rc, Weak},
+};
+
+/// 堆统计 (Heap Statistics)
+///
+/// 值通过 `Rc` 管理，没有独立的堆可以直接遍历，因此统计从解释器的根出发
+/// （全局环境、当前环境、已加载模块、扩展方法），沿引用关系遍历所有可达对象。
+///
+/// - `Rc` 共享的对象（List / Dict / Instance / Class / Module / StringBuilder / NdArray / 环境）按指针去重，
+///   同一个对象无论被引用多少次只计一次。
+/// - 按值存储的类型（Number / String / Tuple / Function 等）每出现一次计一次。
+/// - `bytes` 是估算值：值槽位大小 + 字符串与集合的容量，不含分配器开销。
+/// - 正在执行的调用帧只能看到当前环境链，调用者保存的环境不在统计范围内。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HeapStats {
+    /// 类型名 -> 存活数量
+    pub objects: BTreeMap<&'static str, usize>,
+    /// 存活的环境（作用域）数量
+    pub environments: usize,
+    /// 估算占用字节数
+    pub bytes: usize,
+}
+
+/// 可达对象遍历器
+#[derive(Default)]
+struct HeapWalker {
+    /// 已访问的 `Rc` 对象地址
+    seen: HashSet<*const ()>,
+    stats: HeapStats,
+}
+
+impl HeapWalker {
+    /// 首次访问返回 true
+    fn mark<T>(&mut self, rc: &Rc<T>) -> bool {
+        self.seen.insert(Rc::as_ptr(rc) as *const ())
+    }
+
+    fn visit_env(&mut self, env: &Rc<RefCell<Environment>>) {
+        if !self.mark(env) {
+            return;
+        }
+        self.stats.environments += 1;
+        self.stats.bytes += size_of::<Environment>();
+
+        let env = env.borrow();
+        for (name, value) in &env.values {
+            self.stats.bytes += name.capacity() + size_of::<Value>();
+            self.visit_value(value);
+        }
+        if let Some(enclosing) = &env.enclosing {
+            self.visit_env(enclosing);
+        }
+    }
+
+    fn visit_value(&mut self, value: &Value) {
+        // Rc 对象重复访问时直接返回，避免循环引用导致死循环
+        let is_new = match value {
+            Value::List(rc) => self.mark(rc),
+            Value::Dict(rc) | Value::Set(rc) => self.mark(rc),
+            Value::Class(rc) => self.mark(rc),
+            Value::Instance(rc) => self.mark(rc),
+            Value::Module(rc) => self.mark(rc),
+            Value::StringBuilder(rc) => self.mark(rc),
+            Value::NdArray(rc) => self.mark(rc),
+            Value::Store(rc) => self.mark(rc),
+            _ => true,
+        };
+        if !is_new {
+            return;
+        }
+        *self.stats.objects.entry(value.type_name()).or_default() += 1;
+
+        match value {
+            Value::String(s) | Value::Print(s) => self.stats.bytes += s.capacity(),
+            Value::Bytes(bytes) => self.stats.bytes += bytes.len(),
+            Value::StringBuilder(buffer) => self.stats.bytes += buffer.borrow().capacity(),
+            Value::NdArray(array) => {
+                self.stats.bytes += array.borrow().data.capacity() * size_of::<f64>()
+            }
+            Value::List(list) => {
+                let list = list.borrow();
+                self.stats.bytes += list.capacity() * size_of::<Value>();
+                list.iter().for_each(|v| self.visit_value(v));
+            }
+            Value::Store(store) => store
+                .borrow()
+                .data
+                .values()
+                .for_each(|v| self.visit_value(v)),
+            Value::Tuple(elements) => {
+                self.stats.bytes += elements.capacity() * size_of::<Value>();
+                elements.iter().for_each(|v| self.visit_value(v));
+            }
+            Value::Dict(dict) => {
+                let dict = dict.borrow();
+                self.stats.bytes += dict.capacity() * 2 * size_of::<Value>();
+                for (key, value) in dict.values() {
+                    self.visit_value(key);
+                    self.visit_value(value);
+                }
+            }
+            Value::Set(set) => {
+                let set = set.borrow();
+                self.stats.bytes += set.capacity() * 2 * size_of::<Value>();
+                set.values().for_each(|(key, _)| self.visit_value(key));
+            }
+            Value::Function { closure, .. } => self.visit_env(closure),
+            Value::Class(class) => {
+                let class = class.borrow();
+                class
+                    .methods
+                    .values()
+                    .chain(class.statics.values())
+                    .for_each(|m| self.visit_value(m));
+                if let Some(superclass) = &class.superclass {
+                    self.visit_value(&Value::Class(superclass.clone()));
+                }
+            }
+            Value::Instance(instance) => {
+                let instance = instance.borrow();
+                self.visit_value(&Value::Class(instance.class.clone()));
+                let fields = instance.fields.borrow();
+                self.stats.bytes += fields.len() * size_of::<Value>();
+                fields.values().for_each(|v| self.visit_value(v));
+            }
+            Value::BoundNativeMethod { receiver, method } => {
+                self.visit_value(receiver);
+                self.visit_value(method);
+            }
+            Value::Module(module) => {
+                module
+                    .borrow()
+                    .exports
+                    .values()
+                    .for_each(|v| self.visit_value(v));
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Interpreter {
+    /// 统计从解释器根可达的所有对象
+    pub fn heap_stats(&self) -> HeapStats {
+        let mut walker = HeapWalker::default();
+
+        walker.visit_env(&self.globals);
+        walker.visit_env(&self.environment);
+        for module in self.modules.values() {
+            walker.visit_value(module);
+        }
+        for method in self.extensions.values().flat_map(|m| m.values()) {
+            walker.visit_value(method);
+        }
+
+        walker.stats
+    }
+}
+
+/*
+ * 循环引用回收 (Cycle Collection)
+ *
+ * 值由 Rc 管理，互相引用的对象（eg. 闭包环境中保存了捕获它的函数、`n.self = n`）
+ * 在离开作用域后引用计数永远不会归零。这里采用与 CPython 相同的「试探删除」思路：
+ *
+ * 1. 记录所有可能成环的容器对象（被闭包捕获的环境、实例、类、List / Dict 字面量），只保存 Weak。
+ * 2. 对每个存活对象，用强引用计数减去来自其他被记录对象的引用数，
+ *    剩余大于 0 说明还有外部引用（解释器的根、Rust 调用栈上的临时值等）。
+ * 3. 从有外部引用的对象出发标记可达对象，剩余的就是只被环自身持有的垃圾。
+ *
+ * 由于外部引用是通过计数推断的，不需要枚举 Rust 调用栈上的根，执行途中随时可以回收。
+ * 未被记录的容器（eg. 原生函数创建的 List）会被视为外部引用，只会让判断更保守。
+ */
+
+/// 被记录的容器对象
+#[derive(Clone)]
+enum Object {
+    Env(Rc<RefCell<Environment>>),
+    List(Rc<RefCell<Vec<Value>>>),
+    Dict(Rc<RefCell<DictMap>>),
+    Instance(Rc<RefCell<RoxInstance>>),
+    Class(Rc<RefCell<RoxClass>>),
+}
+
+enum WeakObject {
+    Env(Weak<RefCell<Environment>>),
+    List(Weak<RefCell<Vec<Value>>>),
+    Dict(Weak<RefCell<DictMap>>),
+    Instance(Weak<RefCell<RoxInstance>>),
+    Class(Weak<RefCell<RoxClass>>),
+}
+
+impl WeakObject {
+    fn upgrade(&self) -> Option<Object> {
+        match self {
+            WeakObject::Env(w) => w.upgrade().map(Object::Env),
+            WeakObject::List(w) => w.upgrade().map(Object::List),
+            WeakObject::Dict(w) => w.upgrade().map(Object::Dict),
+            WeakObject::Instance(w) => w.upgrade().map(Object::Instance),
+            WeakObject::Class(w) => w.upgrade().map(Object::Class),
+        }
+    }
+
+    fn is_alive(&self) -> bool {
+        match self {
+            WeakObject::Env(w) => w.strong_count() > 0,
+            WeakObject::List(w) => w.strong_count() > 0,
+            WeakObject::Dict(w) => w.strong_count() > 0,
+            WeakObject::Instance(w) => w.strong_count() > 0,
+            WeakObject::Class(w) => w.strong_count() > 0,
+        }
+    }
+}
+
+/// 对象表：地址 -> 弱引用，超过上限时清理已释放的条目
+struct Tracker {
+    objects: HashMap<*const (), WeakObject>,
+    limit: usize,
+}
+
+const INITIAL_LIMIT: usize = 1024;
+
+thread_local! {
+    static TRACKER: RefCell<Tracker> = RefCell::new(Tracker {
+        objects: HashMap::new(),
+        limit: INITIAL_LIMIT,
+    });
+}
+
+fn address<T>(rc: &Rc<T>) -> *const () {
+    Rc::as_ptr(rc) as *const ()
+}
+
+/// 记录可能参与循环引用的对象（函数记录其捕获的环境）
+pub fn track(value: &Value) {
+    let (ptr, weak) = match value {
+        Value::Function { closure, .. } => {
+            (address(closure), WeakObject::Env(Rc::downgrade(closure)))
+        }
+        Value::List(rc) => (address(rc), WeakObject::List(Rc::downgrade(rc))),
+        Value::Dict(rc) => (address(rc), WeakObject::Dict(Rc::downgrade(rc))),
+        Value::Instance(rc) => (address(rc), WeakObject::Instance(Rc::downgrade(rc))),
+        Value::Class(rc) => (address(rc), WeakObject::Class(Rc::downgrade(rc))),
+        _ => return,
+    };
+
+    TRACKER.with(|tracker| {
+        let mut tracker = tracker.borrow_mut();
+        // 地址可能被已释放对象的旧条目占用，此时直接覆盖
+        if tracker.objects.get(&ptr).is_some_and(WeakObject::is_alive) {
+            return;
+        }
+        tracker.objects.insert(ptr, weak);
+
+        if tracker.objects.len() > tracker.limit {
+            tracker.objects.retain(|_, weak| weak.is_alive());
+            tracker.limit = (tracker.objects.len() * 2).max(INITIAL_LIMIT);
+        }
+    });
+}
+
+/// 收集值直接持有的 Rc 对象地址（按值存储的 Tuple / 绑定方法会继续向内展开）
+fn value_refs(value: &Value, out: &mut Vec<*const ()>) {
+    match value {
+        Value::List(rc) => out.push(address(rc)),
+        Value::Dict(rc) => out.push(address(rc)),
+        Value::Instance(rc) => out.push(address(rc)),
+        Value::Class(rc) => out.push(address(rc)),
+        Value::Module(rc) => out.push(address(rc)),
+        Value::StringBuilder(rc) => out.push(address(rc)),
+        Value::NdArray(rc) => out.push(address(rc)),
+        Value::Function { closure, .. } => out.push(address(closure)),
+        Value::Tuple(elements) => elements.iter().for_each(|v| value_refs(v, out)),
+        Value::BoundNativeMethod { receiver, method } => {
+            value_refs(receiver, out);
+            value_refs(method, out);
+        }
+        _ => {}
+    }
+}
+
+impl Object {
+    fn address(&self) -> *const () {
+        match self {
+            Object::Env(rc) => address(rc),
+            Object::List(rc) => address(rc),
+            Object::Dict(rc) => address(rc),
+            Object::Instance(rc) => address(rc),
+            Object::Class(rc) => address(rc),
+        }
+    }
+
+    fn strong_count(&self) -> usize {
+        match self {
+            Object::Env(rc) => Rc::strong_count(rc),
+            Object::List(rc) => Rc::strong_count(rc),
+            Object::Dict(rc) => Rc::strong_count(rc),
+            Object::Instance(rc) => Rc::strong_count(rc),
+            Object::Class(rc) => Rc::strong_count(rc),
+        }
+    }
+
+    /// 对象直接持有的引用；对象正被借用时返回 `None`
+    fn refs(&self) -> Option<Vec<*const ()>> {
+        let mut out = Vec::new();
+        match self {
+            Object::Env(rc) => {
+                let env = rc.try_borrow().ok()?;
+                env.values.values().for_each(|v| value_refs(v, &mut out));
+                if let Some(enclosing) = &env.enclosing {
+                    out.push(address(enclosing));
+                }
+            }
+            Object::List(rc) => rc
+                .try_borrow()
+                .ok()?
+                .iter()
+                .for_each(|v| value_refs(v, &mut out)),
+            Object::Dict(rc) => {
+                for (key, value) in rc.try_borrow().ok()?.values() {
+                    value_refs(key, &mut out);
+                    value_refs(value, &mut out);
+                }
+            }
+            Object::Instance(rc) => {
+                let instance = rc.try_borrow().ok()?;
+                out.push(address(&instance.class));
+                let fields = instance.fields.try_borrow().ok()?;
+                fields.values().for_each(|v| value_refs(v, &mut out));
+            }
+            Object::Class(rc) => {
+                let class = rc.try_borrow().ok()?;
+                class
+                    .methods
+                    .values()
+                    .chain(class.statics.values())
+                    .for_each(|v| value_refs(v, &mut out));
+                if let Some(superclass) = &class.superclass {
+                    out.push(address(superclass));
+                }
+            }
+        }
+        Some(out)
+    }
+
+    /// 清空对象内容以打破环，被移出的值放入 `trash` 延后释放
+    fn clear(&self, trash: &mut Vec<Value>, envs: &mut Vec<Rc<RefCell<Environment>>>) {
+        match self {
+            Object::Env(rc) => {
+                if let Ok(mut env) = rc.try_borrow_mut() {
+                    trash.extend(env.values.drain().map(|(_, v)| v));
+                    envs.extend(env.enclosing.take());
+                }
+            }
+            Object::List(rc) => {
+                if let Ok(mut list) = rc.try_borrow_mut() {
+                    trash.append(&mut list);
+                }
+            }
+            Object::Dict(rc) => {
+                if let Ok(mut dict) = rc.try_borrow_mut() {
+                    trash.extend(
+                        mem::take(&mut *dict)
+                            .into_values()
+                            .flat_map(|(k, v)| [k, v]),
+                    );
+                }
+            }
+            Object::Instance(rc) => {
+                if let Ok(instance) = rc.try_borrow()
+                    && let Ok(mut fields) = instance.fields.try_borrow_mut()
+                {
+                    trash.extend(fields.drain().map(|(_, v)| v));
+                }
+            }
+            Object::Class(rc) => {
+                if let Ok(mut class) = rc.try_borrow_mut() {
+                    trash.extend(class.methods.drain().map(|(_, v)| v));
+                    trash.extend(class.statics.drain().map(|(_, v)| v));
+                    if let Some(superclass) = class.superclass.take() {
+                        trash.push(Value::Class(superclass));
+                    }
+                }
+            }
+        }
+    }
+
+    /// 泄漏报告中的描述，`leaked` 用于找出把对象留在环中的变量名 / 字段名
+    fn describe(&self, leaked: &HashSet<*const ()>) -> String {
+        let retaining = |values: &mut dyn Iterator<Item = (&String, &Value)>| {
+            let mut names = values
+                .filter(|(_, v)| {
+                    let mut refs = Vec::new();
+                    value_refs(v, &mut refs);
+                    refs.iter().any(|r| leaked.contains(r))
+                })
+                .map(|(name, _)| name.clone())
+                .collect::<Vec<_>>();
+            names.sort();
+            names.join(", ")
+        };
+
+        match self {
+            Object::Env(rc) => match rc.try_borrow() {
+                Ok(env) => format!(
+                    "closure scope (variables: {})",
+                    retaining(&mut env.values.iter())
+                ),
+                Err(_) => "closure scope".to_string(),
+            },
+            Object::List(rc) => format!("List of {} items", rc.borrow().len()),
+            Object::Dict(rc) => format!("Dict of {} entries", rc.borrow().len()),
+            Object::Instance(rc) => {
+                let instance = rc.borrow();
+                let class_name = instance.class.borrow().name.clone();
+                let fields = instance.fields.borrow();
+                format!(
+                    "instance of {} (fields: {})",
+                    class_name,
+                    retaining(&mut fields.iter())
+                )
+            }
+            Object::Class(rc) => format!("class {}", rc.borrow().name),
+        }
+    }
+}
+
+/// 找出只被循环引用自身持有的对象
+fn find_garbage() -> Vec<Object> {
+    let objects = TRACKER.with(|tracker| {
+        let mut tracker = tracker.borrow_mut();
+        tracker.objects.retain(|_, weak| weak.is_alive());
+        tracker
+            .objects
+            .values()
+            .filter_map(WeakObject::upgrade)
+            .collect::<Vec<_>>()
+    });
+
+    let index = objects
+        .iter()
+        .enumerate()
+        .map(|(i, obj)| (obj.address(), i))
+        .collect::<HashMap<_, _>>();
+
+    // 外部引用数 = 强引用数 - 本函数持有的 1 个 - 来自其他被记录对象的引用
+    let mut external = objects
+        .iter()
+        .map(|obj| obj.strong_count() as isize - 1)
+        .collect::<Vec<_>>();
+    let edges = objects
+        .iter()
+        .map(|obj| {
+            obj.refs().map(|refs| {
+                refs.iter()
+                    .filter_map(|r| index.get(r).copied())
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect::<Vec<_>>();
+    for &target in edges.iter().flatten().flatten() {
+        external[target] -= 1;
+    }
+
+    // 正被借用的对象说明正在使用中，同样视为可达
+    let mut reachable = vec![false; objects.len()];
+    let mut worklist = (0..objects.len())
+        .filter(|&i| external[i] > 0 || edges[i].is_none())
+        .collect::<Vec<_>>();
+    while let Some(i) = worklist.pop() {
+        if mem::replace(&mut reachable[i], true) {
+            continue;
+        }
+        worklist.extend(
+            edges[i]
+                .iter()
+                .flatten()
+                .copied()
+                .filter(|&j| !reachable[j]),
+        );
+    }
+
+    objects
+        .into_iter()
+        .zip(reachable)
+        .filter_map(|(obj, reachable)| (!reachable).then_some(obj))
+        .collect()
+}
+
+/// 回收循环引用垃圾，返回被回收的对象数量
+pub fn collect() -> usize {
+    let garbage = find_garbage();
+    let mut trash = Vec::new();
+    let mut envs = Vec::new();
+    for obj in &garbage {
+        obj.clear(&mut trash, &mut envs);
+    }
+    // 所有借用都已释放，此时再真正析构
+    drop(trash);
+    drop(envs);
+    garbage.len()
+}
+
+/// 泄漏报告：列出当前只被循环引用持有的对象，不做回收
+pub fn leak_report() -> Vec<String> {
+    let garbage = find_garbage();
+    let leaked = garbage.iter().map(Object::address).collect::<HashSet<_>>();
+    garbage.iter().map(|obj| obj.describe(&leaked)).collect()
+}
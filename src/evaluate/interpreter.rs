@@ -1,14 +1,30 @@
 use crate::ast::{Ast, Expr, ExprId, Operator, Stmt};
-use crate::evaluate::value::RoxClass;
-use crate::evaluate::{environment::Environment, error::RuntimeError, value::Value};
+use crate::cache::{ModuleCache, bundle::Bundle};
+use crate::evaluate::value::{DictMap, HashKey, RoxClass, RoxTrait, VARIADIC};
+use crate::evaluate::{
+    bigint, decimal, environment::Environment, error::RuntimeError, generator::Generator, heap,
+    value::Value,
+};
 use crate::std_lib::value::RoxModule;
-use crate::std_lib::{self, lookup_method};
+use crate::std_lib::{
+    self,
+    io::trace::{self, IoTrace},
+    log, lookup_method,
+    registry::Registry,
+    signal::SignalState,
+};
 use crate::tokenizer::Token;
 use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
+/// 循环体中交给当前循环处理的控制流信号
+pub(crate) enum LoopSignal {
+    Break,
+    Continue,
+}
+
 #[allow(clippy::empty_line_after_doc_comments)]
 /**
  * Thinking
@@ -68,8 +84,49 @@ pub struct Interpreter {
     pub path_stack: Vec<PathBuf>,
     // 导出栈，栈顶是当前正在执行的模块的导出列表。
     pub exports_stack: Vec<HashSet<String>>,
+    // 内置类型的扩展方法 (类型名 -> 方法名 -> 方法)，由 `extend` 声明填充
+    pub extensions: HashMap<String, HashMap<String, Value>>,
+    // 内置原生模块注册表，决定 `std.*` 下安装哪些模块
+    pub registry: Registry,
+    // 额外的模块查找目录 (eg. rox.toml 中声明的模块根目录与依赖)
+    pub search_paths: Vec<PathBuf>,
+    // 模块编译缓存 (.roxc)，为 None 时每次导入都重新解析
+    pub module_cache: Option<ModuleCache>,
+    // 正在执行的编译产物 (bundle)，存在时 import 不再访问文件系统
+    pub bundle: Option<Rc<Bundle>>,
+    // bundle 模式下的模块栈，栈顶是当前正在执行的模块键
+    pub bundle_stack: Vec<String>,
+    // include 链，用于检测循环包含
+    pub include_stack: Vec<String>,
+    // 是否允许脚本通过 `setGlobal()` 修改全局绑定（默认只读）
+    pub globals_writable: bool,
+    // 确定性模式下的假时钟（秒），`clock()` / `time.now()` 返回它而不是系统时间
+    pub fake_clock: Option<f64>,
+    // IO 录制 / 回放，为 None 时直接访问真实系统
+    pub io_trace: Option<IoTrace>,
+    // 脚本路径之后的命令行参数，由 `os.args()` 返回
+    pub script_args: Vec<String>,
+    // 当前的函数调用深度
+    pub call_depth: usize,
+    // 剩余可执行的语句数（每条语句、每次循环迭代都会消耗），为 None 时不限制
+    pub step_budget: Option<u64>,
+    // 每层函数调用登记的 defer 表达式及其所在的环境，函数退出时逆序求值
+    pub deferred: Vec<Vec<(Expr, Rc<RefCell<Environment>>)>>,
+    // 除以零时按 IEEE 754 返回 Infinity / NaN，而不是报错（默认报错）
+    pub ieee_division: bool,
+    // `term` 模块是否输出颜色，为 None 时根据 stdout 是否是终端自动决定
+    pub term_colors: Option<bool>,
+    // `log` 模块的最低输出级别，由 `log.setLevel()` 修改
+    pub log_level: log::Level,
+    // 脚本通过 `signal.on()` 注册的信号处理函数，在每条语句执行前分发
+    pub signals: SignalState,
 }
 
+/// 函数调用的最大嵌套深度
+///
+/// 树遍历解释器每层调用都会占用若干 Rust 栈帧，需要配合足够大的线程栈（见 `rox::STACK_SIZE`）。
+pub const MAX_CALL_DEPTH: usize = 1000;
+
 impl Interpreter {
     /// 创建一个新的解释器实例
     ///
@@ -78,10 +135,17 @@ impl Interpreter {
     /// 2. (可选) 在全局环境中注册原生函数 (Native Functions)。
     /// 3. 将当前环境 (`environment`) 和全局环境 (`globals`) 都指向这个新环境。
     pub fn new() -> Self {
-        // 创建根环境 (Global Scope)
-        let globals = Rc::new(RefCell::new(Environment::default()));
+        Self::with_registry(Registry::shared())
+    }
 
-        Self::init_globals(&globals);
+    /// 使用指定的内置模块注册表创建解释器
+    ///
+    /// 嵌入方可以借此禁用部分原生模块（eg. 沙箱中禁用 `fs`）。
+    pub fn with_registry(registry: Registry) -> Self {
+        // 创建根环境 (Global Scope)，内置全局名由共享的预置环境提供
+        let globals = Rc::new(RefCell::new(Environment::with_enclosing(
+            registry.prelude(),
+        )));
 
         Self {
             // 初始状态下，当前环境就是全局环境
@@ -92,43 +156,30 @@ impl Interpreter {
             modules: HashMap::new(),
             path_stack: Vec::new(),
             exports_stack: Vec::new(),
-        }
-    }
-
-    fn init_globals(env: &Rc<RefCell<Environment>>) {
-        let mut env_mut = env.borrow_mut();
-        let fs_module = std_lib::io::file_system::create_module();
-        let math_module = std_lib::math::create_module();
-
-        env_mut.define("fs".to_string(), fs_module);
-        env_mut.define("math".to_string(), math_module);
-
-        env_mut.define(
-            "clock".to_string(),
-            Value::NativeFunction {
-                name: "clock".to_string(),
-                arity: 0,
-                func: std_lib::globals::clock,
-            },
-        );
-
-        env_mut.define(
-            "input".to_string(),
-            Value::NativeFunction {
-                name: "input".to_string(),
-                arity: 1,
-                func: std_lib::globals::input,
+            extensions: HashMap::new(),
+            registry,
+            search_paths: Vec::new(),
+            // 测试中不读写用户目录下的缓存
+            module_cache: if cfg!(test) {
+                None
+            } else {
+                ModuleCache::from_env()
             },
-        );
-
-        env_mut.define(
-            "import".to_string(),
-            Value::NativeFunction {
-                name: "import".to_string(),
-                arity: 1,
-                func: std_lib::globals::import,
-            },
-        );
+            bundle: None,
+            bundle_stack: Vec::new(),
+            include_stack: Vec::new(),
+            globals_writable: false,
+            fake_clock: None,
+            io_trace: None,
+            script_args: Vec::new(),
+            call_depth: 0,
+            step_budget: None,
+            deferred: Vec::new(),
+            ieee_division: false,
+            term_colors: None,
+            log_level: log::Level::default(),
+            signals: SignalState::default(),
+        }
     }
 
     // TODO：错误处理机制 (Try-Catch)
@@ -144,14 +195,20 @@ impl Interpreter {
                 Err(e) => {
                     // 如果到了顶层还能捕获到 Break|Continue|Return，说明 Parser/Resolver 有 Bug
                     match e {
-                        RuntimeError::Break => {
-                            panic!("Critical Error: Parser allowed 'break' outside loop!")
+                        RuntimeError::Break(_) => {
+                            return Err(RuntimeError::Generic(
+                                "Critical Error: Parser allowed 'break' outside loop!".into(),
+                            ));
                         }
-                        RuntimeError::Continue => {
-                            panic!("Critical Error: Parser allowed 'continue' outside loop!")
+                        RuntimeError::Continue(_) => {
+                            return Err(RuntimeError::Generic(
+                                "Critical Error: Parser allowed 'continue' outside loop!".into(),
+                            ));
                         }
                         RuntimeError::Return(_) => {
-                            panic!("Critical Error: Parser allowed 'return' outside function!")
+                            return Err(RuntimeError::Generic(
+                                "Critical Error: Parser allowed 'return' outside function!".into(),
+                            ));
                         }
                         // 冒泡到了这里，说明用户代码里没有 try 捕获它
                         RuntimeError::Catchable(val) => {
@@ -171,7 +228,21 @@ impl Interpreter {
 
     // Statement Execution
 
-    fn execute(&mut self, stmt: &Stmt) -> Result<(), RuntimeError> {
+    pub(crate) fn execute(&mut self, stmt: &Stmt) -> Result<(), RuntimeError> {
+        // 安全检查点：分发已到达的信号
+        if self.signals.is_pending() {
+            self.dispatch_signals()?;
+        }
+
+        if let Some(budget) = &mut self.step_budget {
+            if *budget == 0 {
+                return Err(RuntimeError::Generic(
+                    "Execution step limit exceeded.".into(),
+                ));
+            }
+            *budget -= 1;
+        }
+
         match stmt {
             Stmt::Expression { expr } => {
                 self.evaluate(expr)?;
@@ -179,7 +250,8 @@ impl Interpreter {
             }
             Stmt::Print { expr } => {
                 let value = self.evaluate(expr)?;
-                println!("{}", value); // 副作用语句，将内容输出到 IO（控制台）
+                let text = self.stringify(&value)?;
+                println!("{}", text); // 副作用语句，将内容输出到 IO（控制台）
                 Ok(()) // 表示语句执行完成，没有产生供后续计算的值
             }
             Stmt::VarDecl { name, initializer } => {
@@ -194,6 +266,22 @@ impl Interpreter {
                     .define(name.lexeme.clone(), value);
                 Ok(())
             }
+            Stmt::VarDestructure {
+                pattern,
+                initializer,
+            } => {
+                let value = self.evaluate(initializer)?;
+                let targets = pattern.pattern_targets();
+                let values = self.unpack(value, targets.len())?;
+                for (target, value) in targets.iter().zip(values) {
+                    if let Expr::Variable { name, .. } = target {
+                        self.environment
+                            .borrow_mut()
+                            .define(name.lexeme.clone(), value);
+                    }
+                }
+                Ok(())
+            }
             Stmt::Block { body } => {
                 self.execute_block(body, Environment::with_enclosing(self.environment.clone()))?;
                 Ok(())
@@ -219,105 +307,114 @@ impl Interpreter {
 
             Stmt::Try {
                 try_branch,
-                catch_var,
-                catch_branch,
+                catch,
+                finally_branch,
             } => {
                 // 尝试执行 try 块
-                match self.execute(try_branch) {
-                    Ok(_) => Ok(()), // 正常完成，跳过 catch
-                    Err(RuntimeError::Catchable(e)) => {
-                        // 捕获到的错误
-                        let previous = self.environment.clone(); // 进入 catch 作用域
-                        let catch_env =
-                            Rc::new(RefCell::new(Environment::with_enclosing(previous.clone())));
-
-                        // 将错误信息作为字符串绑定到变量
-                        // 注意：这里将 RuntimeError 转为 String Value
-                        let err_msg = Value::String(format!("{}", e));
-                        catch_env
-                            .borrow_mut()
-                            .define(catch_var.lexeme.clone(), err_msg);
+                let result = match (self.execute(try_branch), catch) {
+                    (Err(e), Some((catch_var, catch_branch))) => match e.into_exception() {
+                        Ok(exception) => {
+                            // 捕获到的异常：throw 的值，或运行时错误的信息
+                            let previous = self.environment.clone(); // 进入 catch 作用域
+                            let catch_env = Rc::new(RefCell::new(Environment::with_enclosing(
+                                previous.clone(),
+                            )));
+                            catch_env
+                                .borrow_mut()
+                                .define(catch_var.lexeme.clone(), exception);
 
-                        self.environment = catch_env;
-                        // 执行 catch 块
-                        let result = self.execute(catch_branch);
+                            self.environment = catch_env;
+                            // 执行 catch 块
+                            let result = self.execute(catch_branch);
 
-                        self.environment = previous;
+                            self.environment = previous;
 
-                        result
-                    }
+                            result
+                        }
 
-                    // 其他运行时错误
-                    Err(e) => Err(e),
+                        // return / break / continue / exit 直接穿过 catch
+                        Err(e) => Err(e),
+                    },
+                    // 正常完成或没有 catch
+                    (result, _) => result,
+                };
+
+                // finally 在所有离开路径上执行（正常结束、异常、return / break / continue）；
+                // finally 自身出错或跳转时覆盖之前的结果
+                if let Some(finally_branch) = finally_branch {
+                    self.execute(finally_branch)?;
                 }
+                result
             }
 
-            Stmt::While { condition, body } => {
-                while self.evaluate(condition)?.is_truthy() {
-                    match self.execute(body) {
-                        Ok(_) => {}
-                        Err(RuntimeError::Break) => break,
-                        Err(RuntimeError::Continue) => continue,
-                        Err(e) => return Err(e),
-                    }
-                }
+            Stmt::While { .. } | Stmt::For { .. } | Stmt::ForIn { .. } => {
+                self.execute_loop(stmt, None)
+            }
+            Stmt::Labeled { label, body } => self.execute_loop(body, Some(&label.lexeme)),
+            // trait 与类一样是环境中的值，遵循普通的作用域规则
+            Stmt::Trait { name, methods } => {
+                let methods = methods
+                    .iter()
+                    .map(|(method, params)| {
+                        let params = params.iter().map(|p| p.lexeme.clone()).collect();
+                        (method.lexeme.clone(), params)
+                    })
+                    .collect();
+                let value = Value::Trait(Rc::new(RoxTrait {
+                    name: name.lexeme.clone(),
+                    methods,
+                }));
+                self.environment
+                    .borrow_mut()
+                    .define(name.lexeme.clone(), value);
                 Ok(())
             }
-            Stmt::For {
-                initializer,
-                condition,
-                increment,
-                body,
-            } => {
-                let previous_env = self.environment.clone();
-                // 创建新作用域 (init 变量)
-                self.environment = Rc::new(RefCell::new(Environment::with_enclosing(
-                    previous_env.clone(),
-                )));
-
-                if let Some(init) = initializer {
-                    self.execute(init)?; // init 是 Stmt (VarDecl 或 ExprStmt)
+            Stmt::Extend { target, methods } => {
+                if !std_lib::EXTENDABLE_TYPES.contains(&target.lexeme.as_str()) {
+                    return Err(RuntimeError::TypeError(format!(
+                        "Cannot extend '{}'. Only built-in types ({}) can be extended.",
+                        target.lexeme,
+                        std_lib::EXTENDABLE_TYPES.join(", ")
+                    )));
                 }
 
-                // 循环：使用 try-finally 模式确保环境恢复
-                let result = (|| -> Result<(), RuntimeError> {
-                    loop {
-                        // Check condition
-                        if let Some(cond) = condition
-                            && !self.evaluate(cond)?.is_truthy()
-                        {
-                            break;
-                        }
-
-                        // Run body
-                        match self.execute(body) {
-                            Ok(_) => {}
-                            Err(RuntimeError::Break) => break,
-                            Err(RuntimeError::Continue) => {
-                                // Note：continue 也要执行 increment
-                            }
-                            Err(e) => return Err(e),
-                        }
-
-                        // Run increment
-                        if let Some(incr) = increment {
-                            self.evaluate(incr)?;
-                        }
+                let table = self.extensions.entry(target.lexeme.clone()).or_default();
+                for method in methods {
+                    if let Stmt::Function {
+                        name: m_name,
+                        params,
+                        rest,
+                        body,
+                    } = method
+                    {
+                        let function = Value::Function {
+                            name: m_name.lexeme.clone(),
+                            args: params.iter().map(|t| t.lexeme.clone()).collect(),
+                            rest: *rest,
+                            body: body.clone(),
+                            closure: self.environment.clone(),
+                        };
+                        heap::track(&function);
+                        table.insert(m_name.lexeme.clone(), function);
                     }
-                    Ok(())
-                })();
-
-                self.environment = previous_env;
-                result
+                }
+                Ok(())
             }
-            Stmt::Function { name, params, body } => {
+            Stmt::Function {
+                name,
+                params,
+                rest,
+                body,
+            } => {
                 let function = Value::Function {
                     name: name.lexeme.clone(),
                     // 适配 Value::Function 定义，可能需要转换，参数列表需要以 Vec<String> 存储
                     args: params.iter().map(|t| t.lexeme.clone()).collect(),
+                    rest: *rest,
                     body: body.clone(), // body 是 Vec<Stmt>
                     closure: self.environment.clone(),
                 };
+                heap::track(&function);
                 self.environment
                     .borrow_mut()
                     .define(name.lexeme.clone(), function);
@@ -327,6 +424,8 @@ impl Interpreter {
                 name,
                 superclass,
                 methods,
+                static_methods,
+                traits,
             } => {
                 // 处理父类
                 let mut super_klass: Option<Rc<RefCell<RoxClass>>> = None;
@@ -357,39 +456,53 @@ impl Interpreter {
                 }
 
                 // 将 AST 中的方法 (Stmt::Function) 转换为运行时 Value::Function
-                let mut method_map = HashMap::new();
-                for method in methods {
-                    if let Stmt::Function {
-                        name: m_name,
-                        params,
-                        body,
-                    } = method
-                    {
-                        let function = Value::Function {
-                            name: m_name.lexeme.clone(),
-                            args: params.iter().map(|t| t.lexeme.clone()).collect(),
-                            body: body.clone(),
-                            closure: self.environment.clone(), // 闭包捕获当前环境
-                        };
-                        method_map.insert(m_name.lexeme.clone(), function);
+                let to_method_map = |methods: &[Stmt]| {
+                    let mut method_map = HashMap::new();
+                    for method in methods {
+                        if let Stmt::Function {
+                            name: m_name,
+                            params,
+                            rest,
+                            body,
+                        } = method
+                        {
+                            let function = Value::Function {
+                                name: m_name.lexeme.clone(),
+                                args: params.iter().map(|t| t.lexeme.clone()).collect(),
+                                rest: *rest,
+                                body: body.clone(),
+                                closure: self.environment.clone(), // 闭包捕获当前环境
+                            };
+                            heap::track(&function);
+                            method_map.insert(m_name.lexeme.clone(), function);
+                        }
                     }
-                }
+                    method_map
+                };
+                let method_map = to_method_map(methods);
+                let static_map = to_method_map(static_methods);
 
                 // 创建 Class 对象
-                let klass = RoxClass::new(name.lexeme.clone(), method_map, super_klass.clone());
+                let mut klass = RoxClass::new(name.lexeme.clone(), method_map, super_klass.clone());
+                klass.statics = static_map;
 
                 // 恢复环境 (弹出包含 super 的环境)
                 if super_klass.is_some() {
                     // environment = environment.enclosing
                     let enclosing = self.environment.borrow().enclosing.clone();
-                    self.environment = enclosing.unwrap();
+                    self.environment = enclosing.ok_or_else(|| {
+                        RuntimeError::Generic("Internal error: missing superclass scope.".into())
+                    })?;
                 }
 
+                self.check_traits(name, &klass, traits)?;
+
                 // 定义到环境中
-                self.environment.borrow_mut().define(
-                    name.lexeme.clone(),
-                    Value::Class(Rc::new(RefCell::new(klass))),
-                );
+                let class = Value::Class(Rc::new(RefCell::new(klass)));
+                heap::track(&class);
+                self.environment
+                    .borrow_mut()
+                    .define(name.lexeme.clone(), class);
 
                 Ok(())
             }
@@ -401,22 +514,51 @@ impl Interpreter {
                 };
                 Err(RuntimeError::Return(return_val))
             }
-            Stmt::Break => Err(RuntimeError::Break),
-            Stmt::Continue => Err(RuntimeError::Continue),
+            Stmt::Defer { expr, .. } => match self.deferred.last_mut() {
+                Some(frame) => {
+                    frame.push((expr.clone(), self.environment.clone()));
+                    Ok(())
+                }
+                None => Err(RuntimeError::Generic(
+                    "Can't use 'defer' outside of a function.".into(),
+                )),
+            },
+            // 包含 yield 的函数被调用时得到生成器，yield 由生成器的帧栈执行
+            Stmt::Yield { .. } => Err(RuntimeError::Generic(
+                "Can't yield outside of a generator.".into(),
+            )),
+            Stmt::Break { label } => Err(RuntimeError::Break(
+                label.as_ref().map(|l| l.lexeme.clone()),
+            )),
+            Stmt::Continue { label } => Err(RuntimeError::Continue(
+                label.as_ref().map(|l| l.lexeme.clone()),
+            )),
             Stmt::Export { stmt } => {
                 // 先执行内部的声明语句 (这会在 environment 中定义变量)
                 self.execute(stmt)?;
 
                 // 将定义的名字加入当前的导出集合（栈），从 stmt 中提取名字
-                let name = match &**stmt {
-                    Stmt::VarDecl { name, .. } => name.lexeme.clone(),
-                    Stmt::Function { name, .. } => name.lexeme.clone(),
-                    Stmt::Class { name, .. } => name.lexeme.clone(),
-                    _ => panic!("Parser allowed invalid export statement"),
+                let names = match &**stmt {
+                    Stmt::VarDecl { name, .. } => vec![name.lexeme.clone()],
+                    Stmt::VarDestructure { pattern, .. } => pattern
+                        .pattern_targets()
+                        .iter()
+                        .filter_map(|target| match target {
+                            Expr::Variable { name, .. } => Some(name.lexeme.clone()),
+                            _ => None,
+                        })
+                        .collect(),
+                    Stmt::Function { name, .. } => vec![name.lexeme.clone()],
+                    Stmt::Class { name, .. } => vec![name.lexeme.clone()],
+                    _ => {
+                        return Err(RuntimeError::Generic(
+                            "Only declarations can be exported.".into(),
+                        ));
+                    }
                 };
 
                 if let Some(current_exports) = self.exports_stack.last_mut() {
-                    current_exports.insert(name);
+                    current_exports.extend(names);
                 } else {
                     // 如果栈为空，说明是在 REPL 或主程序顶层 export，忽略
                 }
@@ -429,7 +571,7 @@ impl Interpreter {
 
     // Expression Evaluation
 
-    fn evaluate(&mut self, expr: &Expr) -> Result<Value, RuntimeError> {
+    pub(crate) fn evaluate(&mut self, expr: &Expr) -> Result<Value, RuntimeError> {
         match expr {
             Expr::Number { value } => {
                 let n = value
@@ -437,23 +579,31 @@ impl Interpreter {
                     .map_err(|_| RuntimeError::Generic("Invalid number".into()))?;
                 Ok(Value::Number(n))
             }
+            Expr::BigInt { value } => value
+                .parse()
+                .map(Value::BigInt)
+                .map_err(|_| RuntimeError::Generic("Invalid BigInt".into())),
             Expr::String { value } => Ok(Value::String(value.clone())),
             Expr::Boolean { value } => Ok(Value::Boolean(*value)),
             Expr::Nil => Ok(Value::Nil),
 
             Expr::List { elements } => {
                 let elements = self.evaluate_elements(elements)?;
-                Ok(Value::List(Rc::new(RefCell::new(elements))))
+                let list = Value::List(Rc::new(RefCell::new(elements)));
+                heap::track(&list);
+                Ok(list)
             }
             Expr::Tuple { elements } => Ok(Value::Tuple(self.evaluate_elements(elements)?)),
             Expr::Dict { elements } => {
-                let mut dict = HashMap::new();
+                let dict = Rc::new(RefCell::new(DictMap::new()));
                 for (k, v) in elements {
-                    let key = self.evaluate(k)?.to_string(); // Key 简化处理
+                    let key = self.evaluate(k)?;
                     let val = self.evaluate(v)?;
-                    dict.insert(key, val);
+                    self.dict_insert(&dict, key, val)?;
                 }
-                Ok(Value::Dict(Rc::new(RefCell::new(dict))))
+                let dict = Value::Dict(dict);
+                heap::track(&dict);
+                Ok(dict)
             }
 
             Expr::Variable { id, name } => self.look_up_variable(name, id),
@@ -461,17 +611,37 @@ impl Interpreter {
             Expr::Assign { id, name, expr } => {
                 let value = self.evaluate(expr)?;
 
-                if let Some(&distance) = self.locals.get(id) {
+                let success = if let Some(&distance) = self.locals.get(id) {
                     // 本地赋值
                     self.environment
                         .borrow_mut()
-                        .assign_at(distance, &name.lexeme, value.clone());
+                        .assign_at(distance, &name.lexeme, value.clone())
                 } else {
                     // 全局赋值
-                    let success = self
-                        .globals
-                        .borrow_mut()
-                        .assign(&name.lexeme, value.clone());
+                    self.assign_global(&name.lexeme, value.clone())
+                };
+                if !success {
+                    return Err(RuntimeError::UndefinedVariable(name.lexeme.clone()));
+                }
+                Ok(value)
+            }
+
+            Expr::AssignDestructure { pattern, value } => {
+                let value = self.evaluate(value)?;
+                let targets = pattern.pattern_targets();
+                let values = self.unpack(value.clone(), targets.len())?;
+
+                for (target, item) in targets.iter().zip(values) {
+                    let Expr::Variable { id, name } = target else {
+                        continue;
+                    };
+                    let success = if let Some(&distance) = self.locals.get(id) {
+                        self.environment
+                            .borrow_mut()
+                            .assign_at(distance, &name.lexeme, item)
+                    } else {
+                        self.assign_global(&name.lexeme, item)
+                    };
                     if !success {
                         return Err(RuntimeError::UndefinedVariable(name.lexeme.clone()));
                     }
@@ -494,23 +664,26 @@ impl Interpreter {
                 };
 
                 // 赋值回 (Write)
-                if let Some(&distance) = self.locals.get(id) {
-                    self.environment.borrow_mut().assign_at(
-                        distance,
-                        &name.lexeme,
-                        new_val.clone(),
-                    );
-                } else {
-                    self.globals
+                let success = if let Some(&distance) = self.locals.get(id) {
+                    self.environment
                         .borrow_mut()
-                        .assign(&name.lexeme, new_val.clone());
+                        .assign_at(distance, &name.lexeme, new_val.clone())
+                } else {
+                    self.assign_global(&name.lexeme, new_val.clone())
+                };
+                if !success {
+                    return Err(RuntimeError::UndefinedVariable(name.lexeme.clone()));
                 }
                 Ok(new_val)
             }
 
             Expr::Logical { left, op, right } => {
                 let left_val = self.evaluate(left)?;
-                if *op == Operator::LogicalOr || *op == Operator::OrKeyword {
+                if *op == Operator::NullCoalesce {
+                    if !matches!(left_val, Value::Nil) {
+                        return Ok(left_val);
+                    }
+                } else if *op == Operator::LogicalOr || *op == Operator::OrKeyword {
                     if left_val.is_truthy() {
                         return Ok(left_val);
                     }
@@ -524,6 +697,17 @@ impl Interpreter {
                 let l = self.evaluate(left)?;
                 let r = self.evaluate(right)?;
 
+                if let Some(result) = decimal::binary(op, &l, &r) {
+                    return result;
+                }
+                if let Some(result) = std_lib::nd::binary(op, &l, &r) {
+                    return result;
+                }
+                if let Some(result) = bigint::binary(op, &l, &r) {
+                    return result;
+                }
+
+                let ieee_division = self.ieee_division;
                 match op {
                     Operator::Add => self.add_values(l, r),
                     Operator::Sub => {
@@ -533,14 +717,21 @@ impl Interpreter {
                         self.check_number_operands(l, r, |a, b| Ok(Value::Number(a * b)))
                     }
                     Operator::Div => self.check_number_operands(l, r, |a, b| {
-                        if b == 0.0 {
+                        if b == 0.0 && !ieee_division {
                             Err(RuntimeError::DivisionByZero)
                         } else {
                             Ok(Value::Number(a / b))
                         }
                     }),
+                    Operator::FloorDiv => self.check_number_operands(l, r, |a, b| {
+                        if b == 0.0 && !ieee_division {
+                            Err(RuntimeError::DivisionByZero)
+                        } else {
+                            Ok(Value::Number((a / b).floor()))
+                        }
+                    }),
                     Operator::Mod => self.check_number_operands(l, r, |a, b| {
-                        if b == 0.0 {
+                        if b == 0.0 && !ieee_division {
                             Err(RuntimeError::DivisionByZero)
                         } else {
                             Ok(Value::Number(a.rem_euclid(b)))
@@ -552,242 +743,56 @@ impl Interpreter {
                     Operator::BitwiseXor => self.eval_bitwise(left, right, |a, b| a ^ b),
 
                     // 比较运算
-                    Operator::Greater => {
-                        self.check_number_operands(l, r, |a, b| Ok(Value::Boolean(a > b)))
-                    }
-                    Operator::GreaterEqual => {
-                        self.check_number_operands(l, r, |a, b| Ok(Value::Boolean(a >= b)))
-                    }
-                    Operator::Less => {
-                        self.check_number_operands(l, r, |a, b| Ok(Value::Boolean(a < b)))
-                    }
-                    Operator::LessEqual => {
-                        self.check_number_operands(l, r, |a, b| Ok(Value::Boolean(a <= b)))
-                    }
+                    Operator::Greater => self.compare_operands(l, r, |o| o.is_gt()),
+                    Operator::GreaterEqual => self.compare_operands(l, r, |o| o.is_ge()),
+                    Operator::Less => self.compare_operands(l, r, |o| o.is_lt()),
+                    Operator::LessEqual => self.compare_operands(l, r, |o| o.is_le()),
 
                     // 相等运算 (应该支持所有类型)
-                    Operator::Equal => Ok(Value::Boolean(l == r)),
-                    Operator::NotEqual => Ok(Value::Boolean(l != r)),
+                    Operator::Equal => Ok(Value::Boolean(self.values_equal(&l, &r)?)),
+                    Operator::NotEqual => Ok(Value::Boolean(!self.values_equal(&l, &r)?)),
 
                     _ => Err(RuntimeError::Generic("Unknown binary operator".into())),
                 }
             }
 
-            Expr::Call {
-                id: _,
-                callee,
-                args,
-            } => {
-                // callee 可能是一个表达式：func()(1);
-                // 如果 callee 是一个表达式: func()，则需要先求值
-                // 检查 callee 的类型是否是 Expr::Variable，如果是 evaluate 内部会自动调用 look_up_variable
-                let callee_value = self.evaluate(callee)?;
-                //  let callee_value = self.look_up_variable(name_token, id)?;
-                //  println!("DEBUG: Calling {:?}", callee_value);
-
-                // 求值所有参数
-                let mut arg_vals = Vec::new();
-                for arg in args {
-                    arg_vals.push(self.evaluate(arg)?);
-                }
-
-                self.call_value(&callee_value, arg_vals)
+            Expr::Get { .. } | Expr::GetIndex { .. } | Expr::Call { .. } => {
+                Ok(self.evaluate_chain(expr)?.unwrap_or(Value::Nil))
             }
 
             Expr::This { id, keyword } => self.look_up_variable(keyword, id),
 
             Expr::Lambda {
                 id: _,
+                captures,
                 params,
+                rest,
                 body,
             } => {
+                // 按值捕获：在外层与函数之间插入一个快照环境，保存创建时刻的变量值
+                let closure = if captures.is_empty() {
+                    self.environment.clone()
+                } else {
+                    let mut snapshot = Environment::with_enclosing(self.environment.clone());
+                    for capture in captures {
+                        if let Expr::Variable { id, name } = capture {
+                            let value = self.look_up_variable(name, id)?;
+                            snapshot.define(name.lexeme.clone(), value);
+                        }
+                    }
+                    Rc::new(RefCell::new(snapshot))
+                };
+
                 // 为函数对象提供一个特殊的 "<anonymous>" 名字
-                Ok(Value::Function {
+                let function = Value::Function {
                     name: "<anonymous>".to_string(),
                     args: params.iter().map(|t| t.lexeme.clone()).collect(),
+                    rest: *rest,
                     body: body.clone(),
-                    closure: self.environment.clone(),
-                })
-            }
-
-            Expr::Get { object, name } => {
-                let obj = self.evaluate(object)?;
-
-                match &obj {
-                    // 实例属性/方法，检查是否是实例
-                    Value::Instance(instance_rc) => {
-                        {
-                            // 本地优先，查找实例字段
-                            let instance = instance_rc.borrow();
-
-                            if let Some(value) = instance.fields.borrow().get(&name.lexeme) {
-                                return Ok(value.clone());
-                            }
-                        }
-
-                        let klass_rc = instance_rc.borrow().class.clone();
-                        let klass = klass_rc.borrow();
-
-                        // 向类以及父类 链式查找
-                        if let Some(method) = klass.find_method(&name.lexeme) {
-                            let bound_method = method.bind(Value::Instance(instance_rc.clone()));
-                            return Ok(bound_method);
-                        }
-
-                        Err(RuntimeError::Generic(format!(
-                            "Undefined property '{}'.",
-                            name.lexeme
-                        )))
-                    }
-
-                    // std
-                    Value::String(_) => {
-                        // 使用 std_lib 查找
-                        if let Some(method) = lookup_method(&obj, &name.lexeme) {
-                            // Thinking：
-                            // 原生函数也需要知道 'this' 是谁，
-                            // 复用 bind 逻辑，或者在 NativeFunction 调用时特殊处理。
-                            // 简单做法：让 NativeFunction 类似于 Value::Function，把 'obj' 塞进去。
-                            // 但由于 NativeFunction 是 Rust fn，没办法塞闭包环境。
-
-                            // 返回 Value::BoundNativeMethod { method, this: obj }
-                            // 在 Expr::Call 中处理它。
-                            return Ok(Value::BoundNativeMethod {
-                                method: Box::new(method),
-                                receiver: Box::new(obj),
-                            });
-                        }
-                        Err(RuntimeError::Generic(format!(
-                            "String has no property '{}'.",
-                            name.lexeme
-                        )))
-                    }
-
-                    Value::List(_) => {
-                        if let Some(method) = lookup_method(&obj, &name.lexeme) {
-                            return Ok(Value::BoundNativeMethod {
-                                method: Box::new(method),
-                                receiver: Box::new(obj),
-                            });
-                        }
-                        Err(RuntimeError::Generic(format!(
-                            "List has no property '{}'.",
-                            name.lexeme
-                        )))
-                    }
-
-                    Value::Dict(dict) => {
-                        let this = Value::Dict(dict.clone());
-
-                        // 优先本地方法的调用
-                        if let Some(method) = lookup_method(&obj, &name.lexeme) {
-                            return Ok(Value::BoundNativeMethod {
-                                method: Box::new(method),
-                                receiver: Box::new(this),
-                            });
-                        }
-
-                        // . 运算
-                        let dict_borrow = dict.borrow();
-                        if let Some(value) = dict_borrow.get(&name.lexeme) {
-                            return Ok(value.clone());
-                        }
-
-                        Err(RuntimeError::Generic(format!(
-                            "Dict has no property '{}'.",
-                            name.lexeme
-                        )))
-                    }
-
-                    Value::Module(module_rc) => {
-                        let module = module_rc.borrow();
-
-                        // 尝试查找变量
-                        if let Some(value) = module.exports.get(&name.lexeme) {
-                            return Ok(value.clone());
-                        }
-
-                        // 没找到，分析原因
-                        if !module.is_initialized {
-                            // A: 模块还在加载中（循环依赖）
-                            Err(RuntimeError::Generic(format!(
-                                "Accessing variable '{}' from module '{}' before it is fully initialized. (Circular Dependency detected)",
-                                name.lexeme, module.name
-                            )))
-                        } else {
-                            // B: 模块加载完成，变量不存在
-                            Err(RuntimeError::Generic(format!(
-                                "Module '{}' has no export '{}'.",
-                                module.name, name.lexeme
-                            )))
-                        }
-                    }
-
-                    _ => Err(RuntimeError::TypeError(
-                        "Only instances have properties.".into(),
-                    )),
-                }
-            }
-
-            Expr::GetIndex { object, index, .. } => {
-                let obj = self.evaluate(object)?;
-                let idx = self.evaluate(index)?;
-
-                match obj {
-                    // list[idx]
-                    Value::List(list_rc) => {
-                        if let Value::Number(n) = idx {
-                            // 检查是不是整数
-                            if n.fract() != 0.0 {
-                                return Err(RuntimeError::Generic(
-                                    "List index must be an integer.".into(),
-                                ));
-                            }
-                            let i = n as usize;
-                            let list = list_rc.borrow();
-
-                            if i >= list.len() {
-                                return Err(RuntimeError::Generic(
-                                    "List index out of bounds.".into(),
-                                ));
-                            }
-                            return Ok(list[i].clone());
-                        }
-                        Err(RuntimeError::Generic("List index must be a number.".into()))
-                    }
-
-                    // dict[key]
-                    Value::Dict(dict_rc) => {
-                        // NOTE：HashMap Key 必须可哈希，当前只支持 String 类型 Key
-                        let key = idx.to_string();
-                        let dict = dict_rc.borrow();
-
-                        Ok(dict.get(&key).cloned().unwrap_or(Value::Nil)) // 不存在返回 Nil
-                    }
-
-                    // str[idx]
-                    Value::String(s) => {
-                        if let Value::Number(n) = idx {
-                            let i = n as usize;
-                            if i >= s.len() {
-                                return Err(RuntimeError::Generic(
-                                    "String index out of bounds.".into(),
-                                ));
-                            }
-                            // NOTE：字符串索引比较麻烦（UTF-8），暂时简单处理
-                            let c = s.chars().nth(i).unwrap();
-                            Ok(Value::String(c.to_string()))
-                        } else {
-                            Err(RuntimeError::Generic(
-                                "String index must be a number.".into(),
-                            ))
-                        }
-                    }
-
-                    _ => Err(RuntimeError::TypeError(
-                        "Only lists and dicts support subscripting.".into(),
-                    )),
-                }
+                    closure,
+                };
+                heap::track(&function);
+                Ok(function)
             }
 
             Expr::SetIndex {
@@ -821,11 +826,13 @@ impl Interpreter {
                     }
 
                     Value::Dict(dict_rc) => {
-                        let key = idx.to_string(); // !!
-                        dict_rc.borrow_mut().insert(key, val.clone());
+                        self.dict_insert(&dict_rc, idx, val.clone())?;
                         Ok(val)
                     }
 
+                    Value::Tuple(_) => Err(RuntimeError::TypeError("Tuples are immutable.".into())),
+                    Value::Bytes(_) => Err(RuntimeError::TypeError("Bytes are immutable.".into())),
+
                     _ => Err(RuntimeError::TypeError(
                         "Only lists and dicts support subscript assignment.".into(),
                     )),
@@ -838,8 +845,24 @@ impl Interpreter {
             } => {
                 let obj = self.evaluate(object)?;
 
-                // 检查是否是实例 只有实例才可以通过字段访问属性
-                if let Value::Instance(instance_rc) = obj {
+                // Monkey patch：为类附加方法，所有实例立即可见
+                if let Value::Class(klass_rc) = obj {
+                    let val = self.evaluate(value)?;
+                    if !matches!(val, Value::Function { .. }) {
+                        return Err(RuntimeError::TypeError(format!(
+                            "Only functions can be attached to a class, got {}.",
+                            val.type_name()
+                        )));
+                    }
+
+                    klass_rc
+                        .borrow_mut()
+                        .patch_method(name.lexeme.clone(), val.clone());
+                    return Ok(val);
+                }
+
+                // 检查是否是实例 只有实例才可以通过字段访问属性
+                if let Value::Instance(instance_rc) = obj {
                     let val = self.evaluate(value)?;
 
                     // 写入字段
@@ -867,6 +890,8 @@ impl Interpreter {
 
                     Operator::Sub => match right {
                         Value::Number(n) => Ok(Value::Number(-n)),
+                        Value::BigInt(n) => Ok(Value::BigInt(-n)),
+                        Value::Decimal(d) => Ok(Value::Decimal(-d)),
                         _ => Err(RuntimeError::TypeError("Operand must be a number.".into())),
                     },
 
@@ -888,29 +913,26 @@ impl Interpreter {
             Expr::Super { id, method, .. } => {
                 // 查找 "super" 获取父类对象
                 // Resolver 保证了 "super" 在 distance 处
-                let distance = *self.locals.get(id).unwrap();
-                let superclass = self.environment.borrow().get_at(distance, "super").unwrap();
-
-                // 查找 "this" 获取实例对象
                 // Tip：Resolver 的环境链是 super -> this。
                 //      所以 this 一定在 super 的下一层 (distance - 1)。
-                let instance = self
-                    .environment
-                    .borrow()
-                    .get_at(distance - 1, "this")
-                    .unwrap();
-
-                // 解包
-                let super_klass = if let Value::Class(c) = superclass {
-                    c
-                } else {
-                    panic!("Super not class")
+                // 环境链与 Resolver 的结果不一致时报错而不是 Panic
+                let lookup = self.locals.get(id).and_then(|&distance| {
+                    let env = self.environment.borrow();
+                    let superclass = env.get_at(distance, "super")?;
+                    let instance = env.get_at(distance.checked_sub(1)?, "this")?;
+                    Some((superclass, instance))
+                });
+                let Some((Value::Class(super_klass), instance)) = lookup else {
+                    return Err(RuntimeError::Generic(
+                        "Can't use 'super' outside of a subclass method.".into(),
+                    ));
                 };
 
                 // 查找并绑定方法
-                if let Some(method_val) = super_klass.borrow().find_method(&method.lexeme) {
-                    // 绑定到当前的 instance
-                    return Ok(method_val.bind(instance)); // 绑定 this
+                if let Some(bound_method) =
+                    super_klass.borrow().bind_method(&method.lexeme, instance)
+                {
+                    return Ok(bound_method); // 绑定到当前的 instance
                 }
 
                 Err(RuntimeError::UndefinedVariable(format!(
@@ -922,6 +944,358 @@ impl Interpreter {
     }
 
     // Helper methods
+    /// 求值调用链（属性访问、下标、函数调用）
+    ///
+    /// 返回 `None` 表示链上某个可选访问（`?.` / `?[`）的接收者为 nil，
+    /// 整条链随之短路：`a?.b.c()` 在 `a` 为 nil 时直接得到 nil，不再访问 `.c` 或调用。
+    fn evaluate_chain(&mut self, expr: &Expr) -> Result<Option<Value>, RuntimeError> {
+        match expr {
+            Expr::Get {
+                object,
+                name,
+                optional,
+            } => {
+                let Some(obj) = self.evaluate_chain(object)? else {
+                    return Ok(None);
+                };
+                if *optional && obj == Value::Nil {
+                    return Ok(None);
+                }
+                self.get_property(obj, name).map(Some)
+            }
+
+            Expr::GetIndex {
+                object,
+                index,
+                optional,
+                ..
+            } => {
+                let Some(obj) = self.evaluate_chain(object)? else {
+                    return Ok(None);
+                };
+                // 短路时下标表达式不会被求值
+                if *optional && obj == Value::Nil {
+                    return Ok(None);
+                }
+                let idx = self.evaluate(index)?;
+                self.get_index(obj, idx).map(Some)
+            }
+
+            Expr::Call {
+                callee,
+                args,
+                named,
+                ..
+            } => {
+                // callee 可能是一个表达式：func()(1);
+                // 如果 callee 是一个表达式: func()，则需要先求值
+                // 检查 callee 的类型是否是 Expr::Variable，如果是 evaluate 内部会自动调用 look_up_variable
+                let Some(callee_value) = self.evaluate_chain(callee)? else {
+                    return Ok(None);
+                };
+
+                // 求值所有参数
+                let mut arg_vals = Vec::new();
+                for arg in args {
+                    arg_vals.push(self.evaluate(arg)?);
+                }
+
+                if !named.is_empty() {
+                    let mut named_vals = Vec::with_capacity(named.len());
+                    for (name, arg) in named {
+                        named_vals.push((name.lexeme.clone(), self.evaluate(arg)?));
+                    }
+                    arg_vals = self.arrange_keyword_args(&callee_value, arg_vals, named_vals)?;
+                }
+
+                self.call_value(&callee_value, arg_vals).map(Some)
+            }
+
+            _ => self.evaluate(expr).map(Some),
+        }
+    }
+
+    /// 把关键字参数按形参名放回对应位置，得到一个普通的位置参数列表
+    ///
+    /// 只有用户定义的函数、方法和类 (按 `init` 的形参) 有形参名可供匹配；
+    /// 剩余参数不能通过关键字传入，每个命名参数必须恰好被传入一次。
+    fn arrange_keyword_args(
+        &self,
+        callee: &Value,
+        positional: Vec<Value>,
+        named: Vec<(String, Value)>,
+    ) -> Result<Vec<Value>, RuntimeError> {
+        let (name, params) = Self::keyword_params(callee).ok_or_else(|| {
+            // 原生函数 / 方法报告函数名，而不是内部的类型名
+            let callee = match callee {
+                Value::NativeFunction { name, .. } => format!("Native function '{}'", name),
+                Value::BoundNativeMethod { method, .. } => match &**method {
+                    Value::NativeFunction { name, .. } => format!("Native method '{}'", name),
+                    _ => "Native method".to_string(),
+                },
+                other => other.type_name().to_string(),
+            };
+            RuntimeError::TypeError(format!("{} does not accept keyword arguments.", callee))
+        })?;
+
+        let mut slots: Vec<Option<Value>> = vec![None; params.len()];
+        let mut extra = Vec::new();
+        for (i, value) in positional.into_iter().enumerate() {
+            match slots.get_mut(i) {
+                Some(slot) => *slot = Some(value),
+                None => extra.push(value),
+            }
+        }
+
+        for (key, value) in named {
+            let Some(index) = params.iter().position(|p| *p == key) else {
+                return Err(RuntimeError::Generic(format!(
+                    "Unknown keyword argument '{}' for '{}'.",
+                    key, name
+                )));
+            };
+            if slots[index].is_some() {
+                return Err(RuntimeError::Generic(format!(
+                    "Duplicate argument '{}' for '{}'.",
+                    key, name
+                )));
+            }
+            slots[index] = Some(value);
+        }
+
+        let mut args = Vec::with_capacity(slots.len() + extra.len());
+        for (param, slot) in params.iter().zip(slots) {
+            match slot {
+                Some(value) => args.push(value),
+                None => {
+                    return Err(RuntimeError::Generic(format!(
+                        "Missing argument '{}' for '{}'.",
+                        param, name
+                    )));
+                }
+            }
+        }
+        args.extend(extra);
+        Ok(args)
+    }
+
+    /// 可以按名字传参的形参 (不含剩余参数) 以及报错时使用的名字
+    fn keyword_params(callee: &Value) -> Option<(String, Vec<String>)> {
+        match callee {
+            Value::Function {
+                name, args, rest, ..
+            } => {
+                let named = args.len() - usize::from(*rest);
+                Some((name.clone(), args[..named].to_vec()))
+            }
+            Value::Class(klass) => {
+                let klass = klass.borrow();
+                match klass.bind_method("init", Value::Nil) {
+                    Some(init) => {
+                        Self::keyword_params(&init).map(|(_, params)| (klass.name.clone(), params))
+                    }
+                    None => Some((klass.name.clone(), Vec::new())),
+                }
+            }
+            // 运行时附加的方法：第一个形参是实例本身
+            Value::BoundNativeMethod { method, .. } => {
+                let (name, mut params) = Self::keyword_params(method)?;
+                if !params.is_empty() {
+                    params.remove(0);
+                }
+                Some((name, params))
+            }
+            _ => None,
+        }
+    }
+
+    /// 属性访问 `obj.name`：实例字段/方法、原生方法、字典键与模块导出
+    fn get_property(&mut self, obj: Value, name: &Token) -> Result<Value, RuntimeError> {
+        match &obj {
+            // 实例属性/方法，检查是否是实例
+            Value::Instance(instance_rc) => {
+                {
+                    // 本地优先，查找实例字段
+                    let instance = instance_rc.borrow();
+
+                    if let Some(value) = instance.fields.borrow().get(&name.lexeme) {
+                        return Ok(value.clone());
+                    }
+                }
+
+                let klass_rc = instance_rc.borrow().class.clone();
+                let klass = klass_rc.borrow();
+
+                // 向类以及父类 链式查找
+                if let Some(bound_method) =
+                    klass.bind_method(&name.lexeme, Value::Instance(instance_rc.clone()))
+                {
+                    return Ok(bound_method);
+                }
+
+                Err(RuntimeError::Generic(format!(
+                    "Undefined property '{}'.",
+                    name.lexeme
+                )))
+            }
+
+            // std：原生方法与 extend 扩展方法
+            Value::String(_)
+            | Value::List(_)
+            | Value::Tuple(_)
+            | Value::Number(_)
+            | Value::Boolean(_)
+            | Value::StringBuilder(_)
+            | Value::NdArray(_)
+            | Value::Set(_)
+            | Value::Bytes(_)
+            | Value::DateTime(_)
+            | Value::Store(_)
+            | Value::Generator(_)
+            | Value::Iterator(_) => {
+                if let Some(method) = lookup_method(self, &obj, &name.lexeme) {
+                    return Ok(method);
+                }
+                Err(RuntimeError::Generic(format!(
+                    "{} has no property '{}'.",
+                    obj.type_name(),
+                    name.lexeme
+                )))
+            }
+
+            Value::Dict(dict) => {
+                // 优先本地方法的调用
+                if let Some(method) = lookup_method(self, &obj, &name.lexeme) {
+                    return Ok(method);
+                }
+
+                // . 运算
+                let dict_borrow = dict.borrow();
+                if let Some((_, value)) = dict_borrow.get(&HashKey::String(name.lexeme.clone())) {
+                    return Ok(value.clone());
+                }
+
+                Err(RuntimeError::Generic(format!(
+                    "Dict has no property '{}'.",
+                    name.lexeme
+                )))
+            }
+
+            Value::Module(module_rc) => {
+                // 原生模块在第一次访问时才构建
+                self.registry.load(module_rc);
+                let module = module_rc.borrow();
+
+                // 尝试查找变量
+                if let Some(value) = module.exports.get(&name.lexeme) {
+                    return Ok(value.clone());
+                }
+
+                // 没找到，分析原因
+                if !module.is_initialized {
+                    // A: 模块还在加载中（循环依赖）
+                    Err(RuntimeError::Generic(format!(
+                        "Accessing variable '{}' from module '{}' before it is fully initialized. (Circular Dependency detected)",
+                        name.lexeme, module.name
+                    )))
+                } else {
+                    // B: 模块加载完成，变量不存在
+                    Err(RuntimeError::Generic(format!(
+                        "Module '{}' has no export '{}'.",
+                        module.name, name.lexeme
+                    )))
+                }
+            }
+
+            // 类本身只暴露静态方法
+            Value::Class(klass_rc) => {
+                klass_rc.borrow().find_static(&name.lexeme).ok_or_else(|| {
+                    RuntimeError::Generic(format!(
+                        "Class '{}' has no static method '{}'.",
+                        klass_rc.borrow().name,
+                        name.lexeme
+                    ))
+                })
+            }
+
+            _ => Err(RuntimeError::TypeError(
+                "Only instances have properties.".into(),
+            )),
+        }
+    }
+
+    /// 下标访问 `obj[idx]`：列表、字典、字符串等
+    fn get_index(&mut self, obj: Value, idx: Value) -> Result<Value, RuntimeError> {
+        match obj {
+            // list[idx]
+            Value::List(list_rc) => {
+                if let Value::Number(n) = idx {
+                    // 检查是不是整数
+                    if n.fract() != 0.0 {
+                        return Err(RuntimeError::Generic(
+                            "List index must be an integer.".into(),
+                        ));
+                    }
+                    let i = n as usize;
+                    let list = list_rc.borrow();
+
+                    if i >= list.len() {
+                        return Err(RuntimeError::Generic("List index out of bounds.".into()));
+                    }
+                    return Ok(list[i].clone());
+                }
+                Err(RuntimeError::Generic("List index must be a number.".into()))
+            }
+
+            // tuple[idx]
+            Value::Tuple(items) => match idx {
+                Value::Number(n) if n.fract() == 0.0 && n >= 0.0 => items
+                    .get(n as usize)
+                    .cloned()
+                    .ok_or_else(|| RuntimeError::Generic("Tuple index out of bounds.".into())),
+                _ => Err(RuntimeError::Generic(
+                    "Tuple index must be a non-negative integer.".into(),
+                )),
+            },
+
+            // bytes[idx] -> Number (0..=255)
+            Value::Bytes(bytes) => match idx {
+                Value::Number(n) if n.fract() == 0.0 && n >= 0.0 => bytes
+                    .get(n as usize)
+                    .map(|b| Value::Number(*b as f64))
+                    .ok_or_else(|| RuntimeError::Generic("Bytes index out of bounds.".into())),
+                _ => Err(RuntimeError::Generic(
+                    "Bytes index must be a non-negative integer.".into(),
+                )),
+            },
+
+            // dict[key]
+            Value::Dict(dict_rc) => {
+                // Key 必须可哈希：基础类型、元组或定义了 __hash__ 的实例
+                Ok(self.dict_get(&dict_rc, &idx)?.unwrap_or(Value::Nil)) // 不存在返回 Nil
+            }
+
+            // str[idx]
+            Value::String(s) => {
+                if let Value::Number(n) = idx {
+                    // NOTE：字符串按字符 (char) 索引，而不是按字节
+                    match s.chars().nth(n as usize) {
+                        Some(c) => Ok(Value::String(c.to_string())),
+                        None => Err(RuntimeError::Generic("String index out of bounds.".into())),
+                    }
+                } else {
+                    Err(RuntimeError::Generic(
+                        "String index must be a number.".into(),
+                    ))
+                }
+            }
+
+            _ => Err(RuntimeError::TypeError(
+                "Only lists, tuples, dicts and strings support subscripting.".into(),
+            )),
+        }
+    }
+
     // Resolver 接口
     pub fn resolve(&mut self, expr_id: ExprId, depth: usize) {
         self.locals.insert(expr_id, depth);
@@ -958,6 +1332,184 @@ impl Interpreter {
         }
     }
 
+    /// 全局变量赋值
+    ///
+    /// 预置环境在解释器之间共享且只读：对内置名（eg. `clock`）赋值时，
+    /// 在当前全局环境中定义同名变量将其遮蔽，而不是修改预置环境。
+    fn assign_global(&mut self, name: &str, value: Value) -> bool {
+        let mut globals = self.globals.borrow_mut();
+        if globals.values.contains_key(name) {
+            return globals.assign(name, value);
+        }
+        if globals.get(name).is_none() {
+            return false;
+        }
+        globals.define(name.to_string(), value);
+        true
+    }
+
+    /// 检查类是否实现了 `implements` 列出的所有 trait
+    ///
+    /// 继承的方法也算数；方法不存在或必需参数（不含剩余参数）个数与签名不一致都视为未实现。
+    fn check_traits(
+        &self,
+        name: &Token,
+        klass: &RoxClass,
+        traits: &[Token],
+    ) -> Result<(), RuntimeError> {
+        for trait_name in traits {
+            let signatures = match self.environment.borrow().get(&trait_name.lexeme) {
+                Some(Value::Trait(t)) => t,
+                Some(other) => {
+                    return Err(RuntimeError::TypeError(format!(
+                        "'{}' is not a trait (got {}).",
+                        trait_name.lexeme,
+                        other.type_name()
+                    )));
+                }
+                None => {
+                    return Err(RuntimeError::Generic(format!(
+                        "Undefined trait '{}'.",
+                        trait_name.lexeme
+                    )));
+                }
+            };
+
+            let missing: Vec<String> = signatures
+                .methods
+                .iter()
+                .filter(|(method, params)| match klass.find_method(method) {
+                    Some(Value::Function { args, rest, .. }) => {
+                        args.len() - usize::from(rest) != params.len()
+                    }
+                    _ => true,
+                })
+                .map(|(method, params)| format!("{}({})", method, params.join(", ")))
+                .collect();
+            if !missing.is_empty() {
+                return Err(RuntimeError::TypeError(format!(
+                    "Class '{}' does not implement trait '{}': missing {}.",
+                    name.lexeme,
+                    trait_name.lexeme,
+                    missing.join(", ")
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// 执行循环语句 (While / For / ForIn)
+    ///
+    /// `label` 是循环的标签（`outer: while ...`），带有该标签的 break / continue 也由这个循环处理。
+    fn execute_loop(&mut self, stmt: &Stmt, label: Option<&str>) -> Result<(), RuntimeError> {
+        match stmt {
+            Stmt::While { condition, body } => {
+                while self.evaluate(condition)?.is_truthy() {
+                    if let Err(e) = self.execute(body) {
+                        match Self::loop_signal(e, label)? {
+                            LoopSignal::Break => break,
+                            LoopSignal::Continue => continue,
+                        }
+                    }
+                }
+                Ok(())
+            }
+            Stmt::For {
+                initializer,
+                condition,
+                increment,
+                body,
+            } => {
+                let previous_env = self.environment.clone();
+                // 创建新作用域 (init 变量)
+                self.environment = Rc::new(RefCell::new(Environment::with_enclosing(
+                    previous_env.clone(),
+                )));
+
+                if let Some(init) = initializer {
+                    self.execute(init)?; // init 是 Stmt (VarDecl 或 ExprStmt)
+                }
+
+                // 循环：使用 try-finally 模式确保环境恢复
+                let result = (|| -> Result<(), RuntimeError> {
+                    loop {
+                        // Check condition
+                        if let Some(cond) = condition
+                            && !self.evaluate(cond)?.is_truthy()
+                        {
+                            break;
+                        }
+
+                        // Run body
+                        if let Err(e) = self.execute(body) {
+                            match Self::loop_signal(e, label)? {
+                                LoopSignal::Break => break,
+                                LoopSignal::Continue => {
+                                    // Note：continue 也要执行 increment
+                                }
+                            }
+                        }
+
+                        // Run increment
+                        if let Some(incr) = increment {
+                            self.evaluate(incr)?;
+                        }
+                    }
+                    Ok(())
+                })();
+
+                self.environment = previous_env;
+                result
+            }
+            Stmt::ForIn {
+                vars,
+                iterable,
+                body,
+            } => {
+                let iterable = self.evaluate(iterable)?;
+                let mut iteration = self.iterate(iterable)?;
+
+                let previous_env = self.environment.clone();
+                let result = (|| -> Result<(), RuntimeError> {
+                    while let Some((key, value)) = iteration.next(self)? {
+                        // 每次迭代使用新的作用域，闭包捕获到的是当次的循环变量
+                        self.environment = iteration.bind(vars, key, value, previous_env.clone());
+
+                        if let Err(e) = self.execute(body) {
+                            match Self::loop_signal(e, label)? {
+                                LoopSignal::Break => break,
+                                LoopSignal::Continue => {}
+                            }
+                        }
+                    }
+                    Ok(())
+                })();
+
+                self.environment = previous_env;
+                result
+            }
+            _ => unreachable!("only loops can be labeled"),
+        }
+    }
+
+    /// 判断循环体抛出的错误是否是交给当前循环的 break / continue
+    ///
+    /// 不带标签的信号属于最内层循环；带标签的只由同名循环处理，其余错误继续向外传播。
+    pub(crate) fn loop_signal(
+        error: RuntimeError,
+        label: Option<&str>,
+    ) -> Result<LoopSignal, RuntimeError> {
+        match error {
+            RuntimeError::Break(target) if target.is_none() || target.as_deref() == label => {
+                Ok(LoopSignal::Break)
+            }
+            RuntimeError::Continue(target) if target.is_none() || target.as_deref() == label => {
+                Ok(LoopSignal::Continue)
+            }
+            other => Err(other),
+        }
+    }
+
     /// 执行代码块并在指定环境中运行 (Block Execution)
     ///
     /// 负责管理作用域的 **进入** 和 **退出**。
@@ -1042,6 +1594,50 @@ impl Interpreter {
         }
     }
 
+    /// 比较运算：两个数字按数值比较，两个字符串按字典序 (Unicode 码点) 比较，两个 DateTime 按先后比较
+    ///
+    /// 任一边为 NaN 时结果恒为 false，与 IEEE 754 一致
+    fn compare_operands<F>(&self, left: Value, right: Value, f: F) -> Result<Value, RuntimeError>
+    where
+        F: FnOnce(std::cmp::Ordering) -> bool,
+    {
+        match (left, right) {
+            (Value::Number(n1), Value::Number(n2)) => {
+                Ok(Value::Boolean(n1.partial_cmp(&n2).is_some_and(f)))
+            }
+            (Value::String(s1), Value::String(s2)) => Ok(Value::Boolean(f(s1.cmp(&s2)))),
+            (Value::DateTime(t1), Value::DateTime(t2)) => {
+                Ok(Value::Boolean(t1.partial_cmp(&t2).is_some_and(f)))
+            }
+            _ => Err(RuntimeError::TypeError(
+                "Operands must be two numbers, two strings or two datetimes.".into(),
+            )),
+        }
+    }
+
+    /// 解构绑定：把 List / Tuple 按位置拆成 `count` 个值，元素个数必须一致
+    fn unpack(&self, value: Value, count: usize) -> Result<Vec<Value>, RuntimeError> {
+        let items = match value {
+            Value::List(list) => list.borrow().clone(),
+            Value::Tuple(items) => items,
+            other => {
+                return Err(RuntimeError::TypeError(format!(
+                    "Can only destructure a List or Tuple, got {}.",
+                    other.type_name()
+                )));
+            }
+        };
+
+        if items.len() != count {
+            return Err(RuntimeError::Generic(format!(
+                "Expected {} values to unpack but got {}.",
+                count,
+                items.len()
+            )));
+        }
+        Ok(items)
+    }
+
     /// 处理两个值的加法运算，支持数字、字符串、列表、元组和字典的连接
     ///
     /// # 参数
@@ -1050,7 +1646,17 @@ impl Interpreter {
     ///
     /// # 返回值
     /// * `Ok(Value)` - 连接或相加的结果
-    fn add_values(&self, left: Value, right: Value) -> Result<Value, RuntimeError> {
+    fn add_values(&mut self, left: Value, right: Value) -> Result<Value, RuntimeError> {
+        if let Some(result) = decimal::binary(&Operator::Add, &left, &right) {
+            return result;
+        }
+        if let Some(result) = std_lib::nd::binary(&Operator::Add, &left, &right) {
+            return result;
+        }
+        if let Some(result) = bigint::binary(&Operator::Add, &left, &right) {
+            return result;
+        }
+
         match (left, right) {
             (Value::Number(n1), Value::Number(n2)) => Ok(Value::Number(n1 + n2)),
 
@@ -1061,30 +1667,36 @@ impl Interpreter {
             }
 
             (Value::String(mut s1), other) => {
-                // 使用 other.to_string() 进行隐式转换
-                s1.push_str(&other.to_string());
+                // 使用 stringify 进行隐式转换 (实例会调用 __str__/toString)
+                s1.push_str(&self.stringify(&other)?);
                 Ok(Value::String(s1))
             }
 
             (other, Value::String(s2)) => {
-                let mut res = other.to_string();
+                let mut res = self.stringify(&other)?;
                 res.push_str(&s2);
                 Ok(Value::String(res))
             }
 
             //  列表、元组、字典
             (Value::List(list1), Value::List(list2)) => {
-                list1.borrow_mut().extend(list2.borrow().iter().cloned());
+                // 先复制右侧元素：`l + l` 时两侧是同一个列表，不能同时借用
+                let tail = list2.borrow().clone();
+                list1.borrow_mut().extend(tail);
                 Ok(Value::List(list1))
             }
             (Value::Tuple(mut tuple1), Value::Tuple(tuple2)) => {
                 tuple1.extend(tuple2);
                 Ok(Value::Tuple(tuple1))
             }
+            (Value::Bytes(bytes1), Value::Bytes(bytes2)) => Ok(Value::Bytes(
+                bytes1.iter().chain(bytes2.iter()).copied().collect(),
+            )),
             (Value::Dict(dict1), Value::Dict(dict2)) => {
-                dict1
-                    .borrow_mut()
-                    .extend(dict2.borrow().iter().map(|(k, v)| (k.clone(), v.clone())));
+                let entries = dict2.borrow().values().cloned().collect::<Vec<_>>();
+                for (k, v) in entries {
+                    self.dict_insert(&dict1, k, v)?;
+                }
                 Ok(Value::Dict(dict1))
             }
 
@@ -1097,6 +1709,34 @@ impl Interpreter {
         }
     }
 
+    /// 将值转换为用户可见的字符串 (print / 字符串拼接)
+    ///
+    /// 如果值是定义了 `__str__` (或 `toString`) 方法的实例，则调用该方法，
+    /// 其返回值必须是字符串；其余情况委托给 `Display`。
+    pub fn stringify(&mut self, value: &Value) -> Result<String, RuntimeError> {
+        if let Value::Instance(instance_rc) = value {
+            let method = {
+                let klass = instance_rc.borrow().class.clone();
+                let klass = klass.borrow();
+                klass
+                    .bind_method("__str__", value.clone())
+                    .or_else(|| klass.bind_method("toString", value.clone()))
+            };
+
+            if let Some(bound) = method {
+                return match self.call_value(&bound, vec![])? {
+                    Value::String(s) => Ok(s),
+                    other => Err(RuntimeError::TypeError(format!(
+                        "__str__ must return a String, got {}.",
+                        other.type_name()
+                    ))),
+                };
+            }
+        }
+
+        Ok(value.to_string())
+    }
+
     /// 位运算辅助函数
     ///
     /// 对两个表达式进行位运算操作，支持按位与、按位或操作
@@ -1145,15 +1785,41 @@ impl Interpreter {
 
     /// 公共 API：允许调用一个 Rox 值
     /// 参数 args 必须是已经求值过的 Value 列表
+    ///
+    /// 调用深度超过 `MAX_CALL_DEPTH` 时返回错误，而不是让无限递归耗尽调用栈。
     pub fn call_value(&mut self, callee: &Value, args: Vec<Value>) -> Result<Value, RuntimeError> {
+        if self.call_depth >= MAX_CALL_DEPTH {
+            return Err(RuntimeError::Generic(format!(
+                "Maximum call depth ({}) exceeded.",
+                MAX_CALL_DEPTH
+            )));
+        }
+
+        self.call_depth += 1;
+        let result = self.invoke(callee, args);
+        self.call_depth -= 1;
+        result
+    }
+
+    fn invoke(&mut self, callee: &Value, args: Vec<Value>) -> Result<Value, RuntimeError> {
         match callee {
             Value::Function {
+                name,
                 args: param_names,
+                rest,
                 body,
                 closure,
-                ..
             } => {
-                if args.len() != param_names.len() {
+                // 有剩余参数时，只要求提供剩余参数之前的参数
+                let required = param_names.len() - usize::from(*rest);
+                if *rest && args.len() < required {
+                    return Err(RuntimeError::Generic(format!(
+                        "Expected at least {} arguments but got {}.",
+                        required,
+                        args.len()
+                    )));
+                }
+                if !*rest && args.len() != param_names.len() {
                     return Err(RuntimeError::Generic(format!(
                         "Expected {} arguments but got {}.",
                         param_names.len(),
@@ -1165,13 +1831,30 @@ impl Interpreter {
                 let func_env = Rc::new(RefCell::new(Environment::with_enclosing(closure.clone())));
 
                 // 绑定参数 (此时 args 已经是 Value，直接绑定)
-                for (i, param_name) in param_names.iter().enumerate() {
+                let mut args = args.into_iter();
+                for param_name in &param_names[..required] {
+                    let arg = args.next().unwrap_or(Value::Nil);
+                    func_env.borrow_mut().define(param_name.clone(), arg);
+                }
+
+                // 多余的实参收集到剩余参数的列表中
+                if *rest {
+                    let list = Value::List(Rc::new(RefCell::new(args.collect())));
+                    heap::track(&list);
                     func_env
                         .borrow_mut()
-                        .define(param_name.clone(), args[i].clone());
+                        .define(param_names[required].clone(), list);
+                }
+
+                // 函数体中有 yield：不执行函数体，返回一个生成器
+                if body.iter().any(|stmt| stmt.find_yield().is_some()) {
+                    let generator = Generator::new(name.clone(), body.clone(), func_env);
+                    return Ok(Value::Generator(Rc::new(RefCell::new(generator))));
                 }
 
+                self.deferred.push(Vec::new());
                 let result = self.execute_block(body, (*func_env).clone().into_inner());
+                let result = self.run_deferred(result);
 
                 match result {
                     Ok(_) => Ok(Value::Nil),
@@ -1183,11 +1866,14 @@ impl Interpreter {
             // 类实例化
             Value::Class(klass) => {
                 let instance = Rc::new(RefCell::new(super::value::RoxInstance::new(klass.clone())));
+                heap::track(&Value::Instance(instance.clone()));
 
                 // 查找 init
-                if let Some(init_method) = klass.borrow().find_method("init") {
+                let bound_init = klass
+                    .borrow()
+                    .bind_method("init", Value::Instance(instance.clone()));
+                if let Some(bound_init) = bound_init {
                     // 递归调用自己来处理 init (因为它是一个 Function)
-                    let bound_init = init_method.bind(Value::Instance(instance.clone()));
                     self.call_value(&bound_init, args)?; // 这里的 args 直接传给 init
                 } else if !args.is_empty() {
                     Err(RuntimeError::Generic(format!(
@@ -1199,13 +1885,23 @@ impl Interpreter {
                 Ok(Value::Instance(instance))
             }
 
+            // 运行时附加到类上的方法：实例作为第一个参数
+            Value::BoundNativeMethod { receiver, method }
+                if matches!(**method, Value::Function { .. }) =>
+            {
+                let mut full_args = Vec::with_capacity(args.len() + 1);
+                full_args.push((**receiver).clone());
+                full_args.extend(args);
+                self.call_value(method, full_args)
+            }
+
             // 绑定的原生方法
             Value::BoundNativeMethod { receiver, method } => {
                 // 解包出内部的原生函数
                 if let Value::NativeFunction { arity, func, .. } = &**method {
                     // 检查传入的参数数量
                     // 这里 args 还不包含 this，所以 args.len() 是 1，arity 也是 1。
-                    if args.len() != *arity {
+                    if *arity != VARIADIC && args.len() != *arity {
                         return Err(RuntimeError::Generic(format!(
                             "Expected {} arguments but got {}.",
                             arity,
@@ -1222,13 +1918,16 @@ impl Interpreter {
                     // 直接调用 func 跳过 NativeFunction 分支里的 arity 检查，否则会出现参数获取多个错误
                     func(self, full_args)
                 } else {
-                    panic!("BoundNativeMethod must wrap a NativeFunction");
+                    Err(RuntimeError::TypeError(format!(
+                        "Can only call functions and classes, got {}.",
+                        method.type_name()
+                    )))
                 }
             }
 
             // 4. 原生函数
             Value::NativeFunction { arity, func, .. } => {
-                if args.len() != *arity {
+                if *arity != VARIADIC && args.len() != *arity {
                     return Err(RuntimeError::Generic(format!(
                         "Expected {} arguments but got {}.",
                         arity,
@@ -1243,39 +1942,155 @@ impl Interpreter {
             )),
         }
     }
+    /// 函数退出时逆序执行本层登记的 defer 表达式
+    ///
+    /// 每个 defer 都会执行；函数体本身出错时保留原错误，否则返回第一个 defer 的错误。
+    fn run_deferred(&mut self, result: Result<(), RuntimeError>) -> Result<(), RuntimeError> {
+        let frame = self.deferred.pop().unwrap_or_default();
+        let mut result = result;
+        for (expr, env) in frame.into_iter().rev() {
+            let previous = std::mem::replace(&mut self.environment, env);
+            let outcome = self.evaluate(&expr);
+            self.environment = previous;
+            if let Err(e) = outcome
+                && matches!(result, Ok(()) | Err(RuntimeError::Return(_)))
+            {
+                result = Err(e);
+            }
+        }
+        result
+    }
+
     /// 加载并执行模块
     /// path_str: 相对路径或绝对路径
     pub fn import_module(&mut self, import_path: &str) -> Result<Value, RuntimeError> {
+        // 运行编译产物时，所有模块都来自 bundle
+        if let Some(bundle) = self.bundle.clone() {
+            return self.import_bundled(&bundle, import_path);
+        }
+        trace::deny_file_access(self, "import", import_path)?;
+
         // 1. 提取路径
         let absolute_path = self.resolve_path(import_path)?;
-        //   let path_key = absolute_path.to_string_lossy().to_string();
+        let path_key = Self::path_key(&absolute_path);
 
+        // 2. 检查缓存 (这是打破循环依赖的第一道防线)
+        if let Some(module) = self.modules.get(&path_key) {
+            return Ok(module.clone());
+        }
+
+        // 3. 内容读取
+        let source = fs::read_to_string(&absolute_path).map_err(|e| {
+            RuntimeError::Generic(format!("Failed to read module '{}': {}", path_key, e))
+        })?;
+
+        // 4. Tokenize + Parse (内容未变化时直接读取 .roxc 缓存)
+        let body = self.parse_file_source(source, &path_key)?;
+
+        // 5. 更新路径栈
+        let module_dir = absolute_path
+            .parent()
+            .ok_or_else(|| RuntimeError::Generic("Failed to get module directory".into()))?
+            .to_path_buf();
+
+        self.path_stack.push(module_dir);
+
+        // 6. 执行
+        let result = self.run_module(path_key, body);
+
+        // 7. 恢复路径栈
+        self.path_stack.pop();
+
+        result
+    }
+
+    /// 模块缓存键：绝对路径（去掉 Windows 的 `\\?\` 前缀）
+    fn path_key(absolute_path: &Path) -> String {
         let raw_path_str = absolute_path.to_string_lossy();
-        let path_key = if cfg!(windows) && raw_path_str.starts_with(r"\\?\") {
+        if cfg!(windows) && raw_path_str.starts_with(r"\\?\") {
             raw_path_str[4..].to_string()
         } else {
             raw_path_str.into_owned()
-        };
+        }
+    }
 
-        // 2. 检查缓存 (这是打破循环依赖的第一道防线)
-        if let Some(module) = self.modules.get(&path_key) {
+    /// 解析文件内容，优先使用编译缓存
+    fn parse_file_source(&self, source: String, path_key: &str) -> Result<Vec<Stmt>, RuntimeError> {
+        match &self.module_cache {
+            Some(cache) => cache.parse(source),
+            None => crate::cache::parse_source(source),
+        }
+        .map_err(|e| RuntimeError::Generic(format!("{} (in '{}')", e, path_key)))
+    }
+
+    /// 在 bundle 中查找当前模块导入的目标模块键
+    fn bundled_key(&self, bundle: &Bundle, import_path: &str) -> Result<String, RuntimeError> {
+        let current = self
+            .bundle_stack
+            .last()
+            .cloned()
+            .unwrap_or_else(|| bundle.entry.clone());
+
+        bundle
+            .modules
+            .get(&current)
+            .and_then(|m| m.imports.get(import_path))
+            .cloned()
+            .ok_or_else(|| {
+                RuntimeError::Generic(format!(
+                    "Module '{}' is not part of the compiled bundle (only imports with literal paths are bundled).",
+                    import_path
+                ))
+            })
+    }
+
+    /// bundle 中某个模块的语法树
+    fn bundled_body(bundle: &Bundle, key: &str) -> Result<Vec<Stmt>, RuntimeError> {
+        bundle
+            .modules
+            .get(key)
+            .map(|module| module.body.clone())
+            .ok_or_else(|| {
+                RuntimeError::Generic(format!("Module '{}' is missing from the bundle.", key))
+            })
+    }
+
+    /// 从编译产物 (bundle) 中导入模块
+    ///
+    /// bundle 在编译期已经解析好了每个模块的导入关系，运行时不再访问文件系统。
+    fn import_bundled(
+        &mut self,
+        bundle: &Bundle,
+        import_path: &str,
+    ) -> Result<Value, RuntimeError> {
+        let key = self.bundled_key(bundle, import_path)?;
+
+        if let Some(module) = self.modules.get(&key) {
             return Ok(module.clone());
         }
 
-        // 3. 为新模块压入导出集合
-        self.exports_stack.push(HashSet::new());
+        let body = Self::bundled_body(bundle, &key)?;
 
-        // 4. 内容读取
-        let source = fs::read_to_string(&absolute_path).map_err(|e| {
-            RuntimeError::Generic(format!("Failed to read module '{}': {}", path_key, e))
-        })?;
+        self.bundle_stack.push(key.clone());
+        let result = self.run_module(key, body);
+        self.bundle_stack.pop();
+
+        result
+    }
 
-        // 5. 预先缓存 (打破循环依赖的核心)
+    /// 在独立的模块环境中执行模块代码，并收集其导出
+    ///
+    /// # 参数
+    /// * `path_key` - 模块缓存键 (文件模块为绝对路径)
+    /// * `body` - 已解析的模块语法树
+    fn run_module(&mut self, path_key: String, body: Vec<Stmt>) -> Result<Value, RuntimeError> {
+        // 1. 为新模块压入导出集合
+        self.exports_stack.push(HashSet::new());
+
+        // 2. 预先缓存 (打破循环依赖的核心)
         let rox_module = RoxModule::new(path_key.clone());
         let module_value = Value::Module(Rc::new(RefCell::new(rox_module)));
 
-        self.modules.insert(path_key.clone(), module_value.clone());
-
         // Thinking：
         // 在执行代码之前，先把这个空的 Dict 放入缓存中。
         // 如果 Module A 导入 B，B 又导入 A：
@@ -1288,31 +2103,13 @@ impl Interpreter {
         // 🌈 此时 B 持有的 Dict_A 引用会自动看到 A 填充的数据
         self.modules.insert(path_key.clone(), module_value.clone());
 
-        // 6. 更新路径栈
-        let module_dir = absolute_path
-            .parent()
-            .ok_or_else(|| RuntimeError::Generic("Failed to get module directory".into()))?
-            .to_path_buf();
-
-        self.path_stack.push(module_dir);
-
-        // 7. 编译执行
+        // 3. 编译执行
         // 使用闭包捕获 Result，确保无论成功失败都能执行 cleanup (出栈)
         let result = (|| -> Result<(), RuntimeError> {
-            // Tokenize
-            let tokens = crate::tokenizer::tokenize(crate::reader::Source { contents: source })
-                .map_err(|e| {
-                    RuntimeError::Generic(format!("Scan error in '{}': {}", path_key, e))
-                })?;
-
-            // Parse
-            let ast = crate::parser::parse(tokens).map_err(|e| {
-                RuntimeError::Generic(format!("Parse error in '{}': {}", path_key, e))
-            })?;
-
             // 准备环境
-            let module_env = Rc::new(RefCell::new(Environment::new()));
-            Self::init_globals(&module_env); // 注入全局方法
+            let module_env = Rc::new(RefCell::new(Environment::with_enclosing(
+                self.registry.prelude(), // 注入全局方法
+            )));
 
             // 切换上下文
             let previous_globals = self.globals.clone();
@@ -1322,7 +2119,11 @@ impl Interpreter {
 
             // Resolve (复用 locals 表)
             let mut resolver = crate::resolver::Resolver::new(self);
-            if let Err(msg) = resolver.resolve_stmts(&ast.body) {
+            let resolved = resolver.resolve_stmts(&body);
+            for warning in &resolver.warnings {
+                eprintln!("Warning: {} (in '{}')", warning, path_key);
+            }
+            if let Err(msg) = resolved {
                 // 恢复环境
                 self.globals = previous_globals;
                 self.environment = previous_env;
@@ -1334,8 +2135,8 @@ impl Interpreter {
 
             // Execute
             let exec_res = (|| {
-                for stmt in ast.body {
-                    self.execute(&stmt)?;
+                for stmt in &body {
+                    self.execute(stmt)?;
                 }
                 Ok(())
             })();
@@ -1344,8 +2145,8 @@ impl Interpreter {
             if exec_res.is_ok() {
                 let env = self.environment.borrow();
 
-                // 获取导出列表，不可以 pop 导出列表，因为最后的 path_stack.pop(); 需要恢复路径。通过 last 来 peek
-                let exported_names = self.exports_stack.last().unwrap();
+                // 获取导出列表，不可以 pop 导出列表，通过 last 来 peek
+                let exported_names = self.exports_stack.last().into_iter().flatten();
 
                 // 获取 RoxModule 的可变借用
                 // module_value： Value::Module(Rc<RefCell<RoxModule>>)
@@ -1368,14 +2169,11 @@ impl Interpreter {
             self.globals = previous_globals;
             self.environment = previous_env;
 
-            // 清理导出栈
-            self.exports_stack.pop();
-
             exec_res
         })();
 
-        // 8. 恢复路径栈
-        self.path_stack.pop();
+        // 清理导出栈
+        self.exports_stack.pop();
 
         // 错误处理
         match result {
@@ -1388,11 +2186,137 @@ impl Interpreter {
         }
     }
 
+    /// 在全局环境中执行另一个文件 (`include`)
+    ///
+    /// 与 `import` 不同，被包含文件没有独立的模块环境：它的顶层声明直接定义在调用方的
+    /// 全局环境中，并且可以读写调用方已有的全局变量。每次调用都会重新执行文件。
+    /// 路径查找规则与 `import` 相同；同一文件在包含链上重复出现时报错（循环包含）。
+    pub fn include_file(&mut self, include_path: &str) -> Result<Value, RuntimeError> {
+        let (key, body, dir) = if let Some(bundle) = self.bundle.clone() {
+            let key = self.bundled_key(&bundle, include_path)?;
+            let body = Self::bundled_body(&bundle, &key)?;
+            (key, body, None)
+        } else {
+            trace::deny_file_access(self, "include", include_path)?;
+            let absolute_path = self.resolve_path(include_path)?;
+            let key = Self::path_key(&absolute_path);
+            let source = fs::read_to_string(&absolute_path).map_err(|e| {
+                RuntimeError::Generic(format!("Failed to read file '{}': {}", key, e))
+            })?;
+            let body = self.parse_file_source(source, &key)?;
+            (key, body, absolute_path.parent().map(Path::to_path_buf))
+        };
+
+        if self.include_stack.contains(&key) {
+            return Err(RuntimeError::Generic(format!(
+                "Circular include of '{}'.",
+                key
+            )));
+        }
+
+        // 顶层代码：作用域栈为空，所有声明都解析为全局变量
+        let mut resolver = crate::resolver::Resolver::new(self);
+        let resolved = resolver.resolve_stmts(&body);
+        for warning in &resolver.warnings {
+            eprintln!("Warning: {} (in '{}')", warning, key);
+        }
+        resolved.map_err(|msg| {
+            RuntimeError::Generic(format!("Resolution error in '{}': {}", key, msg))
+        })?;
+
+        self.include_stack.push(key.clone());
+        match &dir {
+            Some(dir) => self.path_stack.push(dir.clone()),
+            None => self.bundle_stack.push(key),
+        }
+        let previous = std::mem::replace(&mut self.environment, self.globals.clone());
+
+        let result = (|| {
+            for stmt in &body {
+                self.execute(stmt)?;
+            }
+            Ok(Value::Nil)
+        })();
+
+        self.environment = previous;
+        if dir.is_some() {
+            self.path_stack.pop();
+        } else {
+            self.bundle_stack.pop();
+        }
+        self.include_stack.pop();
+
+        result
+    }
+
+    /// 在调用者作用域的子作用域中执行一段源码 (`eval`)
+    ///
+    /// - 代码可以读写调用处可见的所有变量，但其中的声明只存在于子作用域，不会泄漏给调用者。
+    /// - 最后一条语句是表达式时返回它的值，否则返回 `nil`；末尾的 `;` 可以省略。
+    /// - 与调用者共用同一个解释器，被注册表禁用的模块在 eval 中同样不可用。
+    pub fn eval_source(&mut self, source: &str) -> Result<Value, RuntimeError> {
+        let body = crate::cache::parse_source(source.to_string())
+            .or_else(|e| crate::cache::parse_source(format!("{};", source)).map_err(|_| e))
+            .map_err(|e| RuntimeError::Generic(format!("eval: {}", e)))?;
+
+        // Resolver 的作用域栈需要与运行时环境链一一对应，距离才能算对：
+        // 调用处到全局之间的每个环境各对应一个作用域，最后再加上 eval 自己的子作用域。
+        let mut chain = Vec::new();
+        let mut current = Some(self.environment.clone());
+        while let Some(env) = current {
+            if Rc::ptr_eq(&env, &self.globals) {
+                break;
+            }
+            let names = env
+                .borrow()
+                .values
+                .keys()
+                .map(|name| (name.clone(), true))
+                .collect::<HashMap<_, _>>();
+            chain.push(names);
+            current = env.borrow().enclosing.clone();
+        }
+        chain.reverse();
+        chain.push(HashMap::new());
+
+        let mut resolver = crate::resolver::Resolver::new(self);
+        resolver.declarations = vec![HashMap::new(); chain.len()];
+        resolver.scopes = chain;
+        resolver
+            .resolve_stmts(&body)
+            .map_err(|msg| RuntimeError::Generic(format!("eval: Resolution error: {}", msg)))?;
+
+        let previous = self.environment.clone();
+        self.environment = Rc::new(RefCell::new(Environment::with_enclosing(previous.clone())));
+
+        let result = (|| {
+            let Some((last, rest)) = body.split_last() else {
+                return Ok(Value::Nil);
+            };
+            for stmt in rest {
+                self.execute(stmt)?;
+            }
+            match last {
+                Stmt::Expression { expr } => self.evaluate(expr),
+                stmt => self.execute(stmt).map(|_| Value::Nil),
+            }
+        })();
+
+        self.environment = previous;
+        result
+    }
+
     /// 解析导入路径为绝对路径
+    ///
+    /// 按以下顺序查找，命中第一个存在的文件：
+    /// 1. 当前执行文件所在目录（入口文件 / REPL 使用进程工作目录）
+    /// 2. 项目清单 (rox.toml) 声明的模块根目录与依赖目录
+    /// 3. 环境变量 `ROX_PATH` 中列出的目录（按平台路径分隔符分隔）
+    /// 4. 标准库目录 `~/.rox/lib`
+    ///
+    /// 每个目录下先尝试原路径，再尝试补上 `.rox` 扩展名。
+    /// 全部失败时，错误信息会列出所有尝试过的路径。
     fn resolve_path(&self, import_path: &str) -> Result<PathBuf, RuntimeError> {
-        // 基准路径 (栈顶) + 相对路径 -> 绝对路径
-        let path = Path::new(import_path);
-
         // 获取基准目录 (Anchor)
         let anchor = if let Some(current_dir) = self.path_stack.last() {
             // 如果正在执行某个文件，以该文件所在目录为基准
@@ -1404,16 +2328,67 @@ impl Interpreter {
             })?
         };
 
-        let joined_path = anchor.join(path);
+        self.resolve_import(&anchor, import_path)
+    }
+
+    /// 以 `anchor` 为基准目录解析导入路径，查找顺序见 `resolve_path`
+    pub fn resolve_import(
+        &self,
+        anchor: &Path,
+        import_path: &str,
+    ) -> Result<PathBuf, RuntimeError> {
+        let path = Path::new(import_path);
+        let mut search_dirs = vec![anchor.to_path_buf()];
+        // 显式的相对路径 ("./x"、"../x") 和绝对路径只相对于基准目录解析
+        let is_explicit = path.is_absolute() || import_path.starts_with('.');
+        if !is_explicit {
+            search_dirs.extend(self.search_paths.iter().cloned());
+            if let Some(rox_path) = std::env::var_os("ROX_PATH") {
+                search_dirs.extend(std::env::split_paths(&rox_path));
+            }
+            if let Some(home) = dirs::home_dir() {
+                search_dirs.push(home.join(".rox").join("lib"));
+            }
+        }
+
+        let mut tried = Vec::new();
+        for dir in search_dirs {
+            let joined_path = dir.join(path);
+            let mut candidates = vec![joined_path.clone()];
+            if joined_path.extension().is_none() {
+                candidates.push(joined_path.with_extension("rox"));
+            }
 
-        // 获取绝对路径 (Canonicalize) 访问文件系统，顺便验证文件是否存在
-        fs::canonicalize(&joined_path).map_err(|e| {
-            RuntimeError::Generic(format!(
-                "Cannot find module '{}': {}",
-                joined_path.display(),
-                e
-            ))
-        })
+            for candidate in candidates {
+                // 获取绝对路径 (Canonicalize) 访问文件系统，顺便验证文件是否存在
+                if candidate.is_file()
+                    && let Ok(absolute) = fs::canonicalize(&candidate)
+                {
+                    return Ok(absolute);
+                }
+                tried.push(candidate);
+            }
+        }
+
+        Err(RuntimeError::Generic(format!(
+            "Cannot find module '{}'. Tried:\n{}",
+            import_path,
+            tried
+                .iter()
+                .map(|p| format!("  - {}", p.display()))
+                .collect::<Vec<_>>()
+                .join("\n")
+        )))
+    }
+
+    /// 开启确定性模式，使脚本测试的输出可以复现
+    ///
+    /// - `math.random` 等随机函数使用固定种子。
+    /// - `clock()` / `time.now()` 返回从 0 开始的假时钟，只能通过 `time.advance(seconds)` 拨动。
+    /// - 字典本身按插入顺序迭代，不需要额外处理。
+    pub fn set_deterministic(&mut self, seed: u32) {
+        std_lib::math::seed(seed);
+        self.fake_clock = Some(0.0);
     }
 
     /// 获取全局变量的值（仅在测试时可用）
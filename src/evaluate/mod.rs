@@ -1,6 +1,13 @@
+pub mod bigint;
+pub mod decimal;
 pub mod environment;
 pub mod error;
+pub mod format;
+pub mod generator;
+pub mod hashing;
+pub mod heap;
 pub mod interpreter;
+pub mod snapshot;
 pub mod value;
 
 pub use interpreter::Interpreter;
@@ -0,0 +1,149 @@
+use crate::{
+    evaluate::{Interpreter, value::Value},
+    std_lib::log,
+};
+use std::collections::{BTreeMap, HashMap};
+
+/// 解释器全局状态快照
+///
+/// 只记录绑定关系：全局变量（包括类与 trait）、已加载的模块、扩展方法、日志级别以及信号处理函数。
+/// 绑定指向的对象（List / Dict / 实例等）与当前状态共享，恢复快照不会回滚对象内部的修改。
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    globals: HashMap<String, Value>,
+    modules: HashMap<String, Value>,
+    extensions: HashMap<String, HashMap<String, Value>>,
+    log_level: log::Level,
+    signals: BTreeMap<&'static str, Value>,
+}
+
+#[allow(dead_code)] // 面向嵌入方的 API，REPL 只使用 reset
+impl Interpreter {
+    /// 清空用户定义的全局状态，回到刚创建时的样子
+    ///
+    /// 内置全局名位于共享的预置环境中，不需要重新构建。
+    /// `locals` 侧表保留：ExprId 全局唯一，旧条目不会与新代码冲突，且恢复快照后仍然需要它们。
+    pub fn reset(&mut self) {
+        self.restore(Snapshot {
+            globals: HashMap::new(),
+            modules: HashMap::new(),
+            extensions: HashMap::new(),
+            log_level: log::Level::default(),
+            signals: BTreeMap::new(),
+        });
+    }
+
+    /// 记录当前的全局状态
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            globals: self.globals.borrow().values.clone(),
+            modules: self.modules.clone(),
+            extensions: self.extensions.clone(),
+            log_level: self.log_level,
+            signals: self.signals.callbacks(),
+        }
+    }
+
+    /// 把全局状态回滚到快照
+    ///
+    /// 原地替换全局环境中的绑定，已经持有全局环境引用的闭包看到的也是恢复后的状态。
+    pub fn restore(&mut self, snapshot: Snapshot) {
+        self.globals.borrow_mut().values = snapshot.globals;
+        self.modules = snapshot.modules;
+        self.extensions = snapshot.extensions;
+        self.log_level = snapshot.log_level;
+        self.signals.restore(&snapshot.signals);
+
+        self.environment = self.globals.clone();
+        self.path_stack.clear();
+        self.exports_stack.clear();
+        self.bundle_stack.clear();
+        self.include_stack.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        evaluate::{Interpreter, Value},
+        parser::parse,
+        reader::Source,
+        resolver::Resolver,
+        std_lib::log,
+        tokenizer::tokenize,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn run(interpreter: &mut Interpreter, code: &str) {
+        let tokens = tokenize(Source {
+            contents: code.to_string(),
+        })
+        .unwrap();
+        let ast = parse(tokens).unwrap();
+        Resolver::new(interpreter).resolve_stmts(&ast.body).unwrap();
+        interpreter.interpret(ast).unwrap();
+    }
+
+    #[test]
+    fn test_snapshot_and_restore() {
+        let mut interpreter = Interpreter::new();
+        run(
+            &mut interpreter,
+            "var a = 1; fun double(x) { return x * 2; }",
+        );
+        let snapshot = interpreter.snapshot();
+
+        run(&mut interpreter, "a = 10; var b = 2; clock = nil;");
+        interpreter.restore(snapshot);
+        run(&mut interpreter, "var res = double(a);");
+
+        assert_eq!(
+            interpreter.get_global_value("res").unwrap(),
+            Value::Number(2.0)
+        );
+        assert!(interpreter.get_global_value("b").is_none());
+        assert!(matches!(
+            interpreter.get_global_value("clock"),
+            Some(Value::NativeFunction { .. })
+        ));
+    }
+
+    #[test]
+    fn test_restore_rolls_back_declarations_and_module_state() {
+        let mut interpreter = Interpreter::new();
+        let snapshot = interpreter.snapshot();
+
+        run(
+            &mut interpreter,
+            r#"trait Show { show(); }
+            class A implements Show { show() {} }
+            log.setLevel("error");
+            signal.on("SIGUSR2", fun() {});"#,
+        );
+        assert!(matches!(
+            interpreter.get_global_value("Show"),
+            Some(Value::Trait(_))
+        ));
+        assert!(!interpreter.signals.callbacks().is_empty());
+
+        interpreter.restore(snapshot);
+        assert!(interpreter.get_global_value("Show").is_none());
+        assert!(interpreter.get_global_value("A").is_none());
+        assert_eq!(interpreter.log_level, log::Level::default());
+        assert!(interpreter.signals.callbacks().is_empty());
+    }
+
+    #[test]
+    fn test_reset_keeps_builtins() {
+        let mut interpreter = Interpreter::new();
+        run(&mut interpreter, "var a = 1; math = nil;");
+        interpreter.reset();
+
+        assert!(interpreter.get_global_value("a").is_none());
+        run(&mut interpreter, "var res = math.abs(-3);");
+        assert_eq!(
+            interpreter.get_global_value("res").unwrap(),
+            Value::Number(3.0)
+        );
+    }
+}
@@ -1,4 +1,7 @@
-use crate::evaluate::{Value, tests::eval_res};
+use crate::evaluate::{
+    Interpreter, Value,
+    tests::{eval_res, eval_res_with},
+};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -7,6 +10,18 @@ fn test_arithmetic() {
     assert_eq!(eval_res(code).unwrap(), Value::Number(7.0));
 }
 
+#[test]
+fn test_floor_division() {
+    let code = r#"
+        var res = [7 ~/ 2, -7 ~/ 2, 7.5 ~/ -2, 1 + 9 ~/ 4 * 2, -7n ~/ 2, 7n ~/ -2, decimal("-7.5") ~/ 2];
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        "[3, -4, -4, 5, -4, -4, -4]"
+    );
+    assert!(eval_res("var res = 1 ~/ 0;").is_err());
+}
+
 #[test]
 fn test_string_concatenation() {
     let code = "var res = \"hello\" + \" \" + \"world\";";
@@ -16,6 +31,41 @@ fn test_string_concatenation() {
     );
 }
 
+#[test]
+fn test_string_interpolation() {
+    let code = r#"
+        var name = "rox";
+        var info = {"age": 3};
+        var res = "${name} is ${info["age"]} (${ {"n": 1}["n"] + 1 }), nested: ${"<${name}>"}, $5";
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap(),
+        Value::String("rox is 3 (2), nested: <rox>, $5".to_string())
+    );
+    assert!(eval_res(r#"var res = "${1 2}";"#).is_err());
+}
+
+#[test]
+fn test_destructuring() {
+    let code = r#"
+        var (a, b) = (1, "two");
+        var [x, y, z] = [a, b, nil];
+        (a, b) = (b, a);
+        fun f() {
+            var [p, q] = [1, 2];
+            [p, q] = [q, p];
+            return (p, q);
+        }
+        var res = [a, b, x, y, z, f()];
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        "[two, 1, 1, two, nil, (2, 1)]"
+    );
+    assert!(eval_res("var (a, b) = [1, 2, 3]; var res = a;").is_err());
+    assert!(eval_res("var [a] = 1; var res = a;").is_err());
+}
+
 #[test]
 fn test_comparison() {
     assert_eq!(eval_res("var res = 1 < 2;").unwrap(), Value::Boolean(true));
@@ -30,6 +80,45 @@ fn test_comparison() {
     );
 }
 
+#[test]
+fn test_null_coalescing() {
+    let code = r#"
+        var calls = 0;
+        fun fallback() { calls += 1; return "fallback"; }
+        var config = {"retries": 0, "debug": false};
+        var res = [
+            nil ?? "default", config["retries"] ?? 3, config["debug"] ?? true,
+            nil ?? nil ?? 1, "set" ?? fallback(), calls, nil or false ?? "x"
+        ];
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        "[default, 0, false, 1, set, 0, false]"
+    );
+}
+
+#[test]
+fn test_optional_chaining() {
+    let code = r#"
+        var calls = 0;
+        fun key() { calls += 1; return "k"; }
+        class Point { init(x) { this.x = x; } getX() { return this.x; } }
+        var none = nil;
+        var p = Point(3);
+        var data = {"items": [1, 2]};
+        var res = [
+            none?.x, none?[key()], none?.x.y.z, none?.getX(), calls,
+            p?.x, p?.getX(), data?["items"]?[1], data["missing"]?.x
+        ];
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        "[nil, nil, nil, nil, 0, 3, 3, 2, nil]"
+    );
+    // 非 nil 接收者上的错误照常抛出
+    assert!(eval_res("var res = 1?.missing;").is_err());
+}
+
 #[test]
 fn test_logic() {
     // And
@@ -52,3 +141,236 @@ fn test_logic() {
     );
     assert_eq!(eval_res("var res = 0 or 2;").unwrap(), Value::Number(2.0));
 }
+
+#[test]
+fn test_self_referential_collections() {
+    let code = r#"
+        var p = [1];
+        var joined = p + p;
+        var l = [1];
+        l.push(l);
+        var d = {"k": 1};
+        d["self"] = d;
+        var res = [joined.len(), l == l, d == d, repr(l), repr(d)];
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        r#"[2, true, true, [1, [...]], {"k": 1, "self": {...}}]"#
+    );
+}
+
+#[test]
+fn test_deep_copy() {
+    let code = r#"
+        class Box {}
+        var b = Box();
+        b.items = [1];
+        var shared = [0];
+        var orig = {"list": [1, [2]], "box": b, "pair": (shared, shared)};
+        var copy = deep_copy(orig);
+        copy["list"][1].push(3);
+        copy["box"].items.push(2);
+        copy["pair"][0].push(9);
+
+        var cyclic = [1];
+        cyclic.push(cyclic);
+        var cycled = cyclic.copy();
+        cycled.push(2);
+
+        var res = [
+            orig, copy["pair"][1], copy["box"] == b,
+            repr(cyclic), repr(cycled), cycled[1] == cycled, deep_copy(3)
+        ];
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        "[{list: [1, [2]], box: <instance Box>, pair: ([0], [0])}, [0, 9], false, \
+         [1, [...]], [1, [...], 2], true, 3]"
+    );
+}
+
+#[test]
+fn test_string_index_counts_chars() {
+    assert_eq!(
+        eval_res(r#"var res = "héllo"[4];"#).unwrap(),
+        Value::String("o".into())
+    );
+    assert!(eval_res(r#"var res = "héllo"[5];"#).is_err());
+}
+
+#[test]
+fn test_bigint_arithmetic() {
+    let code = r#"
+        var big = 9007199254740993n;
+        var d = {1n: "one"};
+        var res = [
+            big + 1n, big * big, -7n / 2n, -7n % 2n, 2n * 3,
+            big > 9007199254740992, 1n == 1, d[1], repr(bigint("-42"))
+        ];
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        "[9007199254740994, 81129638414606699710187514626049, -4, 1, 6, true, true, one, -42n]"
+    );
+    assert!(eval_res("var res = 1n / 0n;").is_err());
+    assert!(eval_res("var res = 1n + 2.5;").is_err());
+}
+
+#[test]
+fn test_decimal_arithmetic() {
+    let code = r#"
+        var price = decimal("19.99");
+        var d = {decimal("2.0"): "two"};
+        var res = [
+            decimal("0.1") + decimal("0.2") == decimal("0.3"), price * 3, decimal(1) / 3,
+            -decimal("7.5") % 2, decimal(0.25) < price, d[2], repr(price - 20)
+        ];
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        r#"[true, 59.97, 0.3333333333333333333333333333, 0.5, true, two, decimal("-0.01")]"#
+    );
+    assert!(eval_res(r#"var res = decimal("1") / 0;"#).is_err());
+    assert!(eval_res(r#"var res = decimal("1") + 0.5;"#).is_err());
+}
+
+#[test]
+fn test_bigint_decimal_equality() {
+    let code = r#"
+        var big = 9007199254740993n;
+        var d = {big: "big"};
+        var res = [
+            1n == decimal("1"), decimal("1.0") == 1n, 1n == decimal("1.5"), big == decimal("9007199254740993"),
+            d[decimal("9007199254740993")], set([1, 1n, decimal("1.0")]).len()
+        ];
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        "[true, true, false, true, big, 1]"
+    );
+}
+
+#[test]
+fn test_type_builtin() {
+    let code = r#"
+        class A { m() {} }
+        fun f() {}
+        var res = [
+            type(1), type("s"), type(nil), type(true), type([1]), type({}), type((1, 2)),
+            type(A), type(A()), type(f), type(clock), type(A().m), type(1n), type(math)
+        ];
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        "[number, string, nil, boolean, list, dict, tuple, class, instance, function, function, function, bigint, module]"
+    );
+    assert_eq!(
+        eval_res(r#"var x = [1]; var res = type(x) == "list";"#).unwrap(),
+        Value::Boolean(true)
+    );
+}
+
+#[test]
+fn test_format_builtin() {
+    let code = r#"
+        class P { __str__() { return "point"; } }
+        var res = [
+            format("x = {}, y = {:.2}", 3, 2.456), format("[{:>5}|{:<4}|{:^7}]", 1, "ab", "mid"),
+            format("{:*^9}", "hi"), format("{:06.1}", -3.14159), format("{1} {0} {1}", "a", "b"),
+            format("{{}} {:.3}", "abcdef"), format("{}", P()), format("{:5}", [1]), printf("{}", 1)
+        ];
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        "[x = 3, y = 2.46, [    1|ab  |  mid  ], ***hi****, -003.1, b a b, {} abc, point, [1]  , nil]"
+    );
+    assert!(eval_res(r#"var res = format("{} {}", 1);"#).is_err());
+    assert!(eval_res(r#"var res = format("{", 1);"#).is_err());
+    assert!(eval_res(r#"var res = format("}", 1);"#).is_err());
+    assert!(eval_res(r#"var res = format("{:x}", 1);"#).is_err());
+    assert!(eval_res(r#"var res = format("{:.70000}", 1);"#).is_err());
+    assert!(eval_res(r#"var res = format("{:99999999999999}", 1);"#).is_err());
+    assert!(eval_res("var res = format(1);").is_err());
+}
+
+#[test]
+fn test_number_conversion_builtins() {
+    let code = r#"
+        var res = [
+            parse_number("3.5"), parse_number(" -1_000 "), parse_number("1e3"), parse_number("12px"),
+            parse_number(""), parse_number("inf"), parse_number("ff", 16), parse_number("0xFF", 16),
+            parse_number("-101", 2), parse_number("in", 36), parse_number("9", 8), parse_number("1.5", 10),
+            to_number("42"), to_number(true), to_number(7n), to_number(2.5),
+            to_string(255, 16), to_string(-5, 2), to_string(0, 36), to_string([1, "a"]), to_string(3, nil)
+        ];
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        "[3.5, -1000, 1000, nil, nil, nil, 255, 255, -5, 671, nil, nil, 42, 1, 7, 2.5, ff, -101, 0, [1, a], 3]"
+    );
+    assert!(eval_res(r#"var res = to_number("abc");"#).is_err());
+    assert!(eval_res("var res = to_number(nil);").is_err());
+    assert!(eval_res("var res = to_string(1.5, 2);").is_err());
+    assert!(eval_res("var res = parse_number(\"1\", 1);").is_err());
+    assert!(eval_res("var res = parse_number(1);").is_err());
+}
+
+#[test]
+fn test_ord_and_chr() {
+    let code = r#"
+        var shifted = "";
+        for (var c in "abz") shifted = shifted + chr((ord(c) - ord("a") + 1) % 26 + ord("a"));
+        var res = [ord("A"), ord("λ"), ord("😀"), chr(65), chr(955), chr(ord("é")), shifted];
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        "[65, 955, 128512, A, λ, é, bca]"
+    );
+    assert!(eval_res(r#"var res = ord("ab");"#).is_err());
+    assert!(eval_res(r#"var res = ord("");"#).is_err());
+    assert!(eval_res("var res = chr(55296);").is_err());
+    assert!(eval_res("var res = chr(1.5);").is_err());
+    assert!(eval_res("var res = chr(-1);").is_err());
+}
+
+#[test]
+fn test_string_comparison() {
+    let code = r#"
+        var res = ["apple" < "banana", "b" > "abc", "a" <= "a", "Z" < "a", "" >= "x"];
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        "[true, true, true, true, false]"
+    );
+    assert!(eval_res(r#"var res = "1" < 2;"#).is_err());
+}
+
+#[test]
+fn test_nan_and_infinity() {
+    let code = r#"
+        var nan = math.NAN;
+        var res = [
+            nan == nan, nan != nan, isNaN(nan), isNaN(1), nan < 1,
+            isFinite(math.INF), isFinite(2), -math.INF < 0, {nan: 1}[math.NAN]
+        ];
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        "[false, true, true, false, false, false, true, true, 1]"
+    );
+    assert!(eval_res("var res = 1 / 0;").is_err());
+
+    let ieee = Interpreter {
+        ieee_division: true,
+        ..Interpreter::default()
+    };
+    assert_eq!(
+        eval_res_with(
+            ieee,
+            "var res = [1 / 0 == math.INF, isNaN(0 / 0), isNaN(1 % 0)];"
+        )
+        .unwrap()
+        .to_string(),
+        "[true, true, true]"
+    );
+}
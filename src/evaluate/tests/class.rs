@@ -106,6 +106,92 @@ fn test_this_outside_class() {
     assert!(eval_res(code).is_err()); // Resolver will panic
 }
 
+#[test]
+fn test_static_methods() {
+    let code = r#"
+        class Math {
+            static square(x) { return x * x; }
+            static cube(x) { return Math.square(x) * x; }
+            twice(x) { return x * 2; }
+        }
+        class MoreMath < Math {}
+        var res = [Math.square(4), Math.cube(2), MoreMath.square(3), Math().twice(5)];
+    "#;
+    assert_eq!(eval_res(code).unwrap().to_string(), "[16, 8, 9, 10]");
+
+    // 静态方法不在实例上，实例方法也不在类上
+    assert!(eval_res("class A { static f() {} } var res = A().f();").is_err());
+    assert!(eval_res("class A { g() {} } var res = A.g();").is_err());
+    assert!(eval_res("class A { static f() { return this; } }").is_err());
+}
+
+#[test]
+fn test_trait_conformance() {
+    let code = r#"
+        trait Shape { area(); scale(factor); }
+        trait Named { name(); }
+        class Base { name() { return "base"; } }
+        class Square < Base implements Shape, Named {
+            init(s) { this.s = s; }
+            area() { return this.s * this.s; }
+            scale(factor) { return Square(this.s * factor); }
+        }
+        var res = [Square(2).scale(3).area(), Square(1).name()];
+    "#;
+    assert_eq!(eval_res(code).unwrap().to_string(), "[36, base]");
+
+    // 缺失或参数个数不符的方法都会被列出
+    let err = eval_res(
+        r#"
+        trait Shape { area(); scale(factor); }
+        class Circle implements Shape { scale() {} }
+    "#,
+    )
+    .unwrap_err();
+    assert!(
+        err.contains(
+            "Class 'Circle' does not implement trait 'Shape': missing area(), scale(factor)."
+        ),
+        "{err}"
+    );
+
+    let err = eval_res("class A implements Missing {}").unwrap_err();
+    assert!(err.contains("Undefined trait 'Missing'."), "{err}");
+    let err = eval_res("var Shape = 1; class A implements Shape {}").unwrap_err();
+    assert!(err.contains("'Shape' is not a trait"), "{err}");
+
+    // 剩余参数不计入必需参数个数
+    let code = r#"
+        trait Logger { log(msg); }
+        class Console implements Logger { log(msg, ...extra) { return extra.len(); } }
+        var res = Console().log("a", 1, 2);
+    "#;
+    assert_eq!(eval_res(code).unwrap(), Value::Number(2.0));
+    assert!(eval_res("trait T { f(a, b); } class A implements T { f(a, ...b) {} }").is_err());
+}
+
+#[test]
+fn test_traits_are_scoped_values() {
+    // 同名的 trait 与类在不同作用域中互不影响
+    let code = r#"
+        fun first() {
+            trait Shape { area(); }
+            class Square { area() { return 1; } }
+            class Tile < Square implements Shape {}
+            return Tile().area();
+        }
+        fun second() {
+            trait Shape { perimeter(); }
+            class Square { perimeter() { return 2; } }
+            class Tile < Square implements Shape {}
+            return Tile().perimeter();
+        }
+        trait Show { show(); }
+        var res = [first(), second(), Show];
+    "#;
+    assert_eq!(eval_res(code).unwrap().to_string(), "[1, 2, <trait Show>]");
+}
+
 #[test]
 fn test_inheritance() {
     let code = r#"
@@ -52,3 +52,180 @@ fn test_loop_break() {
     "#;
     assert_eq!(eval_res(code).unwrap(), Value::Number(3.0));
 }
+
+#[test]
+fn test_for_in_dict() {
+    let code = r#"
+        var d = {"c": 3, "a": 1, "b": 2};
+        var res = "";
+        for (k, v in d) {
+            if (k == "a") continue;
+            res = res + k + v + " ";
+        }
+        for (k in d) {
+            res = res + k + "=" + d[k] + ";";
+            if (k == "a") break;
+        }
+    "#;
+    // 按插入顺序迭代
+    assert_eq!(
+        eval_res(code).unwrap(),
+        Value::String("c3 b2 c=3;a=1;".to_string())
+    );
+}
+
+#[test]
+fn test_for_in_list_and_string() {
+    let code = r#"
+        var res = "";
+        for (x in [1, 2, 3]) res = res + x;
+        for (i, x in ["a", "b"]) res = res + " " + i + x;
+        for (c in "hé!") {
+            if (c == "!") continue;
+            res = res + " " + c;
+        }
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap(),
+        Value::String("123 0a 1b h é".to_string())
+    );
+}
+
+#[test]
+fn test_for_in_requires_iterable() {
+    assert!(eval_res("var res = 0; for (x in 123) res = x;").is_err());
+}
+
+#[test]
+fn test_labeled_break_and_continue() {
+    let code = r#"
+        var pairs = [];
+        outer: for (var i = 0; i < 3; i += 1) {
+            for (var j = 0; j < 3; j += 1) {
+                if (j == 1) continue outer;
+                if (i == 2) break outer;
+                pairs.push([i, j]);
+            }
+        }
+        var n = 0;
+        rows: while (true) {
+            for (c in "abc") {
+                n += 1;
+                if (c == "b") break rows;
+            }
+        }
+        var res = [pairs, n];
+    "#;
+    assert_eq!(eval_res(code).unwrap().to_string(), "[[[0, 0], [1, 0]], 2]");
+    assert!(eval_res("while (true) { break nowhere; } var res = 1;").is_err());
+    assert!(eval_res("outer: while (true) { fun f() { while (true) { break outer; } } }").is_err());
+}
+
+#[test]
+fn test_generator_is_lazy() {
+    let code = r#"
+        var started = false;
+        fun fib() {
+            started = true;
+            var a = 0;
+            var b = 1;
+            while (true) {
+                yield a;
+                (a, b) = (b, a + b);
+            }
+        }
+        var g = fib();
+        var before = started;
+        var out = [];
+        for (i, x in g) {
+            if (i == 8) break;
+            out.push(x);
+        }
+        var res = [before, out];
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        "[false, [0, 1, 1, 2, 3, 5, 8, 13]]"
+    );
+}
+
+#[test]
+fn test_generator_control_flow() {
+    let code = r#"
+        fun evens(items) {
+            for (x in items) {
+                if (x % 2 != 0) continue;
+                if (x > 8) return;
+                yield x;
+            }
+            yield "unreachable";
+        }
+        fun pairs(n) {
+            outer: for (var i = 0; i < n; i += 1) {
+                for (var j = 0; j < n; j += 1) {
+                    if (j > i) continue outer;
+                    yield [i, j];
+                }
+            }
+            yield;
+        }
+        var res = [];
+        for (x in evens([1, 2, 3, 4, 6, 10, 12])) res.push(x);
+        for (p in pairs(3)) res.push(p);
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        "[2, 4, 6, [0, 0], [1, 0], [1, 1], [2, 0], [2, 1], [2, 2], nil]"
+    );
+
+    // 生成器耗尽后不再产出
+    let code = r#"
+        fun one() { yield 1; }
+        var g = one();
+        var res = [];
+        for (x in g) res.push(x);
+        for (x in g) res.push(x);
+    "#;
+    assert_eq!(eval_res(code).unwrap().to_string(), "[1]");
+
+    assert!(eval_res("yield 1;").is_err());
+    assert!(eval_res("fun f() { try { yield 1; } catch (e) {} }").is_err());
+}
+
+#[test]
+fn test_defer_runs_in_reverse_on_exit() {
+    let code = r#"
+        var log = [];
+        fun work(fail) {
+            defer log.push("first");
+            for (i in [0, 1]) {
+                defer log.push("loop " + i);
+            }
+            if (fail) throw "boom";
+            return log.push("body");
+        }
+        work(false);
+        try { work(true); } catch (e) { log.push(e); }
+        var res = log;
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        "[body, loop 1, loop 0, first, loop 1, loop 0, first, boom]"
+    );
+
+    // 捕获的是登记时所在作用域的变量
+    let code = r#"
+        var res = [];
+        fun f() {
+            var x = 1;
+            defer res.push(x);
+            x = 2;
+            return x;
+        }
+        res.push(f());
+    "#;
+    assert_eq!(eval_res(code).unwrap().to_string(), "[2, 2]");
+
+    assert!(eval_res("defer print 1;").is_err());
+    assert!(eval_res("fun g() { defer print 1; yield 1; }").is_err());
+}
@@ -1,4 +1,12 @@
 use crate::evaluate::tests::eval_res;
+use crate::{
+    evaluate::{Interpreter, error::RuntimeError},
+    parser::parse,
+    reader::Source,
+    resolver::Resolver,
+    tokenizer::tokenize,
+};
+use pretty_assertions::assert_eq;
 
 #[test]
 fn test_runtime_error_undefined_var() {
@@ -18,3 +26,115 @@ fn test_static_error_var_redefinition() {
     let code = "{ var a = 1; var a = 2; }";
     assert!(eval_res(code).is_err());
 }
+
+#[test]
+fn test_try_catch_finally() {
+    let code = r#"
+        var log = [];
+        fun risky(n) {
+            try {
+                if (n == 0) throw {"code": 42};
+                if (n == 1) return 1 / nil;
+                return "ok";
+            } finally {
+                log.push(n);
+            }
+        }
+
+        var caught = [];
+        for (var i = 0; i < 3; i += 1) {
+            try {
+                caught.push(risky(i));
+            } catch (e) {
+                caught.push(e);
+            }
+        }
+
+        for (var j = 0; j < 3; j += 1) {
+            try {
+                if (j == 1) break;
+            } finally {
+                log.push("loop " + j);
+            }
+        }
+        var res = [caught[0]["code"], caught[1], caught[2], log];
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        "[42, Type error: Operands must be numbers., ok, [0, 1, 2, loop 0, loop 1]]"
+    );
+}
+
+#[test]
+fn test_uncaught_exception() {
+    let code = r#"
+        try {
+            throw "boom";
+        } finally {
+            var cleaned = true;
+        }
+    "#;
+    assert!(eval_res(code).is_err());
+}
+
+#[test]
+fn test_exit_unwinds_through_defer_and_finally() {
+    let code = r#"
+        var log = [];
+        fun work() {
+            defer log.push("deferred");
+            try {
+                exit(3);
+            } catch (e) {
+                log.push("caught");
+            } finally {
+                log.push("finally");
+            }
+            log.push("after");
+        }
+        work();
+        log.push("unreachable");
+    "#;
+    let mut interpreter = Interpreter::default();
+    let ast = parse(
+        tokenize(Source {
+            contents: code.to_string(),
+        })
+        .unwrap(),
+    )
+    .unwrap();
+    Resolver::new(&mut interpreter)
+        .resolve_stmts(&ast.body)
+        .unwrap();
+
+    assert!(matches!(
+        interpreter.interpret(ast),
+        Err(RuntimeError::Exit(3))
+    ));
+    assert_eq!(
+        interpreter.get_global_value("log").unwrap().to_string(),
+        "[finally, deferred]"
+    );
+    assert_eq!(eval_res("exit();").unwrap_err(), "Exit(0)");
+    assert!(eval_res("exit(256);").unwrap_err().contains("TypeError"));
+    assert!(eval_res("exit(1, 2);").is_err());
+}
+
+#[test]
+fn test_resolve_all_collects_errors() {
+    let code = "return 1;\nfun f() { var y = y; }\nprint 1;\n{ var q = 1; var q = 2; }";
+    let ast = parse(
+        tokenize(Source {
+            contents: code.to_string(),
+        })
+        .unwrap(),
+    )
+    .unwrap();
+    let mut interpreter = Interpreter::default();
+    let errors = Resolver::new(&mut interpreter).resolve_all(&ast.body);
+
+    assert_eq!(errors.len(), 3, "{:?}", errors);
+    assert!(errors[0].contains("[line 1]"));
+    assert!(errors[1].contains("own initializer"));
+    assert!(errors[2].contains("Already a variable"));
+}
@@ -12,6 +12,60 @@ fn test_function_call() {
     assert_eq!(eval_res(code).unwrap(), Value::Number(30.0));
 }
 
+#[test]
+fn test_rest_parameters() {
+    let code = r#"
+        fun log(level, ...args) {
+            return level + ": " + args.join(" ");
+        }
+        var count = fun (...xs) { return xs.len(); };
+        var res = [log("info", "a", 1), log("warn"), count(), count(1, 2, 3)];
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        "[info: a 1, warn: , 0, 3]"
+    );
+    assert!(eval_res("fun f(a, ...b) {} var res = f();").is_err());
+}
+
+#[test]
+fn test_keyword_arguments() {
+    let code = r#"
+        fun draw(x, y, color) { return color + "@" + x + "," + y; }
+        class Point { init(x, y) { this.x = x; this.y = y; } }
+        fun tag(name, ...rest) { return name + rest.len(); }
+        var p = Point(y = 2, x = 1);
+        var res = [draw(1, color = "red", y = 2), draw(y = 0, x = 3, color = "blue"), p.x, p.y, tag(name = "t")];
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        "[red@1,2, blue@3,0, 1, 2, t0]"
+    );
+
+    let err = eval_res("fun f(a, b) {} var res = f(1, c = 2);").unwrap_err();
+    assert!(
+        err.contains("Unknown keyword argument 'c' for 'f'."),
+        "{}",
+        err
+    );
+    let err = eval_res("fun f(a, b) {} var res = f(1, a = 2);").unwrap_err();
+    assert!(err.contains("Duplicate argument 'a' for 'f'."), "{}", err);
+    let err = eval_res("fun f(a, b) {} var res = f(b = 2);").unwrap_err();
+    assert!(err.contains("Missing argument 'a' for 'f'."), "{}", err);
+    let err = eval_res("var res = clock(x = 1);").unwrap_err();
+    assert!(
+        err.contains("Native function 'clock' does not accept keyword arguments."),
+        "{}",
+        err
+    );
+    let err = eval_res("var res = [1].push(x = 2);").unwrap_err();
+    assert!(
+        err.contains("Native method 'push' does not accept keyword arguments."),
+        "{}",
+        err
+    );
+}
+
 #[test]
 fn test_recursive_function() {
     let code = r#"
@@ -59,3 +113,165 @@ fn test_resolver_shadowing() {
     "#;
     assert_eq!(eval_res(code).unwrap(), Value::String("global".to_string()));
 }
+
+#[test]
+fn test_lambda_capture_by_value() {
+    let code = r#"
+        var callbacks = [0];
+        callbacks.pop();
+        for (var i = 0; i < 3; i = i + 1) {
+            callbacks.push(fun [i]() { return i; });
+        }
+        var res = callbacks[0]() + callbacks[1]() * 10 + callbacks[2]() * 100;
+    "#;
+    assert_eq!(eval_res(code).unwrap(), Value::Number(210.0));
+}
+
+#[test]
+fn test_lambda_capture_snapshot_is_independent() {
+    let code = r#"
+        var x = 1;
+        var byValue = fun [x]() { return x; };
+        var byRef = fun () { return x; };
+        x = 2;
+        var res = [byValue(), byRef()];
+    "#;
+    assert_eq!(eval_res(code).unwrap().to_string(), "[1, 2]");
+}
+
+#[test]
+fn test_shadowing_warning() {
+    use crate::{
+        evaluate::Interpreter, parser::parse, reader::Source, resolver::Resolver,
+        tokenizer::tokenize,
+    };
+
+    let code = r#"
+        var a = 1;
+        fun f(b) {
+            var a = 2;
+            {
+                var b = 3;
+            }
+        }
+        { var c = 1; }
+        { var c = 2; }
+    "#;
+    let tokens = tokenize(Source {
+        contents: code.to_string(),
+    })
+    .unwrap();
+    let ast = parse(tokens).unwrap();
+    let mut interpreter = Interpreter::new();
+    let mut resolver = Resolver::new(&mut interpreter);
+    resolver.resolve_stmts(&ast.body).unwrap();
+
+    assert_eq!(
+        resolver.warnings,
+        vec![
+            "[line 4] Variable 'a' shadows an outer declaration at line 2.".to_string(),
+            "[line 6] Variable 'b' shadows an outer declaration at line 3.".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_globals_snapshot() {
+    let code = r#"
+        var b = 2;
+        var a = 1;
+        fun f() {}
+        var g = globals();
+        g["a"] = 100;
+        var res = [g["b"], a, globals()["a"]];
+    "#;
+    assert_eq!(eval_res(code).unwrap().to_string(), "[2, 1, 1]");
+}
+
+#[test]
+fn test_set_global_is_read_only_by_default() {
+    let code = r#"
+        setGlobal("plugin", 1);
+        var res = plugin;
+    "#;
+    assert!(eval_res(code).is_err());
+}
+
+#[test]
+fn test_locals_walks_enclosing_scopes() {
+    let code = r#"
+        var top = 0;
+        fun outer(a) {
+            var x = "outer";
+            fun inner() {
+                var x = "inner";
+                return locals();
+            }
+            return inner();
+        }
+        var res = outer(1);
+    "#;
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        "{a: 1, inner: <fn inner>, x: inner}"
+    );
+}
+
+#[test]
+fn test_eval_expression() {
+    let code = r#"
+        var res = eval("1 + 2");
+    "#;
+    assert_eq!(eval_res(code).unwrap(), Value::Number(3.0));
+}
+
+#[test]
+fn test_eval_sees_caller_scope() {
+    let code = r#"
+        var rate = 2;
+        fun price(base) {
+            var fee = 1;
+            return eval("var total = base * rate; total + fee");
+        }
+        var res = [price(10), eval("var total = 0;")];
+    "#;
+    assert_eq!(eval_res(code).unwrap().to_string(), "[21, nil]");
+}
+
+#[test]
+fn test_eval_declarations_stay_in_child_scope() {
+    let code = r#"
+        fun f() {
+            var a = 1;
+            eval("var a = 2; a = a + 1;");
+            return a;
+        }
+        var res = f();
+    "#;
+    assert_eq!(eval_res(code).unwrap(), Value::Number(1.0));
+}
+
+#[test]
+fn test_expression_bodied_functions() {
+    let code = r#"
+        fun double(x) = x * 2;
+        class Point {
+            init(x) { this.x = x; }
+            shifted(d) = Point(this.x + d);
+        }
+        var res = [double(4), [1, 2].map(fun (x) = x * 10), Point(1).shifted(2).x];
+    "#;
+    assert_eq!(eval_res(code).unwrap().to_string(), "[8, [10, 20], 3]");
+    assert!(eval_res("class A { init() = 1; }").is_err());
+}
+
+#[test]
+fn test_arrow_lambdas() {
+    let code = r#"
+        var k = 3;
+        var add = |a, b| a + b;
+        var make = || |x| x * k;
+        var res = [[1, 2, 3].map(|x| x + k).filter(|x| x % 2 == 0), add(1, 2), make()(2), 1 | 2];
+    "#;
+    assert_eq!(eval_res(code).unwrap().to_string(), "[[4, 6], 3, 6, 3]");
+}
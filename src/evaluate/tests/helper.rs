@@ -9,6 +9,11 @@ use pretty_assertions::assert_eq;
 
 /// 编译并运行源代码，返回最后一个表达式语句的值，或者最后的状态
 pub fn eval_res(source: &str) -> Result<Value, String> {
+    eval_res_with(Interpreter::default(), source)
+}
+
+/// 与 `eval_res` 相同，但使用调用方配置好的解释器
+pub fn eval_res_with(mut interpreter: Interpreter, source: &str) -> Result<Value, String> {
     let source_obj = Source {
         contents: source.to_string(),
     };
@@ -16,7 +21,6 @@ pub fn eval_res(source: &str) -> Result<Value, String> {
     let tokens = tokenize(source_obj).map_err(|e| format!("{:?}", e))?;
     let ast = parse(tokens).map_err(|e| format!("{:?}", e))?;
 
-    let mut interpreter = Interpreter::default();
     let mut resolver = Resolver::new(&mut interpreter);
     resolver
         .resolve_stmts(&ast.body)
@@ -4,6 +4,7 @@ pub mod control_flow;
 pub mod error_handling;
 pub mod function_and_scope;
 pub mod helper;
+pub mod module;
 pub mod oop;
 
 pub use helper::*;
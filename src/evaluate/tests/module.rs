@@ -0,0 +1,119 @@
+use crate::{
+    evaluate::{Interpreter, Value},
+    parser::parse,
+    reader::Source,
+    resolver::Resolver,
+    tokenizer::tokenize,
+};
+use pretty_assertions::assert_eq;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// 以 `dir` 作为当前执行目录运行代码，返回全局变量 `res`
+fn eval_in_dir(dir: &Path, source: &str) -> Result<Value, String> {
+    let tokens = tokenize(Source {
+        contents: source.to_string(),
+    })
+    .map_err(|e| format!("{:?}", e))?;
+    let ast = parse(tokens).map_err(|e| format!("{:?}", e))?;
+
+    let mut interpreter = Interpreter::default();
+    interpreter.path_stack.push(dir.to_path_buf());
+    Resolver::new(&mut interpreter)
+        .resolve_stmts(&ast.body)
+        .map_err(|e| format!("{:?}", e))?;
+    interpreter.interpret(ast).map_err(|e| e.to_string())?;
+
+    interpreter
+        .get_global_value("res")
+        .ok_or("Variable 'res' not found".to_string())
+}
+
+fn temp_project(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rox_module_{}_{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// 相对导入文件所在目录解析，并自动补全 `.rox` 扩展名
+#[test]
+fn test_import_resolves_relative_to_file_without_extension() {
+    let dir = temp_project("relative");
+    fs::write(dir.join("utils.rox"), "export var answer = 42;").unwrap();
+
+    let res = eval_in_dir(&dir, r#"var res = import("utils").answer;"#);
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(res.unwrap(), Value::Number(42.0));
+}
+
+#[test]
+fn test_import_failure_lists_tried_paths() {
+    let dir = temp_project("missing");
+
+    let err = eval_in_dir(&dir, r#"var res = import("nowhere");"#).unwrap_err();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(err.contains("Cannot find module 'nowhere'"));
+    assert!(err.contains(&dir.join("nowhere.rox").display().to_string()));
+}
+
+/// 导入语句把模块绑定到以模块名 / 文件名命名的变量上
+#[test]
+fn test_import_statement() {
+    let dir = temp_project("import_stmt");
+    fs::create_dir_all(dir.join("lib")).unwrap();
+    fs::write(dir.join("utils.rox"), "export var answer = 42;").unwrap();
+    fs::write(
+        dir.join("lib/str_utils.rox"),
+        "export fun shout(s) { return s + \"!\"; }",
+    )
+    .unwrap();
+
+    let res = eval_in_dir(
+        &dir,
+        r#"
+            import utils;
+            import "lib/str_utils.rox";
+            import "./utils.rox" as u;
+            import math;
+            var res = [utils.answer, str_utils.shout("hi"), u.answer, math.abs(-1)];
+        "#,
+    );
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(res.unwrap().to_string(), "[42, hi!, 42, 1]");
+}
+
+/// include 在调用方的全局环境中执行，双方共享全局变量
+#[test]
+fn test_include_shares_global_scope() {
+    let dir = temp_project("include");
+    fs::write(
+        dir.join("helpers.rox"),
+        "var counter = base + 1; fun double(n) { return n * 2; }",
+    )
+    .unwrap();
+
+    let res = eval_in_dir(
+        &dir,
+        r#"var base = 10; include("helpers.rox"); var res = double(counter);"#,
+    );
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(res.unwrap(), Value::Number(22.0));
+}
+
+#[test]
+fn test_circular_include_is_an_error() {
+    let dir = temp_project("include_cycle");
+    fs::write(dir.join("a.rox"), r#"include("b.rox");"#).unwrap();
+    fs::write(dir.join("b.rox"), r#"include("a.rox");"#).unwrap();
+
+    let err = eval_in_dir(&dir, r#"include("a.rox"); var res = 1;"#).unwrap_err();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(err.contains("Circular include"));
+}
@@ -202,3 +202,151 @@ fn test_error_return_value_from_init() {
     // Resolver 应该抛出 "Can't return a value from an initializer."
     assert!(eval_res(code).is_err());
 }
+
+/// __str__ / toString 钩子：print 与字符串拼接会自动调用
+#[test]
+fn test_str_hook() {
+    let code = r#"
+        class Point {
+            init(x, y) {
+                this.x = x;
+                this.y = y;
+            }
+            __str__() {
+                return "(" + this.x + ", " + this.y + ")";
+            }
+        }
+
+        class Tag {
+            toString() { return "<tag>"; }
+        }
+
+        var res = "p = " + Point(1, 2) + ", " + Tag();
+    "#;
+
+    assert_eq!(
+        eval_res(code).unwrap(),
+        Value::String("p = (1, 2), <tag>".to_string())
+    );
+}
+
+#[test]
+fn test_str_hook_must_return_string() {
+    let code = r#"
+        class Bad {
+            __str__() { return 1; }
+        }
+        var res = "" + Bad();
+    "#;
+
+    assert!(eval_res(code).is_err());
+}
+
+#[test]
+fn test_repr() {
+    let code = r#"
+        var res = repr(["a", 1, "line\nbreak"]);
+    "#;
+
+    assert_eq!(
+        eval_res(code.replace("\\n", "\n").as_str()).unwrap(),
+        Value::String(r#"["a", 1, "line\nbreak"]"#.to_string())
+    );
+}
+
+/// __hash__ / __eq__：值对象可以作为字典键
+#[test]
+fn test_hashable_instance_as_dict_key() {
+    let code = r#"
+        class Point {
+            init(x, y) {
+                this.x = x;
+                this.y = y;
+            }
+            __hash__() { return this.x * 31 + this.y; }
+            __eq__(other) { return this.x == other.x and this.y == other.y; }
+        }
+
+        var d = {"name": "points"};
+        d[Point(1, 2)] = "a";
+        d[Point(1, 2)] = "b";   // 相等的键覆盖旧值
+        d[Point(0, 33)] = "c";  // 哈希冲突但不相等
+
+        var res = [d[Point(1, 2)], d[Point(0, 33)], d.keys().len(), Point(1, 2) == Point(1, 2)];
+        d.remove(Point(1, 2));
+        res.push(d[Point(0, 33)]);
+        res.push(d.has(Point(1, 2)));
+    "#;
+
+    assert_eq!(
+        eval_res(code).unwrap().to_string(),
+        "[b, c, 3, true, c, false]"
+    );
+}
+
+#[test]
+fn test_unhashable_dict_key() {
+    assert!(eval_res(r#"var d = {"a": 1}; d[[1]] = 1;"#).is_err());
+    assert!(eval_res(r#"class A {} var d = {"a": 1}; d[A()] = 1;"#).is_err());
+}
+
+/// Monkey patch：运行时为类附加方法，已存在和之后创建的实例都能调用
+#[test]
+fn test_monkey_patch_class_method() {
+    let code = r#"
+        class Point {
+            init(x, y) {
+                this.x = x;
+                this.y = y;
+            }
+        }
+        class Point3 < Point {}
+
+        var a = Point(0, 0);
+        Point.distance = fun(self, other) {
+            return (self.x - other.x) * (self.x - other.x) + (self.y - other.y) * (self.y - other.y);
+        };
+        var b = Point3(3, 4);
+        var res = [a.distance(b), b.distance(a)];
+    "#;
+
+    assert_eq!(eval_res(code).unwrap().to_string(), "[25, 25]");
+}
+
+#[test]
+fn test_monkey_patch_requires_function() {
+    assert!(eval_res("class A {} A.x = 1; var res = 0;").is_err());
+}
+
+/// extend：为内置类型添加方法，this 指向被扩展的值
+#[test]
+fn test_extend_builtin_types() {
+    let code = r#"
+        extend String {
+            shout() { return this.replace("o", "0") + "!"; }
+        }
+        extend Number {
+            double() { return this * 2; }
+        }
+        extend List {
+            sum() {
+                var total = 0;
+                for (var i = 0; i < this.len(); i = i + 1) total = total + this[i];
+                return total;
+            }
+        }
+
+        var n = 21;
+        var res = "foo".shout() + n.double() + [1, 2, 3].sum() + "x".len();
+    "#;
+
+    assert_eq!(
+        eval_res(code).unwrap(),
+        Value::String("f00!4261".to_string())
+    );
+}
+
+#[test]
+fn test_extend_rejects_unknown_type() {
+    assert!(eval_res("extend Foo { bar() { return 1; } } var res = 0;").is_err());
+}
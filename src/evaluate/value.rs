@@ -1,8 +1,20 @@
 use crate::{
     ast::Stmt,
-    evaluate::{Interpreter, environment::Environment, error::RuntimeError},
+    evaluate::{
+        Interpreter,
+        environment::Environment,
+        error::RuntimeError,
+        generator::{Generator, Iteration},
+    },
+    std_lib::{datetime, nd::NdArray, store::Store},
+};
+use indexmap::IndexMap;
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fmt,
+    rc::Rc,
 };
-use std::{cell::RefCell, collections::HashMap, fmt, rc::Rc};
 
 // 类 (Class) 运行时结构
 #[derive(Debug, Clone, PartialEq)]
@@ -11,6 +23,11 @@ pub struct RoxClass {
     pub methods: HashMap<String, Value>,
     /// 存储父类，以便查找方法时进行回溯
     pub superclass: Option<Rc<RefCell<RoxClass>>>,
+    /// 运行时通过 `Class.name = fun(self, ...) {...}` 附加的方法名，
+    /// 这类方法不绑定 this，而是把实例作为第一个参数显式传入
+    pub patched: HashSet<String>,
+    /// 静态方法：`Class.name(...)` 直接调用，不绑定实例
+    pub statics: HashMap<String, Value>,
 }
 
 impl RoxClass {
@@ -23,22 +40,66 @@ impl RoxClass {
             name,
             methods,
             superclass,
+            patched: HashSet::new(),
+            statics: HashMap::new(),
         }
     }
 
-    /// 查找方法（支持继承）
-    // 如果当前类找不到，递归去父类找
-    pub fn find_method(&self, name: &str) -> Option<Value> {
-        if let Some(method) = self.methods.get(name) {
+    /// 查找静态方法，当前类找不到时沿父类链继续查找
+    pub fn find_static(&self, name: &str) -> Option<Value> {
+        if let Some(method) = self.statics.get(name) {
             return Some(method.clone());
         }
+        self.superclass
+            .as_ref()
+            .and_then(|superclass| superclass.borrow().find_static(name))
+    }
+
+    /// 查找方法并绑定到实例（支持继承，当前类找不到时递归去父类找）
+    ///
+    /// 类声明中定义的方法通过 `bind` 绑定 this；
+    /// 运行时附加的方法则包装为 `BoundNativeMethod`，调用时实例作为第一个参数传入。
+    pub fn bind_method(&self, name: &str, instance: Value) -> Option<Value> {
+        if let Some(method) = self.methods.get(name) {
+            if self.patched.contains(name) {
+                return Some(Value::BoundNativeMethod {
+                    receiver: Box::new(instance),
+                    method: Box::new(method.clone()),
+                });
+            }
+            return Some(method.bind(instance));
+        }
 
         if let Some(superclass) = &self.superclass {
-            return superclass.borrow().find_method(name);
+            return superclass.borrow().bind_method(name, instance);
         }
 
         None
     }
+
+    /// 查找类声明中的方法（不绑定实例），当前类找不到时沿父类链继续查找
+    pub fn find_method(&self, name: &str) -> Option<Value> {
+        if let Some(method) = self.methods.get(name) {
+            return Some(method.clone());
+        }
+        self.superclass
+            .as_ref()
+            .and_then(|superclass| superclass.borrow().find_method(name))
+    }
+
+    /// 在运行时为类附加（或覆盖）方法，所有已存在及之后创建的实例都会立即可见
+    pub fn patch_method(&mut self, name: String, method: Value) {
+        self.patched.insert(name.clone());
+        self.methods.insert(name, method);
+    }
+}
+
+// trait 运行时结构：只记录方法签名，由类声明时的 `implements` 检查使用
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoxTrait {
+    pub name: String,
+    /// (方法名, 参数列表)
+    pub methods: Vec<(String, Vec<String>)>,
 }
 
 // 类实例 (Instance) 运行时结构
@@ -78,14 +139,70 @@ impl RoxModule {
     }
 }
 
+/// 字典键 (Hash Key)
+///
+/// `Value` 本身包含 `f64`、`RefCell` 等无法直接实现 `Hash + Eq` 的成员，
+/// 因此字典内部使用规范化后的 `HashKey` 作为键，原始键值与值一起存放在条目中。
+///
+/// * 数字使用位模式存储（`-0.0` 与 `0.0` 归一）。
+/// * 实例通过用户定义的 `__hash__` 参与哈希，判等交给 `__eq__`：
+///   哈希相同但不相等的实例会被放入不同的 `Probe` 槽位。
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HashKey {
+    Nil,
+    Boolean(bool),
+    Number(u64),
+    /// 无法被 f64 精确表示的大整数（其余 BigInt 与等值的 Number 共用数字键）
+    BigInt(num_bigint::BigInt),
+    /// 非整数的 Decimal（规范化后存储，`1.50` 与 `1.5` 是同一个键）
+    Decimal(rust_decimal::Decimal),
+    String(String),
+    Tuple(Vec<HashKey>),
+    Bytes(Vec<u8>),
+    /// DateTime：epoch 秒的位模式
+    DateTime(u64),
+    /// 实例：`__hash__` 的结果
+    Object(u64),
+    /// 包含实例的键需要 `__eq__` 判等，slot 用于区分哈希冲突
+    Probe(Box<HashKey>, usize),
+}
+
+impl HashKey {
+    /// 构造数字键
+    pub fn number(n: f64) -> Self {
+        HashKey::Number(Self::number_bits(n))
+    }
+
+    /// 数字的规范化位模式：`-0.0` 归为 `0.0`，所有 NaN 归为同一个值
+    pub fn number_bits(n: f64) -> u64 {
+        if n == 0.0 {
+            0.0f64.to_bits()
+        } else if n.is_nan() {
+            f64::NAN.to_bits()
+        } else {
+            n.to_bits()
+        }
+    }
+}
+
+/// 字典存储：规范化键 -> (原始键, 值)，保持插入顺序
+pub type DictMap = IndexMap<HashKey, (Value, Value)>;
+
 /// 生函数类型别名
 /// 接收解释器引用(为了访问环境或报错)和参数列表
 pub type NativeFn = fn(&mut Interpreter, Vec<Value>) -> Result<Value, RuntimeError>;
 
+/// 原生函数的 arity 为此值时调用方不检查参数个数，由函数自己校验（eg. 可选参数）
+pub const VARIADIC: usize = usize::MAX;
+
 #[allow(unpredictable_function_pointer_comparisons, dead_code)]
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     Number(f64),
+    // 任意精度整数，字面量写作 `123n`
+    BigInt(num_bigint::BigInt),
+    // 十进制小数，由 `decimal()` 创建
+    Decimal(rust_decimal::Decimal),
     String(String),
     Boolean(bool),
     Nil,
@@ -94,16 +211,30 @@ pub enum Value {
     Function {
         name: String,
         args: Vec<String>,
+        rest: bool, // 最后一个参数收集多余的实参
         body: Vec<Stmt>,
         closure: Rc<RefCell<Environment>>,
     },
 
     Class(Rc<RefCell<RoxClass>>),
+    Trait(Rc<RoxTrait>),
     Instance(Rc<RefCell<RoxInstance>>),
 
     List(Rc<RefCell<Vec<Value>>>),
     Tuple(Vec<Value>),
-    Dict(Rc<RefCell<HashMap<String, Value>>>),
+    Dict(Rc<RefCell<DictMap>>),
+    // 集合，由 `set()` 创建：复用字典的存储，条目的值固定为 nil
+    Set(Rc<RefCell<DictMap>>),
+    // 不可变的字节序列，由 `bytes()` / `fs.readBytes()` 创建
+    Bytes(Rc<[u8]>),
+    // UTC 时间点（epoch 秒），由 `datetime` 模块创建
+    DateTime(f64),
+    // 可变字符串缓冲区，用于高效拼接
+    StringBuilder(Rc<RefCell<String>>),
+    // 数值数组，由 `nd` 模块创建
+    NdArray(Rc<RefCell<NdArray>>),
+    // 持久化键值存储，由 `store.open()` 创建
+    Store(Rc<RefCell<Store>>),
     Print(String),
 
     // 原生方法
@@ -122,18 +253,38 @@ pub enum Value {
 
     // 模块化
     Module(Rc<RefCell<RoxModule>>),
+
+    // 调用包含 yield 的函数得到的生成器，由 for-in 按需取值
+    Generator(Rc<RefCell<Generator>>),
+    // 集合的 `iter()` 返回的迭代器
+    Iterator(Rc<RefCell<Iteration>>),
+}
+
+/// `bytes([104, 105])`：与构造方式一致的写法
+fn render_bytes(bytes: &[u8]) -> String {
+    format!(
+        "bytes([{}])",
+        bytes
+            .iter()
+            .map(u8::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
 }
 
 impl fmt::Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Value::Number(n) => write!(f, "{}", n),
+            Value::BigInt(n) => write!(f, "{}", n),
+            Value::Decimal(d) => write!(f, "{}", d),
             Value::String(s) => write!(f, "{}", s),
             Value::Boolean(b) => write!(f, "{}", b),
             Value::None => write!(f, "none"),
             Value::Nil => write!(f, "nil"),
             Value::Function { name, .. } => write!(f, "<fn {}>", name),
             Value::Class(class) => write!(f, "<class {}>", class.borrow().name),
+            Value::Trait(t) => write!(f, "<trait {}>", t.name),
             Value::Instance(instance) => {
                 write!(f, "<instance {}>", instance.borrow().class.borrow().name)
             }
@@ -141,12 +292,15 @@ impl fmt::Display for Value {
             Value::BoundNativeMethod { method, .. } => write!(f, "{}", method), // 委托给内部的 NativeFunction 打印
             Value::List(list) => write!(
                 f,
-                "[{}]",
-                list.borrow()
-                    .iter()
-                    .map(|v| v.to_string())
-                    .collect::<Vec<_>>()
-                    .join(", ")
+                "{}",
+                render_once(Rc::as_ptr(list) as usize, "[...]", || format!(
+                    "[{}]",
+                    list.borrow()
+                        .iter()
+                        .map(|v| v.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))
             ),
             Value::Tuple(tuple) => write!(
                 f,
@@ -159,19 +313,54 @@ impl fmt::Display for Value {
             ),
             Value::Dict(dict) => write!(
                 f,
-                "{{{}}}",
-                dict.borrow()
-                    .iter()
-                    .map(|(k, v)| format!("{}: {}", k, v))
+                "{}",
+                render_once(Rc::as_ptr(dict) as usize, "{...}", || format!(
+                    "{{{}}}",
+                    dict.borrow()
+                        .values()
+                        .map(|(k, v)| format!("{}: {}", k, v))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))
+            ),
+            Value::Bytes(bytes) => write!(f, "{}", render_bytes(bytes)),
+            Value::DateTime(seconds) => write!(f, "{}", datetime::render(*seconds)),
+            Value::Set(set) => write!(
+                f,
+                "set([{}])",
+                set.borrow()
+                    .values()
+                    .map(|(k, _)| k.to_string())
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
+            Value::StringBuilder(buffer) => write!(f, "{}", buffer.borrow()),
+            Value::Store(store) => write!(f, "<store {}>", store.borrow().path),
+            Value::NdArray(array) => write!(f, "array({})", array.borrow().to_value()),
             Value::Print(print) => write!(f, "{}", print),
             Value::Module(m) => write!(f, "<module '{}'>", m.borrow().name),
+            Value::Generator(g) => write!(f, "<generator {}>", g.borrow().name),
+            Value::Iterator(_) => write!(f, "<iterator>"),
         }
     }
 }
 
+thread_local! {
+    /// 正在输出的容器地址，用于识别自引用的 List / Dict
+    static RENDERING: RefCell<HashSet<usize>> = RefCell::new(HashSet::new());
+}
+
+/// 输出容器内容：容器已经在输出过程中（自引用，eg. `l.push(l)`）时返回占位符，避免无限递归
+fn render_once(ptr: usize, placeholder: &str, render: impl FnOnce() -> String) -> String {
+    if !RENDERING.with(|rendering| rendering.borrow_mut().insert(ptr)) {
+        return placeholder.to_string();
+    }
+
+    let out = render();
+    RENDERING.with(|rendering| rendering.borrow_mut().remove(&ptr));
+    out
+}
+
 impl Value {
     /// 判断值是否为真值（Truthy）
     ///
@@ -183,6 +372,8 @@ impl Value {
             Value::Boolean(b) => *b,
             Value::String(s) => !s.is_empty(),
             Value::Number(n) => *n != 0.0,
+            Value::BigInt(n) => n.sign() != num_bigint::Sign::NoSign,
+            Value::Decimal(d) => !d.is_zero(),
             _ => true,
         }
     }
@@ -194,20 +385,199 @@ impl Value {
     pub fn type_name(&self) -> &'static str {
         match self {
             Value::Number(_) => "Number",
+            Value::BigInt(_) => "BigInt",
+            Value::Decimal(_) => "Decimal",
             Value::String(_) => "String",
             Value::Boolean(_) => "Boolean",
             Value::None => "None",
             Value::Nil => "Nil",
             Value::Function { .. } => "Function",
             Value::Class(_) => "Class",
+            Value::Trait(_) => "Trait",
             Value::Instance(_) => "Instance",
             Value::List(_) => "List",
             Value::Dict(_) => "Dict",
+            Value::Set(_) => "Set",
+            Value::Bytes(_) => "Bytes",
+            Value::DateTime(_) => "DateTime",
             Value::Tuple(_) => "Tuple",
+            Value::StringBuilder(_) => "StringBuilder",
+            Value::Store(_) => "Store",
+            Value::NdArray(_) => "NdArray",
             Value::Print(_) => "Print",
             Value::Module { .. } => "Module",
             Value::NativeFunction { .. } => "NativeFunction",
             Value::BoundNativeMethod { .. } => "BoundNativeMethod",
+            Value::Generator(_) => "Generator",
+            Value::Iterator(_) => "Iterator",
+        }
+    }
+
+    /// 获取值的调试表示 (repr)
+    ///
+    /// 与 `Display` 不同：字符串会带上引号，并显式展示转义字符，
+    /// 集合中的元素同样递归使用 repr 表示，便于区分 `1` 与 `"1"`。
+    ///
+    /// # 返回值
+    /// * `String` - 值的 repr 字符串
+    pub fn repr(&self) -> String {
+        match self {
+            Value::BigInt(n) => format!("{}n", n),
+            Value::Decimal(d) => format!("decimal(\"{}\")", d),
+            Value::String(s) => {
+                let mut out = String::with_capacity(s.len() + 2);
+                out.push('"');
+                for c in s.chars() {
+                    match c {
+                        '"' => out.push_str("\\\""),
+                        '\\' => out.push_str("\\\\"),
+                        '\n' => out.push_str("\\n"),
+                        '\r' => out.push_str("\\r"),
+                        '\t' => out.push_str("\\t"),
+                        '\0' => out.push_str("\\0"),
+                        _ => out.push(c),
+                    }
+                }
+                out.push('"');
+                out
+            }
+            Value::List(list) => render_once(Rc::as_ptr(list) as usize, "[...]", || {
+                format!(
+                    "[{}]",
+                    list.borrow()
+                        .iter()
+                        .map(|v| v.repr())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            }),
+            Value::Tuple(tuple) => format!(
+                "({})",
+                tuple
+                    .iter()
+                    .map(|v| v.repr())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Value::Dict(dict) => render_once(Rc::as_ptr(dict) as usize, "{...}", || {
+                format!(
+                    "{{{}}}",
+                    dict.borrow()
+                        .values()
+                        .map(|(k, v)| format!("{}: {}", k.repr(), v.repr()))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            }),
+            Value::Bytes(bytes) => render_bytes(bytes),
+            Value::Set(set) => format!(
+                "set([{}])",
+                set.borrow()
+                    .values()
+                    .map(|(k, _)| k.repr())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Value::StringBuilder(buffer) => {
+                format!(
+                    "StringBuilder({})",
+                    Value::String(buffer.borrow().clone()).repr()
+                )
+            }
+            _ => self.to_string(),
+        }
+    }
+
+    /// 深拷贝 (deep copy)
+    ///
+    /// 递归复制列表、元组、字典、集合、实例、StringBuilder 与 NdArray，
+    /// 副本与原值不再共享任何可变容器。函数、类、模块、生成器等按引用共享；
+    /// 字典键与集合元素本身不可变，直接沿用。
+    /// 同一容器在原值中出现多次（包括循环引用）时，副本中同样只复制一次；
+    /// 复制出的容器会登记到 heap，循环副本同样能被回收。
+    pub fn deep_copy(&self) -> Value {
+        self.deep_copy_with(&mut HashMap::new())
+    }
+
+    /// `memo` 记录 原容器地址 -> 副本，用于保留共享结构并终止循环
+    fn deep_copy_with(&self, memo: &mut HashMap<usize, Value>) -> Value {
+        match self {
+            Value::List(list) => {
+                let ptr = Rc::as_ptr(list) as usize;
+                if let Some(copied) = memo.get(&ptr) {
+                    return copied.clone();
+                }
+                let copied = Rc::new(RefCell::new(Vec::new()));
+                memo.insert(ptr, Value::List(copied.clone()));
+                let items = list.borrow().clone();
+                let items = items.iter().map(|v| v.deep_copy_with(memo)).collect();
+                *copied.borrow_mut() = items;
+                let copied = Value::List(copied);
+                super::heap::track(&copied);
+                copied
+            }
+            Value::Tuple(items) => {
+                Value::Tuple(items.iter().map(|v| v.deep_copy_with(memo)).collect())
+            }
+            Value::Dict(dict) => {
+                let ptr = Rc::as_ptr(dict) as usize;
+                if let Some(copied) = memo.get(&ptr) {
+                    return copied.clone();
+                }
+                let copied = Rc::new(RefCell::new(DictMap::new()));
+                memo.insert(ptr, Value::Dict(copied.clone()));
+                let entries = dict.borrow().clone();
+                let entries = entries
+                    .into_iter()
+                    .map(|(hash, (k, v))| (hash, (k, v.deep_copy_with(memo))))
+                    .collect();
+                *copied.borrow_mut() = entries;
+                let copied = Value::Dict(copied);
+                super::heap::track(&copied);
+                copied
+            }
+            Value::Set(set) => {
+                let ptr = Rc::as_ptr(set) as usize;
+                memo.entry(ptr)
+                    .or_insert_with(|| Value::Set(Rc::new(RefCell::new(set.borrow().clone()))))
+                    .clone()
+            }
+            Value::Instance(instance) => {
+                let ptr = Rc::as_ptr(instance) as usize;
+                if let Some(copied) = memo.get(&ptr) {
+                    return copied.clone();
+                }
+                let copied = Rc::new(RefCell::new(RoxInstance::new(
+                    instance.borrow().class.clone(),
+                )));
+                memo.insert(ptr, Value::Instance(copied.clone()));
+                let fields = instance.borrow().fields.borrow().clone();
+                let fields = fields
+                    .into_iter()
+                    .map(|(name, v)| (name, v.deep_copy_with(memo)))
+                    .collect();
+                *copied.borrow().fields.borrow_mut() = fields;
+                let copied = Value::Instance(copied);
+                super::heap::track(&copied);
+                copied
+            }
+            Value::StringBuilder(buffer) => {
+                let ptr = Rc::as_ptr(buffer) as usize;
+                memo.entry(ptr)
+                    .or_insert_with(|| {
+                        Value::StringBuilder(Rc::new(RefCell::new(buffer.borrow().clone())))
+                    })
+                    .clone()
+            }
+            Value::NdArray(array) => {
+                let ptr = Rc::as_ptr(array) as usize;
+                memo.entry(ptr)
+                    .or_insert_with(|| {
+                        Value::NdArray(Rc::new(RefCell::new(array.borrow().clone())))
+                    })
+                    .clone()
+            }
+            _ => self.clone(),
         }
     }
 
@@ -219,6 +589,7 @@ impl Value {
             Value::Function {
                 name,
                 args,
+                rest,
                 body,
                 closure,
             } => {
@@ -232,15 +603,22 @@ impl Value {
                     .define("this".to_string(), instance);
 
                 // 返回新的 Function，闭包指向包含 "this" 的环境
-                Value::Function {
+                let method = Value::Function {
                     name: name.clone(),
                     args: args.clone(),
+                    rest: *rest,
                     body: body.clone(),
                     closure: environment,
-                }
+                };
+                super::heap::track(&method);
+                method
             }
 
-            _ => panic!("Only functions can be bound"),
+            // 其他可调用值（eg. 原生函数）没有闭包可以放入 this，调用时实例作为第一个参数传入
+            other => Value::BoundNativeMethod {
+                receiver: Box::new(instance),
+                method: Box::new(other.clone()),
+            },
         }
     }
 }
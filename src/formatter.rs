@@ -0,0 +1,971 @@
+//! 源码格式化 (`rox fmt`)
+//!
+//! 在语法树上重新生成源码：缩进统一为 4 个空格，每条语句占一行，运算符两侧与逗号之后各一个空格，
+//! 连续的空行合并为一行。扫描器保留的注释按行号放回原处。
+//!
+//! 结果需要通过校验才会返回：重新解析得到的语法树（以 `ast::format` 的单行形式比较）与原来一致、
+//! 注释没有丢失，并且再次格式化结果不变。
+
+use crate::{
+    ast::{
+        Expr, Operator, Stmt,
+        format::{format_operator, format_stmt},
+    },
+    error::RoxError,
+    parser,
+    reader::Source,
+    tokenizer::{self, Comment, KEYWORDS, Token},
+};
+use std::{collections::VecDeque, fmt, path::Path};
+
+const INDENT: &str = "    ";
+
+#[derive(Debug)]
+pub enum Error {
+    /// 源码存在词法或语法错误
+    Source(RoxError),
+    /// 格式化结果没有通过校验，不应写回文件
+    Unstable,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Source(e) => write!(f, "{}", e),
+            Error::Unstable => write!(
+                f,
+                "Formatting would change the program, the file was left unchanged."
+            ),
+        }
+    }
+}
+
+impl From<RoxError> for Error {
+    fn from(error: RoxError) -> Self {
+        Error::Source(error)
+    }
+}
+
+/// 格式化一段源码
+///
+/// # 返回值
+/// * `Ok(String)` - 格式化后的源码，以换行结尾（空文件得到空字符串）
+/// * `Err(Error)` - 源码有错误，或结果没有通过校验
+pub fn format_source(source: &str) -> Result<String, Error> {
+    let original = Parsed::new(source)?;
+    let formatted = original.print();
+
+    let reparsed = Parsed::new(&formatted).map_err(|_| Error::Unstable)?;
+    let same_tree = original
+        .body
+        .iter()
+        .map(format_stmt)
+        .eq(reparsed.body.iter().map(format_stmt));
+    if !same_tree
+        || original.comment_texts() != reparsed.comment_texts()
+        || reparsed.print() != formatted
+    {
+        return Err(Error::Unstable);
+    }
+
+    Ok(formatted)
+}
+
+/// 解析结果：语法树、语句与代码块的行号、注释
+struct Parsed {
+    body: Vec<Stmt>,
+    lines: parser::SourceLines,
+    comments: Vec<Comment>,
+}
+
+impl Parsed {
+    fn new(source: &str) -> Result<Self, RoxError> {
+        let tokens = tokenizer::tokenize(Source {
+            contents: source.to_string(),
+        })?;
+        let comments = tokens.comments.clone();
+        let (ast, lines) = parser::parse_with_lines(tokens)?;
+
+        Ok(Self {
+            body: ast.body,
+            lines,
+            comments,
+        })
+    }
+
+    fn print(&self) -> String {
+        let mut printer = Printer {
+            comments: self.comments.iter().cloned().collect(),
+            statements: self.lines.statements.iter().copied().collect(),
+            blocks: self.lines.blocks.iter().copied().collect(),
+            statics: self.lines.statics.iter().copied().collect(),
+            indent: 0,
+        };
+        printer.stmts(&self.body, Some(usize::MAX))
+    }
+
+    fn comment_texts(&self) -> Vec<&str> {
+        let mut texts = self
+            .comments
+            .iter()
+            .map(|c| c.text.trim_end())
+            .collect::<Vec<_>>();
+        texts.sort_unstable();
+        texts
+    }
+}
+
+/// 已输出的行与最后一项所在的源码行
+#[derive(Default)]
+struct Output {
+    text: String,
+    last: Option<usize>,
+}
+
+impl Output {
+    fn line(&mut self, pad: &str, text: &str) {
+        self.text.push_str(pad);
+        self.text.push_str(text);
+        self.text.push('\n');
+    }
+
+    /// 输出一个空行（代码块开头与已有空行之后不输出）
+    fn blank(&mut self) {
+        if !self.text.is_empty() && !self.text.ends_with("\n\n") {
+            self.text.push('\n');
+        }
+    }
+
+    /// 源码中与上一项之间隔着空行时，保留一个空行
+    fn gap(&mut self, line: usize) {
+        if self.last.is_some_and(|last| line > last + 1) {
+            self.blank();
+        }
+    }
+}
+
+/// 类体、trait 体与 extend 体中的成员
+enum Member<'a> {
+    Method(&'a Stmt, bool),
+    Signature(&'a Token, &'a [Token]),
+}
+
+struct Printer {
+    comments: VecDeque<Comment>,
+    /// 尚未输出的语句的 (起始行, 结束行)，与 `parse_statement` 的调用一一对应
+    statements: VecDeque<(usize, usize)>,
+    /// 尚未输出的代码块的结束行，与 `parse_block` 的调用一一对应
+    blocks: VecDeque<usize>,
+    /// 尚未输出的类方法是否为静态方法
+    statics: VecDeque<bool>,
+    indent: usize,
+}
+
+impl Printer {
+    fn pad(&self) -> String {
+        INDENT.repeat(self.indent)
+    }
+
+    /// 语句序列；`end` 是所在代码块的结束行，块内剩余的注释放在序列末尾
+    fn stmts(&mut self, body: &[Stmt], end: Option<usize>) -> String {
+        let mut out = Output::default();
+        for stmt in body {
+            let span = self.statements.pop_front();
+            let stmt_end = span.map(|(_, end)| end);
+            self.item(&mut out, span.map(|(start, _)| start), false, |p| {
+                (p.stmt(stmt, stmt_end), stmt_end)
+            });
+        }
+        if let Some(end) = end {
+            self.leading(&mut out, end);
+        }
+        out.text
+    }
+
+    /// 输出行号小于 `line` 的注释，每条独占一行
+    fn leading(&mut self, out: &mut Output, line: usize) {
+        while self.comments.front().is_some_and(|c| c.line < line) {
+            let Some(comment) = self.comments.pop_front() else {
+                break;
+            };
+            out.gap(comment.line);
+            out.line(&self.pad(), comment.text.trim_end());
+            out.last = Some(comment.line + comment.text.matches('\n').count());
+        }
+    }
+
+    /// 输出一项（语句或成员）及其前后的注释
+    ///
+    /// 之前的注释各占一行；与该项结束行同一行的注释跟在行尾；
+    /// 位于该项内部、没有被内层语句认领的注释（eg. 多行表达式中间）放在该项之前。
+    fn item(
+        &mut self,
+        out: &mut Output,
+        start: Option<usize>,
+        blank: bool,
+        print: impl FnOnce(&mut Self) -> (String, Option<usize>),
+    ) {
+        if blank {
+            out.blank();
+        }
+        if let Some(start) = start {
+            self.leading(out, start);
+            out.gap(start);
+        }
+
+        let (mut text, end) = print(self);
+        if let Some(end) = end {
+            while self.comments.front().is_some_and(|c| c.line <= end) {
+                let Some(comment) = self.comments.pop_front() else {
+                    break;
+                };
+                if comment.line == end {
+                    text.push(' ');
+                    text.push_str(comment.text.trim_end());
+                } else {
+                    out.line(&self.pad(), comment.text.trim_end());
+                }
+            }
+        }
+
+        out.line(&self.pad(), &text);
+        out.last = end.or(start).or(out.last);
+    }
+
+    /// 语句（首行不带缩进，结尾不换行）
+    ///
+    /// `end` 是语句的结束行，类、trait 与 extend 用它放置体内最后的注释。
+    fn stmt(&mut self, stmt: &Stmt, end: Option<usize>) -> String {
+        match stmt {
+            Stmt::Expression { expr } => format!("{};", self.expr(expr)),
+            Stmt::VarDecl { name, initializer } => self.var_decl(name, initializer.as_ref(), true),
+            Stmt::VarDestructure {
+                pattern,
+                initializer,
+            } => format!("var {} = {};", self.expr(pattern), self.expr(initializer)),
+            Stmt::Function {
+                name,
+                params,
+                rest,
+                body,
+            } => format!("fun {}", self.function(name, params, *rest, body).0),
+            Stmt::Class {
+                name,
+                superclass,
+                methods,
+                static_methods,
+                traits,
+            } => {
+                let mut header = format!("class {}", name.lexeme);
+                if let Some(superclass) = superclass {
+                    header.push_str(&format!(" < {}", self.expr(superclass)));
+                }
+                if !traits.is_empty() {
+                    header.push_str(&format!(" implements {}", join_tokens(traits)));
+                }
+
+                // 静态方法与实例方法分开存放，按源码中的先后顺序合并
+                let (mut statics, mut instance) = (static_methods.iter(), methods.iter());
+                let members = (0..methods.len() + static_methods.len())
+                    .filter_map(|_| match self.statics.pop_front() {
+                        Some(true) => statics.next().map(|m| Member::Method(m, true)),
+                        _ => instance.next().map(|m| Member::Method(m, false)),
+                    })
+                    .collect();
+                self.members(header, members, end)
+            }
+            Stmt::Trait { name, methods } => {
+                let members = methods
+                    .iter()
+                    .map(|(method, params)| Member::Signature(method, params))
+                    .collect();
+                self.members(format!("trait {}", name.lexeme), members, end)
+            }
+            Stmt::Extend { target, methods } => {
+                let members = methods.iter().map(|m| Member::Method(m, false)).collect();
+                self.members(format!("extend {}", target.lexeme), members, end)
+            }
+            Stmt::Block { body } => self.block(body).0,
+            Stmt::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                let mut out = format!(
+                    "if ({}) {}",
+                    self.expr(condition),
+                    self.sub_stmt(then_branch)
+                );
+                if let Some(else_branch) = else_branch {
+                    if matches!(**then_branch, Stmt::Block { .. }) {
+                        out.push(' ');
+                    } else {
+                        out.push('\n');
+                        out.push_str(&self.pad());
+                    }
+                    out.push_str("else ");
+                    out.push_str(&self.sub_stmt(else_branch));
+                }
+                out
+            }
+            Stmt::Try {
+                try_branch,
+                catch,
+                finally_branch,
+            } => {
+                let mut out = format!("try {}", self.branch(try_branch));
+                if let Some((catch_var, catch_branch)) = catch {
+                    out.push_str(&format!(
+                        " catch ({}) {}",
+                        catch_var.lexeme,
+                        self.branch(catch_branch)
+                    ));
+                }
+                if let Some(finally_branch) = finally_branch {
+                    out.push_str(&format!(" finally {}", self.branch(finally_branch)));
+                }
+                out
+            }
+            Stmt::Throw { value, .. } => format!("throw {};", self.expr(value)),
+            Stmt::While { condition, body } => {
+                format!("while ({}) {}", self.expr(condition), self.sub_stmt(body))
+            }
+            Stmt::For {
+                initializer,
+                condition,
+                increment,
+                body,
+            } => {
+                let mut out = String::from("for (");
+                match initializer.as_deref() {
+                    Some(Stmt::VarDecl { name, initializer }) => {
+                        out.push_str(&self.var_decl(name, initializer.as_ref(), false))
+                    }
+                    Some(init) => out.push_str(&self.stmt(init, None)),
+                    None => out.push(';'),
+                }
+                if let Some(condition) = condition {
+                    out.push(' ');
+                    out.push_str(&self.expr(condition));
+                }
+                out.push(';');
+                if let Some(increment) = increment {
+                    out.push(' ');
+                    out.push_str(&self.expr(increment));
+                }
+                out.push_str(") ");
+                out.push_str(&self.sub_stmt(body));
+                out
+            }
+            Stmt::ForIn {
+                vars,
+                iterable,
+                body,
+            } => format!(
+                "for ({} in {}) {}",
+                join_tokens(vars),
+                self.expr(iterable),
+                self.sub_stmt(body)
+            ),
+            Stmt::Print { expr } => format!("print {};", self.expr(expr)),
+            Stmt::Return { value, .. } => match value {
+                Some(value) => format!("return {};", self.expr(value)),
+                None => "return;".to_string(),
+            },
+            Stmt::Yield { value, .. } => match value {
+                Some(value) => format!("yield {};", self.expr(value)),
+                None => "yield;".to_string(),
+            },
+            Stmt::Defer { expr, .. } => format!("defer {};", self.expr(expr)),
+            Stmt::Break { label } => match label {
+                Some(label) => format!("break {};", label.lexeme),
+                None => "break;".to_string(),
+            },
+            Stmt::Continue { label } => match label {
+                Some(label) => format!("continue {};", label.lexeme),
+                None => "continue;".to_string(),
+            },
+            Stmt::Labeled { label, body } => {
+                format!("{}: {}", label.lexeme, self.stmt(body, end))
+            }
+            Stmt::Export { stmt } => {
+                let declaration = match stmt.as_ref() {
+                    Stmt::VarDecl { name, initializer } => {
+                        self.var_decl(name, initializer.as_ref(), false)
+                    }
+                    stmt => self.stmt(stmt, end),
+                };
+                format!("export {}", declaration)
+            }
+            Stmt::Empty => ";".to_string(),
+        }
+    }
+
+    /// if / 循环的分支与循环体，它们各自由 `parse_statement` 解析
+    fn sub_stmt(&mut self, stmt: &Stmt) -> String {
+        let end = self.statements.pop_front().map(|(_, end)| end);
+        self.stmt(stmt, end)
+    }
+
+    /// try / catch / finally 的代码块
+    fn branch(&mut self, stmt: &Stmt) -> String {
+        match stmt {
+            Stmt::Block { body } => self.block(body).0,
+            stmt => self.stmt(stmt, None),
+        }
+    }
+
+    /// 代码块，同时返回 `}` 所在的源码行
+    fn block(&mut self, body: &[Stmt]) -> (String, Option<usize>) {
+        let end = self.blocks.pop_front();
+        self.indent += 1;
+        let inner = self.stmts(body, end);
+        self.indent -= 1;
+
+        let text = if inner.is_empty() {
+            "{}".to_string()
+        } else {
+            format!("{{\n{}{}}}", inner, self.pad())
+        };
+        (text, end)
+    }
+
+    fn members(&mut self, header: String, members: Vec<Member>, end: Option<usize>) -> String {
+        let mut out = Output::default();
+        self.indent += 1;
+        for (i, member) in members.iter().enumerate() {
+            // 方法之间总是空一行
+            let blank = i > 0 && matches!(member, Member::Method(..));
+            self.item(&mut out, Some(member_line(member)), blank, |p| {
+                p.member(member)
+            });
+        }
+        if let Some(end) = end {
+            self.leading(&mut out, end);
+        }
+        self.indent -= 1;
+
+        if out.text.is_empty() {
+            format!("{} {{}}", header)
+        } else {
+            format!("{} {{\n{}{}}}", header, out.text, self.pad())
+        }
+    }
+
+    fn member(&mut self, member: &Member) -> (String, Option<usize>) {
+        match member {
+            Member::Method(
+                Stmt::Function {
+                    name,
+                    params,
+                    rest,
+                    body,
+                },
+                is_static,
+            ) => {
+                let (text, end) = self.function(name, params, *rest, body);
+                let prefix = if *is_static { "static " } else { "" };
+                (format!("{}{}", prefix, text), end.or(Some(name.line)))
+            }
+            Member::Method(stmt, _) => (self.stmt(stmt, None), None),
+            Member::Signature(name, params) => (
+                format!("{}({});", name.lexeme, join_tokens(params)),
+                Some(name.line),
+            ),
+        }
+    }
+
+    /// `name(params) { ... }` 或 `name(params) = expr;`，同时返回函数体结束的源码行
+    fn function(
+        &mut self,
+        name: &Token,
+        params: &[Token],
+        rest: bool,
+        body: &[Stmt],
+    ) -> (String, Option<usize>) {
+        let params = format_params(params, rest);
+        match short_body(body) {
+            Some(("=", value)) => (
+                format!("{}({}) = {};", name.lexeme, params, self.expr(value)),
+                None,
+            ),
+            _ => {
+                let (block, end) = self.block(body);
+                (format!("{}({}) {}", name.lexeme, params, block), end)
+            }
+        }
+    }
+
+    /// 变量声明；由 `import` 语句脱糖得到的声明还原为 `import` 语句
+    fn var_decl(&mut self, name: &Token, initializer: Option<&Expr>, allow_import: bool) -> String {
+        if allow_import && let Some(import) = initializer.and_then(|e| import_statement(name, e)) {
+            return import;
+        }
+        match initializer {
+            Some(expr) => format!("var {} = {};", name.lexeme, self.expr(expr)),
+            None => format!("var {};", name.lexeme),
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) -> String {
+        match expr {
+            Expr::Number { value } => format_number(value),
+            Expr::BigInt { value } => format!("{}n", value),
+            Expr::String { value } => format!("\"{}\"", escape_interpolation(value)),
+            Expr::Boolean { value } => value.to_string(),
+            Expr::Nil => "nil".to_string(),
+            Expr::List { elements } => format!("[{}]", self.exprs(elements)),
+            Expr::Dict { elements } => {
+                let pairs = elements
+                    .iter()
+                    .map(|(k, v)| format!("{}: {}", self.expr(k), self.expr(v)))
+                    .collect::<Vec<_>>();
+                format!("{{{}}}", pairs.join(", "))
+            }
+            Expr::Tuple { elements } if elements.len() == 1 => {
+                format!("({},)", self.expr(&elements[0]))
+            }
+            Expr::Tuple { elements } => format!("({})", self.exprs(elements)),
+            Expr::Variable { name, .. } => name.lexeme.clone(),
+            Expr::Assign { name, expr, .. } => format!("{} = {}", name.lexeme, self.expr(expr)),
+            Expr::AssignDestructure { pattern, value } => {
+                format!("{} = {}", self.expr(pattern), self.expr(value))
+            }
+            Expr::AssignOp { op, name, expr, .. } => format!(
+                "{} {}= {}",
+                name.lexeme,
+                format_operator(op),
+                self.expr(expr)
+            ),
+            Expr::Binary { .. } if interpolation_parts(expr).is_some() => self.interpolation(expr),
+            Expr::Binary { op, left, right } | Expr::Logical { op, left, right } => {
+                let precedence = precedence(expr);
+                format!(
+                    "{} {} {}",
+                    self.operand(left, precedence),
+                    format_operator(op),
+                    self.operand(right, precedence + 1)
+                )
+            }
+            Expr::Unary { op, expr: operand } => {
+                format!("{}{}", format_operator(op), self.operand(operand, UNARY))
+            }
+            Expr::Grouping { expr } => format!("({})", self.expr(expr)),
+            Expr::Super { method, .. } => format!("super.{}", method.lexeme),
+            Expr::Call {
+                callee,
+                args,
+                named,
+                ..
+            } => {
+                let callee = self.operand(callee, CALL);
+                let mut parts = args.iter().map(|arg| self.expr(arg)).collect::<Vec<_>>();
+                for (name, value) in named {
+                    parts.push(format!("{} = {}", name.lexeme, self.expr(value)));
+                }
+                format!("{}({})", callee, parts.join(", "))
+            }
+            Expr::Get {
+                object,
+                name,
+                optional,
+            } => {
+                let dot = if *optional { "?." } else { "." };
+                format!("{}{}{}", self.operand(object, CALL), dot, name.lexeme)
+            }
+            Expr::GetIndex {
+                object,
+                index,
+                optional,
+                ..
+            } => {
+                let bracket = if *optional { "?[" } else { "[" };
+                format!(
+                    "{}{}{}]",
+                    self.operand(object, CALL),
+                    bracket,
+                    self.expr(index)
+                )
+            }
+            Expr::SetIndex {
+                object,
+                index,
+                value,
+                ..
+            } => format!(
+                "{}[{}] = {}",
+                self.operand(object, CALL),
+                self.expr(index),
+                self.expr(value)
+            ),
+            Expr::Set {
+                object,
+                name,
+                value,
+            } => format!(
+                "{}.{} = {}",
+                self.operand(object, CALL),
+                name.lexeme,
+                self.expr(value)
+            ),
+            Expr::This { .. } => "this".to_string(),
+            Expr::Lambda {
+                captures,
+                params,
+                rest,
+                body,
+                ..
+            } => {
+                let captures = if captures.is_empty() {
+                    String::new()
+                } else {
+                    format!(" [{}]", self.exprs(captures))
+                };
+                let params = format_params(params, *rest);
+                match short_body(body) {
+                    Some(("|" | "||", value)) => format!("|{}| {}", params, self.expr(value)),
+                    Some((_, value)) => {
+                        format!("fun{}({}) = {}", captures, params, self.expr(value))
+                    }
+                    None => format!("fun{}({}) {}", captures, params, self.block(body).0),
+                }
+            }
+        }
+    }
+
+    fn exprs(&mut self, exprs: &[Expr]) -> String {
+        exprs
+            .iter()
+            .map(|expr| self.expr(expr))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// 运算数，优先级低于 `min` 时加上括号
+    fn operand(&mut self, expr: &Expr, min: u8) -> String {
+        let text = self.expr(expr);
+        if precedence(expr) < min {
+            format!("({})", text)
+        } else {
+            text
+        }
+    }
+
+    /// 把字符串插值脱糖得到的拼接还原为插值：`"a " + x + " b"` -> `"a ${x} b"`
+    fn interpolation(&mut self, expr: &Expr) -> String {
+        let mut out = String::from("\"");
+        for part in interpolation_parts(expr).unwrap_or_default() {
+            match part {
+                Expr::String { value } => out.push_str(&escape_interpolation(value)),
+                part => out.push_str(&format!("${{{}}}", self.expr(part))),
+            }
+        }
+        out.push('"');
+        out
+    }
+}
+
+/// 字符串内容中字面的 `${` 写回源码时需要转义为 `\${`
+fn escape_interpolation(value: &str) -> String {
+    value.replace("${", "\\${")
+}
+
+const UNARY: u8 = 12;
+const CALL: u8 = 13;
+
+/// 表达式的优先级，数值越大结合越紧
+fn precedence(expr: &Expr) -> u8 {
+    match expr {
+        Expr::Assign { .. }
+        | Expr::AssignOp { .. }
+        | Expr::AssignDestructure { .. }
+        | Expr::Set { .. }
+        | Expr::SetIndex { .. } => 1,
+        // `|x| expr` 与 `fun (x) = expr` 的函数体会吞掉后面的整个表达式
+        Expr::Lambda { body, .. } if short_body(body).is_some() => 1,
+        Expr::Logical { op, .. } => match op {
+            Operator::NullCoalesce => 2,
+            Operator::LogicalOr | Operator::OrKeyword => 3,
+            _ => 4,
+        },
+        Expr::Binary { .. } if interpolation_parts(expr).is_some() => 14,
+        Expr::Binary { op, .. } => match op {
+            Operator::Equal | Operator::NotEqual => 5,
+            Operator::Less | Operator::LessEqual | Operator::Greater | Operator::GreaterEqual => 6,
+            Operator::BitwiseOr => 7,
+            Operator::BitwiseXor => 8,
+            Operator::BitwiseAnd => 9,
+            Operator::Add | Operator::Sub => 10,
+            _ => 11,
+        },
+        Expr::Unary { .. } => UNARY,
+        Expr::Call { .. } | Expr::Get { .. } | Expr::GetIndex { .. } | Expr::Super { .. } => CALL,
+        _ => 14,
+    }
+}
+
+/// 以字符串字面值开头、至少含一个非字符串运算数的 `+` 链（左结合），按顺序返回各部分
+///
+/// 两段文本不能相邻，否则无法写成插值。
+fn interpolation_parts(expr: &Expr) -> Option<Vec<&Expr>> {
+    let mut parts = Vec::new();
+    let mut current = expr;
+    while let Expr::Binary {
+        op: Operator::Add,
+        left,
+        right,
+    } = current
+    {
+        parts.push(right.as_ref());
+        current = left;
+    }
+    if !matches!(current, Expr::String { .. }) || parts.is_empty() {
+        return None;
+    }
+    parts.push(current);
+    parts.reverse();
+
+    let is_text = |e: &&Expr| matches!(e, Expr::String { .. });
+    let adjacent_text = parts.windows(2).any(|w| is_text(&w[0]) && is_text(&w[1]));
+    (!adjacent_text).then_some(parts)
+}
+
+/// 只有一条由 `=` / `|x|` 简写产生的 return 语句的函数体，返回 (简写的标记, 表达式)
+fn short_body(body: &[Stmt]) -> Option<(&str, &Expr)> {
+    match body {
+        [
+            Stmt::Return {
+                keyword,
+                value: Some(value),
+            },
+        ] if keyword.lexeme != "return" => Some((keyword.lexeme.as_str(), value)),
+        _ => None,
+    }
+}
+
+/// `var name = import("path");` 对应的 `import` 语句
+fn import_statement(name: &Token, initializer: &Expr) -> Option<String> {
+    let Expr::Call {
+        callee,
+        args,
+        named,
+        ..
+    } = initializer
+    else {
+        return None;
+    };
+    let Expr::Variable { name: callee, .. } = callee.as_ref() else {
+        return None;
+    };
+    let [Expr::String { value: path }] = args.as_slice() else {
+        return None;
+    };
+    if callee.lexeme != "import" || !named.is_empty() {
+        return None;
+    }
+
+    let name = &name.lexeme;
+    if is_identifier(path) {
+        return Some(if path == name {
+            format!("import {};", path)
+        } else {
+            format!("import {} as {};", path, name)
+        });
+    }
+
+    let stem = Path::new(path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned());
+    Some(if stem.as_ref() == Some(name) {
+        format!("import \"{}\";", path)
+    } else {
+        format!("import \"{}\" as {};", path, name)
+    })
+}
+
+fn is_identifier(text: &str) -> bool {
+    text.chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_')
+        && text.chars().all(|c| c.is_alphanumeric() || c == '_')
+        && !KEYWORDS.contains(&text)
+}
+
+/// 数字字面值；f64 的 Display 不使用科学计数法，极大或极小的数改写为科学计数法
+fn format_number(value: &str) -> String {
+    match value.parse::<f64>() {
+        Ok(n) if value.len() > 21 => format!("{:e}", n),
+        _ => value.to_string(),
+    }
+}
+
+fn format_params(params: &[Token], rest: bool) -> String {
+    let mut params = params.iter().map(|p| p.lexeme.clone()).collect::<Vec<_>>();
+    if rest && let Some(last) = params.last_mut() {
+        last.insert_str(0, "...");
+    }
+    params.join(", ")
+}
+
+fn join_tokens(tokens: &[Token]) -> String {
+    tokens
+        .iter()
+        .map(|t| t.lexeme.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn member_line(member: &Member) -> usize {
+    match member {
+        Member::Method(Stmt::Function { name, .. }, _) => name.line,
+        Member::Signature(name, _) => name.line,
+        Member::Method(..) => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, format_source};
+    use pretty_assertions::assert_eq;
+
+    fn check(source: &str, expected: &str) {
+        assert_eq!(format_source(source).unwrap(), expected);
+        assert_eq!(format_source(expected).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_format_layout() {
+        check(
+            "var   x=1+2*3;fun add(a,b){return a+b;}
+if(x>1){print x;}else if (x == 0) print 0;
+else{print -x;}
+
+
+class Point<Base implements Show{init(x,y){this.x=x;} static origin(){return Point(0,0);}show(){return \"(\"+this.x+\")\";}}
+for(var i=0;i<3;i+=1)print i;
+for (;;) { break; }
+while (true) {}",
+            "var x = 1 + 2 * 3;
+fun add(a, b) {
+    return a + b;
+}
+if (x > 1) {
+    print x;
+} else if (x == 0) print 0;
+else {
+    print -x;
+}
+
+class Point < Base implements Show {
+    init(x, y) {
+        this.x = x;
+    }
+
+    static origin() {
+        return Point(0, 0);
+    }
+
+    show() {
+        return \"(${this.x})\";
+    }
+}
+for (var i = 0; i < 3; i += 1) print i;
+for (;;) {
+    break;
+}
+while (true) {}
+",
+        );
+    }
+
+    #[test]
+    fn test_format_keeps_sugar() {
+        check(
+            "import utils;import \"lib/str_utils.rox\";import \"./x\" as y;
+var f=|x|x*2;var g=fun(a,...rest)=a;fun h(x)=x+1;
+var (a,b)=(1,2);var t=(a,);print \"sum: ${a+b}!\";print (1+2)*3;
+var d={\"k\":[1,2],\"m\":{}};print d?.k?[0]??nil;
+outer: for (k, v in d) { continue outer; }
+try { throw \"e\"; } catch (e) { print e; } finally { print 1; }
+export fun e() {}",
+            "import utils;
+import \"lib/str_utils.rox\";
+import \"./x\" as y;
+var f = |x| x * 2;
+var g = fun(a, ...rest) = a;
+fun h(x) = x + 1;
+var (a, b) = (1, 2);
+var t = (a,);
+print \"sum: ${a + b}!\";
+print (1 + 2) * 3;
+var d = {\"k\": [1, 2], \"m\": {}};
+print d?.k?[0] ?? nil;
+outer: for (k, v in d) {
+    continue outer;
+}
+try {
+    throw \"e\";
+} catch (e) {
+    print e;
+} finally {
+    print 1;
+}
+export fun e() {}
+",
+        );
+    }
+
+    #[test]
+    fn test_format_keeps_escaped_interpolation() {
+        check(
+            "print \"\\${a}\" + b;print \"cost: \\${x}\";",
+            "print \"\\${a}${b}\";\nprint \"cost: \\${x}\";\n",
+        );
+    }
+
+    #[test]
+    fn test_format_keeps_comments() {
+        check(
+            "#!/usr/bin/env rox
+// leading
+var x = 1; // trailing
+
+/* block
+   comment */
+fun f() {
+    print x;
+    // last in block
+}
+class A {
+    // about m
+    m() {}
+    // end of class
+}
+// end of file
+",
+            "#!/usr/bin/env rox
+// leading
+var x = 1; // trailing
+
+/* block
+   comment */
+fun f() {
+    print x;
+    // last in block
+}
+class A {
+    // about m
+    m() {}
+    // end of class
+}
+// end of file
+",
+        );
+    }
+
+    #[test]
+    fn test_format_reports_syntax_errors() {
+        assert!(matches!(format_source("print (1;"), Err(Error::Source(_))));
+        assert_eq!(format_source("").unwrap(), "");
+    }
+}
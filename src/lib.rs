@@ -0,0 +1,158 @@
+//! rox：一个用 Rust 编写的树遍历解释器
+//!
+//! 命令行入口位于 `main.rs`；嵌入方与模糊测试 (fuzz) 通过本库调用解释器。
+
+pub mod ast;
+pub mod cache;
+pub mod completion;
+pub mod diagnostics;
+pub mod error;
+pub mod evaluate;
+pub mod formatter;
+pub mod parser;
+pub mod project;
+pub mod reader;
+pub mod resolver;
+pub mod std_lib;
+pub mod tokenizer;
+
+use crate::{
+    error::RoxError,
+    evaluate::{Interpreter, Value, error::RuntimeError},
+    reader::Source,
+    resolver::Resolver,
+    std_lib::{io::trace::IoTrace, registry::Registry},
+};
+use std::thread;
+
+/// 解释器线程的栈大小
+///
+/// 语法嵌套 (`parser::parse::MAX_NESTING`) 与调用深度 (`evaluate::interpreter::MAX_CALL_DEPTH`)
+/// 都有上限，这个大小保证未优化 (debug) 构建在达到上限前也不会栈溢出。
+pub const STACK_SIZE: usize = 256 * 1024 * 1024;
+
+/// 在栈大小为 `STACK_SIZE` 的线程中执行 `f`
+///
+/// # 返回值
+/// * `Ok(T)` - `f` 的返回值
+/// * `Err(..)` - `f` 发生了 panic 或线程无法创建
+pub fn run_with_stack<T: Send + 'static>(
+    f: impl FnOnce() -> T + Send + 'static,
+) -> thread::Result<T> {
+    match thread::Builder::new().stack_size(STACK_SIZE).spawn(f) {
+        Ok(handle) => handle.join(),
+        Err(e) => Err(Box::new(e)),
+    }
+}
+
+/// 沙箱中最多执行的语句数
+pub const SANDBOX_STEP_LIMIT: u64 = 1_000_000;
+
+/// 在沙箱中执行任意源码（模糊测试入口）
+///
+/// 任何输入都只会得到 `Ok` 或 `Err`：
+/// - 沙箱禁用了 `fs` 与 `signal` 模块，拒绝 `input()` 等所有 IO 操作，也不能导入或 include 文件。
+/// - 在大栈线程中执行，语法嵌套与调用深度受限，病态输入不会耗尽调用栈。
+/// - 最多执行 `SANDBOX_STEP_LIMIT` 条语句（循环的每次迭代都计入），死循环会以错误结束。
+/// - 解释器与标准库对非法输入一律返回 `RuntimeError`，不使用 panic 报告错误。
+///   线程边界上的 panic 捕获只是兜底：release 构建与 libFuzzer 使用 `panic = "abort"`，
+///   在那里不会生效，因此新代码同样不能依赖它。
+///
+/// 值 (`Value`) 不能跨线程传递，因此返回值与错误都以字符串形式给出。
+/// Note：步数限制不覆盖单个原生调用内部的耗时（eg. 大列表排序），极端情况仍需调用方的超时机制兜底。
+///
+/// # 返回值
+/// * `Ok(String)` - 执行结果的字符串形式
+/// * `Err(String)` - 词法、语法、作用域或运行时错误信息
+pub fn try_eval(source: &str) -> Result<String, String> {
+    let source = source.to_string();
+    run_with_stack(move || {
+        eval_sandboxed(&source)
+            .map(|value| value.to_string())
+            .map_err(|e| e.to_string())
+    })
+    .unwrap_or_else(|_| Err("Internal error: the interpreter panicked.".to_string()))
+}
+
+fn eval_sandboxed(source: &str) -> Result<Value, RoxError> {
+    let mut registry = Registry::new();
+    registry.disable("fs");
+    // 信号会影响宿主进程（eg. raise("SIGTERM") 直接终止）
+    registry.disable("signal");
+    let mut interpreter = Interpreter::with_registry(registry);
+    interpreter.io_trace = Some(IoTrace::Deny);
+    // 沙箱不读写磁盘上的编译缓存 (~/.rox/cache)
+    interpreter.module_cache = None;
+    interpreter.step_budget = Some(SANDBOX_STEP_LIMIT);
+
+    let tokens = tokenizer::tokenize(Source {
+        contents: source.to_string(),
+    })?;
+    let ast = parser::parse(tokens)?;
+    Resolver::new(&mut interpreter)
+        .resolve_stmts(&ast.body)
+        .map_err(|msg| RuntimeError::Generic(format!("Resolution Error: {}", msg)))?;
+
+    Ok(interpreter.interpret(ast)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::try_eval;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_try_eval_returns_value() {
+        assert_eq!(
+            try_eval("var l = [1]; l.push(l); print l;"),
+            Ok("nil".into())
+        );
+        assert!(try_eval("print (1;").is_err());
+    }
+
+    #[test]
+    fn test_try_eval_survives_pathological_input() {
+        let deep_parens = format!("print {}1{};", "(".repeat(10_000), ")".repeat(10_000));
+        let deep_blocks = "{".repeat(10_000);
+        let cases = [
+            deep_parens.as_str(),
+            deep_blocks.as_str(),
+            "fun f() { return f(); } f();",
+            r#"fs.writeFile("x.txt", "y");"#,
+            r#"input("> ");"#,
+            r#""abc".substring(-1, 2);"#,
+            r#""abc".substring(1, 9);"#,
+            "while (true) {}",
+            "for (;;) {}",
+            "fun f(n) { return f(n + 1) + f(n + 1); } f(0);",
+        ];
+
+        for case in cases {
+            assert!(
+                try_eval(case).is_err(),
+                "expected an error for {:.40}",
+                case
+            );
+        }
+        assert!(
+            try_eval("while (true) {}")
+                .unwrap_err()
+                .contains("step limit exceeded")
+        );
+    }
+
+    #[test]
+    fn test_try_eval_cannot_load_files() {
+        let path = std::env::temp_dir().join(format!("rox_sandbox_{}.rox", std::process::id()));
+        std::fs::write(&path, "var loaded = true;").unwrap();
+        let path = path.to_string_lossy().into_owned();
+
+        let imported = try_eval(&format!("import(\"{}\");", path));
+        let included = try_eval(&format!("include(\"{}\");", path));
+        std::fs::remove_file(&path).unwrap();
+
+        for result in [imported, included] {
+            assert!(result.unwrap_err().contains("IO is disabled"));
+        }
+    }
+}
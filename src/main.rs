@@ -1,70 +1,470 @@
-use crate::{
+use rox::{
+    ast::Ast,
+    cache::bundle::{BUNDLE_EXTENSION, Bundle},
+    completion::RoxHelper,
     diagnostics::print_diagnostic,
     error::RoxError,
     evaluate::{Interpreter, Value, error::RuntimeError},
+    formatter::{Error as FormatError, format_source},
+    project::{Error as ProjectError, Project},
     reader::Source,
     resolver::Resolver,
+    std_lib::io::trace::IoTrace,
 };
-use rustyline::{DefaultEditor, error::ReadlineError};
+use rustyline::{Config, Editor, error::ReadlineError, history::DefaultHistory};
 use std::{
-    env, fs,
+    env,
+    ffi::OsStr,
+    fs,
     path::{Path, PathBuf},
+    rc::Rc,
 };
 
-mod ast;
-mod diagnostics;
-mod error;
-mod evaluate;
-mod parser;
-mod reader;
-mod resolver;
-mod std_lib;
-mod tokenizer;
-
-fn main() -> Result<(), RoxError> {
-    println!("rox v0.1.0 - A simple scripting language interpreter");
+const USAGE: &str = "Usage: rox [--record trace | --replay trace] [script [args...]]
+       rox -e code [args...]
+       rox repl [--preload file.rox]...
+       rox run [script.rox | app.roxb | project-dir]
+       rox check [script.rox | project-dir]...
+       rox compile [script.rox | project-dir] [-o out.roxb]
+       rox fmt [--check] [path...]";
+
+fn main() {
+    // 在大栈线程中运行：递归深度由解释器的上限约束，而不是主线程的栈大小
+    let result = rox::run_with_stack(|| run_cli().map_err(|e| format!("{:?}", e)))
+        .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
 
-    let input_args = env::args().collect::<Vec<_>>();
+fn run_cli() -> Result<(), RoxError> {
+    let input_args = env::args().skip(1).collect::<Vec<_>>();
+    let args = input_args.iter().map(String::as_str).collect::<Vec<_>>();
 
     // 实例化解释器 （包含 Global Environment）
     // 在这里实例化是为了让 REPL 模式下可以保持变量状态
-    let mut interpreter = Interpreter::default();
+    let mut interpreter = Interpreter {
+        globals_writable: env::var_os("ROX_GLOBALS_WRITABLE").is_some(),
+        ieee_division: env::var_os("ROX_IEEE_DIVISION").is_some(),
+        ..Interpreter::default()
+    };
+    // 确定性模式：ROX_DETERMINISTIC=<seed>，未给出种子时使用 0
+    if let Some(seed) = env::var_os("ROX_DETERMINISTIC") {
+        let seed = seed.to_str().and_then(|s| s.parse().ok()).unwrap_or(0);
+        interpreter.set_deterministic(seed);
+    }
 
-    if input_args.len() == 1 {
-        println!("Type 'help' for more information or press Ctrl+C to exit.");
+    // IO 录制 / 回放：rox --record run.trace script.rox
+    let args = match args.as_slice() {
+        [flag @ ("--record" | "--replay"), trace, rest @ ..] => {
+            let trace = match *flag {
+                "--record" => IoTrace::record(Path::new(trace)),
+                _ => IoTrace::replay(Path::new(trace)),
+            };
+            match trace {
+                Ok(trace) => interpreter.io_trace = Some(trace),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(66);
+                }
+            }
+            rest
+        }
+        args => args,
+    };
 
-        // REPL 模式错误处理
-        if let Err(e) = run_prompt(&mut interpreter) {
-            eprintln!("REPL Error: {}", e);
-            std::process::exit(1);
+    let result = match args {
+        [] => start_repl(&[], &mut interpreter),
+        ["repl", options @ ..] => {
+            // rox repl --preload a.rox --preload b.rox
+            let preload = options
+                .chunks(2)
+                .map(|pair| match pair {
+                    ["--preload", file] => Some(*file),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>();
+            match preload {
+                Some(preload) => start_repl(&preload, &mut interpreter),
+                None => {
+                    eprintln!("{}", USAGE);
+                    std::process::exit(64);
+                }
+            }
+        }
+        ["run", bundle] if Path::new(bundle).extension() == Some(OsStr::new(BUNDLE_EXTENSION)) => {
+            run_bundle(bundle, &mut interpreter)
+        }
+        ["run", target @ ..] if target.len() <= 1 => {
+            run_target(target.first().copied(), &mut interpreter, Mode::Run)
+        }
+        ["check", targets @ ..] if !targets.iter().any(|t| t.starts_with('-')) => {
+            run_check(targets, &mut interpreter)
         }
-        Ok(())
-    } else if input_args.len() == 2 {
-        // 脚本模式错误处理
-        match run_file(&input_args[1], &mut interpreter) {
-            Ok(r) => {
-                if r != Value::Nil {
-                    // 脚本模式通常不打印返回值，除非显式 print
+        ["compile", rest @ ..] => {
+            let (target, output) = match rest {
+                [] => (None, None),
+                [target] => (Some(*target), None),
+                ["-o", output] => (None, Some(*output)),
+                [target, "-o", output] => (Some(*target), Some(*output)),
+                _ => {
+                    eprintln!("{}", USAGE);
+                    std::process::exit(64);
                 }
-                Ok(())
+            };
+            run_target(target, &mut interpreter, Mode::Compile(output))
+        }
+        ["fmt", rest @ ..] => run_fmt(rest),
+        ["-e" | "--eval", code, script_args @ ..] => {
+            interpreter.script_args = script_args.iter().map(|arg| arg.to_string()).collect();
+            run_eval(code, &mut interpreter)
+        }
+        [script, script_args @ ..] if !script.starts_with('-') => {
+            interpreter.script_args = script_args.iter().map(|arg| arg.to_string()).collect();
+            run_script(script, &mut interpreter)
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(64);
+        }
+    };
+
+    // 调试模式：退出前报告只被循环引用持有、永远不会释放的对象
+    if env::var_os("ROX_GC_DEBUG").is_some() {
+        report_leaks();
+    }
+
+    result
+}
+
+fn report_leaks() {
+    let leaks = rox::evaluate::heap::leak_report();
+    if leaks.is_empty() {
+        eprintln!("[gc] no reference cycle leaks.");
+        return;
+    }
+
+    eprintln!(
+        "[gc] {} object(s) leaked via reference cycles:",
+        leaks.len()
+    );
+    for leak in leaks {
+        eprintln!("  - {}", leak);
+    }
+}
+
+/// 脚本模式：执行文件，出错时打印诊断信息并以非零状态退出
+fn run_script(file: &str, interpreter: &mut Interpreter) -> Result<(), RoxError> {
+    match run_file(file, interpreter) {
+        Ok(r) => {
+            if r != Value::Nil {
+                // 脚本模式通常不打印返回值，除非显式 print
             }
+            Ok(())
+        }
+        // exit(code)
+        Err(RoxError::Evaluate(RuntimeError::Exit(code))) => std::process::exit(code),
+        Err(e) => {
+            // 读取文件内容用于报错高亮
+            let source_code = fs::read_to_string(file).unwrap_or_default();
+            print_diagnostic(file, &source_code, &e);
+
+            // 脚本错误非零退出
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `rox -e code`：执行命令行给出的代码，出错时打印诊断信息并以非零状态退出
+///
+/// import 的相对路径基于当前工作目录。
+fn run_eval(code: &str, interpreter: &mut Interpreter) -> Result<(), RoxError> {
+    if let Ok(cwd) = env::current_dir() {
+        interpreter.path_stack.push(cwd);
+    }
+    let source = Source {
+        contents: code.to_string(),
+    };
+
+    match run_interpreter_with_state(source, interpreter) {
+        Ok(_) => Ok(()),
+        Err(RoxError::Evaluate(RuntimeError::Exit(code))) => std::process::exit(code),
+        Err(e) => {
+            print_diagnostic("<eval>", code, &e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `rox fmt [--check] [path...]`：格式化 .rox 文件，目录递归查找（跳过隐藏文件），默认为当前目录
+///
+/// `--check` 只列出需要格式化的文件而不写回。有文件出错或（`--check` 时）未格式化则以状态 1 退出。
+fn run_fmt(args: &[&str]) -> Result<(), RoxError> {
+    let check = args.contains(&"--check");
+    let mut paths = args
+        .iter()
+        .copied()
+        .filter(|arg| *arg != "--check")
+        .collect::<Vec<_>>();
+    if paths.iter().any(|path| path.starts_with('-')) {
+        eprintln!("{}", USAGE);
+        std::process::exit(64);
+    }
+    if paths.is_empty() {
+        paths.push(".");
+    }
+
+    let mut files = Vec::new();
+    let mut failed = false;
+    for path in paths {
+        if let Err(e) = collect_rox_files(Path::new(path), &mut files) {
+            eprintln!("Error: {}: {}", path, e);
+            failed = true;
+        }
+    }
+
+    let mut unformatted = false;
+    for file in files {
+        let name = file.display().to_string();
+        let source = match fs::read_to_string(&file) {
+            Ok(source) => source,
             Err(e) => {
-                // 读取文件内容用于报错高亮
-                let source_code = fs::read_to_string(&input_args[1]).unwrap_or_default();
-                print_diagnostic(&input_args[1], &source_code, &e);
+                eprintln!("Error: {}: {}", name, e);
+                failed = true;
+                continue;
+            }
+        };
 
-                // 脚本错误非零退出
-                std::process::exit(1);
+        match format_source(&source) {
+            Ok(formatted) if formatted == source => {}
+            Ok(_) if check => {
+                println!("Would reformat: {}", name);
+                unformatted = true;
+            }
+            Ok(formatted) => match fs::write(&file, formatted) {
+                Ok(()) => println!("Formatted: {}", name),
+                Err(e) => {
+                    eprintln!("Error: {}: {}", name, e);
+                    failed = true;
+                }
+            },
+            Err(FormatError::Source(e)) => {
+                print_diagnostic(&name, &source, &e);
+                failed = true;
             }
+            Err(e) => {
+                eprintln!("Error: {}: {}", name, e);
+                failed = true;
+            }
+        }
+    }
+
+    if failed || unformatted {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// 收集路径下的 .rox 文件：显式给出的文件总是收集，目录按名字顺序递归
+fn collect_rox_files(path: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    if !path.is_dir() {
+        fs::metadata(path)?;
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+    for entry in entries {
+        let hidden = entry
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        if hidden {
+            continue;
+        }
+        if entry.is_dir() || entry.extension().is_some_and(|ext| ext == "rox") {
+            collect_rox_files(&entry, files)?;
+        }
+    }
+    Ok(())
+}
+
+/// `run_target` 的执行方式
+enum Mode<'a> {
+    Run,
+    /// 编译为 bundle（序列化的语法树，不是字节码），可选输出路径（默认与入口同名，扩展名为 .roxb）
+    Compile(Option<&'a str>),
+}
+
+/// `rox run` / `rox compile` 的入口
+fn run_target(
+    target: Option<&str>,
+    interpreter: &mut Interpreter,
+    mode: Mode,
+) -> Result<(), RoxError> {
+    let entry = match find_entry(target, interpreter, true) {
+        Ok(entry) => entry,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
         }
+    };
+
+    let output = match mode {
+        Mode::Run => return run_script(&entry, interpreter),
+        Mode::Compile(output) => output
+            .map(PathBuf::from)
+            .unwrap_or_else(|| Path::new(&entry).with_extension(BUNDLE_EXTENSION)),
+    };
+
+    let compiled =
+        Bundle::compile(Path::new(&entry), interpreter).and_then(|bundle| bundle.write(&output));
+    if let Err(e) = compiled {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+
+    println!("Compiled '{}' -> '{}'", entry, output.display());
+    Ok(())
+}
+
+/// 确定目标的入口文件，并加载所属项目的模块路径
+///
+/// * 目标是 `.rox` 文件：直接使用它，若所在目录（或上级目录）存在 rox.toml，则加载其模块路径。
+/// * 目标是目录或省略：向上查找 rox.toml，使用清单中的入口文件。
+///
+/// `fetch` 为 false 时不获取 git 依赖，只使用本地已有的依赖目录（`rox check` 不访问网络）。
+fn find_entry(
+    target: Option<&str>,
+    interpreter: &mut Interpreter,
+    fetch: bool,
+) -> Result<String, RoxError> {
+    let target_path = Path::new(target.unwrap_or("."));
+
+    let (entry, project) = if target_path.is_file() {
+        let project = match Project::discover(target_path) {
+            Ok(project) => Some(project),
+            Err(ProjectError::NotFound(_)) => None,
+            Err(e) => return Err(e.into()),
+        };
+        (target_path.to_path_buf(), project)
     } else {
-        eprintln!("Usage: rox [script]");
-        std::process::exit(64);
+        let project = Project::discover(target_path)?;
+        (project.entry_path(), Some(project))
+    };
+
+    if let Some(project) = project {
+        interpreter.search_paths = if fetch {
+            project.search_paths()?
+        } else {
+            project.offline_search_paths()
+        };
     }
+
+    Ok(entry.to_string_lossy().into_owned())
+}
+
+/// `rox check [target...]`：只做词法、语法与静态分析，不执行代码
+///
+/// 每个目标的全部错误都会被报告；任一目标有错误时以状态 1 退出。
+fn run_check(targets: &[&str], interpreter: &mut Interpreter) -> Result<(), RoxError> {
+    let targets = match targets {
+        [] => vec![None],
+        targets => targets.iter().copied().map(Some).collect(),
+    };
+
+    let mut failed = false;
+    for target in targets {
+        let entry = match find_entry(target, interpreter, false) {
+            Ok(entry) => entry,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                failed = true;
+                continue;
+            }
+        };
+
+        let errors = check_file(&entry, interpreter);
+        if errors.is_empty() {
+            println!("{}: no errors found.", entry);
+            continue;
+        }
+
+        let source_code = fs::read_to_string(&entry).unwrap_or_default();
+        for e in &errors {
+            print_diagnostic(&entry, &source_code, e);
+        }
+        let plural = if errors.len() == 1 { "" } else { "s" };
+        eprintln!("{}: {} error{} found.", entry, errors.len(), plural);
+        failed = true;
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// 对一个文件做词法、语法与静态分析，返回全部错误
+///
+/// 有语法错误时不再做静态分析（语法树不完整）。
+fn check_file(entry: &str, interpreter: &mut Interpreter) -> Vec<RoxError> {
+    let tokens = rox::reader::reader_source(entry)
+        .map_err(RoxError::from)
+        .and_then(|source| Ok(rox::tokenizer::tokenize(source)?));
+    let ast = match tokens.map(rox::parser::parse_all) {
+        Ok(Ok(ast)) => ast,
+        Ok(Err(errors)) => return errors.into_iter().map(RoxError::from).collect(),
+        Err(e) => return vec![e],
+    };
+
+    let mut resolver = Resolver::new(interpreter);
+    let errors = resolver.resolve_all(&ast.body);
+    for warning in &resolver.warnings {
+        eprintln!("Warning: {}", warning);
+    }
+    errors
+        .into_iter()
+        .map(|msg| RoxError::Evaluate(RuntimeError::Generic(format!("Resolution Error: {}", msg))))
+        .collect()
+}
+
+/// 执行 `rox compile` 生成的 bundle
+fn run_bundle(file: &str, interpreter: &mut Interpreter) -> Result<(), RoxError> {
+    let mut exit_code = None;
+    let result = Bundle::read(Path::new(file)).and_then(|bundle| {
+        let body = bundle.entry_body()?;
+        interpreter.bundle = Some(Rc::new(bundle));
+
+        let mut resolver = Resolver::new(interpreter);
+        resolver
+            .resolve_stmts(&body)
+            .map_err(|msg| format!("Resolution Error: {}", msg))?;
+        match interpreter.interpret(Ast { body }) {
+            Err(RuntimeError::Exit(code)) => {
+                exit_code = Some(code);
+                Ok(())
+            }
+            result => result.map(|_| ()).map_err(|e| e.to_string()),
+        }
+    });
+
+    if let Some(code) = exit_code {
+        std::process::exit(code);
+    }
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+    Ok(())
 }
 
 fn run_file(file: &str, interpreter: &mut Interpreter) -> Result<Value, RoxError> {
-    let source = crate::reader::reader_source(file)?;
+    let source = rox::reader::reader_source(file)?;
 
     // 设置路径上下文 (用于 import)
     let entry_path = Path::new(file);
@@ -92,13 +492,112 @@ fn run_file(file: &str, interpreter: &mut Interpreter) -> Result<Value, RoxError
     result
 }
 
-fn run_prompt(interpreter: &mut Interpreter) -> Result<(), RoxError> {
-    let mut rl = DefaultEditor::new()?;
+/// 启动 REPL：先依次加载预载文件，再进入交互循环
+fn start_repl(preload: &[&str], interpreter: &mut Interpreter) -> Result<(), RoxError> {
+    for file in preload {
+        load_file(file, interpreter);
+    }
+
+    println!("rox v0.1.0 - A simple scripting language interpreter");
+    println!("Type ':help' for more information or ':quit' to exit.");
+
+    // REPL 模式错误处理
+    match run_prompt(interpreter) {
+        Ok(Some(code)) => std::process::exit(code),
+        Ok(None) => Ok(()),
+        Err(e) => {
+            eprintln!("REPL Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// REPL 元命令执行后的去向
+enum Command {
+    Continue,
+    Quit,
+}
+
+const REPL_HELP: &str = "\
+Commands:
+  :help          Show this help
+  :env           List the globals defined in this session
+  :load <file>   Run a file in this session, keeping its definitions
+  :clear         Drop everything defined in this session
+  :quit          Exit the REPL (also :q, Ctrl-D)";
+
+/// 执行 `:` 开头的元命令
+fn run_command(line: &str, interpreter: &mut Interpreter) -> Command {
+    let (name, arg) = line
+        .split_once(char::is_whitespace)
+        .map_or((line, ""), |(name, arg)| (name, arg.trim()));
+
+    match name {
+        ":help" => println!("{}", REPL_HELP),
+        ":env" => print_globals(interpreter),
+        ":clear" => {
+            interpreter.reset();
+            println!("Global state cleared.");
+        }
+        ":load" => match arg {
+            "" => eprintln!("Usage: :load file.rox"),
+            file => load_file(file, interpreter),
+        },
+        ":quit" | ":q" => return Command::Quit,
+        other => eprintln!("Unknown command '{}'. Type :help for a list.", other),
+    }
+    Command::Continue
+}
+
+/// `:env`：按名称列出会话中定义的全局变量（不含内置名）
+fn print_globals(interpreter: &Interpreter) {
+    let globals = interpreter.globals.borrow();
+    let mut names = globals.values.keys().collect::<Vec<_>>();
+    if names.is_empty() {
+        println!("No globals defined.");
+        return;
+    }
+    names.sort();
+    for name in names {
+        println!("{} = {}", name, globals.values[name].repr());
+    }
+}
+
+/// `:load file.rox`：在 REPL 的解释器中执行文件，保留其定义的全局变量、函数与类
+fn load_file(file: &str, interpreter: &mut Interpreter) {
+    match interpreter.include_file(file) {
+        Ok(_) => println!("Loaded '{}'.", file),
+        Err(e) => {
+            let source_code = fs::read_to_string(file).unwrap_or_default();
+            print_diagnostic(file, &source_code, &RoxError::Evaluate(e));
+        }
+    }
+}
+
+/// 交互循环，返回脚本通过 `exit(code)` 要求的退出码
+///
+/// 行编辑由 rustyline 提供：方向键移动光标 / 翻阅历史，Ctrl-R 反向搜索历史。
+/// 历史保存在 `~/.rox_history`，连续重复的输入与空格开头的输入不会记录。
+fn run_prompt(interpreter: &mut Interpreter) -> Result<Option<i32>, RoxError> {
+    let config = Config::builder()
+        .max_history_size(HISTORY_SIZE)?
+        .history_ignore_dups(true)?
+        .history_ignore_space(true)
+        .build();
+    let mut rl = Editor::<RoxHelper, DefaultHistory>::with_config(config)?;
+    rl.set_helper(Some(RoxHelper::new(
+        interpreter.globals.clone(),
+        interpreter.registry.clone(),
+    )));
     let history_file = get_history_path();
 
-    // 加载历史记录
-    if rl.load_history(&history_file).is_err() {
-        println!("No previous history.");
+    let mut exit_code = None;
+
+    // 加载历史记录：第一次启动时文件还不存在
+    if history_file.exists()
+        && let Err(e) = rl.load_history(&history_file)
+    {
+        eprintln!("Warning: Failed to load history: {}", e);
     }
 
     loop {
@@ -114,9 +613,16 @@ fn run_prompt(interpreter: &mut Interpreter) -> Result<(), RoxError> {
                 // 添加历史
                 let _ = rl.add_history_entry(line.as_str());
 
+                if line.trim().starts_with(':') {
+                    match run_command(line.trim(), interpreter) {
+                        Command::Continue => continue,
+                        Command::Quit => break,
+                    }
+                }
+
                 // 解释器会消耗 source，如果报错了，需要原始字符串传给 diagnostics
                 let source_code = line.clone();
-                let source = crate::reader::Source { contents: line };
+                let source = rox::reader::Source { contents: line };
 
                 // 执行并捕获错误
                 match run_interpreter_with_state(source, interpreter) {
@@ -125,6 +631,10 @@ fn run_prompt(interpreter: &mut Interpreter) -> Result<(), RoxError> {
                             println!("{}", r);
                         }
                     }
+                    Err(RoxError::Evaluate(RuntimeError::Exit(code))) => {
+                        exit_code = Some(code);
+                        break;
+                    }
                     Err(e) => {
                         // e: RoxError，source_code: clone 的字符串
                         print_diagnostic("<stdin>", &source_code, &e);
@@ -152,22 +662,24 @@ fn run_prompt(interpreter: &mut Interpreter) -> Result<(), RoxError> {
         eprintln!("Warning: Failed to save history: {}", e);
     }
 
-    Ok(())
+    Ok(exit_code)
 }
 
-fn run_interpreter_with_state(
-    source: Source,
-    interpreter: &mut Interpreter,
-) -> Result<Value, RoxError> {
+/// 编译源码：词法分析、语法分析与静态分析 (Resolve)，不执行
+fn compile(source: Source, interpreter: &mut Interpreter) -> Result<Ast, RoxError> {
     // 1. Tokenize
-    let tokens = crate::tokenizer::tokenize(source)?;
+    let tokens = rox::tokenizer::tokenize(source)?;
 
     // 2. Parse
-    let ast = crate::parser::parse(tokens)?;
+    let ast = rox::parser::parse(tokens)?;
 
     // 3. Resolve
     let mut resolver = Resolver::new(interpreter);
-    if let Err(msg) = resolver.resolve_stmts(&ast.body) {
+    let resolved = resolver.resolve_stmts(&ast.body);
+    for warning in &resolver.warnings {
+        eprintln!("Warning: {}", warning);
+    }
+    if let Err(msg) = resolved {
         // 包装 Resolver 错误
         // 这里暂时借用 RuntimeError::Generic
         // TODO: 定义 ResolutionError
@@ -177,12 +689,24 @@ fn run_interpreter_with_state(
         ))));
     }
 
+    Ok(ast)
+}
+
+fn run_interpreter_with_state(
+    source: Source,
+    interpreter: &mut Interpreter,
+) -> Result<Value, RoxError> {
+    let ast = compile(source, interpreter)?;
+
     // 4. Interpret
     let out = interpreter.interpret(ast)?;
 
     Ok(out)
 }
 
+/// REPL 历史最多保留的条数
+const HISTORY_SIZE: usize = 1000;
+
 fn get_history_path() -> PathBuf {
     // 依赖 'dirs' crate
     // 获取用户 Home 目录，eg. /Users/ray
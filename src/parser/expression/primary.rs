@@ -1,7 +1,7 @@
 use crate::{
-    ast::{Expr, ExprId},
+    ast::{Expr, ExprId, Operator},
     parser::{error::Error, parse::ParseHelper},
-    tokenizer::{Literal, TokenType},
+    tokenizer::{Literal, Token, TokenType},
 };
 
 impl ParseHelper {
@@ -24,19 +24,21 @@ impl ParseHelper {
             if self.match_token(&[TokenType::LeftParen]) {
                 // 发现左括号，说明是函数调用，递归解析参数并包装 expr
                 expr = self.finish_call(expr)?;
-            } else if self.match_token(&[TokenType::LeftBracket]) {
-                let bracket = self.previous().clone(); // '['
+            } else if self.match_token(&[TokenType::LeftBracket, TokenType::QuestionBracket]) {
+                let bracket = self.previous().clone(); // '[' 或 '?['
                 let index = self.parse_expression()?; // 解析下标表达式
                 self.consume(TokenType::RightBracket, "Expect ']' after index.")?;
 
                 expr = Expr::GetIndex {
                     id: self.generate_id(),
                     object: Box::new(expr),
+                    optional: bracket.token_type == TokenType::QuestionBracket,
                     bracket,
                     index: Box::new(index),
                 };
-            } else if self.match_token(&[TokenType::Dot]) {
+            } else if self.match_token(&[TokenType::Dot, TokenType::QuestionDot]) {
                 // “.” 的优先级和函数调用 (func()) 一样高
+                let optional = self.previous().token_type == TokenType::QuestionDot;
                 let name = self
                     .consume(TokenType::Identifier, "Expect property name after '.'.")?
                     .clone();
@@ -44,6 +46,7 @@ impl ParseHelper {
                 expr = Expr::Get {
                     object: Box::new(expr),
                     name,
+                    optional,
                 };
             } else {
                 break;
@@ -58,7 +61,8 @@ impl ParseHelper {
     ///
     /// # 逻辑流程
     /// 1. 检查是否直接遇到 `)` (无参调用)。
-    /// 2. 如果有参数，循环解析表达式，并处理逗号分隔符。
+    /// 2. 如果有参数，循环解析表达式，并处理逗号分隔符；`name = expr` 形式解析为关键字参数，
+    ///    关键字参数之后不能再出现位置参数，同名关键字参数只能出现一次。
     /// 3. 检查参数数量是否超过 255 (但只报错不停止)。
     /// 4. 消耗右括号 `)`。
     /// 5. 将传入的 `callee` 和解析出的 `args` 包装成一个新的 `Expr::Call` 节点。
@@ -67,18 +71,38 @@ impl ParseHelper {
     /// * `callee` - 被调用的表达式（即左括号左边的部分）
     pub fn finish_call(&mut self, callee: Expr) -> Result<Expr, Error> {
         let mut args = Vec::new();
+        let mut named: Vec<(Token, Expr)> = Vec::new();
 
         // 如果不是立即遇到右括号，说明有参数
         if !self.check(TokenType::RightParen) {
             loop {
                 // 限制参数数量，通常只是一个非强制性限制
-                if args.len() >= 255 {
+                if args.len() + named.len() >= 255 {
                     // 报告错误但不中断解析
                     let _ = self.error(self.peek(), "Can't have more than 255 arguments.");
                 }
 
-                // 解析参数表达式
-                args.push(self.parse_expression()?);
+                if self.check(TokenType::Identifier) && self.check_ahead(1, TokenType::Equal) {
+                    // 关键字参数 `name = expr`
+                    let name = self.advance().clone();
+                    self.advance();
+                    if named.iter().any(|(n, _)| n.lexeme == name.lexeme) {
+                        return Err(self.error(
+                            &name,
+                            &format!("Duplicate keyword argument '{}'.", name.lexeme),
+                        ));
+                    }
+                    let value = self.parse_expression()?;
+                    named.push((name, value));
+                } else if !named.is_empty() {
+                    return Err(self.error(
+                        self.peek(),
+                        "Positional argument cannot follow keyword argument.",
+                    ));
+                } else {
+                    // 解析参数表达式
+                    args.push(self.parse_expression()?);
+                }
 
                 // 如果没有逗号，说明参数列表结束
                 if !self.match_token(&[TokenType::Comma]) {
@@ -95,6 +119,7 @@ impl ParseHelper {
             id: self.generate_id(), // 为这次调用分配唯一 ID (用于 Resolver)
             callee: Box::new(callee),
             args,
+            named,
         })
     }
 
@@ -128,6 +153,13 @@ impl ParseHelper {
             };
             return Ok(Expr::Number { value });
         }
+        if self.match_token(&[TokenType::BigInt]) {
+            let value = match &self.previous().literal {
+                Literal::BigInt(digits) => digits.clone(),
+                _ => "0".to_string(),
+            };
+            return Ok(Expr::BigInt { value });
+        }
         if self.match_token(&[TokenType::String]) {
             let value = match &self.previous().literal {
                 Literal::String(s) => s.clone(),
@@ -135,6 +167,9 @@ impl ParseHelper {
             };
             return Ok(Expr::String { value });
         }
+        if self.match_token(&[TokenType::Interpolation]) {
+            return self.parse_interpolation();
+        }
         if self.match_token(&[TokenType::Super]) {
             let keyword = self.previous().clone();
             self.consume(TokenType::Dot, "Expect '.' after 'super'.")?;
@@ -164,8 +199,26 @@ impl ParseHelper {
         if self.match_token(&[TokenType::Fun]) {
             return self.parse_lambda();
         }
+        // 简写匿名函数：`|x| x * 2`，无参数时 `||` 被扫描为一个 token
+        if self.match_token(&[TokenType::Pipe, TokenType::LogicalOr]) {
+            return self.parse_arrow_lambda();
+        }
         if self.match_token(&[TokenType::LeftParen]) {
             let expr = self.parse_expression()?;
+
+            // 逗号分隔的多个表达式是元组字面量：`(1, 2)`、`(x,)`
+            if self.match_token(&[TokenType::Comma]) {
+                let mut elements = vec![expr];
+                while !self.check(TokenType::RightParen) {
+                    elements.push(self.parse_expression()?);
+                    if !self.match_token(&[TokenType::Comma]) {
+                        break;
+                    }
+                }
+                self.consume(TokenType::RightParen, "Expected ')' after tuple elements.")?;
+                return Ok(Expr::Tuple { elements });
+            }
+
             self.consume(TokenType::RightParen, "Expected ')' after expression.")?;
             return Ok(Expr::Grouping {
                 expr: Box::new(expr),
@@ -186,15 +239,52 @@ impl ParseHelper {
         )))
     }
 
+    /// 解析字符串插值
+    ///
+    /// 扫描器把 `"a ${x} b ${y}"` 拆分为 `Interpolation("a ") x Interpolation(" b ") y String("")`，
+    /// 这里脱糖为以字符串开头的左结合拼接 `"a " + x + " b " + y`，
+    /// 左操作数始终是字符串，因此 `+` 会把每个插值的结果转换为字符串。
+    fn parse_interpolation(&mut self) -> Result<Expr, Error> {
+        let mut expr = Expr::String {
+            value: self.previous_text(),
+        };
+
+        loop {
+            let value = self.parse_expression()?;
+            expr = Expr::binary(Operator::Add, expr, value);
+
+            let done = !self.match_token(&[TokenType::Interpolation]);
+            if done {
+                self.consume(TokenType::String, "Expect '}' after interpolation.")?;
+            }
+
+            let text = self.previous_text();
+            if !text.is_empty() {
+                expr = Expr::binary(Operator::Add, expr, Expr::String { value: text });
+            }
+            if done {
+                return Ok(expr);
+            }
+        }
+    }
+
+    /// 上一个字符串 / 插值标记中的文本
+    fn previous_text(&self) -> String {
+        match &self.previous().literal {
+            Literal::String(s) => s.clone(),
+            _ => "".to_string(),
+        }
+    }
+
     /// Generate Unique ExprId
     ///
     /// # 用途
     /// Resolver (语义分析器) 使用此 ID 来区分源代码中不同位置的同一个变量名。
     /// eg. 在 `var a = a;` 中，左边和右边的 `a` 拥有不同的 ID，
     /// Resolver 可以据此判断右边的 `a` 是否引用了未初始化的左边的 `a`。
+    ///
+    /// ID 由 `ExprId::fresh` 在进程范围内分配，跨多次解析也不会重复。
     pub fn generate_id(&mut self) -> ExprId {
-        let id = self.next_id;
-        self.next_id += 1;
-        ExprId(id)
+        ExprId::fresh()
     }
 }
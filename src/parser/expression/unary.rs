@@ -18,7 +18,7 @@ impl ParseHelper {
             // Note：
             // 一元运算符的操作数是 unary 自身（eg: -1、!var、!!true）或更高优先级的项
             // 所以不能用 parse_expression，否则会吞掉后续的二元运算
-            let right = self.parse_unary()?;
+            let right = self.nested(Self::parse_unary)?;
             return Ok(Expr::Unary {
                 op,
                 expr: Box::new(right),
@@ -6,7 +6,7 @@ pub mod statement;
 
 // 导出 parser 模块中的 Error 类型和 parse 函数
 pub use error::Error;
-pub use parse::parse;
+pub use parse::{SourceLines, parse, parse_all, parse_with_lines};
 
 #[cfg(test)]
 mod tests;
@@ -42,18 +42,35 @@ pub struct ParseHelper {
     ///   如果 `func_depth == 0` (顶层代码)，使用 `return` 应报错。
     pub func_depth: usize,
 
-    // next_id: 静态分析，为 Resolver 和 Interpreter 提供正确处理闭包和变量遮蔽的条件。
-    /// Unique ID Generator 计数器
-    ///
-    /// 用于生成全局唯一的 `ExprId`。
-    /// - 每次创建一个涉及变量引用 (`Variable`, `Assign`, `Call`) 的 AST 节点时，
-    ///   该计数器自增并分配 ID。
-    /// - **用途**：**Resolver** (语义分析器) 需要利用这个 ID 来区分源代码中
-    ///   不同的变量访问位置，以便在 `Interpreter` Side Table (`locals`) 中存储
-    ///   对应的作用域跳跃距离 (Distance)。
-    pub next_id: usize,
+    /// Nesting Depth
+    ///
+    /// 记录当前递归下降的嵌套层数（表达式、语句、一元运算）。
+    /// - **用途**：超过 `MAX_NESTING` 时报错，避免病态输入（eg. 上万层括号）耗尽调用栈。
+    pub nesting_depth: usize,
+
+    /// 语句与代码块的源码行号，只供格式化工具使用
+    pub lines: SourceLines,
+
+    /// 错误恢复模式下收集的错误（`parse_all`）；为 `None` 时遇到第一个错误即返回
+    pub errors: Option<Vec<Error>>,
+}
+
+/// 语句与代码块在源码中的行号，供格式化工具 (`rox fmt`) 把注释放回原处
+///
+/// 两个列表都按开始位置的先后（即语法树的先序）排列。
+#[derive(Debug, Default)]
+pub struct SourceLines {
+    /// 每次 `parse_statement` 解析的语句的 (起始行, 结束行)
+    pub statements: Vec<(usize, usize)>,
+    /// 每个 `parse_block` 解析的代码块的结束行 (`}` 所在行)
+    pub blocks: Vec<usize>,
+    /// 类体中的方法依次是否为静态方法（静态方法与实例方法在语法树中分开存放）
+    pub statics: Vec<bool>,
 }
 
+/// 语法嵌套的最大层数
+pub const MAX_NESTING: usize = 256;
+
 impl ParseHelper {
     //  (Helper Methods)
 
@@ -113,6 +130,22 @@ impl ParseHelper {
         self.peek().token_type == token_type
     }
 
+    /// 向前查看第 `offset` 个 Token 的类型，但**不消耗**
+    ///
+    /// 用于需要多个 Token 才能确定语法分支的场景（eg. 区分 `for (;;)` 与 `for (x in xs)`）。
+    ///
+    /// # 参数
+    /// * `offset` - 相对当前指针的偏移，`0` 等价于 `peek()`
+    ///
+    /// # 返回值
+    /// * `bool` - 如果类型匹配返回 true，越界时返回 false
+    pub fn check_ahead(&self, offset: usize, token_type: TokenType) -> bool {
+        self.tokens
+            .tokens
+            .get(self.index + offset)
+            .is_some_and(|t| t.token_type == token_type)
+    }
+
     /// 匹配并消耗 Token
     ///
     /// 检查当前 Token 是否属于给定的类型列表中的任意一种。
@@ -187,17 +220,32 @@ impl ParseHelper {
         }
     }
 
+    /// 进入一层嵌套后执行 `parse`，超过 `MAX_NESTING` 时报错
+    pub fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        if self.nesting_depth >= MAX_NESTING {
+            return Err(self.error(self.peek(), "Too deeply nested."));
+        }
+
+        self.nesting_depth += 1;
+        let result = parse(self);
+        self.nesting_depth -= 1;
+        result
+    }
+
     //  (Expression Parsing) - 按优先级从低到高
 
     /// 解析入口
     pub fn parse_expression(&mut self) -> Result<Expr, Error> {
-        self.parse_assignment()
+        self.nested(Self::parse_assignment)
     }
 
     /// 赋值 (Assignment): variable = value
     pub fn parse_assignment(&mut self) -> Result<Expr, Error> {
-        // 优先级：乘除 > 加减 > 位与 > 位或 > 比较 > 相等 > 逻辑与 > 逻辑或 > 赋值
-        let expr = self.parse_or()?;
+        // 优先级：乘除 > 加减 > 位与 > 位或 > 比较 > 相等 > 逻辑与 > 逻辑或 > 空值合并 > 赋值
+        let expr = self.parse_coalesce()?;
 
         if self.match_token(&[
             TokenType::Equal,
@@ -210,7 +258,7 @@ impl ParseHelper {
             // 导致 self.previous() 变成右值表达式的最后一个 Token，而不是操作符。
             let operator_token = self.previous().clone();
 
-            let value = self.parse_assignment()?; // 递归解析右值
+            let value = self.parse_expression()?; // 递归解析右值
 
             match expr {
                 // 检查左值是否合法
@@ -253,7 +301,11 @@ impl ParseHelper {
 
                 // 对象属性赋值 (Set：赋值行为)
                 // 如果左值是一个 Get 表达式 (a.b)，转换为 Set 表达式 (a.b = value)
-                Expr::Get { object, name } => {
+                Expr::Get {
+                    object,
+                    name,
+                    optional: false,
+                } => {
                     match operator_token.token_type {
                         TokenType::Equal => Ok(Expr::Set {
                             object,
@@ -272,6 +324,7 @@ impl ParseHelper {
                     object,
                     index,
                     bracket,
+                    optional: false,
                     ..
                 } => {
                     match operator_token.token_type {
@@ -294,6 +347,17 @@ impl ParseHelper {
                     }
                 }
 
+                // 解构赋值：`(a, b) = (b, a)`、`[x, y] = list`，模式中只能是变量
+                Expr::Tuple { ref elements } | Expr::List { ref elements }
+                    if operator_token.token_type == TokenType::Equal
+                        && elements.iter().all(|e| matches!(e, Expr::Variable { .. })) =>
+                {
+                    Ok(Expr::AssignDestructure {
+                        pattern: Box::new(expr),
+                        value: Box::new(value),
+                    })
+                }
+
                 // 报错时使用 operator_token 定位，指向操作符位置更准确
                 // 对象属性赋值 (Set)
                 // 如果左值是一个 Get 表达式 (例如 a.b)，将其转换为 Set 表达式 (a.b = value)
@@ -305,6 +369,21 @@ impl ParseHelper {
         }
     }
 
+    /// 空值合并 `a ?? b`：左侧为 nil 时才求值并返回右侧
+    pub fn parse_coalesce(&mut self) -> Result<Expr, Error> {
+        let mut expr = self.parse_or()?;
+
+        while self.match_token(&[TokenType::QuestionQuestion]) {
+            let right = self.parse_or()?;
+            expr = Expr::Logical {
+                left: Box::new(expr),
+                op: Operator::NullCoalesce,
+                right: Box::new(right),
+            };
+        }
+        Ok(expr)
+    }
+
     /// OR
     pub fn parse_or(&mut self) -> Result<Expr, Error> {
         let mut expr = self.parse_and()?;
@@ -353,6 +432,15 @@ impl ParseHelper {
     /// * `Ok(Expr)` - 解析得到的表达式
     /// * `Err(Error)` - 解析过程中发生的错误
     pub fn parse_statement(&mut self) -> Result<Stmt, Error> {
+        let index = self.lines.statements.len();
+        self.lines.statements.push((self.peek().line, 0));
+        let stmt = self.nested(Self::parse_statement_kind)?;
+        self.lines.statements[index].1 = self.previous().line;
+        Ok(stmt)
+    }
+
+    /// 按起始关键字分派到具体的语句解析
+    fn parse_statement_kind(&mut self) -> Result<Stmt, Error> {
         if self.match_token(&[TokenType::If]) {
             return self.parse_if_statement();
         }
@@ -374,6 +462,12 @@ impl ParseHelper {
         if self.match_token(&[TokenType::Class]) {
             return self.parse_class_declaration();
         }
+        if self.match_token(&[TokenType::Trait]) {
+            return self.parse_trait_declaration();
+        }
+        if self.match_token(&[TokenType::Extend]) {
+            return self.parse_extend_declaration();
+        }
         // 允许空语句: ";", "for(;;);", "{ ; }"
         if self.match_token(&[TokenType::Semicolon]) {
             return Ok(Stmt::Empty);
@@ -385,9 +479,20 @@ impl ParseHelper {
         if self.match_token(&[TokenType::Return]) {
             return self.parse_return_statement();
         }
+        if self.match_token(&[TokenType::Yield]) {
+            return self.parse_yield_statement();
+        }
+        if self.match_token(&[TokenType::Defer]) {
+            return self.parse_defer_statement();
+        }
         if self.match_token(&[TokenType::For]) {
             return self.parse_for_statement();
         }
+        // 标签只能出现在循环前：`outer: for (...) {...}`
+        if self.check(TokenType::Identifier) && self.check_ahead(1, TokenType::Colon) {
+            self.advance();
+            return self.parse_labeled_statement();
+        }
         if self.match_token(&[TokenType::Break]) {
             return self.parse_break_statement();
         }
@@ -400,6 +505,15 @@ impl ParseHelper {
         if self.match_token(&[TokenType::Export]) {
             return self.parse_export_statement();
         }
+        // `import` 不是关键字：后面紧跟模块名或路径时是导入语句，否则是对 import() 的调用
+        if self.check(TokenType::Identifier)
+            && self.peek().lexeme == "import"
+            && (self.check_ahead(1, TokenType::Identifier)
+                || self.check_ahead(1, TokenType::String))
+        {
+            self.advance();
+            return self.parse_import_statement();
+        }
         // 解析表达式语句（以分号结尾的表达式）
         self.parse_expression_statement()
     }
@@ -409,7 +523,7 @@ impl ParseHelper {
         let mut elements = Vec::new();
 
         // 如果不是空列表
-        if !self.check(TokenType::LeftBracket) {
+        if !self.check(TokenType::RightBracket) {
             loop {
                 // 解析元素
                 elements.push(self.parse_expression()?);
@@ -431,7 +545,7 @@ impl ParseHelper {
         let mut elements = Vec::new();
 
         // 如果不是空字典
-        if !self.check(TokenType::LeftBrace) {
+        if !self.check(TokenType::RightBrace) {
             loop {
                 let key = self.parse_expression()?;
 
@@ -475,10 +589,56 @@ impl ParseHelper {
     pub fn parse_program(&mut self) -> Result<Vec<Stmt>, Error> {
         let mut statements = Vec::new();
         while !self.is_at_end() {
-            statements.push(self.parse_statement()?);
+            let start = self.index;
+            match self.parse_statement() {
+                Ok(stmt) => statements.push(stmt),
+                Err(e) => self.recover(e, start)?,
+            }
         }
         Ok(statements)
     }
+
+    /// 语句解析失败后的处理
+    ///
+    /// 非恢复模式下原样返回错误；恢复模式下记录错误并跳到下一条语句的开头，
+    /// 使同一代码块中后续的语句仍能被解析。
+    ///
+    /// # 参数
+    /// * `start` - 出错语句开始时的 token 位置
+    pub fn recover(&mut self, error: Error, start: usize) -> Result<(), Error> {
+        let Some(errors) = self.errors.as_mut() else {
+            return Err(error);
+        };
+        errors.push(error);
+
+        // 保证每次恢复至少前进一个 token，顶层多余的 `}` 不会导致死循环
+        if self.index == start && !self.is_at_end() {
+            self.advance();
+        }
+        // 丢弃 token，直到上一个 token 是 `;`，或下一个 token 是语句关键字或 `}`
+        while !self.is_at_end() && self.previous().token_type != TokenType::Semicolon {
+            match self.peek().token_type {
+                TokenType::Class
+                | TokenType::Fun
+                | TokenType::Var
+                | TokenType::For
+                | TokenType::If
+                | TokenType::While
+                | TokenType::Print
+                | TokenType::Return
+                | TokenType::Try
+                | TokenType::Throw
+                | TokenType::Trait
+                | TokenType::Extend
+                | TokenType::Yield
+                | TokenType::Defer
+                | TokenType::Export
+                | TokenType::RightBrace => return Ok(()),
+                _ => self.advance(),
+            };
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -501,7 +661,9 @@ impl Parser {
                 index: 0,
                 loop_depth: 0,
                 func_depth: 0,
-                next_id: 0,
+                nesting_depth: 0,
+                lines: SourceLines::default(),
+                errors: None,
             },
         }
     }
@@ -523,7 +685,30 @@ impl Parser {
 /// # 返回值
 /// * `Result<AST, Error>` - 解析得到的抽象语法树或错误
 pub fn parse(tokens: Tokens) -> Result<Ast, Error> {
+    parse_with_lines(tokens).map(|(ast, _)| ast)
+}
+
+/// 解析整个程序并收集全部语法错误（供 `rox check` 使用）
+///
+/// 出错的语句会被跳过，解析从下一条语句继续，因此后续的错误也能被报告；
+/// 之前错误的连带错误（eg. 缺少 `}` 导致的错误）也可能出现在结果中。
+///
+/// # 返回值
+/// * `Ok(Ast)` - 没有语法错误
+/// * `Err(Vec<Error>)` - 按出现顺序排列的全部错误
+pub fn parse_all(tokens: Tokens) -> Result<Ast, Vec<Error>> {
+    let mut parser = Parser::new(tokens);
+    parser.helper.errors = Some(Vec::new());
+    let statements = parser.parse_program().map_err(|e| vec![e])?;
+    match parser.helper.errors {
+        Some(errors) if !errors.is_empty() => Err(errors),
+        _ => Ok(Ast { body: statements }),
+    }
+}
+
+/// 解析并返回语句与代码块的源码行号（见 `SourceLines`）
+pub fn parse_with_lines(tokens: Tokens) -> Result<(Ast, SourceLines), Error> {
     let mut parser = Parser::new(tokens);
     let statements = parser.parse_program()?;
-    Ok(Ast { body: statements })
+    Ok((Ast { body: statements }, parser.helper.lines))
 }
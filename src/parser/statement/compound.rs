@@ -9,12 +9,19 @@ impl ParseHelper {
     /// 解析代码块语句
     pub fn parse_block(&mut self) -> Result<Vec<Stmt>, Error> {
         let mut statements = Vec::new();
+        let index = self.lines.blocks.len();
+        self.lines.blocks.push(0);
 
         while !self.check(TokenType::RightBrace) && !self.is_at_end() {
-            statements.push(self.parse_statement()?);
+            let start = self.index;
+            match self.parse_statement() {
+                Ok(stmt) => statements.push(stmt),
+                Err(e) => self.recover(e, start)?,
+            }
         }
 
         self.consume(TokenType::RightBrace, "Expect '}' after block.")?;
+        self.lines.blocks[index] = self.previous().line;
         Ok(statements)
     }
 
@@ -43,4 +50,35 @@ impl ParseHelper {
 
         Ok(Stmt::Return { keyword, value })
     }
+
+    /// 解析 yield 语句：`yield expr;` 或 `yield;`（产出 nil）
+    pub fn parse_yield_statement(&mut self) -> Result<Stmt, Error> {
+        let keyword = self.previous().clone();
+
+        if self.func_depth == 0 {
+            self.error(&keyword, "Cannot yield from top-level code.");
+        }
+
+        let value = if !self.check(TokenType::Semicolon) {
+            Some(self.parse_expression()?)
+        } else {
+            None
+        };
+
+        self.consume(TokenType::Semicolon, "Expect ';' after yield value.")?;
+
+        Ok(Stmt::Yield { keyword, value })
+    }
+
+    /// 解析 defer 语句：`defer expr;`
+    pub fn parse_defer_statement(&mut self) -> Result<Stmt, Error> {
+        let keyword = self.previous().clone();
+        let expr = self.parse_expression()?;
+        self.consume(
+            TokenType::Semicolon,
+            "Expect ';' after deferred expression.",
+        )?;
+
+        Ok(Stmt::Defer { keyword, expr })
+    }
 }
@@ -1,7 +1,7 @@
 use crate::{
     ast::Stmt,
     parser::{error::Error, parse::ParseHelper},
-    tokenizer::TokenType,
+    tokenizer::{Token, TokenType},
 };
 
 // 处理控制语句行为
@@ -56,6 +56,15 @@ impl ParseHelper {
     pub fn parse_for_statement(&mut self) -> Result<Stmt, Error> {
         self.consume(TokenType::LeftParen, "Expect '(' after 'for'.")?;
 
+        // for (k in xs) / for (var k in xs) / for (k, v in xs)
+        let offset = usize::from(self.check(TokenType::Var));
+        if self.check_ahead(offset, TokenType::Identifier)
+            && (self.check_ahead(offset + 1, TokenType::In)
+                || self.check_ahead(offset + 1, TokenType::Comma))
+        {
+            return self.parse_for_in_statement();
+        }
+
         let initializer = if self.match_token(&[TokenType::Semicolon]) {
             None // for (;...)
         } else if self.match_token(&[TokenType::Var]) {
@@ -94,22 +103,87 @@ impl ParseHelper {
         })
     }
 
-    /// 解析 break 语句
+    /// 解析 for-in 循环语句
+    ///
+    /// 语法: "for" "(" "var"? IDENTIFIER ( "," IDENTIFIER )? "in" expression ")" statement
+    pub fn parse_for_in_statement(&mut self) -> Result<Stmt, Error> {
+        self.match_token(&[TokenType::Var]); // var 可选，循环变量总是新的局部变量
+
+        let mut vars = vec![
+            self.consume(TokenType::Identifier, "Expect loop variable name.")?
+                .clone(),
+        ];
+        if self.match_token(&[TokenType::Comma]) {
+            vars.push(
+                self.consume(
+                    TokenType::Identifier,
+                    "Expect loop variable name after ','.",
+                )?
+                .clone(),
+            );
+        }
+
+        self.consume(TokenType::In, "Expect 'in' after loop variables.")?;
+        let iterable = self.parse_expression()?;
+        self.consume(TokenType::RightParen, "Expect ')' after for-in clause.")?;
+
+        self.loop_depth += 1;
+        let body_result = self.parse_statement();
+        self.loop_depth -= 1;
+
+        let body = body_result?;
+        Ok(Stmt::ForIn {
+            vars,
+            iterable,
+            body: Box::new(body),
+        })
+    }
+
+    /// 解析带标签的循环
+    ///
+    /// 语法: IDENTIFIER ":" ( while_stmt | for_stmt )
+    pub fn parse_labeled_statement(&mut self) -> Result<Stmt, Error> {
+        let label = self.previous().clone();
+        self.consume(TokenType::Colon, "Expect ':' after label.")?;
+
+        let body = if self.match_token(&[TokenType::While]) {
+            self.parse_while_statement()?
+        } else if self.match_token(&[TokenType::For]) {
+            self.parse_for_statement()?
+        } else {
+            return Err(self.error(self.peek(), "Expect a loop after label."));
+        };
+
+        Ok(Stmt::Labeled {
+            label,
+            body: Box::new(body),
+        })
+    }
+
+    /// 解析 break 语句，可以带上外层循环的标签：`break outer;`
     pub fn parse_break_statement(&mut self) -> Result<Stmt, Error> {
         if self.loop_depth == 0 {
             return Err(self.error(self.previous(), "Cannot use 'break' outside of a loop."));
         }
 
+        let label = self.parse_jump_label();
         self.consume(TokenType::Semicolon, "Expect ';' after 'break'.")?;
-        Ok(Stmt::Break)
+        Ok(Stmt::Break { label })
     }
 
-    /// 解析 continue 语句
+    /// 解析 continue 语句，可以带上外层循环的标签：`continue outer;`
     pub fn parse_continue_statement(&mut self) -> Result<Stmt, Error> {
         if self.loop_depth == 0 {
             return Err(self.error(self.previous(), "Cannot use 'continue' outside of a loop."));
         }
-        self.consume(TokenType::Semicolon, "Expect ';' after 'break'.")?;
-        Ok(Stmt::Continue)
+
+        let label = self.parse_jump_label();
+        self.consume(TokenType::Semicolon, "Expect ';' after 'continue'.")?;
+        Ok(Stmt::Continue { label })
+    }
+
+    fn parse_jump_label(&mut self) -> Option<Token> {
+        self.match_token(&[TokenType::Identifier])
+            .then(|| self.previous().clone())
     }
 }
@@ -1,13 +1,18 @@
 use crate::{
     ast::{Expr, Stmt},
     parser::{error::Error, parse::ParseHelper},
-    tokenizer::{Token, TokenType},
+    tokenizer::{Literal, Token, TokenType},
 };
+use std::path::Path;
 
 // 声明语句（变量声明、函数声明）
 impl ParseHelper {
     /// 解析变量声明语句
     pub fn parse_var_declaration(&mut self) -> Result<Stmt, Error> {
+        if self.match_token(&[TokenType::LeftParen, TokenType::LeftBracket]) {
+            return self.parse_var_destructure();
+        }
+
         let name_token = self.consume(TokenType::Identifier, "Expect variable name.")?;
         let name = name_token.clone();
 
@@ -25,6 +30,51 @@ impl ParseHelper {
         Ok(Stmt::VarDecl { name, initializer })
     }
 
+    /// 解析解构声明
+    ///
+    /// 语法: "var" ( "(" names ")" | "[" names "]" ) "=" expression ";"
+    /// 其中 names 为逗号分隔的标识符，右值必须是元素个数相同的 List 或 Tuple。
+    fn parse_var_destructure(&mut self) -> Result<Stmt, Error> {
+        let is_list = self.previous().token_type == TokenType::LeftBracket;
+        let close = if is_list {
+            TokenType::RightBracket
+        } else {
+            TokenType::RightParen
+        };
+
+        let mut names = Vec::new();
+        loop {
+            let name = self
+                .consume(TokenType::Identifier, "Expect variable name in pattern.")?
+                .clone();
+            names.push(Expr::Variable {
+                id: self.generate_id(),
+                name,
+            });
+            if !self.match_token(&[TokenType::Comma]) || self.check(close) {
+                break;
+            }
+        }
+        self.consume(close, "Expect closing bracket after destructuring pattern.")?;
+
+        self.consume(TokenType::Equal, "Expect '=' after destructuring pattern.")?;
+        let initializer = self.parse_expression()?;
+        self.consume(
+            TokenType::Semicolon,
+            "Expect ';' after variable declaration.",
+        )?;
+
+        let pattern = if is_list {
+            Expr::List { elements: names }
+        } else {
+            Expr::Tuple { elements: names }
+        };
+        Ok(Stmt::VarDestructure {
+            pattern,
+            initializer,
+        })
+    }
+
     /// 解析函数声明语句
     pub fn parse_function_declaration(&mut self) -> Result<Stmt, Error> {
         self.parse_function("function")
@@ -35,22 +85,99 @@ impl ParseHelper {
             .consume(TokenType::Identifier, &format!("Expect {} name.", kind))?
             .clone();
 
-        let (params, body) = self.parse_function_params_and_body(kind)?;
+        let (params, rest, body) = self.parse_function_params_and_body(kind)?;
 
-        Ok(Stmt::Function { name, params, body })
+        Ok(Stmt::Function {
+            name,
+            params,
+            rest,
+            body,
+        })
     }
 
+    /// 解析匿名函数
+    ///
+    /// 语法: "fun" ( "[" IDENTIFIER ( "," IDENTIFIER )* "]" )? "(" parameters? ")" block
+    ///
+    /// 方括号中的变量在创建函数时按值捕获（快照），其余变量仍按引用捕获外层环境。
     pub fn parse_lambda(&mut self) -> Result<Expr, Error> {
         // !! 'fun' 在 parse_primary 中被 match 消耗了
-        let (params, body) = self.parse_function_params_and_body("lambda")?;
+        let mut captures = Vec::new();
+        if self.match_token(&[TokenType::LeftBracket]) {
+            loop {
+                let name = self
+                    .consume(
+                        TokenType::Identifier,
+                        "Expect variable name in capture list.",
+                    )?
+                    .clone();
+                captures.push(Expr::Variable {
+                    id: self.generate_id(),
+                    name,
+                });
+                if !self.match_token(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+            self.consume(TokenType::RightBracket, "Expect ']' after capture list.")?;
+        }
+
+        let (params, rest, body) = self.parse_function_params_and_body("lambda")?;
 
         Ok(Expr::Lambda {
             id: self.generate_id(),
+            captures,
             params,
+            rest,
             body,
         })
     }
 
+    /// 解析简写匿名函数
+    ///
+    /// 语法: "|" parameters? "|" expression，或无参数的 "||" expression
+    ///
+    /// 与 `fun (x) = expr` 一样生成 Lambda 节点，函数体是返回该表达式的 return 语句。
+    pub fn parse_arrow_lambda(&mut self) -> Result<Expr, Error> {
+        let mut params = Vec::new();
+        if self.previous().token_type == TokenType::Pipe {
+            if !self.check(TokenType::Pipe) {
+                loop {
+                    params.push(
+                        self.consume(TokenType::Identifier, "Expect parameter name.")?
+                            .clone(),
+                    );
+                    if !self.match_token(&[TokenType::Comma]) {
+                        break;
+                    }
+                }
+            }
+            self.consume(TokenType::Pipe, "Expect '|' after lambda parameters.")?;
+        }
+
+        let keyword = self.previous().clone();
+        let previous_func_depth = self.func_depth;
+        let previous_loop_depth = self.loop_depth;
+        self.func_depth += 1;
+        self.loop_depth = 0;
+
+        let value = self.parse_expression();
+
+        self.func_depth = previous_func_depth;
+        self.loop_depth = previous_loop_depth;
+
+        Ok(Expr::Lambda {
+            id: self.generate_id(),
+            captures: Vec::new(),
+            params,
+            rest: false,
+            body: vec![Stmt::Return {
+                keyword,
+                value: Some(value?),
+            }],
+        })
+    }
+
     pub fn parse_class_declaration(&mut self) -> Result<Stmt, Error> {
         let name = self
             .consume(TokenType::Identifier, "Expect class name.")?
@@ -67,11 +194,31 @@ impl ParseHelper {
             None
         };
 
+        let mut traits = Vec::new();
+        if self.match_token(&[TokenType::Implements]) {
+            loop {
+                traits.push(
+                    self.consume(TokenType::Identifier, "Expect trait name.")?
+                        .clone(),
+                );
+                if !self.match_token(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+
         self.consume(TokenType::LeftBrace, "Expect '{' before class body.")?;
 
         let mut methods = Vec::new();
+        let mut static_methods = Vec::new();
         while !self.check(TokenType::RightBrace) && !self.is_at_end() {
-            methods.push(self.parse_function("method")?);
+            let is_static = self.match_token(&[TokenType::Static]);
+            self.lines.statics.push(is_static);
+            if is_static {
+                static_methods.push(self.parse_function("static method")?);
+            } else {
+                methods.push(self.parse_function("method")?);
+            }
         }
 
         self.consume(TokenType::RightBrace, "Expect '}' after class body.")?;
@@ -80,20 +227,90 @@ impl ParseHelper {
             name,
             superclass,
             methods,
+            static_methods,
+            traits,
         })
     }
 
+    /// 解析 trait 声明
+    ///
+    /// 语法: "trait" IDENTIFIER "{" ( IDENTIFIER "(" parameters? ")" ";" )* "}"
+    pub fn parse_trait_declaration(&mut self) -> Result<Stmt, Error> {
+        let name = self
+            .consume(TokenType::Identifier, "Expect trait name.")?
+            .clone();
+
+        self.consume(TokenType::LeftBrace, "Expect '{' before trait body.")?;
+
+        let mut methods = Vec::new();
+        while !self.check(TokenType::RightBrace) && !self.is_at_end() {
+            let method = self
+                .consume(TokenType::Identifier, "Expect method name.")?
+                .clone();
+            self.consume(TokenType::LeftParen, "Expect '(' after method name.")?;
+
+            let mut params = Vec::new();
+            if !self.check(TokenType::RightParen) {
+                loop {
+                    params.push(
+                        self.consume(TokenType::Identifier, "Expect parameter name.")?
+                            .clone(),
+                    );
+                    if !self.match_token(&[TokenType::Comma]) {
+                        break;
+                    }
+                }
+            }
+            self.consume(TokenType::RightParen, "Expect ')' after parameters.")?;
+            self.consume(
+                TokenType::Semicolon,
+                "Expect ';' after trait method signature.",
+            )?;
+
+            methods.push((method, params));
+        }
+
+        self.consume(TokenType::RightBrace, "Expect '}' after trait body.")?;
+
+        Ok(Stmt::Trait { name, methods })
+    }
+
+    /// 解析内置类型扩展声明
+    ///
+    /// 语法: "extend" IDENTIFIER "{" function* "}"
+    pub fn parse_extend_declaration(&mut self) -> Result<Stmt, Error> {
+        let target = self
+            .consume(TokenType::Identifier, "Expect type name after 'extend'.")?
+            .clone();
+
+        self.consume(TokenType::LeftBrace, "Expect '{' before extend body.")?;
+
+        let mut methods = Vec::new();
+        while !self.check(TokenType::RightBrace) && !self.is_at_end() {
+            methods.push(self.parse_function("method")?);
+        }
+
+        self.consume(TokenType::RightBrace, "Expect '}' after extend body.")?;
+
+        Ok(Stmt::Extend { target, methods })
+    }
+
     /// 辅助方法：解析函数的参数列表和函数体
     ///
     /// # 参数
     /// * `kind` - 函数类型描述（如 "function" 或 "lambda"），用于生成错误信息
     ///
+    /// 最后一个参数可以写成剩余参数 `...args`，调用时多余的实参被收集到一个列表中。
+    ///
+    /// 函数体也可以写成 `= 表达式`（`fun double(x) = x * 2;`），等价于 `{ return 表达式; }`；
+    /// 具名函数与方法以 `;` 结尾，匿名函数不需要。
+    ///
     /// # 返回值
-    /// * `Ok((Vec<Token>, Vec<Stmt>))` - 返回解析出的 (参数列表, 函数体语句)
+    /// * `Ok((Vec<Token>, bool, Vec<Stmt>))` - 返回解析出的 (参数列表, 是否有剩余参数, 函数体语句)
     fn parse_function_params_and_body(
         &mut self,
         kind: &str,
-    ) -> Result<(Vec<Token>, Vec<Stmt>), Error> {
+    ) -> Result<(Vec<Token>, bool, Vec<Stmt>), Error> {
         // 1. 解析参数列表
         // 注意：这里的报错信息可以稍微泛化，或者根据 kind 格式化
         self.consume(
@@ -102,12 +319,14 @@ impl ParseHelper {
         )?;
 
         let mut params = Vec::new();
+        let mut rest = false;
         if !self.check(TokenType::RightParen) {
             loop {
                 if params.len() >= 255 {
                     let _ = self.error(self.peek(), "Can't have more than 255 parameters.");
                 }
 
+                rest = self.match_token(&[TokenType::Ellipsis]);
                 params.push(
                     self.consume(TokenType::Identifier, "Expect parameter name.")?
                         .clone(),
@@ -116,15 +335,21 @@ impl ParseHelper {
                 if !self.match_token(&[TokenType::Comma]) {
                     break;
                 }
+                if rest {
+                    return Err(self.error(self.previous(), "Rest parameter must be last."));
+                }
             }
         }
         self.consume(TokenType::RightParen, "Expect ')' after parameters.")?;
 
-        // 2. 解析函数体前的左花括号
-        self.consume(
-            TokenType::LeftBrace,
-            &format!("Expect '{{' before {} body.", kind),
-        )?;
+        // 2. 解析函数体前的左花括号（或表达式函数体的 '='）
+        let expression_body = self.match_token(&[TokenType::Equal]);
+        if !expression_body {
+            self.consume(
+                TokenType::LeftBrace,
+                &format!("Expect '{{' before {} body.", kind),
+            )?;
+        }
 
         // 3. 上下文维护 (核心复用逻辑)
         let previous_func_depth = self.func_depth;
@@ -134,15 +359,99 @@ impl ParseHelper {
         self.loop_depth = 0; // 函数体隔离外部循环
 
         // 4. 解析块
-        let body_result = self.parse_block();
+        let body_result = if expression_body {
+            let keyword = self.previous().clone();
+            self.parse_expression().map(|value| {
+                vec![Stmt::Return {
+                    keyword,
+                    value: Some(value),
+                }]
+            })
+        } else {
+            self.parse_block()
+        };
 
         // 5. 恢复上下文
         self.func_depth = previous_func_depth;
         self.loop_depth = previous_loop_depth;
 
         let body = body_result?;
+        if expression_body && kind != "lambda" {
+            self.consume(
+                TokenType::Semicolon,
+                &format!("Expect ';' after {} body.", kind),
+            )?;
+        }
+
+        Ok((params, rest, body))
+    }
 
-        Ok((params, body))
+    /// 解析导入语句
+    ///
+    /// 语法: "import" ( IDENTIFIER | STRING ( "as" IDENTIFIER )? ) ";"
+    ///
+    /// 脱糖为变量声明：`import utils;` 等价于 `var utils = import("utils");`，
+    /// `import "lib/str_utils.rox";` 使用文件名 `str_utils` 作为变量名，也可以用 `as` 指定。
+    pub fn parse_import_statement(&mut self) -> Result<Stmt, Error> {
+        let keyword = self.previous().clone();
+        let target = self.advance().clone();
+
+        let (path, default_name) = match &target.literal {
+            Literal::String(path) => {
+                let stem = Path::new(path)
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                (path.clone(), stem)
+            }
+            _ => (target.lexeme.clone(), target.lexeme.clone()),
+        };
+
+        let name = if self.check(TokenType::Identifier) && self.peek().lexeme == "as" {
+            self.advance();
+            self.consume(TokenType::Identifier, "Expect module name after 'as'.")?
+                .clone()
+        } else {
+            let valid = default_name
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_alphabetic() || c == '_')
+                && default_name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '_');
+            if !valid {
+                return Err(self.error(
+                    &target,
+                    "Module file name is not a valid identifier; use 'as' to name the import.",
+                ));
+            }
+            Token {
+                token_type: TokenType::Identifier,
+                lexeme: default_name,
+                ..target.clone()
+            }
+        };
+
+        self.consume(TokenType::Semicolon, "Expect ';' after import.")?;
+
+        let callee = Expr::Variable {
+            id: self.generate_id(),
+            name: Token {
+                token_type: TokenType::Identifier,
+                ..keyword
+            },
+        };
+        let initializer = Expr::Call {
+            id: self.generate_id(),
+            callee: Box::new(callee),
+            args: vec![Expr::String { value: path }],
+            named: Vec::new(),
+        };
+
+        Ok(Stmt::VarDecl {
+            name,
+            initializer: Some(initializer),
+        })
     }
 
     pub fn parse_export_statement(&mut self) -> Result<Stmt, Error> {
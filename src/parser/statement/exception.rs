@@ -5,26 +5,45 @@ use crate::{
 };
 
 impl ParseHelper {
+    /// 解析 try 语句
+    ///
+    /// 语法: "try" block ( "catch" "(" IDENTIFIER ")" block )? ( "finally" block )?
+    /// catch 与 finally 至少要有一个。
     pub fn parse_try_statement(&mut self) -> Result<Stmt, Error> {
         self.consume(TokenType::LeftBrace, "Expect '{' after 'try'.")?;
         let try_body = self.parse_block()?;
         let try_branch = Stmt::Block { body: try_body };
 
-        self.consume(TokenType::Catch, "Expect 'catch' after try block.")?;
-        self.consume(TokenType::LeftParen, "Expect '(' after 'catch'.")?;
-        let catch_var = self
-            .consume(TokenType::Identifier, "Expect catch variable name.")?
-            .clone();
-        self.consume(TokenType::RightParen, "Expect ')' after catch variable.")?;
+        let catch = if self.match_token(&[TokenType::Catch]) {
+            self.consume(TokenType::LeftParen, "Expect '(' after 'catch'.")?;
+            let catch_var = self
+                .consume(TokenType::Identifier, "Expect catch variable name.")?
+                .clone();
+            self.consume(TokenType::RightParen, "Expect ')' after catch variable.")?;
 
-        self.consume(TokenType::LeftBrace, "Expect '{' after catch clause.")?;
-        let catch_body = self.parse_block()?;
-        let catch_branch = Stmt::Block { body: catch_body };
+            self.consume(TokenType::LeftBrace, "Expect '{' after catch clause.")?;
+            let catch_body = self.parse_block()?;
+            Some((catch_var, Box::new(Stmt::Block { body: catch_body })))
+        } else {
+            None
+        };
+
+        let finally_branch = if self.match_token(&[TokenType::Finally]) {
+            self.consume(TokenType::LeftBrace, "Expect '{' after 'finally'.")?;
+            let finally_body = self.parse_block()?;
+            Some(Box::new(Stmt::Block { body: finally_body }))
+        } else {
+            None
+        };
+
+        if catch.is_none() && finally_branch.is_none() {
+            return Err(self.error(self.peek(), "Expect 'catch' or 'finally' after try block."));
+        }
 
         Ok(Stmt::Try {
             try_branch: Box::new(try_branch),
-            catch_var,
-            catch_branch: Box::new(catch_branch),
+            catch,
+            finally_branch,
         })
     }
 
@@ -1,4 +1,8 @@
-use crate::parser::tests::assert_error;
+use crate::{
+    parser::{parse_all, tests::assert_error},
+    reader::Source,
+    tokenizer::tokenize,
+};
 
 #[test]
 fn test_error_missing_semicolon() {
@@ -15,6 +19,7 @@ fn test_error_invalid_assignment() {
     // 1 是右值，不能赋值给它
     assert_error("1 = a;", "Invalid assignment target");
     assert_error("a + b = c;", "Invalid assignment target");
+    assert_error("a?.b = c;", "Invalid assignment target");
 }
 
 #[test]
@@ -22,3 +27,27 @@ fn test_error_unexpected_token() {
     // 无法识别的语法结构
     assert_error("var 1 = a;", "Expect variable name");
 }
+
+#[test]
+fn test_parse_all_collects_errors() {
+    let errors = |code: &str| {
+        let tokens = tokenize(Source {
+            contents: code.to_string(),
+        })
+        .unwrap();
+        parse_all(tokens)
+            .map(|_| Vec::new())
+            .unwrap_or_else(|errors| errors.iter().map(|e| e.to_string()).collect())
+    };
+
+    // 出错后从下一条语句继续，代码块中的错误不影响块外的语句
+    let found = errors("var a = ;\nfun f() { print (1 + ; return 1; }\nprint 2\nprint 3;");
+    assert_eq!(found.len(), 3, "{:?}", found);
+    assert!(found[0].contains("[line 1]"));
+    assert!(found[1].contains("[line 2]"));
+    assert!(found[2].contains("Expect ';' after value"));
+
+    // 顶层多余的 `}` 不会导致死循环
+    assert_eq!(errors("} print 1;").len(), 1);
+    assert!(errors("print 1;").is_empty());
+}
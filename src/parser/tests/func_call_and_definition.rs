@@ -1,4 +1,4 @@
-use crate::parser::tests::{assert_parse, parse_to_string};
+use crate::parser::tests::{assert_error, assert_parse, parse_to_string};
 
 #[test]
 fn test_function_call() {
@@ -7,6 +7,19 @@ fn test_function_call() {
     assert_parse("get()(1);", "get()(1);");
 }
 
+#[test]
+fn test_keyword_arguments() {
+    assert_parse(
+        "draw(1, y = 2, color = \"red\");",
+        "draw(1, y = 2, color = \"red\");",
+    );
+    assert_error(
+        "f(x = 1, 2);",
+        "Positional argument cannot follow keyword argument.",
+    );
+    assert_error("f(x = 1, x = 2);", "Duplicate keyword argument 'x'.");
+}
+
 #[test]
 fn test_function_declaration() {
     assert_parse(
@@ -15,6 +28,41 @@ fn test_function_declaration() {
     );
 }
 
+#[test]
+fn test_rest_parameter() {
+    assert_parse(
+        "fun log(level, ...args) { print args; }",
+        "fun log(level, ...args) { print args; }",
+    );
+    assert_parse("var f = fun (...xs) {};", "var f = fun (...xs) {};");
+    assert_error("fun f(...a, b) {}", "Rest parameter must be last.");
+}
+
+#[test]
+fn test_expression_body() {
+    // `= 表达式` 等价于只有一条 return 的函数体
+    assert_parse(
+        "fun double(x) = x * 2;",
+        "fun double(x) { return (x * 2); }",
+    );
+    assert_parse(
+        "xs.map(fun (x) = x + 1);",
+        "xs.map(fun (x) {return (x + 1);});",
+    );
+    assert_error("fun double(x) = x * 2", "Expect ';' after function body.");
+}
+
+#[test]
+fn test_arrow_lambda() {
+    assert_parse("xs.map(|x| x * 2);", "xs.map(fun (x) {return (x * 2);});");
+    assert_parse(
+        "var f = |a, b| a + b;",
+        "var f = fun (a, b) {return (a + b);};",
+    );
+    assert_parse("var f = || 1;", "var f = fun () {return 1;};");
+    assert_error("var f = |a b;", "Expect '|' after lambda parameters.");
+}
+
 #[test]
 fn test_call_max_args() {
     // 构造一个超长参数列表
@@ -28,3 +76,11 @@ fn test_call_max_args() {
     // 这里假设我们允许解析通过，或者你可以测试它报错
     let _ = parse_to_string(&code);
 }
+
+#[test]
+fn test_lambda_capture_list() {
+    assert_parse(
+        "var f = fun [x, y] (a) { return a + x; };",
+        "var f = fun [x, y] (a) {return (a + x);};",
+    );
+}
@@ -35,3 +35,11 @@ fn test_unary() {
     assert_parse("- -1;", "(-(-1));");
     assert_parse("!true;", "(!true);");
 }
+
+#[test]
+fn test_precedence_null_coalescing() {
+    // ?? 的优先级低于 OR：a ?? b || c -> a ?? (b || c)
+    assert_parse("a ?? b || c;", "(a ?? (b || c));");
+    assert_parse("a ?? b ?? c;", "((a ?? b) ?? c);");
+    assert_parse("a?.b[0]?[k]?.c();", "a?.b[0]?[k]?.c();");
+}
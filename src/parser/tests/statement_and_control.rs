@@ -1,4 +1,4 @@
-use crate::parser::tests::assert_parse;
+use crate::parser::tests::{assert_error, assert_parse};
 
 #[test]
 fn test_var_declaration() {
@@ -13,6 +13,14 @@ fn test_assignment() {
     assert_parse("a = b = 2;", "a = b = 2;");
 }
 
+#[test]
+fn test_destructuring() {
+    assert_parse("var (a, b) = pair;", "var (a, b) = pair;");
+    assert_parse("var [x, y, z,] = list;", "var [x, y, z] = list;");
+    assert_parse("(a, b) = (b, a);", "(a, b) = (b, a);");
+    assert_parse("[a, b] = f();", "[a, b] = f();");
+}
+
 #[test]
 fn test_block() {
     assert_parse("{ var a = 1; print a; }", "{ var a = 1; print a; }");
@@ -54,3 +62,73 @@ fn test_for_loop_native() {
     // 空子句 for
     assert_parse("for (;;) { break; }", "for (; ; ) { break; }");
 }
+
+#[test]
+fn test_extend_declaration() {
+    assert_parse(
+        "extend String { shout() { return this; } }",
+        "extend String { fun shout() { return this; } }",
+    );
+}
+
+#[test]
+fn test_for_in_loop() {
+    assert_parse("for (e in d) print e;", "for (e in d) print e;");
+    assert_parse(
+        "for (var k, v in d) { break; }",
+        "for (k, v in d) { break; }",
+    );
+    // 普通 for 的初始化表达式不受影响
+    assert_parse(
+        "for (i = 0; i < 1;) print i;",
+        "for (i = 0; (i < 1); ) print i;",
+    );
+}
+
+#[test]
+fn test_try_finally() {
+    assert_parse(
+        "try { f(); } catch (e) { print e; } finally { g(); }",
+        "try { f(); } catch (e) { print e; } finally { g(); }",
+    );
+    assert_parse(
+        "try { f(); } finally { g(); }",
+        "try { f(); } finally { g(); }",
+    );
+}
+
+#[test]
+fn test_import_statement() {
+    assert_parse("import utils;", "var utils = import(\"utils\");");
+    assert_parse(
+        "import \"lib/str-utils.rox\" as su;",
+        "var su = import(\"lib/str-utils.rox\");",
+    );
+    assert_parse("import(\"utils\");", "import(\"utils\");");
+    assert_error("import \"lib/str-utils.rox\";", "use 'as'");
+}
+
+#[test]
+fn test_labeled_loop() {
+    assert_parse(
+        "outer: while (true) { break outer; }",
+        "outer: while (true) { break outer; }",
+    );
+    assert_parse(
+        "rows: for (x in xs) continue rows;",
+        "rows: for (x in xs) continue rows;",
+    );
+    assert_error("label: print 1;", "Expect a loop after label.");
+}
+
+#[test]
+fn test_defer_statement() {
+    assert_parse(
+        "fun f(file) { defer file.close(); }",
+        "fun f(file) { defer file.close(); }",
+    );
+    assert_error(
+        "fun f() { defer 1 }",
+        "Expect ';' after deferred expression.",
+    );
+}
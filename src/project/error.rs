@@ -0,0 +1,35 @@
+use std::{fmt::Display, io, path::PathBuf};
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// rox.toml 语法或字段错误
+    Manifest(PathBuf, String),
+    /// 找不到 rox.toml
+    NotFound(PathBuf),
+    /// 依赖声明或获取失败
+    Dependency(String, String),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "Could not read project: {}", err),
+            Error::Manifest(path, msg) => {
+                write!(f, "Invalid manifest '{}': {}", path.display(), msg)
+            }
+            Error::NotFound(dir) => write!(
+                f,
+                "Could not find 'rox.toml' in '{}' or any parent directory.",
+                dir.display()
+            ),
+            Error::Dependency(name, msg) => write!(f, "Dependency '{}': {}", name, msg),
+        }
+    }
+}
@@ -0,0 +1,365 @@
+use super::error::Error;
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// 项目清单文件名
+pub const MANIFEST_FILE: &str = "rox.toml";
+
+/// 项目清单 (rox.toml)
+///
+/// ```toml
+/// [package]
+/// name = "app"
+/// entry = "src/main.rox"    # 默认 main.rox
+/// roots = ["src", "lib"]    # 模块查找根目录，相对于 rox.toml
+///
+/// [dependencies]
+/// utils = { path = "../utils" }
+/// http = { git = "https://example.com/http.git", rev = "v1.0" }
+/// ```
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    pub package: Package,
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, Dependency>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Package {
+    pub name: String,
+    pub version: Option<String>,
+    #[serde(default = "default_entry")]
+    pub entry: String,
+    #[serde(default)]
+    pub roots: Vec<String>,
+}
+
+fn default_entry() -> String {
+    "main.rox".to_string()
+}
+
+/// 依赖声明：本地路径或 git 仓库，二者必须且只能出现一个
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Dependency {
+    pub path: Option<String>,
+    pub git: Option<String>,
+    pub rev: Option<String>,
+}
+
+impl Manifest {
+    /// 解析清单内容并校验依赖声明
+    pub fn parse(path: &Path, contents: &str) -> Result<Self, Error> {
+        let manifest: Manifest = toml::from_str(contents)
+            .map_err(|e| Error::Manifest(path.to_path_buf(), e.message().to_string()))?;
+
+        for (name, dep) in &manifest.dependencies {
+            // 依赖名会拼接为 `.rox/deps/<name>`，只允许普通标识符，防止写到目录之外
+            if !is_plain_identifier(name) {
+                return Err(Error::Dependency(
+                    name.clone(),
+                    "names must be plain identifiers (letters, digits, '_' or '-').".into(),
+                ));
+            }
+            match (&dep.path, &dep.git) {
+                (Some(_), None) | (None, Some(_)) => {}
+                _ => {
+                    return Err(Error::Dependency(
+                        name.clone(),
+                        "expected exactly one of 'path' or 'git'.".into(),
+                    ));
+                }
+            }
+            if dep.rev.is_some() && dep.git.is_none() {
+                return Err(Error::Dependency(
+                    name.clone(),
+                    "'rev' is only valid for git dependencies.".into(),
+                ));
+            }
+            // 以 `-` 开头的值会被 git 当作选项（eg. `--upload-pack=<cmd>`）
+            for (field, value) in [("git", &dep.git), ("rev", &dep.rev)] {
+                if value
+                    .as_deref()
+                    .is_some_and(|v| v.is_empty() || v.starts_with('-'))
+                {
+                    return Err(Error::Dependency(
+                        name.clone(),
+                        format!("'{}' must not be empty or start with '-'.", field),
+                    ));
+                }
+            }
+        }
+
+        Ok(manifest)
+    }
+}
+
+fn is_plain_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// 项目：清单 + 清单所在的根目录
+#[derive(Debug, Clone, PartialEq)]
+pub struct Project {
+    pub root: PathBuf,
+    pub manifest: Manifest,
+}
+
+impl Project {
+    /// 从 `start` 开始向上查找 rox.toml
+    pub fn discover(start: &Path) -> Result<Self, Error> {
+        let start = fs::canonicalize(start)?;
+        let mut dir = if start.is_file() {
+            start.parent().map(Path::to_path_buf)
+        } else {
+            Some(start.clone())
+        };
+
+        while let Some(current) = dir {
+            let candidate = current.join(MANIFEST_FILE);
+            if candidate.is_file() {
+                return Self::load(&candidate);
+            }
+            dir = current.parent().map(Path::to_path_buf);
+        }
+
+        Err(Error::NotFound(start))
+    }
+
+    /// 读取指定的 rox.toml
+    pub fn load(manifest_path: &Path) -> Result<Self, Error> {
+        let contents = fs::read_to_string(manifest_path)?;
+        let manifest = Manifest::parse(manifest_path, &contents)?;
+        let root = manifest_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+
+        Ok(Self { root, manifest })
+    }
+
+    /// 入口文件的路径
+    pub fn entry_path(&self) -> PathBuf {
+        self.root.join(&self.manifest.package.entry)
+    }
+
+    /// 模块查找路径：项目模块根目录在前，依赖根目录在后
+    ///
+    /// git 依赖会被检出到 `.rox/deps/<name>`，已存在时不再重复获取。
+    pub fn search_paths(&self) -> Result<Vec<PathBuf>, Error> {
+        let mut paths = self.root_paths();
+
+        for (name, dep) in &self.manifest.dependencies {
+            let dir = match (&dep.path, &dep.git) {
+                (Some(path), _) => self.root.join(path),
+                (None, Some(url)) => self.fetch_git(name, url, dep.rev.as_deref())?,
+                (None, None) => unreachable!("validated in Manifest::parse"),
+            };
+
+            if !dir.is_dir() {
+                return Err(Error::Dependency(
+                    name.clone(),
+                    format!("directory '{}' does not exist.", dir.display()),
+                ));
+            }
+            paths.push(dir);
+        }
+
+        Ok(paths)
+    }
+
+    /// 不访问网络、不运行 git 的模块查找路径（`rox check` 使用）
+    ///
+    /// 只包含已存在的本地路径依赖与已经检出到 `.rox/deps/<name>` 的 git 依赖，缺失的依赖直接跳过。
+    pub fn offline_search_paths(&self) -> Vec<PathBuf> {
+        let deps = self
+            .manifest
+            .dependencies
+            .iter()
+            .map(|(name, dep)| match &dep.path {
+                Some(path) => self.root.join(path),
+                None => self.deps_dir(name),
+            })
+            .filter(|dir| dir.is_dir());
+
+        let mut paths = self.root_paths();
+        paths.extend(deps);
+        paths
+    }
+
+    fn root_paths(&self) -> Vec<PathBuf> {
+        self.manifest
+            .package
+            .roots
+            .iter()
+            .map(|root| self.root.join(root))
+            .collect()
+    }
+
+    /// git 依赖的检出目录
+    fn deps_dir(&self, name: &str) -> PathBuf {
+        self.root.join(".rox").join("deps").join(name)
+    }
+
+    fn fetch_git(&self, name: &str, url: &str, rev: Option<&str>) -> Result<PathBuf, Error> {
+        let dir = self.deps_dir(name);
+        if dir.is_dir() {
+            return Ok(dir);
+        }
+
+        let git = |args: &[&str], cwd: &Path| -> Result<(), Error> {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(cwd)
+                .status()
+                .map_err(|e| Error::Dependency(name.to_string(), format!("git: {}", e)))?;
+            if status.success() {
+                Ok(())
+            } else {
+                Err(Error::Dependency(
+                    name.to_string(),
+                    format!("'git {}' failed.", args.join(" ")),
+                ))
+            }
+        };
+
+        fs::create_dir_all(dir.parent().unwrap_or(&self.root))?;
+        let target = dir.to_string_lossy().to_string();
+        // `--` 之后的参数不会被解析为选项
+        git(&["clone", "--quiet", "--", url, &target], &self.root)?;
+        if let Some(rev) = rev {
+            git(&["checkout", "--quiet", rev, "--"], &dir)?;
+        }
+
+        Ok(dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Manifest, Project};
+    use pretty_assertions::assert_eq;
+    use std::{fs, path::Path};
+
+    #[test]
+    fn test_parse_manifest_defaults() {
+        let manifest = Manifest::parse(
+            Path::new("rox.toml"),
+            r#"
+                [package]
+                name = "app"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.package.entry, "main.rox");
+        assert!(manifest.package.roots.is_empty());
+        assert!(manifest.dependencies.is_empty());
+    }
+
+    #[test]
+    fn test_parse_manifest_rejects_ambiguous_dependency() {
+        let err = Manifest::parse(
+            Path::new("rox.toml"),
+            r#"
+                [package]
+                name = "app"
+
+                [dependencies]
+                utils = { path = "../utils", git = "https://example.com/utils.git" }
+            "#,
+        );
+
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_parse_manifest_rejects_unsafe_dependency_values() {
+        let parse = |dependency: &str| {
+            Manifest::parse(
+                Path::new("rox.toml"),
+                &format!(
+                    "[package]\nname = \"app\"\n\n[dependencies]\n{}\n",
+                    dependency
+                ),
+            )
+        };
+
+        assert!(parse(r#""../../x" = { git = "https://example.com/x.git" }"#).is_err());
+        assert!(parse(r#"x = { git = "--upload-pack=touch /tmp/pwned" }"#).is_err());
+        assert!(
+            parse(r#"x = { git = "https://example.com/x.git", rev = "-c core.sshCommand=sh" }"#)
+                .is_err()
+        );
+        assert!(parse(r#"my_dep-2 = { git = "https://example.com/x.git", rev = "v1.0" }"#).is_ok());
+    }
+
+    #[test]
+    fn test_discover_project_and_search_paths() {
+        let root = std::env::temp_dir().join(format!("rox_project_{}", std::process::id()));
+        fs::create_dir_all(root.join("src/nested")).unwrap();
+        fs::create_dir_all(root.join("vendor/utils")).unwrap();
+        fs::write(
+            root.join("rox.toml"),
+            r#"
+                [package]
+                name = "app"
+                entry = "src/main.rox"
+                roots = ["src"]
+
+                [dependencies]
+                utils = { path = "vendor/utils" }
+            "#,
+        )
+        .unwrap();
+
+        let project = Project::discover(&root.join("src/nested")).unwrap();
+        let root = fs::canonicalize(&root).unwrap();
+        let search_paths = project.search_paths();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(project.entry_path(), root.join("src/main.rox"));
+        assert_eq!(
+            search_paths.unwrap(),
+            vec![root.join("src"), root.join("vendor/utils")]
+        );
+    }
+
+    #[test]
+    fn test_offline_search_paths_never_fetch() {
+        let root = std::env::temp_dir().join(format!("rox_offline_{}", std::process::id()));
+        fs::create_dir_all(root.join(".rox/deps/cached")).unwrap();
+        fs::write(
+            root.join("rox.toml"),
+            r#"
+                [package]
+                name = "app"
+
+                [dependencies]
+                cached = { git = "https://invalid.example/cached.git" }
+                missing = { git = "https://invalid.example/missing.git" }
+                local = { path = "vendor/local" }
+            "#,
+        )
+        .unwrap();
+
+        let project = Project::load(&root.join("rox.toml")).unwrap();
+        let paths = project.offline_search_paths();
+        let fetched = root.join(".rox/deps/missing").exists();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(paths, vec![root.join(".rox/deps/cached")]);
+        assert!(!fetched);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod error;
+pub mod manifest;
+
+pub use error::Error;
+pub use manifest::*;
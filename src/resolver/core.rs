@@ -37,6 +37,8 @@ pub enum ClassType {
     Class,
     /// 子类状态
     Subclass,
+    /// 静态方法体内：没有 this / super
+    Static,
 }
 
 /// 语义分析器 (Resolver)
@@ -55,6 +57,8 @@ pub enum ClassType {
 ///    - 在循环外使用 `break` 或 `continue`。
 ///    - 在变量初始化完成前读取自身 (`var a = a;`)。
 ///    - 在同一作用域重复声明变量。
+/// 3. **警告 (Warnings)**：
+///    - 内层 `var` 遮蔽外层同名变量。
 pub struct Resolver<'a> {
     /// 解释器的可变引用
     ///
@@ -74,6 +78,17 @@ pub struct Resolver<'a> {
     ///   - `true`: **已定义 (Defined)**。变量已初始化完毕，可以安全使用。
     pub scopes: Vec<HashMap<String, bool>>,
 
+    /// 声明位置表，与 `scopes` 一一对应：变量名 -> 声明所在行
+    ///
+    /// 只用于生成遮蔽警告 (Shadowing Warning)。
+    pub declarations: Vec<HashMap<String, usize>>,
+
+    /// 顶层声明的位置（顶层变量不进入 `scopes`）
+    pub global_declarations: HashMap<String, usize>,
+
+    /// 静态分析产生的警告，不会中断执行，由调用方决定如何输出
+    pub warnings: Vec<String>,
+
     /// 当前函数上下文状态
     ///
     /// 用于检查 `return` 语句是否出现在合法的位置。
@@ -87,4 +102,9 @@ pub struct Resolver<'a> {
     /// 用于检查 `break` 和 `continue` 语句是否出现在合法的位置。
     /// 每当进入循环语句时，保存旧状态并设置为 `LoopType::Loop`；退出时恢复。
     pub current_loop: LoopType,
+
+    /// 包围当前代码的循环标签（由外到内）
+    ///
+    /// 用于检查 `break label` / `continue label` 指向存在的外层循环；进入函数体时清空。
+    pub loop_labels: Vec<String>,
 }
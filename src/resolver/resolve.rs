@@ -16,9 +16,13 @@ impl<'a> Resolver<'a> {
         Self {
             interpreter,
             scopes: Vec::new(),
+            declarations: Vec::new(),
+            global_declarations: HashMap::new(),
+            warnings: Vec::new(),
             current_function: FunctionType::None,
             current_class: ClassType::None,
             current_loop: LoopType::None,
+            loop_labels: Vec::new(),
         }
     }
 
@@ -34,6 +38,25 @@ impl<'a> Resolver<'a> {
         Ok(())
     }
 
+    /// 解析整个程序并收集全部错误（供 `rox check` 使用）
+    ///
+    /// 以顶层语句为单位：某条语句出错后丢弃它留下的作用域与上下文状态，继续解析下一条。
+    pub fn resolve_all(&mut self, statements: &Vec<Stmt>) -> Vec<String> {
+        let mut errors = Vec::new();
+        for stmt in statements {
+            if let Err(e) = self.resolve_stmt(stmt) {
+                errors.push(e);
+                self.scopes.clear();
+                self.declarations.clear();
+                self.current_function = FunctionType::None;
+                self.current_class = ClassType::None;
+                self.current_loop = LoopType::None;
+                self.loop_labels.clear();
+            }
+        }
+        errors
+    }
+
     // 语句解析
 
     /// 解析单个语句
@@ -53,16 +76,40 @@ impl<'a> Resolver<'a> {
             // 处理步骤：声明 (Declare) -> 解析初始化表达式 -> 定义 (Define)。
             // 分步是为了处理 `var a = a;` 自引用错误情况。
             Stmt::VarDecl { name, initializer } => {
+                self.check_shadowing(name);
                 self.declare(name)?;
                 if let Some(init) = initializer {
                     self.resolve_expr(init)?;
                 }
                 self.define(name);
             }
+            Stmt::VarDestructure {
+                pattern,
+                initializer,
+            } => {
+                let names = pattern
+                    .pattern_targets()
+                    .iter()
+                    .filter_map(|target| match target {
+                        Expr::Variable { name, .. } => Some(name),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                for name in &names {
+                    self.check_shadowing(name);
+                    self.declare(name)?;
+                }
+                self.resolve_expr(initializer)?;
+                for name in names {
+                    self.define(name);
+                }
+            }
 
             // 函数声明
             // 函数名在当前作用域立即可见（支持递归），然后创建新作用域解析函数体。
-            Stmt::Function { name, params, body } => {
+            Stmt::Function {
+                name, params, body, ..
+            } => {
                 self.declare(name)?;
                 self.define(name);
 
@@ -76,6 +123,8 @@ impl<'a> Resolver<'a> {
                 name,
                 superclass,
                 methods,
+                static_methods,
+                ..
             } => {
                 let enclosing_class = self.current_class;
                 self.current_class = ClassType::Class;
@@ -99,6 +148,16 @@ impl<'a> Resolver<'a> {
                     self.resolve_expr(super_expr)?;
                 }
 
+                // 静态方法不绑定实例，在 this / super 作用域之外解析
+                let class_type = self.current_class;
+                self.current_class = ClassType::Static;
+                for method in static_methods {
+                    if let Stmt::Function { params, body, .. } = method {
+                        self.resolve_function(params, body, FunctionType::Function)?;
+                    }
+                }
+                self.current_class = class_type;
+
                 // Core：如果有父类，开启新的作用域，定义 "super"
                 if superclass.is_some() {
                     self.begin_scope();
@@ -119,6 +178,7 @@ impl<'a> Resolver<'a> {
                         params,
                         body,
                         name: method_name,
+                        ..
                     } = method
                     {
                         let declaration = if method_name.lexeme == "init" {
@@ -140,6 +200,32 @@ impl<'a> Resolver<'a> {
                 self.current_class = enclosing_class;
             }
 
+            // trait 与类一样是一个具名的声明，`implements` 的检查在类声明执行时进行
+            Stmt::Trait { name, .. } => {
+                self.declare(name)?;
+                self.define(name);
+            }
+
+            // 扩展方法与类方法一样可以使用 this（指向被扩展的内置值）
+            Stmt::Extend { methods, .. } => {
+                let enclosing_class = self.current_class;
+                self.current_class = ClassType::Class;
+
+                self.begin_scope();
+                if let Some(scope) = self.scopes.last_mut() {
+                    scope.insert("this".to_string(), true);
+                }
+
+                for method in methods {
+                    if let Stmt::Function { params, body, .. } = method {
+                        self.resolve_function(params, body, FunctionType::Method)?;
+                    }
+                }
+
+                self.end_scope();
+                self.current_class = enclosing_class;
+            }
+
             // 表达式语句 递归解析内部表达式。
             Stmt::Expression { expr } => {
                 self.resolve_expr(expr)?;
@@ -162,18 +248,33 @@ impl<'a> Resolver<'a> {
             }
             Stmt::Try {
                 try_branch,
-                catch_var,
-                catch_branch,
+                catch,
+                finally_branch,
             } => {
+                // 生成器暂停时无法保留 try 的上下文
+                if let Some(keyword) = stmt.find_yield() {
+                    return Err(format!(
+                        "[line {}] Can't yield inside a try statement.",
+                        keyword.line
+                    ));
+                }
+
                 // try block
                 self.resolve_stmt(try_branch)?;
 
                 // catch block
-                self.begin_scope();
-                self.declare(catch_var)?;
-                self.define(catch_var);
-                self.resolve_stmt(catch_branch)?;
-                self.end_scope();
+                if let Some((catch_var, catch_branch)) = catch {
+                    self.begin_scope();
+                    self.declare(catch_var)?;
+                    self.define(catch_var);
+                    self.resolve_stmt(catch_branch)?;
+                    self.end_scope();
+                }
+
+                // finally block
+                if let Some(finally_branch) = finally_branch {
+                    self.resolve_stmt(finally_branch)?;
+                }
             }
 
             // 解析循环体时需要更新 `current_loop` 状态，以便检查 break/continue。
@@ -213,11 +314,61 @@ impl<'a> Resolver<'a> {
                 self.end_scope();
             }
 
+            // 被迭代的表达式在外层作用域求值，循环变量属于循环自身的作用域
+            Stmt::ForIn {
+                vars,
+                iterable,
+                body,
+            } => {
+                self.resolve_expr(iterable)?;
+
+                self.begin_scope();
+                for var in vars {
+                    self.declare(var)?;
+                    self.define(var);
+                }
+
+                let enclosing_loop = self.current_loop;
+                self.current_loop = LoopType::Loop;
+                self.resolve_stmt(body)?;
+                self.current_loop = enclosing_loop;
+
+                self.end_scope();
+            }
+
             Stmt::Print { expr } => {
                 self.resolve_expr(expr)?;
             }
 
             // Return 检查 `return` 是否非法出现在顶层代码中。
+            Stmt::Yield { keyword, value } => {
+                if self.current_function == FunctionType::None {
+                    return Err(format!(
+                        "[line {}] Can't yield from top-level code.",
+                        keyword.line
+                    ));
+                }
+                if self.current_function == FunctionType::Initializer {
+                    return Err(format!(
+                        "[line {}] Can't yield from an initializer.",
+                        keyword.line
+                    ));
+                }
+                if let Some(val) = value {
+                    self.resolve_expr(val)?;
+                }
+            }
+
+            Stmt::Defer { keyword, expr } => {
+                if self.current_function == FunctionType::None {
+                    return Err(format!(
+                        "[line {}] Can't use 'defer' outside of a function.",
+                        keyword.line
+                    ));
+                }
+                self.resolve_expr(expr)?;
+            }
+
             Stmt::Return { keyword, value } => {
                 // 检查是否在函数中
                 if self.current_function == FunctionType::None {
@@ -241,16 +392,30 @@ impl<'a> Resolver<'a> {
                 }
             }
 
-            // Break/Continue 检查是否非法出现在循环外部
-            Stmt::Break => {
+            // Break/Continue 检查是否非法出现在循环外部，以及标签是否指向外层循环
+            Stmt::Break { label } => {
                 if self.current_loop == LoopType::None {
                     return Err("Can't use 'break' outside of a loop.".to_string());
                 }
+                self.check_loop_label(label)?;
             }
-            Stmt::Continue => {
+            Stmt::Continue { label } => {
                 if self.current_loop == LoopType::None {
                     return Err("Can't use 'continue' outside of a loop.".to_string());
                 }
+                self.check_loop_label(label)?;
+            }
+            Stmt::Labeled { label, body } => {
+                if self.loop_labels.contains(&label.lexeme) {
+                    return Err(format!(
+                        "[line {}] Label '{}' is already used by an enclosing loop.",
+                        label.line, label.lexeme
+                    ));
+                }
+                self.loop_labels.push(label.lexeme.clone());
+                let result = self.resolve_stmt(body);
+                self.loop_labels.pop();
+                result?;
             }
 
             Stmt::Export { stmt } => {
@@ -289,6 +454,14 @@ impl<'a> Resolver<'a> {
                 self.resolve_expr(expr)?;
                 self.resolve_local(id, name);
             }
+            Expr::AssignDestructure { pattern, value } => {
+                self.resolve_expr(value)?;
+                for target in pattern.pattern_targets() {
+                    if let Expr::Variable { id, name } = target {
+                        self.resolve_local(id, name);
+                    }
+                }
+            }
             Expr::AssignOp { id, name, expr, .. } => {
                 self.resolve_expr(expr)?;
                 self.resolve_local(id, name);
@@ -304,12 +477,16 @@ impl<'a> Resolver<'a> {
                 id: _,
                 callee,
                 args,
+                named,
             } => {
                 self.resolve_expr(callee)?;
 
                 for arg in args {
                     self.resolve_expr(arg)?;
                 }
+                for (_, arg) in named {
+                    self.resolve_expr(arg)?;
+                }
             }
             Expr::Super { id, keyword, .. } => {
                 if self.current_class == ClassType::None {
@@ -317,6 +494,11 @@ impl<'a> Resolver<'a> {
                         "[line {}] Can't use 'super' outside of a class.",
                         keyword.line
                     ));
+                } else if self.current_class == ClassType::Static {
+                    return Err(format!(
+                        "[line {}] Can't use 'super' in a static method.",
+                        keyword.line
+                    ));
                 } else if self.current_class != ClassType::Subclass {
                     return Err(format!(
                         "[line {}] Can't use 'super' in a class with no superclass.",
@@ -328,12 +510,31 @@ impl<'a> Resolver<'a> {
                 self.resolve_local(id, keyword);
             }
             Expr::Lambda {
-                id: _,
+                captures,
                 params,
                 body,
+                ..
             } => {
-                // !! Lambda 也是函数
-                self.resolve_function(params, body, FunctionType::Function)?;
+                if captures.is_empty() {
+                    // !! Lambda 也是函数
+                    self.resolve_function(params, body, FunctionType::Function)?;
+                    return Ok(());
+                }
+
+                // 捕获的变量先在外层解析，再放进一个介于外层与函数之间的快照作用域
+                for capture in captures {
+                    self.resolve_expr(capture)?;
+                }
+                self.begin_scope();
+                for capture in captures {
+                    if let Expr::Variable { name, .. } = capture {
+                        self.declare(name)?;
+                        self.define(name);
+                    }
+                }
+                let result = self.resolve_function(params, body, FunctionType::Function);
+                self.end_scope();
+                result?;
             }
 
             Expr::List { elements } | Expr::Tuple { elements } => {
@@ -347,8 +548,12 @@ impl<'a> Resolver<'a> {
                     self.resolve_expr(v)?;
                 }
             }
-            Expr::Number { .. } | Expr::String { .. } | Expr::Boolean { .. } | Expr::Nil => {}
-            Expr::Get { object, name: _ } => {
+            Expr::Number { .. }
+            | Expr::BigInt { .. }
+            | Expr::String { .. }
+            | Expr::Boolean { .. }
+            | Expr::Nil => {}
+            Expr::Get { object, .. } => {
                 // 只解析对象 (object)，属性名(Token) 是动态的 不需要解析
                 self.resolve_expr(object)?;
             }
@@ -381,6 +586,11 @@ impl<'a> Resolver<'a> {
                         "[line {}] Can't use 'this' outside of a class.",
                         keyword.line
                     ));
+                } else if self.current_class == ClassType::Static {
+                    return Err(format!(
+                        "[line {}] Can't use 'this' in a static method.",
+                        keyword.line
+                    ));
                 }
                 // 像解析普通局部变量一样解析 'this'
                 self.resolve_local(id, keyword);
@@ -396,6 +606,7 @@ impl<'a> Resolver<'a> {
     /// 向作用域栈压入一个新的 HashMap。
     fn begin_scope(&mut self) {
         self.scopes.push(HashMap::new());
+        self.declarations.push(HashMap::new());
     }
 
     /// 退出作用域
@@ -403,6 +614,7 @@ impl<'a> Resolver<'a> {
     /// 从作用域栈弹出一个 HashMap，销毁其中定义的局部变量。
     fn end_scope(&mut self) {
         self.scopes.pop();
+        self.declarations.pop();
     }
 
     /// 声明变量 (Declare)
@@ -411,6 +623,8 @@ impl<'a> Resolver<'a> {
     /// 如果变量名已存在，则报错（禁止在同一作用域重复声明）。
     fn declare(&mut self, name: &Token) -> Result<(), String> {
         if self.scopes.is_empty() {
+            self.global_declarations
+                .insert(name.lexeme.clone(), name.line);
             return Ok(());
         }
 
@@ -423,9 +637,37 @@ impl<'a> Resolver<'a> {
         }
 
         scope.insert(name.lexeme.clone(), false);
+        if let Some(lines) = self.declarations.last_mut() {
+            lines.insert(name.lexeme.clone(), name.line);
+        }
         Ok(())
     }
 
+    /// 遮蔽检查 (Shadowing Check)
+    ///
+    /// 局部 `var` 与外层作用域（包括此前声明的顶层变量）同名时记录一条警告，
+    /// 同时给出两处声明的行号。同一作用域内的重复声明由 `declare` 报错，这里不处理。
+    fn check_shadowing(&mut self, name: &Token) {
+        if self.scopes.is_empty() {
+            return;
+        }
+
+        let outer = self
+            .declarations
+            .iter()
+            .rev()
+            .skip(1)
+            .find_map(|lines| lines.get(&name.lexeme))
+            .or_else(|| self.global_declarations.get(&name.lexeme));
+
+        if let Some(outer_line) = outer {
+            self.warnings.push(format!(
+                "[line {}] Variable '{}' shadows an outer declaration at line {}.",
+                name.line, name.lexeme, outer_line
+            ));
+        }
+    }
+
     /// 定义变量 (Define)
     ///
     /// 将变量状态更新为 `true` (已初始化/可用)。
@@ -468,8 +710,20 @@ impl<'a> Resolver<'a> {
         body: &Vec<Stmt>,
         f_type: FunctionType,
     ) -> Result<(), String> {
+        // 生成器在 yield 处暂停，没有确定的退出时机来执行 defer
+        if body.iter().any(|stmt| stmt.find_yield().is_some())
+            && let Some(keyword) = body.iter().find_map(Stmt::find_defer)
+        {
+            return Err(format!(
+                "[line {}] Can't use 'defer' in a generator.",
+                keyword.line
+            ));
+        }
+
         let enclosing_func = self.current_function;
         self.current_function = f_type;
+        // 函数体不能跳到外层函数的循环
+        let enclosing_labels = std::mem::take(&mut self.loop_labels);
 
         self.begin_scope();
 
@@ -483,6 +737,18 @@ impl<'a> Resolver<'a> {
         self.end_scope();
 
         self.current_function = enclosing_func;
+        self.loop_labels = enclosing_labels;
         Ok(())
     }
+
+    /// 检查 break / continue 的标签是否属于某个外层循环
+    fn check_loop_label(&self, label: &Option<Token>) -> Result<(), String> {
+        match label {
+            Some(label) if !self.loop_labels.contains(&label.lexeme) => Err(format!(
+                "[line {}] Undefined loop label '{}'.",
+                label.line, label.lexeme
+            )),
+            _ => Ok(()),
+        }
+    }
 }
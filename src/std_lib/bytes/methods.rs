@@ -0,0 +1,96 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::std_lib::{
+    Interpreter, Value,
+    error::RuntimeError,
+    heap,
+    utils::{ensure_bytes, slice_range},
+};
+
+// bytes(data): 字符串取其 UTF-8 编码，列表的每个元素必须是 0..=255 的整数。bytes(String | List | Bytes) -> Bytes
+pub fn new(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let bytes: Rc<[u8]> = match &args[0] {
+        Value::Bytes(bytes) => bytes.clone(),
+        Value::String(s) => s.as_bytes().into(),
+        Value::List(list) => list
+            .borrow()
+            .iter()
+            .map(|item| match item {
+                Value::Number(n) if n.fract() == 0.0 && (0.0..=255.0).contains(n) => Ok(*n as u8),
+                other => Err(RuntimeError::TypeError(format!(
+                    "Bytes must be integers between 0 and 255, got {}.",
+                    other
+                ))),
+            })
+            .collect::<Result<_, _>>()?,
+        other => {
+            return Err(RuntimeError::TypeError(format!(
+                "bytes() expects a String, List or Bytes, got {}.",
+                other.type_name()
+            )));
+        }
+    };
+    Ok(Value::Bytes(bytes))
+}
+
+// b.len() -> Number
+pub fn len(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    Ok(Value::Number(ensure_bytes(&args[0])?.len() as f64))
+}
+
+// b.slice(start, end): [start, end) 区间的新 Bytes，负数下标从末尾倒数
+pub fn slice(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let bytes = ensure_bytes(&args[0])?;
+    let range = slice_range(&args[1], &args[2], bytes.len())?;
+    Ok(Value::Bytes(bytes[range].into()))
+}
+
+// b.toList(): 每个字节对应一个数字 -> List
+pub fn to_list(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let bytes = ensure_bytes(&args[0])?;
+    let list = Value::List(Rc::new(RefCell::new(
+        bytes.iter().map(|b| Value::Number(*b as f64)).collect(),
+    )));
+    heap::track(&list);
+    Ok(list)
+}
+
+// b.decode(): 按 UTF-8 解码为字符串，不是合法 UTF-8 时报错 -> String
+pub fn decode(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let bytes = ensure_bytes(&args[0])?;
+    std::str::from_utf8(bytes)
+        .map(|s| Value::String(s.to_string()))
+        .map_err(|e| RuntimeError::Generic(format!("Bytes are not valid UTF-8: {}.", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_bytes_methods() {
+        let code = r#"
+            var b = bytes("hé");
+            var total = 0;
+            for (var x in b) total = total + x;
+            var d = {};
+            d[bytes([1, 2])] = "key";
+            var res = [
+                b, b.len(), b[0], b.slice(1, -0 + 3).toList(), b.decode(), total,
+                bytes([1]) + bytes([2, 3]), bytes([1, 2]) == bytes([1, 2]), d[bytes([1, 2])],
+                type(b), bytes(b).slice(-1, 3)
+            ];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[bytes([104, 195, 169]), 3, 104, [195, 169], hé, 468, bytes([1, 2, 3]), true, key, bytes, bytes([169])]"
+        );
+
+        assert!(eval_res("var res = bytes([256]);").is_err());
+        assert!(eval_res("var res = bytes(1);").is_err());
+        assert!(eval_res("var res = bytes([1])[1];").is_err());
+        assert!(eval_res("var b = bytes([1]); b[0] = 2;").is_err());
+        assert!(eval_res("var res = bytes([255]).decode();").is_err());
+    }
+}
@@ -0,0 +1,15 @@
+pub mod methods;
+use super::value::Value;
+use crate::{native_fn, std_lib::iterator};
+
+/// Bytes：不可变的字节序列，支持 `b[i]` 下标（得到 0..=255 的数字）与 `+` 拼接
+pub fn lookup(name: &str) -> Option<Value> {
+    match name {
+        "len" => native_fn!("len", 0, methods::len),
+        "slice" => native_fn!("slice", 2, methods::slice),
+        "toList" => native_fn!("toList", 0, methods::to_list),
+        "decode" => native_fn!("decode", 0, methods::decode),
+        "iter" => native_fn!("iter", 0, iterator::methods::collection_iter),
+        _ => None,
+    }
+}
@@ -0,0 +1,19 @@
+use crate::evaluate::{Interpreter, error::RuntimeError, value::Value};
+use crate::std_lib::{
+    compress::{decode, encode, input},
+    utils::ensure_bytes,
+};
+use flate2::{Compression, read::DeflateDecoder, write::DeflateEncoder};
+
+// deflate.compress(data): 压缩 Bytes 或字符串（UTF-8）。deflate.compress(Bytes | String) -> Bytes
+pub fn compress(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let data = input("deflate", &args[0])?;
+    let encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+    encode("deflate", encoder, &data, DeflateEncoder::finish)
+}
+
+// deflate.decompress(data): 解压为 Bytes，文本可再调用 decode()。deflate.decompress(Bytes) -> Bytes
+pub fn decompress(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let data = ensure_bytes(&args[0])?;
+    decode("deflate", DeflateDecoder::new(data))
+}
@@ -0,0 +1,17 @@
+mod methods;
+
+use crate::{
+    evaluate::value::{NativeFn, Value},
+    std_lib::utils,
+};
+
+/// deflate 模块：不带文件头的原始 DEFLATE 流（RFC 1951），eg. 网络协议与归档格式中的压缩数据
+const FUNCTIONS: &[(&str, usize, NativeFn)] = &[
+    ("compress", 1, methods::compress),
+    ("decompress", 1, methods::decompress),
+];
+
+// 创建 deflate 模块对象
+pub fn create_module() -> Value {
+    utils::native_module("deflate", FUNCTIONS)
+}
@@ -0,0 +1,47 @@
+use crate::evaluate::{Interpreter, error::RuntimeError, value::Value};
+use crate::std_lib::{
+    compress::{decode, encode, input},
+    utils::ensure_bytes,
+};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+
+// gzip.compress(data): 压缩 Bytes 或字符串（UTF-8）。gzip.compress(Bytes | String) -> Bytes
+pub fn compress(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let data = input("gzip", &args[0])?;
+    let encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encode("gzip", encoder, &data, GzEncoder::finish)
+}
+
+// gzip.decompress(data): 解压为 Bytes，文本可再调用 decode()。gzip.decompress(Bytes) -> Bytes
+pub fn decompress(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let data = ensure_bytes(&args[0])?;
+    decode("gzip", GzDecoder::new(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_round_trip_and_interop() {
+        let code = r#"
+            var text = "log line ".repeat(100);
+            var packed = gzip.compress(text);
+            var raw = deflate.compress(bytes(text));
+            var res = [
+                gzip.decompress(packed).decode() == text, packed.len() < 100, packed[0], packed[1],
+                deflate.decompress(raw).decode() == text, raw.len() < packed.len(),
+                gzip.decompress(bytes(base64.decodeBytes("H4sIAAAAAAACA8tIzcnJVyjKrwAAhw0H+gkAAAA="))).decode(),
+                gzip.decompress(gzip.compress(bytes([]))).len()
+            ];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[true, true, 31, 139, true, true, hello rox, 0]"
+        );
+        assert!(eval_res(r#"gzip.decompress(bytes("not gzip"));"#).is_err());
+        assert!(eval_res(r#"gzip.decompress("text");"#).is_err());
+        assert!(eval_res("deflate.compress(1);").is_err());
+    }
+}
@@ -0,0 +1,17 @@
+mod methods;
+
+use crate::{
+    evaluate::value::{NativeFn, Value},
+    std_lib::utils,
+};
+
+/// gzip 模块：gzip 格式（RFC 1952）的压缩与解压，读写 `.gz` 文件时配合 fs.readBytes / fs.writeBytes 使用
+const FUNCTIONS: &[(&str, usize, NativeFn)] = &[
+    ("compress", 1, methods::compress),
+    ("decompress", 1, methods::decompress),
+];
+
+// 创建 gzip 模块对象
+pub fn create_module() -> Value {
+    utils::native_module("gzip", FUNCTIONS)
+}
@@ -0,0 +1,41 @@
+pub mod deflate;
+pub mod gzip;
+
+use crate::evaluate::{error::RuntimeError, value::Value};
+use std::io::{Read, Write};
+
+/// 待压缩的数据：Bytes 原样使用，字符串取 UTF-8 字节
+fn input(module: &str, value: &Value) -> Result<Vec<u8>, RuntimeError> {
+    match value {
+        Value::Bytes(bytes) => Ok(bytes.to_vec()),
+        Value::String(s) => Ok(s.as_bytes().to_vec()),
+        other => Err(RuntimeError::TypeError(format!(
+            "{}.compress expects Bytes or a String, got {}.",
+            module,
+            other.type_name()
+        ))),
+    }
+}
+
+/// 以默认压缩级别写入编码器
+fn encode<W: Write>(
+    module: &str,
+    mut encoder: W,
+    data: &[u8],
+    finish: impl FnOnce(W) -> std::io::Result<Vec<u8>>,
+) -> Result<Value, RuntimeError> {
+    encoder
+        .write_all(data)
+        .and_then(|_| finish(encoder))
+        .map(|bytes| Value::Bytes(bytes.into()))
+        .map_err(|e| RuntimeError::Generic(format!("{} compression failed: {}.", module, e)))
+}
+
+/// 从解码器读出全部数据，数据损坏时报错
+fn decode(module: &str, mut decoder: impl Read) -> Result<Value, RuntimeError> {
+    let mut out = Vec::new();
+    decoder
+        .read_to_end(&mut out)
+        .map(|_| Value::Bytes(out.into()))
+        .map_err(|e| RuntimeError::Generic(format!("Invalid {} data: {}.", module, e)))
+}
@@ -0,0 +1,83 @@
+use crate::evaluate::{Interpreter, error::RuntimeError, heap, value::Value};
+use crate::std_lib::utils::{ensure_string, string_dict};
+use ::toml::{Table, Value as Toml};
+use std::{cell::RefCell, rc::Rc};
+
+// toml.parse(text): 解析 TOML 文档。toml.parse(String) -> Dict
+// 表 -> Dict（保持书写顺序），数组 -> List，日期时间 -> 原样的 String
+pub fn parse(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let text = ensure_string(&args[0])?;
+    let table: Table = text
+        .parse()
+        .map_err(|e| RuntimeError::Generic(format!("Invalid TOML: {}", e)))?;
+    Ok(from_table(table))
+}
+
+fn from_table(table: Table) -> Value {
+    let dict = string_dict(table.into_iter().map(|(k, v)| (k, from_toml(v))));
+    heap::track(&dict);
+    dict
+}
+
+fn from_toml(toml: Toml) -> Value {
+    match toml {
+        Toml::String(s) => Value::String(s),
+        Toml::Integer(n) => Value::Number(n as f64),
+        Toml::Float(n) => Value::Number(n),
+        Toml::Boolean(b) => Value::Boolean(b),
+        Toml::Datetime(dt) => Value::String(dt.to_string()),
+        Toml::Array(items) => {
+            let list = Value::List(Rc::new(RefCell::new(
+                items.into_iter().map(from_toml).collect(),
+            )));
+            heap::track(&list);
+            list
+        }
+        Toml::Table(table) => from_table(table),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse() {
+        let code = r#"
+            var text = "title = 'demo'
+port = 8080
+ratio = 0.5
+started = 2026-01-02T03:04:05Z
+
+[server]
+hosts = ['a', 'b']
+debug = true
+
+[[users]]
+name = 'ann'
+
+[[users]]
+name = 'bob'
+";
+            var config = toml.parse(text);
+            var res = [
+                config.keys(), config["port"], config["ratio"], config["started"],
+                config["server"]["hosts"], config["server"]["debug"], config["users"][1]["name"],
+                toml.parse("")
+            ];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[[title, port, ratio, started, server, users], 8080, 0.5, 2026-01-02T03:04:05Z, [a, b], true, bob, {}]"
+        );
+        assert!(eval_res(r#"var res = toml.parse("key = ");"#).is_err());
+        assert!(
+            eval_res(
+                r#"var res = toml.parse("a = 1
+a = 2");"#
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,14 @@
+mod methods;
+
+use crate::{
+    evaluate::value::{NativeFn, Value},
+    std_lib::utils,
+};
+
+/// toml 模块：把 TOML 配置文本解析为嵌套的 Dict
+const FUNCTIONS: &[(&str, usize, NativeFn)] = &[("parse", 1, methods::parse)];
+
+// 创建 toml 模块对象
+pub fn create_module() -> Value {
+    utils::native_module("toml", FUNCTIONS)
+}
@@ -0,0 +1,233 @@
+use crate::evaluate::{Interpreter, error::RuntimeError, heap, value::Value};
+use crate::std_lib::utils::{ensure_list, ensure_string, string_dict};
+use std::{cell::RefCell, mem, rc::Rc};
+
+// csv.parse(text, header): 解析 CSV 文本，字段一律为字符串，空行被跳过。csv.parse(String, Boolean) -> List
+// header 为 false 时每行是一个 List；为 true 时第一行作为列名，其余每行是 {列名: 字段} 的 Dict
+pub fn parse(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let text = ensure_string(&args[0])?;
+    let header = match &args[1] {
+        Value::Boolean(b) => *b,
+        _ => {
+            return Err(RuntimeError::TypeError(
+                "CSV header option must be a boolean.".into(),
+            ));
+        }
+    };
+
+    let mut rows = parse_rows(text)?.into_iter();
+    let values = if header {
+        let names = rows.next().unwrap_or_default();
+        rows.enumerate()
+            .map(|(i, row)| {
+                if row.len() != names.len() {
+                    return Err(RuntimeError::Generic(format!(
+                        "CSV record {} has {} fields, expected {}.",
+                        i + 1,
+                        row.len(),
+                        names.len()
+                    )));
+                }
+                let dict = string_dict(
+                    names
+                        .iter()
+                        .cloned()
+                        .zip(row.into_iter().map(Value::String)),
+                );
+                heap::track(&dict);
+                Ok(dict)
+            })
+            .collect::<Result<Vec<_>, _>>()?
+    } else {
+        rows.map(|row| tracked_list(row.into_iter().map(Value::String).collect()))
+            .collect()
+    };
+    Ok(tracked_list(values))
+}
+
+fn tracked_list(items: Vec<Value>) -> Value {
+    let list = Value::List(Rc::new(RefCell::new(items)));
+    heap::track(&list);
+    list
+}
+
+/// 逐字符解析：引号内的逗号、换行和 `""`（转义的引号）都属于字段内容
+fn parse_rows(text: &str) -> Result<Vec<Vec<String>>, RuntimeError> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    // 当前行是否已经读到内容，用于跳过空行
+    let mut started = false;
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                if started {
+                    row.push(mem::take(&mut field));
+                    rows.push(mem::take(&mut row));
+                    started = false;
+                }
+            }
+            ',' => {
+                row.push(mem::take(&mut field));
+                started = true;
+            }
+            '"' if field.is_empty() => {
+                in_quotes = true;
+                started = true;
+            }
+            _ => {
+                field.push(c);
+                started = true;
+            }
+        }
+    }
+
+    if in_quotes {
+        return Err(RuntimeError::Generic(
+            "Unterminated quoted field in CSV.".into(),
+        ));
+    }
+    if started {
+        row.push(field);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+// csv.stringify(rows): 生成 CSV 文本，每行以换行结尾。csv.stringify(List) -> String
+// 行可以是 List / Tuple；也可以都是 Dict，此时以第一行的键作为表头，缺少的字段写为空
+// nil 写为空字段，其他非字符串值按打印形式写出
+pub fn stringify(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let rows = ensure_list(&args[0])?.borrow().clone();
+    let mut out = String::new();
+
+    let header = match rows.first() {
+        Some(Value::Dict(first)) => {
+            let names = first
+                .borrow()
+                .values()
+                .map(|(key, _)| key.clone())
+                .collect::<Vec<_>>();
+            write_record(&mut out, &names);
+            Some(names)
+        }
+        _ => None,
+    };
+
+    for row in &rows {
+        let cells = match (row, &header) {
+            (Value::List(items), None) => items.borrow().clone(),
+            (Value::Tuple(items), None) => items.to_vec(),
+            (Value::Dict(dict), Some(names)) => {
+                let dict = dict.borrow();
+                names
+                    .iter()
+                    .map(|name| {
+                        dict.values()
+                            .find(|(key, _)| key == name)
+                            .map_or(Value::Nil, |(_, value)| value.clone())
+                    })
+                    .collect()
+            }
+            (other, _) => {
+                return Err(RuntimeError::TypeError(format!(
+                    "CSV rows must all be Lists or all be Dicts, got {}.",
+                    other.type_name()
+                )));
+            }
+        };
+        write_record(&mut out, &cells);
+    }
+    Ok(Value::String(out))
+}
+
+fn write_record(out: &mut String, cells: &[Value]) {
+    for (i, cell) in cells.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        let text = match cell {
+            Value::Nil => String::new(),
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        if text.contains([',', '"', '\n', '\r']) {
+            out.push('"');
+            out.push_str(&text.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(&text);
+        }
+    }
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse() {
+        // Rox 字符串不支持转义，引号用 base64 解码得到
+        let code = r#"
+            var q = base64.decode("Ig==");
+            var text = "name,note
+ann," + q + "hi, " + q + q + "you" + q + q + q + "
+
+bob," + q + "two
+lines" + q + "
+";
+            var rows = csv.parse(text, false);
+            var records = csv.parse(text, true);
+            var res = [rows.len(), rows[1], rows[2][1], records[0]["note"], records[1]["name"], csv.parse("", true)];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[3, [ann, hi, \"you\"], two\nlines, hi, \"you\", bob, []]"
+        );
+        assert!(
+            eval_res(r#"var res = csv.parse("a," + base64.decode("Ig==") + "b", false);"#).is_err()
+        );
+        assert!(eval_res(r#"var res = csv.parse("a,b,c", true) + csv.parse("a", 1);"#).is_err());
+    }
+
+    #[test]
+    fn test_stringify() {
+        let code = r#"
+            var q = base64.decode("Ig==");
+            var rows = [["a", "b,c"], (1, nil), ["say " + q + "hi" + q, true]];
+            var text = csv.stringify(rows);
+            var res = [text, csv.parse(text, false) == [["a", "b,c"], ["1", ""], ["say " + q + "hi" + q, "true"]]];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[a,\"b,c\"\n1,\n\"say \"\"hi\"\"\",true\n, true]"
+        );
+
+        let code = r#"
+            var res = csv.stringify([{"id": 1, "name": "ann"}, {"name": "bob"}]);
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "id,name\n1,ann\n,bob\n"
+        );
+        assert!(eval_res(r#"var res = csv.stringify([["a"], {"b": 1}]);"#).is_err());
+    }
+}
@@ -0,0 +1,17 @@
+mod methods;
+
+use crate::{
+    evaluate::value::{NativeFn, Value},
+    std_lib::utils,
+};
+
+/// csv 模块：按 RFC 4180 解析与生成 CSV 文本（逗号分隔，双引号转义）
+const FUNCTIONS: &[(&str, usize, NativeFn)] = &[
+    ("parse", 2, methods::parse),
+    ("stringify", 1, methods::stringify),
+];
+
+// 创建 csv 模块对象
+pub fn create_module() -> Value {
+    utils::native_module("csv", FUNCTIONS)
+}
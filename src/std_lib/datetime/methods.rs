@@ -0,0 +1,325 @@
+use crate::std_lib::{
+    Interpreter, Value, error::RuntimeError, globals, i18n::civil_from_days, utils::ensure_string,
+};
+
+/// 支持的范围：0001-01-01T00:00:00Z 到 9999-12-31T23:59:59Z
+const MIN_SECONDS: f64 = -62_135_596_800.0;
+const MAX_SECONDS: f64 = 253_402_300_800.0;
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// 拆分后的日期与时间分量
+struct Fields {
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    /// 1 = 周一 … 7 = 周日 (ISO 8601)
+    weekday: u32,
+    millis: u32,
+}
+
+impl Fields {
+    fn of(seconds: f64) -> Self {
+        let whole = seconds.floor();
+        let days = (whole as i64).div_euclid(86_400);
+        let time = (whole as i64).rem_euclid(86_400) as u32;
+        let (year, month, day) = civil_from_days(days);
+        Self {
+            year,
+            month,
+            day,
+            hour: time / 3_600,
+            minute: time / 60 % 60,
+            second: time % 60,
+            // 1970-01-01 是周四
+            weekday: ((days + 3).rem_euclid(7) + 1) as u32,
+            millis: (((seconds - whole) * 1_000.0).floor() as u32).min(999),
+        }
+    }
+}
+
+/// 公历日期转换为 1970-01-01 起的天数 (Howard Hinnant 的 days_from_civil 算法)
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = i64::from((month + 9) % 12);
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// ISO 8601 表示：`2024-03-01T12:30:00Z`，有毫秒时带上 `.250`
+pub fn render(seconds: f64) -> String {
+    let f = Fields::of(seconds);
+    let millis = if f.millis == 0 {
+        String::new()
+    } else {
+        format!(".{:03}", f.millis)
+    };
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}Z",
+        f.year, f.month, f.day, f.hour, f.minute, f.second, millis
+    )
+}
+
+/// 检查范围后构造 DateTime
+fn date_time(seconds: f64) -> Result<Value, RuntimeError> {
+    if !(MIN_SECONDS..MAX_SECONDS).contains(&seconds) {
+        return Err(RuntimeError::Generic(
+            "DateTime out of range (years 1 to 9999).".into(),
+        ));
+    }
+    Ok(Value::DateTime(seconds))
+}
+
+fn ensure_date_time(value: &Value) -> Result<f64, RuntimeError> {
+    match value {
+        Value::DateTime(seconds) => Ok(*seconds),
+        other => Err(RuntimeError::TypeError(format!(
+            "Expected DateTime, got {}.",
+            other.type_name()
+        ))),
+    }
+}
+
+fn duration(value: &Value) -> Result<f64, RuntimeError> {
+    match value {
+        Value::Number(n) if n.is_finite() => Ok(*n),
+        _ => Err(RuntimeError::TypeError(
+            "Duration must be a finite number of seconds.".into(),
+        )),
+    }
+}
+
+fn unknown_directive(c: Option<char>) -> RuntimeError {
+    match c {
+        Some(c) => RuntimeError::Generic(format!("Unknown format directive '%{}'.", c)),
+        None => RuntimeError::Generic("Format ends with a lone '%'.".into()),
+    }
+}
+
+/// 按 strftime 风格的格式输出：%Y %m %d %H %M %S %a %b %%
+fn format_fields(seconds: f64, fmt: &str) -> Result<String, RuntimeError> {
+    let f = Fields::of(seconds);
+    let mut out = String::new();
+    let mut chars = fmt.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('Y') => out.push_str(&format!("{:04}", f.year)),
+            Some('m') => out.push_str(&format!("{:02}", f.month)),
+            Some('d') => out.push_str(&format!("{:02}", f.day)),
+            Some('H') => out.push_str(&format!("{:02}", f.hour)),
+            Some('M') => out.push_str(&format!("{:02}", f.minute)),
+            Some('S') => out.push_str(&format!("{:02}", f.second)),
+            Some('a') => out.push_str(WEEKDAYS[f.weekday as usize - 1]),
+            Some('b') => out.push_str(MONTHS[f.month as usize - 1]),
+            Some('%') => out.push('%'),
+            other => return Err(unknown_directive(other)),
+        }
+    }
+    Ok(out)
+}
+
+/// 按格式解析，格式中未出现的分量取 1970-01-01 00:00:00 的对应值
+fn parse_fields(fmt: &str, text: &str) -> Result<f64, RuntimeError> {
+    let mismatch =
+        || RuntimeError::Generic(format!("Cannot parse '{}' with format '{}'.", text, fmt));
+    let (mut year, mut month, mut day) = (1970, 1, 1);
+    let (mut hour, mut minute, mut second) = (0, 0, 0);
+
+    let mut rest = text;
+    // 读取 min..=max 位数字
+    let number = |rest: &mut &str, min: usize, max: usize| {
+        let len = rest
+            .bytes()
+            .take(max)
+            .take_while(u8::is_ascii_digit)
+            .count();
+        if len < min {
+            return Err(mismatch());
+        }
+        let (digits, tail) = rest.split_at(len);
+        *rest = tail;
+        digits.parse::<u32>().map_err(|_| mismatch())
+    };
+    let name = |rest: &mut &str, names: &[&str]| {
+        let position = names.iter().position(|name| {
+            rest.get(..3)
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(name))
+        });
+        *rest = rest.get(3..).unwrap_or_default();
+        position.map(|i| i as u32 + 1).ok_or_else(mismatch)
+    };
+
+    let mut chars = fmt.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            rest = rest.strip_prefix(c).ok_or_else(mismatch)?;
+            continue;
+        }
+        match chars.next() {
+            Some('Y') => year = i64::from(number(&mut rest, 4, 4)?),
+            Some('m') => month = number(&mut rest, 1, 2)?,
+            Some('d') => day = number(&mut rest, 1, 2)?,
+            Some('H') => hour = number(&mut rest, 1, 2)?,
+            Some('M') => minute = number(&mut rest, 1, 2)?,
+            Some('S') => second = number(&mut rest, 1, 2)?,
+            // 星期由日期决定，只校验名称
+            Some('a') => _ = name(&mut rest, &WEEKDAYS)?,
+            Some('b') => month = name(&mut rest, &MONTHS)?,
+            Some('%') => rest = rest.strip_prefix('%').ok_or_else(mismatch)?,
+            other => return Err(unknown_directive(other)),
+        }
+    }
+
+    let valid = rest.is_empty()
+        && year >= 1
+        && (1..=12).contains(&month)
+        && (1..=days_in_month(year, month)).contains(&day)
+        && hour < 24
+        && minute < 60
+        && second < 60;
+    if !valid {
+        return Err(mismatch());
+    }
+
+    let days = days_from_civil(year, month, day);
+    Ok((days * 86_400 + i64::from(hour * 3_600 + minute * 60 + second)) as f64)
+}
+
+// datetime.now(): 当前时间（与 clock() 一致，确定性模式下使用假时钟）。datetime.now() -> DateTime
+pub fn now(interpreter: &mut Interpreter, _: Vec<Value>) -> Result<Value, RuntimeError> {
+    let Value::Number(now) = globals::clock(interpreter, vec![])? else {
+        unreachable!("clock() always returns a Number");
+    };
+    date_time(now)
+}
+
+// datetime.parse(fmt, text): 按格式解析 UTC 时间，不匹配时报错。datetime.parse(String, String) -> DateTime
+pub fn parse(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let fmt = ensure_string(&args[0])?;
+    let text = ensure_string(&args[1])?;
+    date_time(parse_fields(fmt, text)?)
+}
+
+// datetime.fromTimestamp(seconds): epoch 秒（eg. time.now()）转换为 DateTime。datetime.fromTimestamp(Number) -> DateTime
+pub fn from_timestamp(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    date_time(duration(&args[0])?)
+}
+
+// d.year() / d.month() / ... : 日期与时间分量（UTC）-> Number
+macro_rules! field {
+    ($name:ident) => {
+        pub fn $name(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+            let fields = Fields::of(ensure_date_time(&args[0])?);
+            Ok(Value::Number(fields.$name as f64))
+        }
+    };
+}
+
+field!(year);
+field!(month);
+field!(day);
+field!(hour);
+field!(minute);
+field!(second);
+// d.weekday(): 1 = 周一 … 7 = 周日
+field!(weekday);
+
+// d.timestamp(): epoch 秒 -> Number
+pub fn timestamp(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    Ok(Value::Number(ensure_date_time(&args[0])?))
+}
+
+// d.format(fmt): 格式化为字符串。d.format(String) -> String
+pub fn format(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let seconds = ensure_date_time(&args[0])?;
+    let fmt = ensure_string(&args[1])?;
+    Ok(Value::String(format_fields(seconds, fmt)?))
+}
+
+// d.add(seconds): 之后 seconds 秒的时间。d.add(Number) -> DateTime
+pub fn add(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    date_time(ensure_date_time(&args[0])? + duration(&args[1])?)
+}
+
+// d.subtract(seconds): 之前 seconds 秒的时间。d.subtract(Number) -> DateTime
+pub fn subtract(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    date_time(ensure_date_time(&args[0])? - duration(&args[1])?)
+}
+
+// d.diff(other): 两个时间相差的秒数 (d - other)。d.diff(DateTime) -> Number
+pub fn diff(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    Ok(Value::Number(
+        ensure_date_time(&args[0])? - ensure_date_time(&args[1])?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_format_and_fields() {
+        let code = r#"
+            var d = datetime.parse("%Y-%m-%d %H:%M:%S", "2024-02-28 23:30:05");
+            var next = d.add(datetime.DAY + 0.25);
+            var res = [
+                d, next, d.year(), d.month(), d.day(), d.hour(), d.minute(), d.second(), d.weekday(),
+                next.format("%a %d %b %Y, %H:%M (%%)"), next.subtract(datetime.WEEK).day(),
+                next.diff(d), d < next, d == datetime.fromTimestamp(d.timestamp()),
+                datetime.parse("%d/%b/%Y", "1/mar/2000"), datetime.fromTimestamp(-1), type(d)
+            ];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[2024-02-28T23:30:05Z, 2024-02-29T23:30:05.250Z, 2024, 2, 28, 23, 30, 5, 3, \
+             Thu 29 Feb 2024, 23:30 (%), 22, 86400.25, true, true, \
+             2000-03-01T00:00:00Z, 1969-12-31T23:59:59Z, datetime]"
+        );
+    }
+
+    #[test]
+    fn test_invalid_dates_are_errors() {
+        for code in [
+            r#"datetime.parse("%Y-%m-%d", "2023-02-29");"#,
+            r#"datetime.parse("%Y-%m-%d", "2023-02-01x");"#,
+            r#"datetime.parse("%H:%M", "24:00");"#,
+            r#"datetime.parse("%Q", "1");"#,
+            r#"datetime.now().format("%");"#,
+            r#"datetime.fromTimestamp(1e20);"#,
+            r#"datetime.now().add("1");"#,
+        ] {
+            assert!(eval_res(code).is_err(), "{}", code);
+        }
+    }
+
+    #[test]
+    fn test_now_is_a_date_time() {
+        let code = r#"
+            var res = datetime.now().timestamp() > 0;
+        "#;
+        assert_eq!(eval_res(code).unwrap().to_string(), "true");
+    }
+}
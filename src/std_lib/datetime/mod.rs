@@ -0,0 +1,52 @@
+pub mod methods;
+
+use crate::{
+    evaluate::value::{NativeFn, Value},
+    native_fn,
+    std_lib::utils,
+};
+
+pub use methods::render;
+
+/// datetime 模块：UTC 时间点，内部保存 epoch 秒。时长统一用秒（Number）表示
+const FUNCTIONS: &[(&str, usize, NativeFn)] = &[
+    ("now", 0, methods::now),
+    ("parse", 2, methods::parse),
+    ("fromTimestamp", 1, methods::from_timestamp),
+];
+
+/// DateTime 上的方法：分量访问、格式化与时长运算
+pub fn lookup(name: &str) -> Option<Value> {
+    match name {
+        "year" => native_fn!("year", 0, methods::year),
+        "month" => native_fn!("month", 0, methods::month),
+        "day" => native_fn!("day", 0, methods::day),
+        "hour" => native_fn!("hour", 0, methods::hour),
+        "minute" => native_fn!("minute", 0, methods::minute),
+        "second" => native_fn!("second", 0, methods::second),
+        "weekday" => native_fn!("weekday", 0, methods::weekday),
+        "timestamp" => native_fn!("timestamp", 0, methods::timestamp),
+        "format" => native_fn!("format", 1, methods::format),
+        "add" => native_fn!("add", 1, methods::add),
+        "subtract" => native_fn!("subtract", 1, methods::subtract),
+        "diff" => native_fn!("diff", 1, methods::diff),
+        _ => None,
+    }
+}
+
+// 创建 datetime 模块对象
+pub fn create_module() -> Value {
+    let mut exports = utils::native_exports(FUNCTIONS.iter().copied());
+
+    // 常用时长（秒），eg. `d.add(2 * datetime.DAY)`
+    for (name, seconds) in [
+        ("MINUTE", 60.0),
+        ("HOUR", 3_600.0),
+        ("DAY", 86_400.0),
+        ("WEEK", 604_800.0),
+    ] {
+        exports.insert(name.to_string(), Value::Number(seconds));
+    }
+
+    utils::new_module("datetime", exports)
+}
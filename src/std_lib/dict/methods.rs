@@ -4,10 +4,9 @@ use std::{cell::RefCell, rc::Rc};
 pub fn keys(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
     let dict = ensure_dict(&args[0])?;
     let borrowed_dict = dict.borrow();
-    let keys = borrowed_dict.keys();
-
-    let rox_keys = keys
-        .map(|k| Value::String(k.clone()))
+    let rox_keys = borrowed_dict
+        .values()
+        .map(|(k, _)| k.clone())
         .collect::<Vec<Value>>();
 
     Ok(Value::List(Rc::new(RefCell::new(rox_keys))))
@@ -16,28 +15,74 @@ pub fn keys(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError
 pub fn values(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
     let dict = ensure_dict(&args[0])?;
     let borrowed_dict = dict.borrow();
-    let values = borrowed_dict.values();
-
-    let rox_values = values.cloned().collect::<Vec<Value>>();
+    let rox_values = borrowed_dict
+        .values()
+        .map(|(_, v)| v.clone())
+        .collect::<Vec<Value>>();
 
     Ok(Value::List(Rc::new(RefCell::new(rox_values))))
 }
 
-pub fn has(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+pub fn has(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
     let dict = ensure_dict(&args[0])?;
-    Ok(Value::Boolean(
-        dict.borrow().get(&args[1].to_string()).is_some(),
-    ))
+    Ok(Value::Boolean(interpreter.dict_contains(dict, &args[1])?))
 }
 
-pub fn remove(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+pub fn remove(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
     let dict = ensure_dict(&args[0])?;
-    Ok(dict
-        .borrow_mut()
-        .remove(&args[1].to_string())
+    Ok(interpreter
+        .dict_remove(dict, &args[1])?
         .unwrap_or(Value::Nil))
 }
 
+// dict.get(key, default): 键存在时返回对应的值，否则返回 default
+pub fn get(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let dict = ensure_dict(&args[0])?;
+    Ok(interpreter
+        .dict_get(dict, &args[1])?
+        .unwrap_or_else(|| args[2].clone()))
+}
+
+// dict.merge(other): 返回合并后的新字典，键相同时取 other 中的值，两个原字典都不变
+pub fn merge(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let merged = RefCell::new(ensure_dict(&args[0])?.borrow().clone());
+    let entries: Vec<_> = ensure_dict(&args[1])?.borrow().values().cloned().collect();
+    for (key, value) in entries {
+        interpreter.dict_insert(&merged, key, value)?;
+    }
+    Ok(Value::Dict(Rc::new(merged)))
+}
+
+// dict.entries(): [键, 值] 组成的列表，按插入顺序 -> List
+pub fn entries(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let dict = ensure_dict(&args[0])?;
+    let pairs = dict
+        .borrow()
+        .values()
+        .map(|(k, v)| Value::List(Rc::new(RefCell::new(vec![k.clone(), v.clone()]))))
+        .collect::<Vec<Value>>();
+
+    Ok(Value::List(Rc::new(RefCell::new(pairs))))
+}
+
+// dict.clear(): 删除所有条目
+pub fn clear(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    ensure_dict(&args[0])?.borrow_mut().clear();
+    Ok(Value::Nil)
+}
+
+// dict.copy(): 深拷贝，值中嵌套的容器同样被复制 -> Dict
+pub fn copy(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    ensure_dict(&args[0])?;
+    Ok(args[0].deep_copy())
+}
+
+// dict.len(): 条目数量 -> Number
+pub fn len(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let dict = ensure_dict(&args[0])?;
+    Ok(Value::Number(dict.borrow().len() as f64))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::std_lib::{Value, eval_res};
@@ -115,4 +160,46 @@ mod tests {
 
         assert_eq!(eval_res(ast).unwrap(), Value::Number(1.0));
     }
+
+    #[test]
+    fn test_dict_get_and_len_methods() {
+        let ast = r#"
+            var dict = {"a": 1, "b": nil};
+            var res = [dict.get("a", 0), dict.get("b", 0), dict.get("z", 0), dict.len(), {}.len()];
+        "#;
+
+        assert_eq!(eval_res(ast).unwrap().to_string(), "[1, nil, 0, 2, 0]");
+    }
+
+    #[test]
+    fn test_dict_merge_entries_clear_methods() {
+        let ast = r#"
+            var base = {"a": 1, "b": 2};
+            var merged = base.merge({"b": 20, "c": 30});
+            var entries = merged.entries();
+            base.clear();
+            var res = [entries, merged.len(), base.len(), base];
+        "#;
+
+        assert_eq!(
+            eval_res(ast).unwrap().to_string(),
+            "[[[a, 1], [b, 20], [c, 30]], 3, 0, {}]"
+        );
+    }
+
+    #[test]
+    fn test_dict_copy_method() {
+        let ast = r#"
+            var base = {"a": [1]};
+            var copy = base.copy();
+            copy["a"].push(2);
+            copy["b"] = 3;
+            var res = [base, copy];
+        "#;
+
+        assert_eq!(
+            eval_res(ast).unwrap().to_string(),
+            "[{a: [1]}, {a: [1, 2], b: 3}]"
+        );
+    }
 }
@@ -1,5 +1,5 @@
 pub mod methods;
-use crate::native_fn;
+use crate::{native_fn, std_lib::iterator};
 
 pub fn lookup(name: &str) -> Option<super::Value> {
     match name {
@@ -7,6 +7,13 @@ pub fn lookup(name: &str) -> Option<super::Value> {
         "values" => native_fn!("values", 0, methods::values),
         "remove" => native_fn!("remove", 1, methods::remove),
         "has" => native_fn!("has", 1, methods::has),
+        "get" => native_fn!("get", 2, methods::get),
+        "merge" => native_fn!("merge", 1, methods::merge),
+        "entries" => native_fn!("entries", 0, methods::entries),
+        "copy" => native_fn!("copy", 0, methods::copy),
+        "clear" => native_fn!("clear", 0, methods::clear),
+        "len" => native_fn!("len", 0, methods::len),
+        "iter" => native_fn!("iter", 0, iterator::methods::collection_iter),
         _ => None,
     }
 }
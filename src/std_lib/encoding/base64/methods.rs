@@ -0,0 +1,83 @@
+use crate::evaluate::{Interpreter, error::RuntimeError, heap, value::Value};
+use crate::std_lib::utils::ensure_string;
+use ::base64::{Engine, engine::general_purpose::STANDARD};
+use std::{cell::RefCell, rc::Rc};
+
+/// 字符串按 UTF-8 取字节；Bytes 原样使用；列表视为字节序列，每个元素必须是 0..=255 的整数
+fn bytes_of(value: &Value) -> Result<Vec<u8>, RuntimeError> {
+    match value {
+        Value::String(s) => Ok(s.as_bytes().to_vec()),
+        Value::Bytes(bytes) => Ok(bytes.to_vec()),
+        Value::List(list) => list
+            .borrow()
+            .iter()
+            .map(|item| match item {
+                Value::Number(n) if n.fract() == 0.0 && (0.0..=255.0).contains(n) => Ok(*n as u8),
+                _ => Err(RuntimeError::TypeError(
+                    "Bytes must be integers between 0 and 255.".into(),
+                )),
+            })
+            .collect(),
+        other => Err(RuntimeError::TypeError(format!(
+            "base64.encode expects a String, Bytes or a List of bytes, got {}.",
+            other.type_name()
+        ))),
+    }
+}
+
+fn decode_text(value: &Value) -> Result<Vec<u8>, RuntimeError> {
+    STANDARD
+        .decode(ensure_string(value)?)
+        .map_err(|e| RuntimeError::Generic(format!("Invalid base64: {}.", e)))
+}
+
+// base64.encode(data): 编码字符串（UTF-8）、Bytes 或字节列表。base64.encode(String | Bytes | List) -> String
+pub fn encode(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    Ok(Value::String(STANDARD.encode(bytes_of(&args[0])?)))
+}
+
+// base64.decode(text): 解码为字符串，结果不是合法 UTF-8 时报错（改用 decodeBytes）。base64.decode(String) -> String
+pub fn decode(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    String::from_utf8(decode_text(&args[0])?)
+        .map(Value::String)
+        .map_err(|_| {
+            RuntimeError::Generic(
+                "Decoded base64 is not valid UTF-8; use base64.decodeBytes.".into(),
+            )
+        })
+}
+
+// base64.decodeBytes(text): 解码为字节列表。base64.decodeBytes(String) -> List
+pub fn decode_bytes(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let bytes = decode_text(&args[0])?;
+    let list = Value::List(Rc::new(RefCell::new(
+        bytes.into_iter().map(|b| Value::Number(b as f64)).collect(),
+    )));
+    heap::track(&list);
+    Ok(list)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_base64_round_trip() {
+        let code = r#"
+            var res = [
+                base64.encode("user:pass"), base64.decode("dXNlcjpwYXNz"),
+                base64.encode(""), base64.encode([0, 255, 16]), base64.decodeBytes("AP8Q"),
+                base64.decode(base64.encode("héllo")), base64.encode(bytes([0, 255, 16]))
+            ];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[dXNlcjpwYXNz, user:pass, , AP8Q, [0, 255, 16], héllo, AP8Q]"
+        );
+        assert!(eval_res(r#"var res = base64.decode("not base64!");"#).is_err());
+        assert!(eval_res(r#"var res = base64.decode("/w==");"#).is_err());
+        assert!(eval_res("var res = base64.encode([256]);").is_err());
+        assert!(eval_res("var res = base64.encode(1);").is_err());
+    }
+}
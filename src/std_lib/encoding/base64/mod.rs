@@ -0,0 +1,18 @@
+mod methods;
+
+use crate::{
+    evaluate::value::{NativeFn, Value},
+    std_lib::utils,
+};
+
+/// base64 模块：标准字母表、带 `=` 填充的 Base64 编解码
+const FUNCTIONS: &[(&str, usize, NativeFn)] = &[
+    ("encode", 1, methods::encode),
+    ("decode", 1, methods::decode),
+    ("decodeBytes", 1, methods::decode_bytes),
+];
+
+// 创建 base64 模块对象
+pub fn create_module() -> Value {
+    utils::native_module("base64", FUNCTIONS)
+}
@@ -0,0 +1,140 @@
+use crate::evaluate::{Interpreter, error::RuntimeError, value::Value};
+use crate::std_lib::{
+    io::trace::traced,
+    utils::{ensure_string, string_dict},
+};
+use std::{env, fs};
+
+/// 解析 `.env` 文本，按出现顺序返回 (键, 值)
+///
+/// 每行一个 `KEY=VALUE`，可带 `export ` 前缀；空行与 `#` 开头的行被忽略。
+/// 双引号值支持 `\n`、`\t`、`\"`、`\\` 转义，单引号值按原样保留，
+/// 未加引号的值去掉首尾空白以及 ` #` 之后的行尾注释。不做变量展开。
+fn parse_env(text: &str) -> Result<Vec<(String, String)>, String> {
+    let mut pairs = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = |what: &str| format!("line {}: {}", index + 1, what);
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, raw) = line
+            .split_once('=')
+            .ok_or_else(|| invalid("expected KEY=VALUE"))?;
+        let key = key.trim();
+        let valid_key = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+        if !valid_key {
+            return Err(invalid(&format!("invalid name '{}'", key)));
+        }
+
+        let raw = raw.trim();
+        let value = match raw.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let mut value = String::new();
+                let mut chars = raw[1..].chars();
+                loop {
+                    match chars.next() {
+                        None => return Err(invalid("unterminated quoted value")),
+                        Some(c) if c == quote => break,
+                        Some('\\') if quote == '"' => match chars.next() {
+                            Some('n') => value.push('\n'),
+                            Some('t') => value.push('\t'),
+                            Some('r') => value.push('\r'),
+                            Some(c) => value.push(c),
+                            None => return Err(invalid("unterminated quoted value")),
+                        },
+                        Some(c) => value.push(c),
+                    }
+                }
+                let rest = chars.as_str().trim();
+                if !rest.is_empty() && !rest.starts_with('#') {
+                    return Err(invalid("unexpected text after quoted value"));
+                }
+                value
+            }
+            _ => match raw.find(" #") {
+                Some(comment) => raw[..comment].trim_end().to_string(),
+                None => raw.to_string(),
+            },
+        };
+        pairs.push((key.to_string(), value));
+    }
+    Ok(pairs)
+}
+
+// env.load(path): 读取 `.env` 文件并写入进程环境变量，已存在的变量保持不变。
+// 返回文件中的全部键值（不论是否写入）。env.load(String) -> Dict
+pub fn load(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path = ensure_string(&args[0])?;
+
+    traced(interpreter, "env.load", &[path], || {
+        let text = fs::read_to_string(path)
+            .map_err(|e| RuntimeError::Generic(format!("Failed to read '{}': {}", path, e)))?;
+        let pairs = parse_env(&text)
+            .map_err(|e| RuntimeError::Generic(format!("Invalid env file '{}', {}.", path, e)))?;
+
+        for (key, value) in &pairs {
+            if env::var_os(key).is_none() && !value.contains('\0') {
+                // SAFETY: 同 os.setenv，环境变量只在解释器线程中读写
+                unsafe { env::set_var(key, value) };
+            }
+        }
+        Ok(string_dict(
+            pairs
+                .into_iter()
+                .map(|(key, value)| (key, Value::String(value))),
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_load_env_file() {
+        let file = std::env::temp_dir().join(format!("rox_env_{}.env", std::process::id()));
+        std::fs::write(
+            &file,
+            "# settings\n\
+             ROX_ENV_PLAIN = value with spaces # comment\n\
+             export ROX_ENV_QUOTED=\"line\\nnext # kept\"\n\
+             ROX_ENV_SINGLE='raw \\n'\n\
+             \n\
+             ROX_ENV_EMPTY=\n\
+             ROX_ENV_KEEP=from-file\n",
+        )
+        .unwrap();
+        let code = format!(
+            r#"
+            os.setenv("ROX_ENV_KEEP", "existing");
+            var loaded = env.load("{}");
+            var newline = "
+";
+            var res = [
+                loaded.keys(), loaded["ROX_ENV_KEEP"], os.getenv("ROX_ENV_KEEP"),
+                os.getenv("ROX_ENV_PLAIN"), os.getenv("ROX_ENV_QUOTED").split(newline), os.getenv("ROX_ENV_SINGLE"),
+                os.getenv("ROX_ENV_EMPTY")
+            ];
+            "#,
+            file.display()
+        );
+        let result = eval_res(&code);
+        std::fs::write(&file, "NOT VALID\n").unwrap();
+        let invalid = eval_res(&format!(r#"env.load("{}");"#, file.display()));
+        let _ = std::fs::remove_file(&file);
+
+        assert_eq!(
+            result.unwrap().to_string(),
+            "[[ROX_ENV_PLAIN, ROX_ENV_QUOTED, ROX_ENV_SINGLE, ROX_ENV_EMPTY, ROX_ENV_KEEP], \
+             from-file, existing, value with spaces, [line, next # kept], raw \\n, ]"
+        );
+        assert!(invalid.unwrap_err().contains("line 1"));
+    }
+}
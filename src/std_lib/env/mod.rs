@@ -0,0 +1,14 @@
+mod methods;
+
+use crate::{
+    evaluate::value::{NativeFn, Value},
+    std_lib::utils,
+};
+
+/// env 模块：从 `.env` 文件加载本地配置到进程环境变量（读写单个变量使用 `os.getenv` / `os.setenv`）
+const FUNCTIONS: &[(&str, usize, NativeFn)] = &[("load", 1, methods::load)];
+
+// 创建 env 模块对象
+pub fn create_module() -> Value {
+    utils::native_module("env", FUNCTIONS)
+}
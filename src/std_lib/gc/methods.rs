@@ -0,0 +1,107 @@
+use crate::{
+    evaluate::{Interpreter, error::RuntimeError, heap, value::Value},
+    std_lib::utils::string_dict,
+};
+
+// gc.stats(): 统计可达对象。gc.stats() -> Dict
+// { "objects": { "List": 2, ... }, "environments": Number, "bytes": Number }
+pub fn stats(interpreter: &mut Interpreter, _: Vec<Value>) -> Result<Value, RuntimeError> {
+    let stats = interpreter.heap_stats();
+
+    let objects = string_dict(
+        stats
+            .objects
+            .into_iter()
+            .map(|(kind, count)| (kind.to_string(), Value::Number(count as f64))),
+    );
+
+    Ok(string_dict([
+        ("objects".to_string(), objects),
+        (
+            "environments".to_string(),
+            Value::Number(stats.environments as f64),
+        ),
+        ("bytes".to_string(), Value::Number(stats.bytes as f64)),
+    ]))
+}
+
+// gc.collect(): 回收循环引用垃圾。gc.collect() -> Number (回收的对象数量)
+pub fn collect(_: &mut Interpreter, _: Vec<Value>) -> Result<Value, RuntimeError> {
+    Ok(Value::Number(heap::collect() as f64))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        evaluate::heap,
+        std_lib::{Value, eval_res},
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_stats_counts_shared_objects_once() {
+        let ast = r#"
+		   var a = [1, 2];
+		   var b = a;
+		   var s = gc.stats();
+		   var res = s["objects"]["List"];
+		"#;
+
+        assert_eq!(eval_res(ast).unwrap(), Value::Number(1.0));
+    }
+
+    #[test]
+    fn test_stats_reports_environments_and_bytes() {
+        let ast = r#"
+		   fun make() { var x = "captured"; return fun() { return x; }; }
+		   var f = make();
+		   var s = std.gc.stats();
+		   var res = s["environments"] >= 2 and s["bytes"] > 0;
+		"#;
+
+        assert_eq!(eval_res(ast).unwrap(), Value::Boolean(true));
+    }
+
+    #[test]
+    fn test_collect_instance_cycle() {
+        let ast = r#"
+		   class Node {}
+		   fun leak() { var n = Node(); n.me = n; }
+		   leak();
+		   leak();
+		   var res = gc.collect();
+		"#;
+
+        assert_eq!(eval_res(ast).unwrap(), Value::Number(2.0));
+    }
+
+    #[test]
+    fn test_collect_closure_cycle() {
+        let ast = r#"
+		   fun make() { fun inner() { return 1; } return 0; }
+		   make();
+		   var first = gc.collect();
+		   var res = first > 0 and gc.collect() == 0;
+		"#;
+
+        assert_eq!(eval_res(ast).unwrap(), Value::Boolean(true));
+    }
+
+    #[test]
+    fn test_leak_report_names_retaining_fields() {
+        let ast = r#"
+		   class Node {}
+		   fun leak() { var n = Node(); n.next = n; }
+		   leak();
+		   var res = nil;
+		"#;
+        eval_res(ast).unwrap();
+
+        let report = heap::leak_report();
+        assert!(
+            report.contains(&"instance of Node (fields: next)".to_string()),
+            "{:?}",
+            report
+        );
+    }
+}
@@ -0,0 +1,34 @@
+mod methods;
+
+use crate::evaluate::value::{RoxModule, Value};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+// 创建 gc 模块对象
+pub fn create_module() -> Value {
+    let mut exports = HashMap::new();
+
+    exports.insert(
+        "stats".to_string(),
+        Value::NativeFunction {
+            name: "stats".to_string(),
+            arity: 0,
+            func: methods::stats,
+        },
+    );
+    exports.insert(
+        "collect".to_string(),
+        Value::NativeFunction {
+            name: "collect".to_string(),
+            arity: 0,
+            func: methods::collect,
+        },
+    );
+
+    let module = RoxModule {
+        name: "gc".to_string(),
+        exports,
+        is_initialized: true,
+    };
+
+    Value::Module(Rc::new(RefCell::new(module)))
+}
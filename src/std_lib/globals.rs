@@ -1,31 +1,395 @@
-use crate::evaluate::{error::RuntimeError, interpreter::Interpreter, value::Value};
+use crate::evaluate::{
+    bigint::{self as big, from_integral},
+    decimal as dec,
+    error::RuntimeError,
+    interpreter::Interpreter,
+    value::{NativeFn, VARIADIC, Value},
+};
+use crate::std_lib::{
+    bytes,
+    io::trace::traced,
+    set, string_builder,
+    utils::{ensure_string, string_dict},
+};
+use num_traits::ToPrimitive;
+use std::collections::BTreeMap;
 use std::io::{self, Write};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::rc::Rc;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// 全局函数清单 (名称, 参数个数, 实现)，由注册表安装到预置环境
+pub const FUNCTIONS: &[(&str, usize, NativeFn)] = &[
+    ("clock", 0, clock),
+    ("sleep", 1, sleep),
+    ("exit", VARIADIC, exit),
+    ("input", VARIADIC, input),
+    ("format", VARIADIC, format),
+    ("printf", VARIADIC, printf),
+    ("repr", 1, repr),
+    ("type", 1, type_of),
+    ("deep_copy", 1, deep_copy),
+    ("bigint", 1, bigint),
+    ("decimal", 1, decimal),
+    ("set", 1, set::methods::new),
+    ("bytes", 1, bytes::methods::new),
+    ("isNaN", 1, is_nan),
+    ("isFinite", 1, is_finite),
+    ("parse_number", VARIADIC, parse_number),
+    ("to_number", 1, to_number),
+    ("to_string", VARIADIC, to_string),
+    ("ord", 1, ord),
+    ("chr", 1, chr),
+    ("import", 1, import),
+    ("StringBuilder", 0, string_builder::methods::new),
+    ("globals", 0, globals),
+    ("setGlobal", 2, set_global),
+    ("locals", 0, locals),
+    ("eval", 1, eval),
+    ("include", 1, include),
+];
 
 // clock(): 获取时间戳（用于性能测试）。clock() -> Number
-pub fn clock(_: &mut Interpreter, _: Vec<Value>) -> Result<Value, RuntimeError> {
+pub fn clock(interpreter: &mut Interpreter, _: Vec<Value>) -> Result<Value, RuntimeError> {
+    if let Some(fake) = interpreter.fake_clock {
+        return Ok(Value::Number(fake));
+    }
+
     let start = SystemTime::now();
-    let since_the_epoch = start.duration_since(UNIX_EPOCH).unwrap();
+    // 系统时钟早于 1970 年时返回 0
+    let since_the_epoch = start.duration_since(UNIX_EPOCH).unwrap_or_default();
     Ok(Value::Number(since_the_epoch.as_secs_f64()))
 }
 
-// input(): 读取用户输入。input(prompt) -> String
-pub fn input(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+// sleep(): 阻塞指定的秒数（可为小数）。sleep(seconds) -> Nil
+// 确定性模式下不真正等待，而是把假时钟向前拨动
+pub fn sleep(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let seconds = match args[0] {
+        Value::Number(n) if n.is_finite() && n >= 0.0 => n,
+        _ => {
+            return Err(RuntimeError::TypeError(
+                "sleep() expects a non-negative number of seconds.".into(),
+            ));
+        }
+    };
+
+    // 超出 Duration 范围的时长（eg. 1e300）报错，而不是让 from_secs_f64 panic
+    let duration = Duration::try_from_secs_f64(seconds)
+        .map_err(|_| RuntimeError::Generic(format!("sleep() duration {} is too long.", seconds)))?;
+
+    match interpreter.fake_clock.as_mut() {
+        Some(fake) => *fake += seconds,
+        None => thread::sleep(duration),
+    }
+    Ok(Value::Nil)
+}
+
+// exit(): 结束脚本，进程以 code 退出（默认 0）。exit(code?) -> 不返回
+// 不是立即终止进程：defer 与 finally 仍会执行，且 catch 无法拦截
+pub fn exit(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let code = match args.as_slice() {
+        [] => 0,
+        [Value::Number(n)] if n.fract() == 0.0 && (0.0..=255.0).contains(n) => *n as i32,
+        [_] => {
+            return Err(RuntimeError::TypeError(
+                "Exit code must be an integer between 0 and 255.".into(),
+            ));
+        }
+        _ => {
+            return Err(RuntimeError::Generic(format!(
+                "Expected at most 1 argument but got {}.",
+                args.len()
+            )));
+        }
+    };
+    Err(RuntimeError::Exit(code))
+}
+
+// input(): 打印可选的提示符后从 stdin 读取一行（去掉行尾换行）。input(prompt?) -> String
+pub fn input(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    if args.len() > 1 {
+        return Err(RuntimeError::Generic(format!(
+            "Expected at most 1 argument but got {}.",
+            args.len()
+        )));
+    }
+
     // 如果有提示符 先打印
-    if let Some(prompt) = args.first() {
-        print!("{}", prompt);
-        io::stdout().flush().unwrap();
+    let prompt = args.first().map(Value::to_string).unwrap_or_default();
+    print!("{}", prompt);
+    io::stdout()
+        .flush()
+        .map_err(|e| RuntimeError::Generic(format!("Failed to write prompt: {}", e)))?;
+
+    // stdin（录制 / 回放时经过 IO 追踪）
+    traced(interpreter, "input", &[&prompt], || {
+        let mut buffer = String::new();
+        io::stdin()
+            .read_line(&mut buffer)
+            .map_err(|_| RuntimeError::Generic("Failed to read input".into()))?;
+
+        Ok(Value::String(buffer.trim_end().to_string()))
+    })
+}
+
+/// 拆出模板与其余参数，模板必须是字符串
+fn template_args<'a>(
+    name: &str,
+    args: &'a [Value],
+) -> Result<(&'a String, &'a [Value]), RuntimeError> {
+    match args.split_first() {
+        Some((Value::String(template), rest)) => Ok((template, rest)),
+        _ => Err(RuntimeError::TypeError(format!(
+            "{}() expects a format string as its first argument.",
+            name
+        ))),
     }
+}
+
+// format(): 按模板格式化参数，规则见 evaluate::format。format(template, ...args) -> String
+pub fn format(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let (template, rest) = template_args("format", &args)?;
+    Ok(Value::String(interpreter.format_template(template, rest)?))
+}
+
+// printf(): 格式化后打印并换行，与 print 相同。printf(template, ...args) -> Nil
+pub fn printf(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let (template, rest) = template_args("printf", &args)?;
+    println!("{}", interpreter.format_template(template, rest)?);
+    Ok(Value::Nil)
+}
+
+// repr(): 获取值的调试表示。repr(value) -> String
+pub fn repr(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    Ok(Value::String(args[0].repr()))
+}
+
+// deep_copy(): 递归复制容器，副本与原值互不影响。deep_copy(value) -> Value
+pub fn deep_copy(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    Ok(args[0].deep_copy())
+}
+
+// type(): 获取值的类型名（小写）。type(value) -> String
+// 所有可调用的值（函数、原生函数、绑定方法）都是 "function"
+pub fn type_of(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let name = match &args[0] {
+        Value::Function { .. } | Value::NativeFunction { .. } | Value::BoundNativeMethod { .. } => {
+            "function".to_string()
+        }
+        other => other.type_name().to_lowercase(),
+    };
+    Ok(Value::String(name))
+}
+
+// bigint(): 转换为任意精度整数。bigint(Number | String | BigInt) -> BigInt
+// Number 必须是整数值；String 为十进制数字，可带符号
+pub fn bigint(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let converted = match &args[0] {
+        Value::BigInt(n) => Some(n.clone()),
+        Value::Number(n) => from_integral(*n),
+        Value::String(s) => big::parse(s),
+        other => {
+            return Err(RuntimeError::TypeError(format!(
+                "bigint() expects a Number or String, got {}.",
+                other.type_name()
+            )));
+        }
+    };
+
+    converted.map(Value::BigInt).ok_or_else(|| {
+        RuntimeError::Generic(format!(
+            "bigint() cannot convert {} to an integer.",
+            args[0].repr()
+        ))
+    })
+}
+
+// decimal(): 转换为十进制小数。decimal(String | Number | BigInt | Decimal) -> Decimal
+// 金额等需要精确计算的场景应当从字符串创建：decimal("19.99")
+pub fn decimal(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let converted = match &args[0] {
+        Value::Decimal(d) => Some(*d),
+        Value::Number(n) => Some(dec::from_number(*n)?),
+        Value::BigInt(n) => dec::parse(&n.to_string()),
+        Value::String(s) => dec::parse(s),
+        other => {
+            return Err(RuntimeError::TypeError(format!(
+                "decimal() expects a String or Number, got {}.",
+                other.type_name()
+            )));
+        }
+    };
+
+    converted.map(Value::Decimal).ok_or_else(|| {
+        RuntimeError::Generic(format!(
+            "decimal() cannot convert {} to a Decimal.",
+            args[0].repr()
+        ))
+    })
+}
 
-    // stdin
-    let mut buffer = String::new();
-    io::stdin()
-        .read_line(&mut buffer)
-        .map_err(|_| RuntimeError::Generic("Failed to read input".into()))?;
+// isNaN(): 是否为 NaN。isNaN(value) -> Boolean
+// NaN 与任何值（包括它自身）都不相等，`x == math.NAN` 永远为 false，需要用 isNaN 判断
+pub fn is_nan(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    Ok(Value::Boolean(
+        matches!(args[0], Value::Number(n) if n.is_nan()),
+    ))
+}
+
+// isFinite(): 是否为有限数字（非 NaN、非 Infinity）。isFinite(value) -> Boolean
+// BigInt / Decimal 总是有限的，非数字返回 false
+pub fn is_finite(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    Ok(Value::Boolean(match &args[0] {
+        Value::Number(n) => n.is_finite(),
+        Value::BigInt(_) | Value::Decimal(_) => true,
+        _ => false,
+    }))
+}
 
-    Ok(Value::String(buffer.trim_end().to_string()))
+/// 取出可选的进制参数：省略或为 nil 时返回 None，否则必须是 2..=36 的整数
+fn radix_arg(name: &str, args: &[Value]) -> Result<Option<u32>, RuntimeError> {
+    if !(1..=2).contains(&args.len()) {
+        return Err(RuntimeError::Generic(format!(
+            "{}() expects 1 or 2 arguments but got {}.",
+            name,
+            args.len()
+        )));
+    }
+    match args.get(1) {
+        None | Some(Value::Nil) => Ok(None),
+        Some(Value::Number(r)) if r.fract() == 0.0 && (2.0..=36.0).contains(r) => {
+            Ok(Some(*r as u32))
+        }
+        _ => Err(RuntimeError::TypeError(
+            "Radix must be an integer between 2 and 36.".into(),
+        )),
+    }
 }
 
+/// 按十进制（可带小数与指数）或指定进制（整数）解析，失败返回 None
+/// 允许首尾空白、正负号与 `_` 分隔符；进制为 16 / 8 / 2 时可带 `0x` / `0o` / `0b` 前缀
+fn parse_numeral(text: &str, radix: Option<u32>) -> Option<f64> {
+    let text = text.trim().replace('_', "");
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(&text)),
+    };
+    // 空串与二次符号不算数字
+    if !digits.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '.') {
+        return None;
+    }
+
+    let value = match radix {
+        // Rust 接受的 "inf" / "NaN" 不算数字
+        None if digits.contains(['i', 'I', 'n', 'N']) => return None,
+        None => digits.parse::<f64>().ok()?,
+        Some(radix) => {
+            let prefix = match radix {
+                16 => "0x",
+                8 => "0o",
+                2 => "0b",
+                _ => "",
+            };
+            let digits = match digits.get(..2) {
+                Some(p) if !prefix.is_empty() && p.eq_ignore_ascii_case(prefix) => &digits[2..],
+                _ => digits,
+            };
+            u128::from_str_radix(digits, radix).ok()? as f64
+        }
+    };
+    Some(if negative { -value } else { value })
+}
+
+// parse_number(): 把字符串解析为数字，失败时返回 nil。parse_number(text, radix?) -> Number | Nil
+// eg. parse_number("3.5") -> 3.5，parse_number("ff", 16) -> 255，parse_number("12px") -> nil
+pub fn parse_number(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let radix = radix_arg("parse_number", &args)?;
+    let text = ensure_string(&args[0])?;
+    Ok(parse_numeral(text, radix).map_or(Value::Nil, Value::Number))
+}
+
+// to_number(): 转换为 Number，无法转换时报错。to_number(String | Number | Boolean | BigInt | Decimal) -> Number
+// Boolean 转为 1 / 0；BigInt / Decimal 可能损失精度
+pub fn to_number(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let n = match &args[0] {
+        Value::Number(n) => *n,
+        Value::Boolean(b) => f64::from(u8::from(*b)),
+        Value::BigInt(n) => n.to_f64().unwrap_or(f64::NAN),
+        Value::Decimal(d) => d.to_f64().unwrap_or(f64::NAN),
+        Value::String(s) => parse_numeral(s, None)
+            .ok_or_else(|| RuntimeError::Generic(format!("Cannot convert '{}' to a number.", s)))?,
+        other => {
+            return Err(RuntimeError::TypeError(format!(
+                "Cannot convert {} to a number.",
+                other.type_name()
+            )));
+        }
+    };
+    Ok(Value::Number(n))
+}
+
+// to_string(): 转换为字符串，与 print 的输出一致。to_string(value, radix?) -> String
+// 给出进制时 value 必须是整数，eg. to_string(255, 16) -> "ff"，to_string(-5, 2) -> "-101"
+pub fn to_string(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let Some(radix) = radix_arg("to_string", &args)? else {
+        return Ok(Value::String(interpreter.stringify(&args[0])?));
+    };
+
+    let n = match &args[0] {
+        Value::Number(n) if n.fract() == 0.0 && n.abs() < u128::MAX as f64 => *n,
+        _ => {
+            return Err(RuntimeError::TypeError(
+                "to_string() with a radix expects an integer Number.".into(),
+            ));
+        }
+    };
+
+    let mut magnitude = n.abs() as u128;
+    let mut digits = Vec::new();
+    loop {
+        digits.push(char::from_digit((magnitude % radix as u128) as u32, radix).unwrap());
+        magnitude /= radix as u128;
+        if magnitude == 0 {
+            break;
+        }
+    }
+    if n < 0.0 {
+        digits.push('-');
+    }
+    Ok(Value::String(digits.into_iter().rev().collect()))
+}
+
+// ord(): 单个字符的 Unicode 码点。ord(String) -> Number，eg. ord("A") -> 65
+pub fn ord(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let text = ensure_string(&args[0])?;
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(Value::Number(u32::from(c) as f64)),
+        _ => Err(RuntimeError::TypeError(format!(
+            "ord() expects a single character, got a string of length {}.",
+            text.chars().count()
+        ))),
+    }
+}
+
+// chr(): 码点对应的单字符字符串。chr(Number) -> String，eg. chr(955) -> "λ"
+pub fn chr(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let c = match &args[0] {
+        Value::Number(n) if n.fract() == 0.0 && (0.0..=u32::MAX as f64).contains(n) => {
+            char::from_u32(*n as u32)
+        }
+        _ => None,
+    };
+    c.map(|c| Value::String(c.to_string())).ok_or_else(|| {
+        RuntimeError::Generic(format!(
+            "chr() expects a valid Unicode code point, got {}.",
+            args[0]
+        ))
+    })
+}
+
+// import(): 动态导入模块。import(name | path) -> Module
+// 内置模块名（eg. "math"）优先，否则按文件路径加载
 pub fn import(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
     if args.len() != 1 {
         return Err(RuntimeError::Generic(
@@ -34,6 +398,9 @@ pub fn import(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value,
     }
 
     if let Value::String(path) = &args[0] {
+        if let Some(module) = interpreter.registry.import(path) {
+            return Ok(module);
+        }
         interpreter.import_module(path)
     } else {
         Err(RuntimeError::TypeError(
@@ -41,3 +408,75 @@ pub fn import(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value,
         ))
     }
 }
+
+// globals(): 全局绑定的快照。globals() -> Dict
+// 只包含脚本定义的全局名（不含 `clock`、`math` 等内置名），按名称排序；
+// 修改返回的字典不会影响全局变量，需要写入时使用 setGlobal()。
+pub fn globals(interpreter: &mut Interpreter, _: Vec<Value>) -> Result<Value, RuntimeError> {
+    let globals = interpreter.globals.borrow();
+    let mut bindings = globals
+        .values
+        .iter()
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect::<Vec<_>>();
+    bindings.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok(string_dict(bindings))
+}
+
+// setGlobal(): 定义或更新全局绑定。setGlobal(name, value) -> value
+// 仅当解释器开启 `globals_writable`（eg. 设置环境变量 ROX_GLOBALS_WRITABLE）时可用，
+// 供插件注册表等需要动态注入全局名的场景使用。
+pub fn set_global(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    if !interpreter.globals_writable {
+        return Err(RuntimeError::Generic(
+            "Globals are read-only. Enable ROX_GLOBALS_WRITABLE to allow setGlobal().".into(),
+        ));
+    }
+
+    let name = ensure_string(&args[0])?.clone();
+    interpreter
+        .globals
+        .borrow_mut()
+        .define(name, args[1].clone());
+    Ok(args[1].clone())
+}
+
+// locals(): 当前作用域可见的局部绑定快照。locals() -> Dict
+// 从当前环境沿 `enclosing` 向外收集到全局环境为止，内层同名变量优先，按名称排序。
+// 在顶层调用时等同于 globals()。
+pub fn locals(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    if Rc::ptr_eq(&interpreter.environment, &interpreter.globals) {
+        return globals(interpreter, args);
+    }
+
+    let mut bindings = BTreeMap::new();
+    let mut current = Some(interpreter.environment.clone());
+    while let Some(env) = current {
+        if Rc::ptr_eq(&env, &interpreter.globals) {
+            break;
+        }
+        let env = env.borrow();
+        for (name, value) in &env.values {
+            bindings
+                .entry(name.clone())
+                .or_insert_with(|| value.clone());
+        }
+        current = env.enclosing.clone();
+    }
+
+    Ok(string_dict(bindings))
+}
+
+// eval(): 在调用处的子作用域中执行代码。eval(code) -> Value (最后一个表达式的值)
+pub fn eval(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let code = ensure_string(&args[0])?.clone();
+    interpreter.eval_source(&code)
+}
+
+// include(): 在全局环境中执行另一个文件。include(path) -> nil
+// 文件中的顶层声明与调用方共享全局环境（传统的脚本拼接方式），区别于 import 的独立模块。
+pub fn include(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path = ensure_string(&args[0])?.clone();
+    interpreter.include_file(&path)
+}
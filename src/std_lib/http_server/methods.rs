@@ -0,0 +1,327 @@
+use crate::evaluate::{
+    Interpreter,
+    error::RuntimeError,
+    value::{HashKey, Value},
+};
+use crate::std_lib::utils::string_dict;
+use std::{
+    io::{BufRead, BufReader, ErrorKind, Read, Write},
+    net::{TcpListener, TcpStream},
+    thread,
+    time::Duration,
+};
+
+/// 请求头部分与请求体的大小上限
+const MAX_HEADER_BYTES: usize = 64 * 1024;
+const MAX_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+/// 解析好的请求，或应当直接返回给客户端的错误状态
+type Request = Result<Value, (u16, &'static str)>;
+
+/// 响应
+struct Response {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn text(status: u16, body: &str) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    fn write_to(self, stream: &mut TcpStream) -> std::io::Result<()> {
+        let mut head = format!("HTTP/1.1 {} {}\r\n", self.status, reason(self.status));
+        let has_type = self
+            .headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("content-type"));
+        if !has_type {
+            head.push_str("Content-Type: text/plain; charset=utf-8\r\n");
+        }
+        for (name, value) in &self.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        head.push_str(&format!(
+            "Content-Length: {}\r\nConnection: close\r\n\r\n",
+            self.body.len()
+        ));
+        stream.write_all(head.as_bytes())?;
+        stream.write_all(&self.body)?;
+        stream.flush()
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        301 => "Moved Permanently",
+        302 => "Found",
+        304 => "Not Modified",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        500 => "Internal Server Error",
+        _ => "",
+    }
+}
+
+/// 读取并解析一个请求：{"method", "path", "query", "headers", "body"}
+/// 头部名称转为小写；query 为 `?` 之后的原始字符串
+fn read_request(stream: &TcpStream) -> Request {
+    let mut reader = BufReader::new(stream);
+    let mut head = Vec::new();
+    loop {
+        let mut line = String::new();
+        match reader.read_line(&mut line) {
+            Ok(0) | Err(_) => return Err((400, "Incomplete request.")),
+            Ok(_) => {}
+        }
+        if line == "\r\n" || line == "\n" {
+            break;
+        }
+        head.push(line.trim_end().to_string());
+        if head.iter().map(String::len).sum::<usize>() > MAX_HEADER_BYTES {
+            return Err((400, "Request headers are too large."));
+        }
+    }
+
+    let mut request_line = head.first().ok_or((400, "Empty request."))?.split(' ');
+    let (Some(method), Some(target)) = (request_line.next(), request_line.next()) else {
+        return Err((400, "Malformed request line."));
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let mut headers = Vec::new();
+    let mut length = 0;
+    for line in &head[1..] {
+        let (name, value) = line.split_once(':').ok_or((400, "Malformed header."))?;
+        let (name, value) = (name.trim().to_ascii_lowercase(), value.trim().to_string());
+        if name == "content-length" {
+            length = value
+                .parse()
+                .map_err(|_| (400, "Invalid Content-Length."))?;
+        }
+        headers.push((name, Value::String(value)));
+    }
+    if length > MAX_BODY_BYTES {
+        return Err((413, "Request body is too large."));
+    }
+
+    let mut body = vec![0; length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|_| (400, "Incomplete request body."))?;
+
+    Ok(string_dict([
+        ("method".to_string(), Value::String(method.to_string())),
+        ("path".to_string(), Value::String(path.to_string())),
+        ("query".to_string(), Value::String(query.to_string())),
+        ("headers".to_string(), string_dict(headers)),
+        (
+            "body".to_string(),
+            Value::String(String::from_utf8_lossy(&body).into_owned()),
+        ),
+    ]))
+}
+
+/// 处理函数的返回值转换为响应：
+/// 字符串作为 200 的响应体；字典可包含 status（默认 200）、headers 与 body（String 或 Bytes）
+fn to_response(value: Value) -> Result<Response, RuntimeError> {
+    let invalid = |what: &str| RuntimeError::TypeError(format!("Invalid HTTP response: {}.", what));
+    let dict = match value {
+        Value::String(body) => return Ok(Response::text(200, &body)),
+        Value::Dict(dict) => dict,
+        other => {
+            return Err(invalid(&format!(
+                "expected a Dict or String, got {}",
+                other.type_name()
+            )));
+        }
+    };
+    let dict = dict.borrow();
+    let field = |name: &str| dict.get(&HashKey::String(name.to_string())).map(|(_, v)| v);
+
+    let status = match field("status") {
+        None => 200,
+        Some(Value::Number(n)) if n.fract() == 0.0 && (100.0..=599.0).contains(n) => *n as u16,
+        Some(_) => return Err(invalid("status must be an integer between 100 and 599")),
+    };
+    let body = match field("body") {
+        None | Some(Value::Nil) => Vec::new(),
+        Some(Value::String(s)) => s.as_bytes().to_vec(),
+        Some(Value::Bytes(bytes)) => bytes.to_vec(),
+        Some(_) => return Err(invalid("body must be a String or Bytes")),
+    };
+    let headers = match field("headers") {
+        None | Some(Value::Nil) => Vec::new(),
+        Some(Value::Dict(headers)) => headers
+            .borrow()
+            .values()
+            .map(|(name, value)| {
+                let name = name.to_string();
+                let value = value.to_string();
+                // 禁止换行，避免响应头注入
+                if [&name, &value].iter().any(|s| s.contains(['\r', '\n'])) {
+                    return Err(invalid("header names and values cannot contain newlines"));
+                }
+                Ok((name, value))
+            })
+            .collect::<Result<_, _>>()?,
+        Some(_) => return Err(invalid("headers must be a Dict")),
+    };
+
+    Ok(Response {
+        status,
+        headers,
+        body,
+    })
+}
+
+// http.serve(port, handler): 在 127.0.0.1:port（或 "host:port"）上监听，逐个处理请求，不会主动返回。
+// handler(request) 返回响应；处理函数出错时返回 500 并继续服务。
+// 在处理函数或信号处理函数中调用 exit() 可以停止服务。http.serve(Number | String, Function) -> Nil
+pub fn serve(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let address = match &args[0] {
+        Value::Number(port) if port.fract() == 0.0 && (0.0..=65_535.0).contains(port) => {
+            format!("127.0.0.1:{}", port)
+        }
+        Value::String(address) => address.clone(),
+        _ => {
+            return Err(RuntimeError::TypeError(
+                "http.serve expects a port number or a \"host:port\" string.".into(),
+            ));
+        }
+    };
+    if interpreter.io_trace.is_some() {
+        return Err(RuntimeError::Generic(format!(
+            "http.serve({}) is unavailable while IO is recorded, replayed or disabled.",
+            address
+        )));
+    }
+    let handler = args[1].clone();
+
+    let listener = TcpListener::bind(&address)
+        .and_then(|listener| listener.set_nonblocking(true).map(|_| listener))
+        .map_err(|e| RuntimeError::Generic(format!("Failed to listen on {}: {}", address, e)))?;
+
+    loop {
+        // 非阻塞轮询：等待连接期间仍然能处理信号
+        let mut stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                if interpreter.signals.is_pending() {
+                    interpreter.dispatch_signals()?;
+                }
+                thread::sleep(Duration::from_millis(10));
+                continue;
+            }
+            Err(_) => continue,
+        };
+        let _ = stream.set_nonblocking(false);
+        let _ = stream.set_read_timeout(Some(Duration::from_secs(10)));
+
+        let response = match read_request(&stream) {
+            Ok(request) => match interpreter
+                .call_value(&handler, vec![request])
+                .and_then(to_response)
+            {
+                Ok(response) => response,
+                // 与 catch 一致：exit() 等控制流信号不被拦截，直接结束服务
+                Err(e) => {
+                    let error = e.into_exception()?;
+                    eprintln!("http.serve: {}", error);
+                    Response::text(500, "Internal Server Error")
+                }
+            },
+            Err((status, message)) => Response::text(status, message),
+        };
+        // 客户端提前断开等写入错误不影响后续请求
+        let _ = response.write_to(&mut stream);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+    use std::{
+        io::{Read, Write},
+        net::{TcpListener, TcpStream},
+        thread,
+        time::Duration,
+    };
+
+    fn send(port: u16, request: &str) -> String {
+        for _ in 0..200 {
+            if let Ok(mut stream) = TcpStream::connect(("127.0.0.1", port)) {
+                stream.write_all(request.as_bytes()).unwrap();
+                let mut response = String::new();
+                let _ = stream.read_to_string(&mut response);
+                return response;
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+        panic!("server did not start");
+    }
+
+    #[test]
+    fn test_serve_calls_handler_per_request() {
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let client = thread::spawn(move || {
+            [
+                send(
+                    port,
+                    "POST /echo?x=1 HTTP/1.1\r\nHost: a\r\nContent-Length: 5\r\n\r\nhello",
+                ),
+                send(port, "GET /json HTTP/1.1\r\n\r\n"),
+                send(port, "GET /fail HTTP/1.1\r\n\r\n"),
+                send(port, "nonsense\r\n\r\n"),
+                send(port, "GET /quit HTTP/1.1\r\n\r\n"),
+            ]
+        });
+
+        let code = format!(
+            r#"
+            fun handle(req) {{
+                if (req["path"] == "/echo") {{
+                    return req["method"] + " " + req["query"] + " " + req["headers"]["host"] + " " + req["body"];
+                }}
+                if (req["path"] == "/json") {{
+                    return {{"status": 201, "headers": {{"Content-Type": "application/json"}}, "body": "[]"}};
+                }}
+                if (req["path"] == "/quit") exit(0);
+                return req["missing"].boom();
+            }}
+            http.serve({}, handle);
+            "#,
+            port
+        );
+        let result = eval_res(&code);
+        let responses = client.join().unwrap();
+
+        assert_eq!(result.unwrap_err(), "Exit(0)");
+        assert!(responses[0].starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(responses[0].ends_with("\r\n\r\nPOST x=1 a hello"));
+        assert!(
+            responses[1].starts_with("HTTP/1.1 201 Created\r\nContent-Type: application/json\r\n")
+        );
+        assert!(responses[1].ends_with("\r\n\r\n[]"));
+        assert!(responses[2].starts_with("HTTP/1.1 500 "));
+        assert!(responses[3].starts_with("HTTP/1.1 400 "));
+        assert!(eval_res(r#"http.serve(-1, fun(r) { return ""; });"#).is_err());
+    }
+}
@@ -0,0 +1,14 @@
+mod methods;
+
+use crate::{
+    evaluate::value::{NativeFn, Value},
+    std_lib::utils,
+};
+
+/// http 模块：最小化的阻塞式 HTTP/1.1 服务器，请求逐个交给 rox 处理函数
+const FUNCTIONS: &[(&str, usize, NativeFn)] = &[("serve", 2, methods::serve)];
+
+// 创建 http 模块对象
+pub fn create_module() -> Value {
+    utils::native_module("http", FUNCTIONS)
+}
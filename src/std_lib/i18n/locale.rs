@@ -0,0 +1,85 @@
+use crate::evaluate::error::RuntimeError;
+
+/// 日期中年 / 月 / 日的排列顺序
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DateOrder {
+    Dmy,
+    Mdy,
+    Ymd,
+}
+
+/// 区域设置 (Locale) 的格式化规则
+#[derive(Debug)]
+pub struct Locale {
+    pub tag: &'static str,
+    /// 千位分隔符
+    pub group: &'static str,
+    /// 小数点
+    pub decimal: &'static str,
+    /// 整数部分至少有多少位才分组（eg. 西班牙语中 `1234` 不分组）
+    pub min_grouping: usize,
+    pub date_order: DateOrder,
+    pub date_separator: &'static str,
+    /// 日、月是否补齐两位
+    pub date_padded: bool,
+    /// 排序规则中作为独立字母、排在 `z` 之后 / 指定字母之后的字符
+    pub tailoring: &'static [(char, char)],
+}
+
+const fn locale(
+    tag: &'static str,
+    group: &'static str,
+    decimal: &'static str,
+    date_order: DateOrder,
+    date_separator: &'static str,
+    date_padded: bool,
+) -> Locale {
+    Locale {
+        tag,
+        group,
+        decimal,
+        min_grouping: 1,
+        date_order,
+        date_separator,
+        date_padded,
+        tailoring: &[],
+    }
+}
+
+/// 内置的区域设置
+///
+/// 只覆盖常用语言，查找时先匹配完整标签（`de-ch`），再退回到语言（`de`）。
+const LOCALES: &[Locale] = &[
+    locale("en", ",", ".", DateOrder::Mdy, "/", false),
+    locale("en-gb", ",", ".", DateOrder::Dmy, "/", true),
+    locale("de", ".", ",", DateOrder::Dmy, ".", false),
+    locale("de-ch", "’", ".", DateOrder::Dmy, ".", false),
+    locale("fr", "\u{202f}", ",", DateOrder::Dmy, "/", true),
+    Locale {
+        min_grouping: 2,
+        tailoring: &[('ñ', 'n')],
+        ..locale("es", ".", ",", DateOrder::Dmy, "/", false)
+    },
+    locale("it", ".", ",", DateOrder::Dmy, "/", false),
+    locale("pt", ".", ",", DateOrder::Dmy, "/", true),
+    locale("nl", ".", ",", DateOrder::Dmy, "-", false),
+    locale("ru", "\u{a0}", ",", DateOrder::Dmy, ".", true),
+    Locale {
+        tailoring: &[('å', 'z'), ('ä', 'å'), ('ö', 'ä')],
+        ..locale("sv", "\u{a0}", ",", DateOrder::Ymd, "-", true)
+    },
+    locale("ja", ",", ".", DateOrder::Ymd, "/", false),
+    locale("zh", ",", ".", DateOrder::Ymd, "/", false),
+];
+
+/// 按标签查找区域设置，大小写与 `_` / `-` 不敏感
+pub fn find(tag: &str) -> Result<&'static Locale, RuntimeError> {
+    let normalized = tag.trim().to_lowercase().replace('_', "-");
+    let language = normalized.split('-').next().unwrap_or_default();
+
+    LOCALES
+        .iter()
+        .find(|l| l.tag == normalized)
+        .or_else(|| LOCALES.iter().find(|l| l.tag == language))
+        .ok_or_else(|| RuntimeError::Generic(format!("Unsupported locale '{}'.", tag)))
+}
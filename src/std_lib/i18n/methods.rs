@@ -0,0 +1,227 @@
+use super::locale::{self, DateOrder, Locale};
+use crate::evaluate::{
+    Interpreter,
+    error::RuntimeError,
+    value::{HashKey, Value},
+};
+use crate::std_lib::utils::{ensure_dict, ensure_list, ensure_string};
+use std::{cell::RefCell, cmp::Ordering, rc::Rc};
+use unicode_normalization::{UnicodeNormalization, char::is_combining_mark};
+
+fn ensure_number(val: &Value, func: &str) -> Result<f64, RuntimeError> {
+    match val {
+        Value::Number(n) => Ok(*n),
+        other => Err(RuntimeError::TypeError(format!(
+            "{}() expects a Number, got {}.",
+            func,
+            other.type_name()
+        ))),
+    }
+}
+
+// i18n.formatNumber(n, locale) -> String
+// 千位分组并使用区域对应的小数点，最多保留 3 位小数：formatNumber(1234.5, "de") -> "1.234,5"
+pub fn format_number(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let n = ensure_number(&args[0], "formatNumber")?;
+    let locale = locale::find(ensure_string(&args[1])?)?;
+    Ok(Value::String(render_number(n, locale)))
+}
+
+fn render_number(n: f64, locale: &Locale) -> String {
+    if n.is_nan() {
+        return "NaN".to_string();
+    }
+    if n.is_infinite() {
+        return if n > 0.0 { "∞" } else { "-∞" }.to_string();
+    }
+
+    let fixed = format!("{:.3}", n.abs());
+    let (int_part, fraction) = fixed.split_once('.').unwrap_or((&fixed, ""));
+    let fraction = fraction.trim_end_matches('0');
+
+    let mut out = String::new();
+    // 舍入后为 0 时不输出 "-0"
+    if n < 0.0 && fixed.bytes().any(|b| matches!(b, b'1'..=b'9')) {
+        out.push('-');
+    }
+
+    let digits = int_part.len();
+    let grouped = digits > 3 && digits - 3 >= locale.min_grouping;
+    for (i, c) in int_part.chars().enumerate() {
+        if grouped && i > 0 && (digits - i) % 3 == 0 {
+            out.push_str(locale.group);
+        }
+        out.push(c);
+    }
+
+    if !fraction.is_empty() {
+        out.push_str(locale.decimal);
+        out.push_str(fraction);
+    }
+    out
+}
+
+// i18n.formatDate(timestamp, locale) -> String
+// 时间戳为 UTC 秒（eg. time.now()），按区域的年月日顺序输出：formatDate(0, "de") -> "1.1.1970"
+pub fn format_date(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let timestamp = ensure_number(&args[0], "formatDate")?;
+    let locale = locale::find(ensure_string(&args[1])?)?;
+    if !timestamp.is_finite() {
+        return Err(RuntimeError::Generic(format!(
+            "Invalid timestamp {}.",
+            timestamp
+        )));
+    }
+
+    let (year, month, day) = civil_from_days((timestamp / 86_400.0).floor() as i64);
+    let part = |n: u32| {
+        if locale.date_padded {
+            format!("{:02}", n)
+        } else {
+            n.to_string()
+        }
+    };
+    let parts = match locale.date_order {
+        DateOrder::Dmy => [part(day), part(month), year.to_string()],
+        DateOrder::Mdy => [part(month), part(day), year.to_string()],
+        DateOrder::Ymd => [year.to_string(), part(month), part(day)],
+    };
+    Ok(Value::String(parts.join(locale.date_separator)))
+}
+
+/// 1970-01-01 起的天数转换为公历日期 (Howard Hinnant 的 civil_from_days 算法)
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// 排序键：先比较基本字母（忽略重音与大小写），再比较重音，最后比较大小写（小写在前）
+fn collation_key(text: &str, locale: &Locale) -> (Vec<u32>, Vec<u32>, Vec<bool>) {
+    let (mut primary, mut secondary, mut tertiary) = (Vec::new(), Vec::new(), Vec::new());
+
+    for c in text.chars() {
+        let lower = c.to_lowercase().next().unwrap_or(c);
+        tertiary.push(c != lower);
+
+        // 区域中的独立字母（eg. 瑞典语的 å ä ö 排在 z 之后）
+        if let Some(weight) = tailored_weight(lower, locale) {
+            primary.push(weight);
+            secondary.push(0);
+            continue;
+        }
+
+        let mut accents = 0;
+        for d in lower.nfd() {
+            if is_combining_mark(d) {
+                accents = accents * 31 + d as u32;
+            } else {
+                primary.push((d as u32) << 8);
+            }
+        }
+        secondary.push(accents);
+    }
+
+    (primary, secondary, tertiary)
+}
+
+/// 定制字母的权重：紧跟在它所排在的字母之后
+fn tailored_weight(c: char, locale: &Locale) -> Option<u32> {
+    let (_, after) = locale.tailoring.iter().find(|(letter, _)| *letter == c)?;
+    let base = tailored_weight(*after, locale).unwrap_or((*after as u32) << 8);
+    Some(base + 1)
+}
+
+fn collate(a: &str, b: &str, locale: &Locale) -> Ordering {
+    collation_key(a, locale).cmp(&collation_key(b, locale))
+}
+
+// i18n.compare(a, b, locale) -> Number (-1 / 0 / 1)，按区域的字母顺序比较字符串
+pub fn compare(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let (a, b) = (ensure_string(&args[0])?, ensure_string(&args[1])?);
+    let locale = locale::find(ensure_string(&args[2])?)?;
+    Ok(Value::Number(collate(a, b, locale) as i8 as f64))
+}
+
+// i18n.sort(list, locale) -> List，返回按区域字母顺序排序的新列表（元素必须是字符串）
+pub fn sort(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let mut items = ensure_list(&args[0])?
+        .borrow()
+        .iter()
+        .map(|item| ensure_string(item).cloned())
+        .collect::<Result<Vec<_>, _>>()?;
+    let locale = locale::find(ensure_string(&args[1])?)?;
+
+    items.sort_by(|a, b| collate(a, b, locale));
+    let items = items.into_iter().map(Value::String).collect();
+    Ok(Value::List(Rc::new(RefCell::new(items))))
+}
+
+// i18n.translate(catalog, locale, key) -> String
+// catalog 是 { 区域: { 键: 消息 } } 形式的字典；依次查找完整区域（"de-AT"）、语言（"de"），
+// 都找不到时返回键本身，便于发现缺失的翻译
+pub fn translate(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let catalog = ensure_dict(&args[0])?.borrow();
+    let tag = ensure_string(&args[1])?;
+    let key = ensure_string(&args[2])?;
+
+    let language = tag.split(['-', '_']).next().unwrap_or_default();
+    for candidate in [tag.as_str(), language] {
+        let Some((_, Value::Dict(messages))) = catalog.get(&HashKey::String(candidate.into()))
+        else {
+            continue;
+        };
+        if let Some((_, message)) = messages.borrow().get(&HashKey::String(key.clone())) {
+            return Ok(Value::String(message.to_string()));
+        }
+    }
+
+    Ok(Value::String(key.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_format_number_and_date() {
+        let code = r#"
+            var res = [
+                i18n.formatNumber(1234.5, "de"), i18n.formatNumber(-1234567.125, "en"),
+                i18n.formatNumber(1234, "es"), i18n.formatNumber(12345.25, "de-CH"),
+                i18n.formatDate(0, "en"), i18n.formatDate(1709640000, "de"),
+                i18n.formatDate(1709640000, "en_GB"), i18n.formatDate(1709640000, "ja")
+            ];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[1.234,5, -1,234,567.125, 1234, 12’345.25, 1/1/1970, 5.3.2024, 05/03/2024, 2024/3/5]"
+        );
+        assert!(eval_res(r#"var res = i18n.formatNumber(1, "xx");"#).is_err());
+    }
+
+    #[test]
+    fn test_collation_and_catalog() {
+        let code = r#"
+            var words = ["zebra", "Äpfel", "apple", "Zoo", "öl"];
+            var catalog = {"de": {"hello": "Hallo"}, "de-AT": {"hello": "Servus"}};
+            var res = [
+                i18n.sort(words, "de"), i18n.sort(words, "sv"), i18n.compare("a", "A", "en"),
+                i18n.translate(catalog, "de-AT", "hello"), i18n.translate(catalog, "de-DE", "hello"),
+                i18n.translate(catalog, "fr", "hello")
+            ];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[[Äpfel, apple, öl, zebra, Zoo], [apple, zebra, Zoo, Äpfel, öl], -1, Servus, Hallo, hello]"
+        );
+    }
+}
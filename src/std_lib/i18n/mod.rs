@@ -0,0 +1,23 @@
+mod locale;
+mod methods;
+
+pub(crate) use methods::civil_from_days;
+
+use crate::{
+    evaluate::value::{NativeFn, Value},
+    std_lib::utils,
+};
+
+/// i18n 模块：区域相关的数字 / 日期格式化、字符串排序规则与消息目录查找
+const FUNCTIONS: &[(&str, usize, NativeFn)] = &[
+    ("formatNumber", 2, methods::format_number),
+    ("formatDate", 2, methods::format_date),
+    ("compare", 3, methods::compare),
+    ("sort", 2, methods::sort),
+    ("translate", 3, methods::translate),
+];
+
+// 创建 i18n 模块对象
+pub fn create_module() -> Value {
+    utils::native_module("i18n", FUNCTIONS)
+}
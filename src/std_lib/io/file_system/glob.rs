@@ -0,0 +1,155 @@
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// 展开 glob 模式，返回排好序、去重后的匹配路径
+///
+/// 模式按 `/` 分段：
+/// * `*` 匹配段内任意字符，`?` 匹配单个字符，`[abc]` / `[a-z]` / `[!a]` 匹配字符集合；
+/// * 单独一段的 `**` 匹配零层或多层目录；
+/// * 以 `.` 开头的隐藏文件只被以 `.` 开头的段匹配。
+///
+/// 无法读取的目录会被跳过；目录的符号链接不会被 `**` 递归进入，避免死循环。
+pub fn expand(pattern: &str) -> Vec<String> {
+    let mut root = PathBuf::new();
+    let mut parts = pattern.split('/').peekable();
+    // 绝对路径：第一段为空
+    if pattern.starts_with('/') {
+        root.push("/");
+        parts.next();
+    }
+    let parts = parts.filter(|part| !part.is_empty()).collect::<Vec<_>>();
+
+    let mut found = BTreeSet::new();
+    walk(&root, &parts, &mut found);
+    found
+        .into_iter()
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect()
+}
+
+fn walk(dir: &Path, parts: &[&str], found: &mut BTreeSet<PathBuf>) {
+    let Some((&part, rest)) = parts.split_first() else {
+        if !dir.as_os_str().is_empty() {
+            found.insert(dir.to_path_buf());
+        }
+        return;
+    };
+
+    if part == "**" {
+        walk(dir, rest, found);
+        for (name, path) in entries(dir) {
+            let is_real_dir = fs::symlink_metadata(&path).is_ok_and(|meta| meta.is_dir());
+            if is_real_dir && !name.starts_with('.') {
+                walk(&path, parts, found);
+            }
+        }
+        return;
+    }
+
+    if !part.contains(['*', '?', '[']) {
+        let path = dir.join(part);
+        if path.exists() {
+            walk(&path, rest, found);
+        }
+        return;
+    }
+
+    let pattern = part.chars().collect::<Vec<_>>();
+    for (name, path) in entries(dir) {
+        if name.starts_with('.') && !part.starts_with('.') {
+            continue;
+        }
+        if matches(&pattern, &name.chars().collect::<Vec<_>>())
+            && (rest.is_empty() || path.is_dir())
+        {
+            walk(&path, rest, found);
+        }
+    }
+}
+
+/// 目录下的 (名称, 路径)；当前目录用空路径表示，输出时不带 `./` 前缀
+fn entries(dir: &Path) -> Vec<(String, PathBuf)> {
+    let read = if dir.as_os_str().is_empty() {
+        fs::read_dir(".")
+    } else {
+        fs::read_dir(dir)
+    };
+    let Ok(read) = read else {
+        return Vec::new();
+    };
+    read.flatten()
+        .map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let path = dir.join(&name);
+            (name, path)
+        })
+        .collect()
+}
+
+/// 单段通配符匹配
+fn matches(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|skip| matches(rest, &name[skip..])),
+        Some(('?', rest)) => !name.is_empty() && matches(rest, &name[1..]),
+        Some(('[', rest)) => match (class(rest), name.split_first()) {
+            (Some((hit, after)), Some((&c, tail))) => hit(c) && matches(after, tail),
+            // 没有闭合的 `[` 按普通字符处理
+            (None, Some(('[', tail))) => matches(rest, tail),
+            _ => false,
+        },
+        Some((&c, rest)) => name.first() == Some(&c) && matches(rest, &name[1..]),
+    }
+}
+
+/// 解析 `[...]`（不含开头的 `[`），返回判定函数与剩余的模式
+fn class(pattern: &[char]) -> Option<(impl Fn(char) -> bool, &[char])> {
+    let (negated, body) = match pattern.first() {
+        Some('!') => (true, &pattern[1..]),
+        _ => (false, pattern),
+    };
+    // 紧跟在 `[` 后的 `]` 是普通字符
+    let end = body.iter().skip(1).position(|&c| c == ']')? + 1;
+    let set = body[..end].to_vec();
+    let hit = move |c: char| {
+        let mut i = 0;
+        let mut found = false;
+        while i < set.len() {
+            if i + 2 < set.len() && set[i + 1] == '-' {
+                found |= (set[i]..=set[i + 2]).contains(&c);
+                i += 3;
+            } else {
+                found |= set[i] == c;
+                i += 1;
+            }
+        }
+        found != negated
+    };
+    Some((hit, &body[end + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::matches;
+
+    #[test]
+    fn test_segment_wildcards() {
+        let m = |p: &str, n: &str| {
+            matches(
+                &p.chars().collect::<Vec<_>>(),
+                &n.chars().collect::<Vec<_>>(),
+            )
+        };
+        assert!(m("*.rox", "main.rox"));
+        assert!(!m("*.rox", "main.rs"));
+        assert!(m("a?c", "abc"));
+        assert!(m("[a-c]x", "bx"));
+        assert!(!m("[!a-c]x", "bx"));
+        assert!(m("[]]", "]"));
+        assert!(m("[ab", "[ab"));
+        assert!(m("*", ""));
+    }
+}
@@ -0,0 +1,67 @@
+use crate::evaluate::{Interpreter, error::RuntimeError, value::Value};
+use crate::std_lib::io::trace::traced;
+use std::{
+    cell::RefCell,
+    fs::File,
+    io::{BufRead, BufReader},
+    rc::Rc,
+};
+
+/// `fs.readLines` 返回的逐行读取器
+///
+/// 每次只从文件读取一行，读到末尾后关闭文件。
+/// 录制 / 回放时每一行都是一条 `fs.readLines.next` 记录；回放时不会打开文件（`reader` 为 None）。
+#[derive(Debug, Clone)]
+pub struct LineReader {
+    path: String,
+    reader: Option<Rc<RefCell<BufReader<File>>>>,
+    done: bool,
+}
+
+impl LineReader {
+    pub fn new(path: String, file: Option<File>) -> Self {
+        Self {
+            path,
+            reader: file.map(|file| Rc::new(RefCell::new(BufReader::new(file)))),
+            done: false,
+        }
+    }
+
+    /// 读取下一行（不含行尾的 `\n` / `\r\n`），文件结束时返回 None
+    pub fn next(&mut self, interpreter: &mut Interpreter) -> Result<Option<Value>, RuntimeError> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let reader = self.reader.clone();
+        let line = traced(interpreter, "fs.readLines.next", &[&self.path], || {
+            let Some(reader) = reader else {
+                return Err(RuntimeError::Generic("Line reader is not open.".into()));
+            };
+            let mut line = String::new();
+            let read = reader
+                .borrow_mut()
+                .read_line(&mut line)
+                .map_err(|e| RuntimeError::Generic(format!("Failed to read line: {}", e)))?;
+            if read == 0 {
+                return Ok(Value::Nil);
+            }
+            if line.ends_with('\n') {
+                line.pop();
+                if line.ends_with('\r') {
+                    line.pop();
+                }
+            }
+            Ok(Value::String(line))
+        });
+
+        match line {
+            Ok(Value::Nil) | Err(_) => {
+                self.done = true;
+                self.reader = None;
+                line.map(|_| None)
+            }
+            Ok(line) => Ok(Some(line)),
+        }
+    }
+}
@@ -1,22 +1,35 @@
-use crate::evaluate::{error::RuntimeError, interpreter::Interpreter, value::Value};
-use std::fs;
+use super::{glob, lines::LineReader};
+use crate::evaluate::{
+    error::RuntimeError, generator::Iteration, heap, interpreter::Interpreter, value::Value,
+};
+use crate::std_lib::{io::trace::traced, utils::string_dict};
+use ::base64::{Engine, engine::general_purpose::STANDARD};
+use std::fs::{self, File};
+use std::io::Write;
 use std::path::Path;
+use std::time::UNIX_EPOCH;
+use std::{cell::RefCell, rc::Rc};
 
 // fs.readFile(path) -> String
-pub fn read_file(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+pub fn read_file(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
     let path_str = match args.first() {
         Some(Value::String(s)) => s,
         _ => return Err(RuntimeError::TypeError("Path must be a string.".into())),
     };
 
-    match fs::read_to_string(path_str) {
-        Ok(content) => Ok(Value::String(content)),
-        Err(e) => Err(RuntimeError::Generic(format!("Failed to read file: {}", e))),
-    }
+    traced(
+        interpreter,
+        "fs.readFile",
+        &[path_str],
+        || match fs::read_to_string(path_str) {
+            Ok(content) => Ok(Value::String(content)),
+            Err(e) => Err(RuntimeError::Generic(format!("Failed to read file: {}", e))),
+        },
+    )
 }
 
 // fs.writeFile(path, content) -> Nil
-pub fn write_file(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+pub fn write_file(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
     if args.len() != 2 {
         return Err(RuntimeError::Generic("Expected 2 arguments.".into()));
     }
@@ -31,21 +44,342 @@ pub fn write_file(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, Runtim
         _ => return Err(RuntimeError::TypeError("Content must be a string.".into())),
     };
 
-    match fs::write(path_str, content) {
-        Ok(_) => Ok(Value::Nil),
-        Err(e) => Err(RuntimeError::Generic(format!(
-            "Failed to write file: {}",
-            e
-        ))),
-    }
+    traced(
+        interpreter,
+        "fs.writeFile",
+        &[path_str, content],
+        || match fs::write(path_str, content) {
+            Ok(_) => Ok(Value::Nil),
+            Err(e) => Err(RuntimeError::Generic(format!(
+                "Failed to write file: {}",
+                e
+            ))),
+        },
+    )
 }
 
 // fs.exists(path) -> Bool
-pub fn exists(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+pub fn exists(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
     let path_str = match args.first() {
         Some(Value::String(s)) => s,
         _ => return Err(RuntimeError::TypeError("Path must be a string.".into())),
     };
 
-    Ok(Value::Boolean(Path::new(path_str).exists()))
+    traced(interpreter, "fs.exists", &[path_str], || {
+        Ok(Value::Boolean(Path::new(path_str).exists()))
+    })
+}
+
+fn path_arg(value: &Value) -> Result<&String, RuntimeError> {
+    match value {
+        Value::String(s) => Ok(s),
+        _ => Err(RuntimeError::TypeError("Path must be a string.".into())),
+    }
+}
+
+fn io_error(action: &str, e: std::io::Error) -> RuntimeError {
+    RuntimeError::Generic(format!("Failed to {}: {}", action, e))
+}
+
+// fs.readBytes(path): 按原样读取二进制内容 -> Bytes
+pub fn read_bytes(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path_str = path_arg(&args[0])?;
+
+    traced(interpreter, "fs.readBytes", &[path_str], || {
+        fs::read(path_str)
+            .map(|bytes| Value::Bytes(bytes.into()))
+            .map_err(|e| io_error("read file", e))
+    })
+}
+
+// fs.writeBytes(path, bytes): 写入二进制内容，覆盖原文件 -> Nil
+pub fn write_bytes(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path_str = path_arg(&args[0])?;
+    let bytes = match &args[1] {
+        Value::Bytes(bytes) => bytes,
+        _ => return Err(RuntimeError::TypeError("Content must be bytes.".into())),
+    };
+    // 追踪记录只保存字符串参数，内容用 base64 表示
+    let content = STANDARD.encode(bytes);
+
+    traced(interpreter, "fs.writeBytes", &[path_str, &content], || {
+        fs::write(path_str, bytes)
+            .map(|_| Value::Nil)
+            .map_err(|e| io_error("write file", e))
+    })
+}
+
+// fs.appendFile(path, content): 追加写入，文件不存在时创建 -> Nil
+pub fn append_file(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path_str = path_arg(&args[0])?;
+    let content = match &args[1] {
+        Value::String(s) => s,
+        _ => return Err(RuntimeError::TypeError("Content must be a string.".into())),
+    };
+
+    traced(interpreter, "fs.appendFile", &[path_str, content], || {
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path_str)
+            .and_then(|mut file| file.write_all(content.as_bytes()))
+            .map(|_| Value::Nil)
+            .map_err(|e| io_error("append to file", e))
+    })
+}
+
+// fs.readDir(path): 目录下的条目名（不含路径），按名称排序 -> List
+pub fn read_dir(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path_str = path_arg(&args[0])?;
+
+    traced(interpreter, "fs.readDir", &[path_str], || {
+        let mut names = fs::read_dir(path_str)
+            .and_then(|entries| {
+                entries
+                    .map(|entry| entry.map(|e| e.file_name().to_string_lossy().into_owned()))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .map_err(|e| io_error("read directory", e))?;
+        names.sort();
+
+        let list = Value::List(Rc::new(RefCell::new(
+            names.into_iter().map(Value::String).collect(),
+        )));
+        heap::track(&list);
+        Ok(list)
+    })
+}
+
+// fs.glob(pattern): 匹配模式的路径列表（排序），eg. fs.glob("src/**/*.rox") -> List
+// 支持 `*`、`?`、`[a-z]` 以及跨目录的 `**`；没有匹配时返回空列表
+pub fn glob(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let pattern = match &args[0] {
+        Value::String(s) => s,
+        _ => return Err(RuntimeError::TypeError("Pattern must be a string.".into())),
+    };
+
+    traced(interpreter, "fs.glob", &[pattern], || {
+        let list = Value::List(Rc::new(RefCell::new(
+            glob::expand(pattern)
+                .into_iter()
+                .map(Value::String)
+                .collect(),
+        )));
+        heap::track(&list);
+        Ok(list)
+    })
+}
+
+// fs.mkdir(path): 创建目录，缺失的上级目录一并创建，已存在时不报错 -> Nil
+pub fn mkdir(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path_str = path_arg(&args[0])?;
+
+    traced(interpreter, "fs.mkdir", &[path_str], || {
+        fs::create_dir_all(path_str)
+            .map(|_| Value::Nil)
+            .map_err(|e| io_error("create directory", e))
+    })
+}
+
+// fs.remove(path): 删除文件，或连同内容删除整个目录 -> Nil
+pub fn remove(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path_str = path_arg(&args[0])?;
+
+    traced(interpreter, "fs.remove", &[path_str], || {
+        let path = Path::new(path_str);
+        // symlink_metadata 不跟随符号链接：指向目录的链接只删除链接本身
+        let result = match fs::symlink_metadata(path) {
+            Ok(meta) if meta.is_dir() => fs::remove_dir_all(path),
+            Ok(_) => fs::remove_file(path),
+            Err(e) => Err(e),
+        };
+        result
+            .map(|_| Value::Nil)
+            .map_err(|e| io_error("remove", e))
+    })
+}
+
+// fs.copy(from, to): 复制文件，目标已存在时覆盖 -> Nil
+pub fn copy(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let from = path_arg(&args[0])?;
+    let to = path_arg(&args[1])?;
+
+    traced(interpreter, "fs.copy", &[from, to], || {
+        fs::copy(from, to)
+            .map(|_| Value::Nil)
+            .map_err(|e| io_error("copy file", e))
+    })
+}
+
+// fs.rename(from, to): 移动或重命名文件 / 目录 -> Nil
+pub fn rename(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let from = path_arg(&args[0])?;
+    let to = path_arg(&args[1])?;
+
+    traced(interpreter, "fs.rename", &[from, to], || {
+        fs::rename(from, to)
+            .map(|_| Value::Nil)
+            .map_err(|e| io_error("rename", e))
+    })
+}
+
+// fs.stat(path): 文件信息 -> Dict
+// {"size": 字节数, "isFile": Boolean, "isDir": Boolean, "modified": 修改时间（Unix 秒，不可用时为 nil）}
+pub fn stat(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path_str = path_arg(&args[0])?;
+
+    traced(interpreter, "fs.stat", &[path_str], || {
+        let meta = fs::metadata(path_str).map_err(|e| io_error("read metadata", e))?;
+        let modified = meta
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map_or(Value::Nil, |d| Value::Number(d.as_secs_f64()));
+
+        Ok(string_dict([
+            ("size".to_string(), Value::Number(meta.len() as f64)),
+            ("isFile".to_string(), Value::Boolean(meta.is_file())),
+            ("isDir".to_string(), Value::Boolean(meta.is_dir())),
+            ("modified".to_string(), modified),
+        ]))
+    })
+}
+
+// fs.readLines(path): 逐行读取的迭代器，不会一次读入整个文件 -> Iterator
+// 行内容不含行尾换行符；文件在创建时打开，读到末尾后关闭
+pub fn read_lines(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path_str = path_arg(&args[0])?;
+
+    let mut file = None;
+    traced(interpreter, "fs.readLines", &[path_str], || {
+        file = Some(File::open(path_str).map_err(|e| io_error("open file", e))?);
+        Ok(Value::Nil)
+    })?;
+
+    Ok(Value::Iterator(Rc::new(RefCell::new(Iteration::Lines {
+        reader: Box::new(LineReader::new(path_str.clone(), file)),
+        index: 0,
+    }))))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+    use std::fs;
+
+    #[test]
+    fn test_binary_files() {
+        let file = std::env::temp_dir().join(format!("rox_fs_bytes_{}.bin", std::process::id()));
+        let code = format!(
+            r#"
+            var file = "{}";
+            fs.writeBytes(file, bytes([0, 255, 10, 128]));
+            var res = [fs.readBytes(file), fs.stat(file)["size"]];
+            "#,
+            file.display()
+        );
+        let result = eval_res(&code);
+        let _ = fs::remove_file(&file);
+
+        assert_eq!(result.unwrap().to_string(), "[bytes([0, 255, 10, 128]), 4]");
+        assert!(eval_res(r#"fs.writeBytes("x.bin", "text");"#).is_err());
+    }
+
+    #[test]
+    fn test_read_lines_streams_file() {
+        let file = std::env::temp_dir().join(format!("rox_fs_lines_{}.txt", std::process::id()));
+        fs::write(&file, "first\r\nsecond\n\nlast").unwrap();
+        let code = format!(
+            r#"
+            var lines = fs.readLines("{}");
+            var res = [lines.next()];
+            for (i, line in lines) res.push([i, line]);
+            res.push(lines.next());
+            "#,
+            file.display()
+        );
+        let result = eval_res(&code);
+        let _ = fs::remove_file(&file);
+
+        assert_eq!(
+            result.unwrap().to_string(),
+            "[first, [1, second], [2, ], [3, last], nil]"
+        );
+        assert!(eval_res(r#"fs.readLines("rox-no-such-file");"#).is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_glob_walks_directories() {
+        let dir = std::env::temp_dir().join(format!("rox_fs_glob_{}", std::process::id()));
+        for file in [
+            "a.rox",
+            "b.txt",
+            ".hidden.rox",
+            "src/c.rox",
+            "src/deep/d.rox",
+            "src/deep/e.rs",
+        ] {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        let code = format!(
+            r#"
+            var root = "{}";
+            var strip = fun(paths) {{ return paths.map(fun(p) {{ return p.replace(root + "/", ""); }}); }};
+            var res = [
+                strip(fs.glob(root + "/**/*.rox")), strip(fs.glob(root + "/*")),
+                strip(fs.glob(root + "/src/*/?.r[a-s]")), strip(fs.glob(root + "/.*.rox")),
+                fs.glob(root + "/missing/**")
+            ];
+            "#,
+            dir.display()
+        );
+        let result = eval_res(&code);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(
+            result.unwrap().to_string(),
+            "[[a.rox, src/c.rox, src/deep/d.rox], [a.rox, b.txt, src], [src/deep/e.rs], [.hidden.rox], []]"
+        );
+    }
+
+    #[test]
+    fn test_directory_and_file_management() {
+        let dir = std::env::temp_dir().join(format!("rox_fs_{}", std::process::id()));
+        let code = format!(
+            r#"
+            var root = "{}";
+            fs.mkdir(root + "/sub/deep");
+            fs.writeFile(root + "/a.txt", "one");
+            fs.appendFile(root + "/a.txt", "+two");
+            fs.appendFile(root + "/new.txt", "fresh");
+            fs.copy(root + "/a.txt", root + "/b.txt");
+            fs.rename(root + "/new.txt", root + "/sub/moved.txt");
+            var info = fs.stat(root + "/b.txt");
+            var listed = fs.readDir(root);
+            fs.remove(root + "/sub");
+            var res = [
+                listed, fs.readDir(root), fs.readFile(root + "/b.txt"),
+                info["size"], info["isFile"], info["isDir"], info["modified"] > 0,
+                fs.stat(root)["isDir"]
+            ];
+            fs.remove(root);
+            "#,
+            dir.display()
+        );
+        let result = eval_res(&code);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(
+            result.unwrap().to_string(),
+            "[[a.txt, b.txt, sub], [a.txt, b.txt], one+two, 7, true, false, true, true]"
+        );
+        assert!(!dir.exists());
+        assert!(eval_res(r#"var res = fs.stat("rox-no-such-file");"#).is_err());
+        assert!(eval_res(r#"var res = fs.remove("rox-no-such-file");"#).is_err());
+        assert!(eval_res("var res = fs.readDir(1);").is_err());
+    }
 }
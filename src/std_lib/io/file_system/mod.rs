@@ -1,3 +1,5 @@
+mod glob;
+pub mod lines;
 mod methods;
 
 use crate::evaluate::value::{RoxModule, Value};
@@ -27,6 +29,41 @@ pub fn create_module() -> Value {
         "exists".to_string(),
         define_native("exists", 1, methods::exists),
     );
+    exports.insert(
+        "readBytes".to_string(),
+        define_native("readBytes", 1, methods::read_bytes),
+    );
+    exports.insert(
+        "writeBytes".to_string(),
+        define_native("writeBytes", 2, methods::write_bytes),
+    );
+    exports.insert(
+        "appendFile".to_string(),
+        define_native("appendFile", 2, methods::append_file),
+    );
+    exports.insert(
+        "readDir".to_string(),
+        define_native("readDir", 1, methods::read_dir),
+    );
+    exports.insert(
+        "mkdir".to_string(),
+        define_native("mkdir", 1, methods::mkdir),
+    );
+    exports.insert(
+        "remove".to_string(),
+        define_native("remove", 1, methods::remove),
+    );
+    exports.insert("copy".to_string(), define_native("copy", 2, methods::copy));
+    exports.insert(
+        "rename".to_string(),
+        define_native("rename", 2, methods::rename),
+    );
+    exports.insert("stat".to_string(), define_native("stat", 1, methods::stat));
+    exports.insert("glob".to_string(), define_native("glob", 1, methods::glob));
+    exports.insert(
+        "readLines".to_string(),
+        define_native("readLines", 1, methods::read_lines),
+    );
 
     let module = RoxModule {
         name: "fs".to_string(),
@@ -0,0 +1,292 @@
+use crate::evaluate::{Interpreter, error::RuntimeError, heap, value::Value};
+use crate::std_lib::utils::string_dict;
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    fs::{self, File},
+    io::Write,
+    path::Path,
+    rc::Rc,
+};
+
+/// IO 录制 / 回放 (Record / Replay)
+///
+/// - 录制：照常访问真实系统，并把每次 IO 交互（操作名、参数、结果）追加写入追踪文件，
+///   每条记录一行 JSON，脚本中途崩溃时已发生的交互也不会丢失。
+/// - 回放：按顺序取出录制的结果直接返回，不再读写文件或等待输入；
+///   脚本发起的操作与录制不一致时报错，提示执行路径已经偏离。
+///
+/// 经过追踪的操作：`fs` 模块的全部函数、`input`，
+/// `os.getenv`、`os.setenv`、`os.cwd`、`os.chdir`、`path.absolute`、`process.run`、`process.spawn`，
+/// 以及 `store` 的读取与保存。
+#[derive(Debug)]
+pub enum IoTrace {
+    Record(File),
+    Replay(VecDeque<TraceEntry>),
+    /// 拒绝所有 IO（沙箱）
+    Deny,
+}
+
+/// 一次 IO 交互
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceEntry {
+    pub op: String,
+    pub args: Vec<String>,
+    /// 成功时的返回值，或失败时的错误信息
+    pub result: Result<TracedValue, String>,
+}
+
+/// IO 操作可能返回的值
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TracedValue {
+    Nil,
+    Boolean(bool),
+    Number(f64),
+    String(String),
+    /// 字节序列（eg. `fs.readBytes` 的结果）
+    Bytes(Vec<u8>),
+    /// 列表（eg. `fs.readDir` 的结果）
+    List(Vec<TracedValue>),
+    /// 字符串键的字典（eg. `process.run` 的结果）
+    Dict(Vec<(String, TracedValue)>),
+}
+
+impl IoTrace {
+    /// 开始录制，创建（或清空）追踪文件
+    pub fn record(path: &Path) -> Result<Self, String> {
+        File::create(path)
+            .map(IoTrace::Record)
+            .map_err(|e| format!("Cannot create trace '{}': {}", path.display(), e))
+    }
+
+    /// 读取追踪文件用于回放
+    pub fn replay(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read trace '{}': {}", path.display(), e))?;
+
+        contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .enumerate()
+            .map(|(index, line)| {
+                serde_json::from_str(line).map_err(|e| {
+                    format!(
+                        "Invalid trace '{}' (entry {}): {}",
+                        path.display(),
+                        index + 1,
+                        e
+                    )
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(IoTrace::Replay)
+    }
+}
+
+/// 执行一次经过追踪的 IO 操作
+///
+/// 未开启追踪时直接调用 `real`；录制时调用 `real` 并记录结果；回放时返回录制的结果。
+pub fn traced(
+    interpreter: &mut Interpreter,
+    op: &str,
+    args: &[&str],
+    real: impl FnOnce() -> Result<Value, RuntimeError>,
+) -> Result<Value, RuntimeError> {
+    match interpreter.io_trace.as_mut() {
+        None => real(),
+        Some(IoTrace::Deny) => Err(denied(op, args)),
+        Some(IoTrace::Record(file)) => {
+            let result = real();
+            let entry = TraceEntry {
+                op: op.to_string(),
+                args: args.iter().map(|arg| arg.to_string()).collect(),
+                result: match &result {
+                    Ok(value) => Ok(TracedValue::from_value(value)?),
+                    Err(e) => Err(e.to_string()),
+                },
+            };
+
+            let line = serde_json::to_string(&entry)
+                .map_err(|e| RuntimeError::Generic(format!("Failed to record '{}': {}", op, e)))?;
+            writeln!(file, "{}", line)
+                .map_err(|e| RuntimeError::Generic(format!("Failed to record '{}': {}", op, e)))?;
+            result
+        }
+        Some(IoTrace::Replay(entries)) => {
+            let call = format!("{}({})", op, args.join(", "));
+            let Some(entry) = entries.pop_front() else {
+                return Err(RuntimeError::Generic(format!(
+                    "Replay diverged: {} was not recorded (trace exhausted).",
+                    call
+                )));
+            };
+            if entry.op != op || entry.args != args {
+                return Err(RuntimeError::Generic(format!(
+                    "Replay diverged: expected {}({}), got {}.",
+                    entry.op,
+                    entry.args.join(", "),
+                    call
+                )));
+            }
+
+            match entry.result {
+                Ok(value) => Ok(value.into_value()),
+                Err(message) => Err(RuntimeError::Generic(message)),
+            }
+        }
+    }
+}
+
+/// 沙箱 (`IoTrace::Deny`) 中拒绝读取源码文件
+///
+/// 导入文件模块与 `include` 直接读取文件，不经过 `traced`（录制时不需要记录源码），
+/// 但沙箱同样不允许它们访问文件系统。
+pub fn deny_file_access(
+    interpreter: &Interpreter,
+    op: &str,
+    path: &str,
+) -> Result<(), RuntimeError> {
+    match interpreter.io_trace {
+        Some(IoTrace::Deny) => Err(denied(op, &[path])),
+        _ => Ok(()),
+    }
+}
+
+fn denied(op: &str, args: &[&str]) -> RuntimeError {
+    RuntimeError::Generic(format!(
+        "IO is disabled in this context: {}({}).",
+        op,
+        args.join(", ")
+    ))
+}
+
+impl TracedValue {
+    fn from_value(value: &Value) -> Result<Self, RuntimeError> {
+        match value {
+            Value::Nil => Ok(TracedValue::Nil),
+            Value::Boolean(b) => Ok(TracedValue::Boolean(*b)),
+            Value::Number(n) => Ok(TracedValue::Number(*n)),
+            Value::String(s) => Ok(TracedValue::String(s.clone())),
+            Value::Bytes(bytes) => Ok(TracedValue::Bytes(bytes.to_vec())),
+            Value::List(list) => list
+                .borrow()
+                .iter()
+                .map(Self::from_value)
+                .collect::<Result<_, _>>()
+                .map(TracedValue::List),
+            Value::Dict(dict) => dict
+                .borrow()
+                .values()
+                .map(|(key, value)| match key {
+                    Value::String(key) => Ok((key.clone(), Self::from_value(value)?)),
+                    _ => Err(RuntimeError::Generic(
+                        "Cannot record IO result with non-string keys.".into(),
+                    )),
+                })
+                .collect::<Result<_, _>>()
+                .map(TracedValue::Dict),
+            other => Err(RuntimeError::Generic(format!(
+                "Cannot record IO result of type {}.",
+                other.type_name()
+            ))),
+        }
+    }
+
+    fn into_value(self) -> Value {
+        match self {
+            TracedValue::Nil => Value::Nil,
+            TracedValue::Boolean(b) => Value::Boolean(b),
+            TracedValue::Number(n) => Value::Number(n),
+            TracedValue::String(s) => Value::String(s),
+            TracedValue::Bytes(bytes) => Value::Bytes(bytes.into()),
+            TracedValue::List(items) => {
+                let list = Value::List(Rc::new(RefCell::new(
+                    items.into_iter().map(Self::into_value).collect(),
+                )));
+                heap::track(&list);
+                list
+            }
+            TracedValue::Dict(entries) => string_dict(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, value.into_value())),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IoTrace, TraceEntry, TracedValue};
+    use crate::{
+        evaluate::{Interpreter, Value},
+        parser::parse,
+        reader::Source,
+        resolver::Resolver,
+        tokenizer::tokenize,
+    };
+    use pretty_assertions::assert_eq;
+    use std::fs;
+
+    fn run(interpreter: &mut Interpreter, code: &str) -> Result<Value, String> {
+        let tokens = tokenize(Source {
+            contents: code.to_string(),
+        })
+        .unwrap();
+        let ast = parse(tokens).unwrap();
+        Resolver::new(interpreter).resolve_stmts(&ast.body).unwrap();
+        interpreter.interpret(ast).map_err(|e| format!("{:?}", e))?;
+        Ok(interpreter.get_global_value("res").unwrap())
+    }
+
+    #[test]
+    fn test_record_then_replay_without_files() {
+        let dir = std::env::temp_dir().join(format!("rox_trace_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let data = dir.join("data.txt");
+        let trace = dir.join("run.trace");
+        fs::write(&data, "recorded").unwrap();
+
+        let code = format!(
+            r#"var path = "{}"; var res = [fs.exists(path), fs.readFile(path), fs.readLines(path).next()];"#,
+            data.display()
+        );
+
+        let mut recorder = Interpreter::new();
+        recorder.io_trace = Some(IoTrace::record(&trace).unwrap());
+        let recorded = run(&mut recorder, &code).unwrap();
+
+        // 数据文件删除后，回放仍然得到录制时的结果
+        fs::remove_file(&data).unwrap();
+        let mut replayer = Interpreter::new();
+        replayer.io_trace = Some(IoTrace::replay(&trace).unwrap());
+        let replayed = run(&mut replayer, &code);
+
+        let mut diverged = Interpreter::new();
+        diverged.io_trace = Some(IoTrace::replay(&trace).unwrap());
+        let diverged = run(&mut diverged, r#"var res = fs.readFile("other.txt");"#);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(recorded.to_string(), "[true, recorded, recorded]");
+        assert_eq!(replayed.unwrap(), recorded);
+        assert!(diverged.unwrap_err().contains("Replay diverged"));
+    }
+
+    #[test]
+    fn test_input_prompt_is_optional() {
+        let entry = |prompt: &str, line: &str| TraceEntry {
+            op: "input".to_string(),
+            args: vec![prompt.to_string()],
+            result: Ok(TracedValue::String(line.to_string())),
+        };
+        let mut interpreter = Interpreter::new();
+        interpreter.io_trace = Some(IoTrace::Replay(
+            [entry("", "first"), entry("name? ", "second")].into(),
+        ));
+
+        let res = run(&mut interpreter, r#"var res = [input(), input("name? ")];"#);
+        assert_eq!(res.unwrap().to_string(), "[first, second]");
+        assert!(run(&mut Interpreter::new(), r#"var res = input("a", "b");"#).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+use super::Adapter;
+use crate::evaluate::{Interpreter, error::RuntimeError, generator::Iteration, value::Value};
+use std::{cell::RefCell, rc::Rc};
+
+/// 包装为迭代器值
+fn adapt(adapter: Adapter) -> Value {
+    Value::Iterator(Rc::new(RefCell::new(Iteration::Adapter {
+        adapter: Box::new(adapter),
+        index: 0,
+    })))
+}
+
+/// 回调在取值时才会被调用，这里提前检查，让错误出现在组合处
+fn callback(name: &str, value: &Value) -> Result<Value, RuntimeError> {
+    match value {
+        Value::Function { .. }
+        | Value::NativeFunction { .. }
+        | Value::BoundNativeMethod { .. }
+        | Value::Class(_) => Ok(value.clone()),
+        other => Err(RuntimeError::TypeError(format!(
+            "iter.{}() expects a function, got {}.",
+            name,
+            other.type_name()
+        ))),
+    }
+}
+
+/// 非负整数个数
+fn size(name: &str, value: &Value) -> Result<usize, RuntimeError> {
+    match value {
+        Value::Number(n) if n.fract() == 0.0 && *n >= 0.0 => Ok(*n as usize),
+        _ => Err(RuntimeError::TypeError(format!(
+            "iter.{}() expects a non-negative integer count.",
+            name
+        ))),
+    }
+}
+
+fn expect_args(name: &str, args: &[Value], max: usize) -> Result<(), RuntimeError> {
+    if args.len() > max {
+        return Err(RuntimeError::Generic(format!(
+            "iter.{}() expects at most {} arguments but got {}.",
+            name,
+            max,
+            args.len()
+        )));
+    }
+    Ok(())
+}
+
+// iter.map(iterable, fn): 逐个映射。iter.map(Iterable, Function) -> Iterator
+pub fn map(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let func = callback("map", &args[1])?;
+    let source = interpreter.iterate(args[0].clone())?;
+    Ok(adapt(Adapter::Map { source, func }))
+}
+
+// iter.filter(iterable, fn): 保留回调返回真值的元素。iter.filter(Iterable, Function) -> Iterator
+pub fn filter(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let func = callback("filter", &args[1])?;
+    let source = interpreter.iterate(args[0].clone())?;
+    Ok(adapt(Adapter::Filter { source, func }))
+}
+
+// iter.take(iterable, n): 最多取前 n 个。iter.take(Iterable, Number) -> Iterator
+pub fn take(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let remaining = size("take", &args[1])?;
+    let source = interpreter.iterate(args[0].clone())?;
+    Ok(adapt(Adapter::Take { source, remaining }))
+}
+
+// iter.drop(iterable, n): 跳过前 n 个。iter.drop(Iterable, Number) -> Iterator
+pub fn drop(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let skip = size("drop", &args[1])?;
+    let source = interpreter.iterate(args[0].clone())?;
+    Ok(adapt(Adapter::Drop { source, skip }))
+}
+
+// iter.chain(a, b, ...): 依次连接多个可迭代值。iter.chain(...Iterable) -> Iterator
+pub fn chain(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let sources = args
+        .into_iter()
+        .map(|iterable| interpreter.iterate(iterable))
+        .collect::<Result<_, _>>()?;
+    Ok(adapt(Adapter::Chain { sources }))
+}
+
+// iter.repeat(value, times?): 重复 value，省略 times 时无限重复。iter.repeat(Value, Number?) -> Iterator
+pub fn repeat(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    expect_args("repeat", &args, 2)?;
+    let value = args
+        .first()
+        .cloned()
+        .ok_or_else(|| RuntimeError::Generic("iter.repeat() expects a value to repeat.".into()))?;
+    let remaining = match args.get(1) {
+        None | Some(Value::Nil) => None,
+        Some(times) => Some(size("repeat", times)?),
+    };
+    Ok(adapt(Adapter::Repeat { value, remaining }))
+}
+
+// iter.count(start?, step?): 从 start（默认 0）起按 step（默认 1）无限计数。iter.count(Number?, Number?) -> Iterator
+pub fn count(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    expect_args("count", &args, 2)?;
+    let number = |value: Option<&Value>, default: f64| match value {
+        None => Ok(default),
+        Some(Value::Number(n)) if n.is_finite() => Ok(*n),
+        Some(_) => Err(RuntimeError::TypeError(
+            "iter.count() expects finite numbers.".into(),
+        )),
+    };
+    let next = number(args.first(), 0.0)?;
+    let step = number(args.get(1), 1.0)?;
+    Ok(adapt(Adapter::Count { next, step }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_lazy_combinators() {
+        let code = r#"
+            fun collect(it) {
+                var out = [];
+                for (x in it) out.push(x);
+                return out;
+            }
+            fun gen() { yield 1; yield 2; yield 3; }
+            var calls = 0;
+            var squares = iter.map(iter.count(1), fun(x) { calls = calls + 1; return x * x; });
+            var evens = iter.filter(squares, fun(x) { return x % 2 == 0; });
+            var res = [
+                collect(iter.take(evens, 3)), calls,
+                collect(iter.drop(gen(), 1)),
+                collect(iter.chain([1], (2, 3), "ab", {"k": 0})),
+                collect(iter.take(iter.repeat("x"), 2)), collect(iter.repeat(0, 3)),
+                collect(iter.take(iter.count(10, -2.5), 3)),
+                collect(iter.take([1, 2], 5)), collect(iter.drop(set([1]), 3))
+            ];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[[4, 16, 36], 6, [2, 3], [1, 2, 3, a, b, k], [x, x], [0, 0, 0], [10, 7.5, 5], [1, 2], []]"
+        );
+    }
+
+    #[test]
+    fn test_combinator_errors() {
+        assert!(eval_res("iter.map([1], 2);").is_err());
+        assert!(eval_res("iter.take(1, 2);").is_err());
+        assert!(eval_res("iter.take([1], -1);").is_err());
+        assert!(eval_res("iter.repeat();").is_err());
+        assert!(eval_res("iter.count(1, 2, 3);").is_err());
+        assert!(
+            eval_res("var it = iter.map([1], fun(x) { return x.nope(); }); it.next();").is_err()
+        );
+    }
+}
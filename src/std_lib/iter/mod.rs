@@ -0,0 +1,122 @@
+mod methods;
+
+use crate::evaluate::{
+    Interpreter,
+    error::RuntimeError,
+    generator::Iteration,
+    value::{NativeFn, VARIADIC, Value},
+};
+use crate::std_lib::utils;
+use std::collections::VecDeque;
+
+/// iter 模块：作用于任意可迭代值的惰性组合子。
+/// 结果都是迭代器，只在 for-in / `next()` 取值时才向源头拉取元素，因此可以组合无限序列
+const FUNCTIONS: &[(&str, usize, NativeFn)] = &[
+    ("map", 2, methods::map),
+    ("filter", 2, methods::filter),
+    ("take", 2, methods::take),
+    ("drop", 2, methods::drop),
+    ("chain", VARIADIC, methods::chain),
+    ("repeat", VARIADIC, methods::repeat),
+    ("count", VARIADIC, methods::count),
+];
+
+/// 惰性迭代器的状态，由 `Iteration::Adapter` 驱动
+#[derive(Debug, Clone)]
+pub enum Adapter {
+    Map {
+        source: Iteration,
+        func: Value,
+    },
+    Filter {
+        source: Iteration,
+        func: Value,
+    },
+    Take {
+        source: Iteration,
+        remaining: usize,
+    },
+    Drop {
+        source: Iteration,
+        skip: usize,
+    },
+    Chain {
+        sources: VecDeque<Iteration>,
+    },
+    /// `remaining` 为 None 时无限重复
+    Repeat {
+        value: Value,
+        remaining: Option<usize>,
+    },
+    Count {
+        next: f64,
+        step: f64,
+    },
+}
+
+impl Adapter {
+    /// 取下一个元素，结束时返回 None
+    pub fn next(&mut self, interpreter: &mut Interpreter) -> Result<Option<Value>, RuntimeError> {
+        match self {
+            Adapter::Map { source, func } => match source.next_item(interpreter)? {
+                Some(item) => Ok(Some(interpreter.call_value(func, vec![item])?)),
+                None => Ok(None),
+            },
+            Adapter::Filter { source, func } => {
+                while let Some(item) = source.next_item(interpreter)? {
+                    if interpreter
+                        .call_value(func, vec![item.clone()])?
+                        .is_truthy()
+                    {
+                        return Ok(Some(item));
+                    }
+                }
+                Ok(None)
+            }
+            Adapter::Take { source, remaining } => {
+                // 取够之后不再访问源头
+                if *remaining == 0 {
+                    return Ok(None);
+                }
+                *remaining -= 1;
+                source.next_item(interpreter)
+            }
+            Adapter::Drop { source, skip } => {
+                while *skip > 0 {
+                    *skip -= 1;
+                    if source.next_item(interpreter)?.is_none() {
+                        return Ok(None);
+                    }
+                }
+                source.next_item(interpreter)
+            }
+            Adapter::Chain { sources } => {
+                while let Some(source) = sources.front_mut() {
+                    if let Some(item) = source.next_item(interpreter)? {
+                        return Ok(Some(item));
+                    }
+                    sources.pop_front();
+                }
+                Ok(None)
+            }
+            Adapter::Repeat { value, remaining } => match remaining {
+                Some(0) => Ok(None),
+                Some(n) => {
+                    *n -= 1;
+                    Ok(Some(value.clone()))
+                }
+                None => Ok(Some(value.clone())),
+            },
+            Adapter::Count { next, step } => {
+                let current = *next;
+                *next += *step;
+                Ok(Some(Value::Number(current)))
+            }
+        }
+    }
+}
+
+// 创建 iter 模块对象
+pub fn create_module() -> Value {
+    utils::native_module("iter", FUNCTIONS)
+}
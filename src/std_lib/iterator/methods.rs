@@ -0,0 +1,85 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::std_lib::{Interpreter, Value, error::RuntimeError};
+
+// it.next() -> Value，迭代结束后返回 nil
+pub fn next(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let item = match &args[0] {
+        Value::Generator(generator) => interpreter.resume_generator(generator)?,
+        Value::Iterator(iteration) => {
+            let Ok(mut iteration) = iteration.try_borrow_mut() else {
+                return Err(RuntimeError::Generic("Iterator is already running.".into()));
+            };
+            iteration.next_item(interpreter)?
+        }
+        _ => return Err(RuntimeError::TypeError("Expected Iterator.".into())),
+    };
+    Ok(item.unwrap_or(Value::Nil))
+}
+
+// it.iter() -> Iterator，迭代器本身即可迭代
+pub fn iter(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    Ok(args[0].clone())
+}
+
+// xs.iter() -> Iterator：集合（List / Dict / Tuple / String）上的迭代器，字典产出键
+pub fn collection_iter(
+    interpreter: &mut Interpreter,
+    args: Vec<Value>,
+) -> Result<Value, RuntimeError> {
+    let iteration = interpreter.iterate(args[0].clone())?;
+    Ok(Value::Iterator(Rc::new(RefCell::new(iteration))))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_native_iterators() {
+        let code = r#"
+            var it = [1, 2].iter();
+            var d = {"a": 1}.iter();
+            fun gen() { yield "g"; }
+            var g = gen();
+            var res = [it.next(), it.next(), it.next(), d.next(), "hi".iter().next(), g.next(), g.next()];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[1, 2, nil, a, h, g, nil]"
+        );
+    }
+
+    #[test]
+    fn test_for_in_uses_iterator_protocol() {
+        let code = r#"
+            class Countdown {
+                init(n) { this.n = n; }
+                next() {
+                    if (this.n == 0) return nil;
+                    this.n = this.n - 1;
+                    return this.n + 1;
+                }
+            }
+            class Range {
+                init(n) { this.n = n; }
+                iter() { return Countdown(this.n); }
+            }
+            var res = [];
+            for (x in Range(3)) res.push(x);
+            for (i, x in Countdown(2)) res.push([i, x]);
+
+            // 部分消费后，for-in 从迭代器当前位置继续
+            var it = [10, 20, 30].iter();
+            it.next();
+            for (x in it) res.push(x);
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[3, 2, 1, [0, 2], [1, 1], 20, 30]"
+        );
+
+        assert!(eval_res("class A {} for (x in A()) {}").is_err());
+    }
+}
@@ -0,0 +1,12 @@
+pub mod methods;
+use super::value::Value;
+use crate::native_fn;
+
+/// 迭代器与生成器：`next()` 取下一个元素，耗尽后返回 nil
+pub fn lookup(name: &str) -> Option<Value> {
+    match name {
+        "next" => native_fn!("next", 0, methods::next),
+        "iter" => native_fn!("iter", 0, methods::iter),
+        _ => None,
+    }
+}
@@ -0,0 +1,210 @@
+use crate::evaluate::{Interpreter, error::RuntimeError, heap, value::Value};
+use crate::std_lib::utils::{ensure_string, string_dict};
+use serde_json::Value as Json;
+use std::{cell::RefCell, rc::Rc};
+
+// json.parse(text): 把 JSON 文本解析为 Rox 值。json.parse(String) -> Value
+// 对象 -> Dict（保持键的顺序），数组 -> List，null -> nil
+pub fn parse(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let text = ensure_string(&args[0])?;
+    let json: Json = serde_json::from_str(text)
+        .map_err(|e| RuntimeError::Generic(format!("Invalid JSON: {}.", e)))?;
+    Ok(from_json(json))
+}
+
+fn from_json(json: Json) -> Value {
+    let value = match json {
+        Json::Null => return Value::Nil,
+        Json::Bool(b) => return Value::Boolean(b),
+        Json::Number(n) => return Value::Number(n.as_f64().unwrap_or(f64::NAN)),
+        Json::String(s) => return Value::String(s),
+        Json::Array(items) => Value::List(Rc::new(RefCell::new(
+            items.into_iter().map(from_json).collect(),
+        ))),
+        Json::Object(entries) => string_dict(entries.into_iter().map(|(k, v)| (k, from_json(v)))),
+    };
+    heap::track(&value);
+    value
+}
+
+// json.stringify(value, indent): 把值序列化为 JSON 文本。json.stringify(Value, Number|nil) -> String
+// indent 为 nil 时输出紧凑格式，为数字时按该数量的空格缩进换行
+pub fn stringify(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let indent = match &args[1] {
+        Value::Nil => None,
+        Value::Number(n) if n.fract() == 0.0 && (0.0..=10.0).contains(n) => {
+            Some(" ".repeat(*n as usize))
+        }
+        _ => {
+            return Err(RuntimeError::TypeError(
+                "JSON indent must be nil or an integer between 0 and 10.".into(),
+            ));
+        }
+    };
+
+    let mut writer = Writer {
+        out: String::new(),
+        indent,
+        path: Vec::new(),
+    };
+    writer.write(&args[0], 0)?;
+    Ok(Value::String(writer.out))
+}
+
+struct Writer {
+    out: String,
+    indent: Option<String>,
+    // 当前正在序列化的容器，用于发现循环引用
+    path: Vec<*const ()>,
+}
+
+impl Writer {
+    fn write(&mut self, value: &Value, depth: usize) -> Result<(), RuntimeError> {
+        match value {
+            Value::Nil => self.out.push_str("null"),
+            Value::Boolean(b) => self.out.push_str(&b.to_string()),
+            Value::Number(n) => {
+                if !n.is_finite() {
+                    return Err(RuntimeError::Generic(
+                        "Cannot serialize NaN or Infinity to JSON.".into(),
+                    ));
+                }
+                self.out.push_str(&n.to_string());
+            }
+            Value::BigInt(n) => self.out.push_str(&n.to_string()),
+            Value::Decimal(d) => self.out.push_str(&d.to_string()),
+            Value::String(s) => self.write_string(s),
+            Value::List(list) => {
+                self.enter(Rc::as_ptr(list).cast())?;
+                let items = list.borrow().clone();
+                self.write_items('[', ']', depth, &items, |w, item, depth| {
+                    w.write(item, depth)
+                })?;
+                self.path.pop();
+            }
+            Value::Tuple(items) => {
+                self.write_items('[', ']', depth, items, |w, item, depth| {
+                    w.write(item, depth)
+                })?;
+            }
+            Value::Dict(dict) => {
+                self.enter(Rc::as_ptr(dict).cast())?;
+                let entries: Vec<_> = dict.borrow().values().cloned().collect();
+                self.write_items('{', '}', depth, &entries, |w, (key, value), depth| {
+                    let Value::String(key) = key else {
+                        return Err(RuntimeError::TypeError(format!(
+                            "JSON object keys must be strings, got {}.",
+                            key.type_name()
+                        )));
+                    };
+                    w.write_string(key);
+                    w.out.push(':');
+                    if w.indent.is_some() {
+                        w.out.push(' ');
+                    }
+                    w.write(value, depth)
+                })?;
+                self.path.pop();
+            }
+            other => {
+                return Err(RuntimeError::TypeError(format!(
+                    "Cannot serialize {} to JSON.",
+                    other.type_name()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn enter(&mut self, ptr: *const ()) -> Result<(), RuntimeError> {
+        if self.path.contains(&ptr) {
+            return Err(RuntimeError::Generic(
+                "Cannot serialize a cyclic structure to JSON.".into(),
+            ));
+        }
+        self.path.push(ptr);
+        Ok(())
+    }
+
+    /// 写出数组或对象：有缩进时每个元素独占一行，空容器始终写作 `[]` / `{}`
+    fn write_items<T>(
+        &mut self,
+        open: char,
+        close: char,
+        depth: usize,
+        items: &[T],
+        mut write_item: impl FnMut(&mut Self, &T, usize) -> Result<(), RuntimeError>,
+    ) -> Result<(), RuntimeError> {
+        self.out.push(open);
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                self.out.push(',');
+            }
+            self.newline(depth + 1);
+            write_item(self, item, depth + 1)?;
+        }
+        if !items.is_empty() {
+            self.newline(depth);
+        }
+        self.out.push(close);
+        Ok(())
+    }
+
+    fn newline(&mut self, depth: usize) {
+        if let Some(indent) = &self.indent {
+            self.out.push('\n');
+            self.out.push_str(&indent.repeat(depth));
+        }
+    }
+
+    fn write_string(&mut self, s: &str) {
+        // 字符串转义交给 serde_json，保证输出是合法的 JSON
+        self.out
+            .push_str(&serde_json::to_string(s).expect("strings always serialize"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse() {
+        let code = r#"
+            var res = [json.parse("[1, 2.5, true, null, []]"), json.parse(" 42 "), json.parse("null")];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[[1, 2.5, true, nil, []], 42, nil]"
+        );
+        assert!(eval_res(r#"var res = json.parse("{oops}");"#).is_err());
+        assert!(eval_res(r#"var res = json.parse("[1,");"#).is_err());
+    }
+
+    #[test]
+    fn test_stringify() {
+        let code = r#"
+            var value = {"b": [1, 2], "a": "x", "c": nil, "d": {}, "e": (true, 1.5)};
+            var res = [json.stringify(value, nil), json.stringify([1, {"k": []}], 2)];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[{\"b\":[1,2],\"a\":\"x\",\"c\":null,\"d\":{},\"e\":[true,1.5]}, [\n  1,\n  {\n    \"k\": []\n  }\n]]"
+        );
+
+        // 往返转换：对象的键保持原有顺序
+        let code = r#"
+            var value = {"z": [1, "two", {"three": 3}], "ok": false};
+            var text = json.stringify(value, nil);
+            var back = json.parse(text);
+            var res = [back.keys(), back["z"][2]["three"], json.stringify(back, nil) == text];
+        "#;
+        assert_eq!(eval_res(code).unwrap().to_string(), "[[z, ok], 3, true]");
+
+        assert!(eval_res("var l = []; l.push(l); var res = json.stringify(l, nil);").is_err());
+        assert!(eval_res("var res = json.stringify({1: 2}, nil);").is_err());
+        assert!(eval_res("var res = json.stringify(clock, nil);").is_err());
+        assert!(eval_res("var res = json.stringify(math.NAN, nil);").is_err());
+    }
+}
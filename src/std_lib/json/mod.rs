@@ -0,0 +1,19 @@
+mod methods;
+
+pub(crate) use methods::{parse, stringify};
+
+use crate::{
+    evaluate::value::{NativeFn, Value},
+    std_lib::utils,
+};
+
+/// json 模块：JSON 文本与 Dict / List / Number / String / Boolean / nil 之间的转换
+const FUNCTIONS: &[(&str, usize, NativeFn)] = &[
+    ("parse", 1, methods::parse),
+    ("stringify", 2, methods::stringify),
+];
+
+// 创建 json 模块对象
+pub fn create_module() -> Value {
+    utils::native_module("json", FUNCTIONS)
+}
@@ -1,6 +1,10 @@
 use std::{cell::RefCell, rc::Rc};
 
-use crate::std_lib::{Interpreter, Value, error::RuntimeError, utils::ensure_list};
+use crate::std_lib::{
+    Interpreter, Value,
+    error::RuntimeError,
+    utils::{ensure_list, slice_range},
+};
 
 pub fn push(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
     // args[0] 是 list 实例
@@ -15,9 +19,15 @@ pub fn pop(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError>
     Ok(value.unwrap_or(Value::Nil))
 }
 
+// list.copy(): 深拷贝，嵌套的容器同样被复制 -> List
+pub fn copy(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    ensure_list(&args[0])?;
+    Ok(args[0].deep_copy())
+}
+
 pub fn len(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
     let list = ensure_list(&args[0])?;
-    let value = list.borrow_mut().len();
+    let value = list.borrow().len();
     Ok(Value::Number(value as f64))
 }
 
@@ -96,13 +106,15 @@ pub fn map(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, Run
     let callback = &args[1];
 
     if let Value::List(list_rc) = list_val {
-        let list = list_rc.borrow();
+        // 先复制元素：回调执行期间不能持有列表的借用，回调可能再次访问这个列表
+        let elements = list_rc.borrow().clone();
         let mut new_elements = Vec::new();
 
-        for item in list.iter() {
+        for item in elements.iter() {
             // Rust 调用 Rox 回调
             // 构造参数列表 [item]
             let result = interpreter.call_value(callback, vec![item.clone()])?;
+            ensure_len(list_rc, elements.len(), "map")?;
             new_elements.push(result);
         }
 
@@ -123,11 +135,12 @@ pub fn filter(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value,
     let callback = &args[1];
 
     if let Value::List(list_rc) = list_val {
-        let list = list_rc.borrow();
+        let elements = list_rc.borrow().clone();
         let mut new_elements = Vec::new();
 
-        for item in list.iter() {
+        for item in elements.iter() {
             let result = interpreter.call_value(callback, vec![item.clone()])?;
+            ensure_len(list_rc, elements.len(), "filter")?;
 
             // 判断回调结果是否为真
             if result.is_truthy() {
@@ -140,6 +153,127 @@ pub fn filter(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value,
     }
 }
 
+// list.reduce(callback, initial): 从 initial 开始依次调用 callback(acc, item)，返回最终的累积值
+pub fn reduce(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let list_rc = ensure_list(&args[0])?;
+    let callback = &args[1];
+
+    let elements = list_rc.borrow().clone();
+    let mut acc = args[2].clone();
+    for item in elements.iter() {
+        acc = interpreter.call_value(callback, vec![acc, item.clone()])?;
+        ensure_len(list_rc, elements.len(), "reduce")?;
+    }
+    Ok(acc)
+}
+
+// list.slice(start, end): [start, end) 区间的新列表，负数下标从末尾倒数
+pub fn slice(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let list = ensure_list(&args[0])?.borrow();
+    let range = slice_range(&args[1], &args[2], list.len())?;
+    Ok(Value::List(Rc::new(RefCell::new(list[range].to_vec()))))
+}
+
+// list.contains(value): 是否包含与 value 相等（`==`）的元素 -> Boolean
+pub fn contains(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let index = position(interpreter, &args[0], &args[1])?;
+    Ok(Value::Boolean(index.is_some()))
+}
+
+// list.index_of(value): 第一个相等元素的下标，找不到返回 -1 -> Number
+pub fn index_of(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let index = position(interpreter, &args[0], &args[1])?;
+    Ok(Value::Number(index.map_or(-1.0, |i| i as f64)))
+}
+
+fn position(
+    interpreter: &mut Interpreter,
+    list: &Value,
+    target: &Value,
+) -> Result<Option<usize>, RuntimeError> {
+    // 比较可能调用 __eq__，不能在比较期间持有列表的借用
+    let elements = ensure_list(list)?.borrow().clone();
+    for (i, item) in elements.iter().enumerate() {
+        if interpreter.values_equal(item, target)? {
+            return Ok(Some(i));
+        }
+    }
+    Ok(None)
+}
+
+// list.concat(other): 两个列表拼接成的新列表 -> List
+pub fn concat(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let mut elements = ensure_list(&args[0])?.borrow().clone();
+    elements.extend(ensure_list(&args[1])?.borrow().iter().cloned());
+    Ok(Value::List(Rc::new(RefCell::new(elements))))
+}
+
+// list.flatten(): 把元素中的列表展开一层 -> List
+pub fn flatten(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let list = ensure_list(&args[0])?.borrow();
+    let mut elements = Vec::with_capacity(list.len());
+    for item in list.iter() {
+        match item {
+            Value::List(inner) => elements.extend(inner.borrow().iter().cloned()),
+            other => elements.push(other.clone()),
+        }
+    }
+    Ok(Value::List(Rc::new(RefCell::new(elements))))
+}
+
+// list.unique(): 去掉重复（`==`）的元素，保留第一次出现的顺序 -> List
+pub fn unique(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let elements = ensure_list(&args[0])?.borrow().clone();
+    let mut kept: Vec<Value> = Vec::with_capacity(elements.len());
+    for item in elements {
+        let mut seen = false;
+        for existing in &kept {
+            if interpreter.values_equal(existing, &item)? {
+                seen = true;
+                break;
+            }
+        }
+        if !seen {
+            kept.push(item);
+        }
+    }
+    Ok(Value::List(Rc::new(RefCell::new(kept))))
+}
+
+// list.zip(other): 按位置配对成 (a, b) 元组，长度取两者中较短的 -> List
+pub fn zip(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let left = ensure_list(&args[0])?.borrow();
+    let right = ensure_list(&args[1])?.borrow();
+    let pairs = left
+        .iter()
+        .zip(right.iter())
+        .map(|(a, b)| Value::Tuple(vec![a.clone(), b.clone()]))
+        .collect();
+    Ok(Value::List(Rc::new(RefCell::new(pairs))))
+}
+
+// list.enumerate(): (下标, 元素) 元组组成的列表 -> List
+pub fn enumerate(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let list = ensure_list(&args[0])?.borrow();
+    let pairs = list
+        .iter()
+        .enumerate()
+        .map(|(i, item)| Value::Tuple(vec![Value::Number(i as f64), item.clone()]))
+        .collect();
+    Ok(Value::List(Rc::new(RefCell::new(pairs))))
+}
+
+/// 回调改变了列表长度时报错，而不是基于过期的元素副本继续迭代
+fn ensure_len(list: &RefCell<Vec<Value>>, len: usize, method: &str) -> Result<(), RuntimeError> {
+    if list.borrow().len() != len {
+        return Err(RuntimeError::Generic(format!(
+            "List changed size during {}().",
+            method
+        )));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use std::{cell::RefCell, rc::Rc};
@@ -222,4 +356,97 @@ mod tests {
         ])));
         assert_eq!(eval_res(ast).unwrap(), expected);
     }
+
+    #[test]
+    fn test_map_callback_can_access_list() {
+        let ast = r#"
+		   var list = [1, 2];
+		   var res = list.map(fun(x) { list[0] = 9; return x + list.len(); });
+		"#;
+
+        assert_eq!(eval_res(ast).unwrap().to_string(), "[3, 4]");
+    }
+
+    #[test]
+    fn test_reduce() {
+        let ast = r#"
+		   var xs = [3, 1, 4, 1, 5];
+		   var sum = xs.reduce(|acc, x| acc + x, 0);
+		   var max = xs.reduce(fun(acc, x) { if (x > acc) return x; return acc; }, xs[0]);
+		   var groups = xs.reduce(fun(acc, x) {
+		       if (x % 2 == 0) acc["even"].push(x); else acc["odd"].push(x);
+		       return acc;
+		   }, {"even": [], "odd": []});
+		   var res = [sum, max, groups["odd"], [].reduce(|a, x| a + x, "empty")];
+		"#;
+
+        assert_eq!(
+            eval_res(ast).unwrap().to_string(),
+            "[14, 5, [3, 1, 1, 5], empty]"
+        );
+    }
+
+    #[test]
+    fn test_slice() {
+        let ast = r#"
+		   var xs = [0, 1, 2, 3, 4];
+		   var res = [xs.slice(1, 3), xs.slice(-2, 5), xs.slice(2, 2), xs.slice(0, -1)];
+		"#;
+
+        assert_eq!(
+            eval_res(ast).unwrap().to_string(),
+            "[[1, 2], [3, 4], [], [0, 1, 2, 3]]"
+        );
+        assert!(eval_res("var res = [1, 2].slice(0, 3);").is_err());
+        assert!(eval_res("var res = [1, 2].slice(2, 1);").is_err());
+        assert!(eval_res("var res = [1, 2].slice(0.5, 1);").is_err());
+    }
+
+    #[test]
+    fn test_search_and_combine() {
+        let ast = r#"
+		   var xs = [1, "a", [2], 1];
+		   var res = [
+		       xs.contains("a"), xs.contains([2]), xs.contains(3),
+		       xs.index_of(1), xs.index_of([2]), xs.index_of("b"),
+		       [1, 2].concat([3]), [[1, 2], 3, [[4]]].flatten(), xs.unique()
+		   ];
+		"#;
+
+        assert_eq!(
+            eval_res(ast).unwrap().to_string(),
+            "[true, true, false, 0, 2, -1, [1, 2, 3], [1, 2, 3, [4]], [1, a, [2]]]"
+        );
+        assert!(eval_res("var res = [1].concat(2);").is_err());
+    }
+
+    #[test]
+    fn test_zip_and_enumerate() {
+        let ast = r#"
+		   var names = ["a", "b", "c"];
+		   var sums = [];
+		   for (pair in [1, 2, 3].zip([10, 20])) sums.push(pair[0] + pair[1]);
+		   var res = [names.enumerate(), names.zip([1, 2, 3]), sums, [].enumerate()];
+		"#;
+
+        assert_eq!(
+            eval_res(ast).unwrap().to_string(),
+            "[[(0, a), (1, b), (2, c)], [(a, 1), (b, 2), (c, 3)], [11, 22], []]"
+        );
+        assert!(eval_res("var res = [1].zip((1, 2));").is_err());
+    }
+
+    #[test]
+    fn test_resizing_list_in_callback_is_an_error() {
+        let ast = r#"
+		   var list = [1, 2];
+		   var res = list.filter(fun(x) { list.push(x); return true; });
+		"#;
+
+        assert!(
+            eval_res(ast)
+                .unwrap_err()
+                .contains("List changed size during filter().")
+        );
+    }
 }
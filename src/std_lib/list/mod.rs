@@ -1,5 +1,5 @@
 pub mod methods;
-use crate::native_fn;
+use crate::{native_fn, std_lib::iterator};
 
 pub fn lookup(name: &str) -> Option<super::Value> {
     match name {
@@ -11,6 +11,17 @@ pub fn lookup(name: &str) -> Option<super::Value> {
         "reverse" => native_fn!("reverse", 0, methods::reverse),
         "map" => native_fn!("map", 1, methods::map),
         "filter" => native_fn!("filter", 1, methods::filter),
+        "reduce" => native_fn!("reduce", 2, methods::reduce),
+        "copy" => native_fn!("copy", 0, methods::copy),
+        "slice" => native_fn!("slice", 2, methods::slice),
+        "contains" => native_fn!("contains", 1, methods::contains),
+        "index_of" => native_fn!("index_of", 1, methods::index_of),
+        "concat" => native_fn!("concat", 1, methods::concat),
+        "flatten" => native_fn!("flatten", 0, methods::flatten),
+        "unique" => native_fn!("unique", 0, methods::unique),
+        "zip" => native_fn!("zip", 1, methods::zip),
+        "enumerate" => native_fn!("enumerate", 0, methods::enumerate),
+        "iter" => native_fn!("iter", 0, iterator::methods::collection_iter),
         _ => None,
     }
 }
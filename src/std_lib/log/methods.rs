@@ -0,0 +1,113 @@
+use super::Level;
+use crate::evaluate::{Interpreter, error::RuntimeError, value::Value};
+use crate::std_lib::{globals, i18n::civil_from_days, utils::ensure_string};
+
+/// 一行日志：`2026-01-02T03:04:05Z INFO  message`，级别名补齐到相同宽度便于对齐
+fn render(level: Level, seconds: f64, message: &str) -> String {
+    let seconds = seconds.floor() as i64;
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+    let time = seconds.rem_euclid(86_400);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z {:<5} {}",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60,
+        level.name().to_uppercase(),
+        message
+    )
+}
+
+fn emit(
+    interpreter: &mut Interpreter,
+    level: Level,
+    args: &[Value],
+) -> Result<Value, RuntimeError> {
+    if level < interpreter.log_level {
+        return Ok(Value::Nil);
+    }
+    let message = interpreter.stringify(&args[0])?;
+    // 时间与 clock() 一致，确定性模式下使用假时钟
+    let Value::Number(now) = globals::clock(interpreter, vec![])? else {
+        unreachable!("clock() always returns a Number");
+    };
+
+    let line = render(level, now, &message);
+    if level >= Level::Warn {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+    Ok(Value::Nil)
+}
+
+// log.debug(message) -> Nil
+pub fn debug(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    emit(interpreter, Level::Debug, &args)
+}
+
+// log.info(message) -> Nil
+pub fn info(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    emit(interpreter, Level::Info, &args)
+}
+
+// log.warn(message) -> Nil，写到 stderr
+pub fn warn(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    emit(interpreter, Level::Warn, &args)
+}
+
+// log.error(message) -> Nil，写到 stderr
+pub fn error(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    emit(interpreter, Level::Error, &args)
+}
+
+// log.setLevel(name): 设置最低输出级别，"debug" | "info" | "warn" | "error"（默认 "info"）
+pub fn set_level(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let name = ensure_string(&args[0])?;
+    interpreter.log_level = Level::parse(name).ok_or_else(|| {
+        RuntimeError::Generic(format!(
+            "Unknown log level '{}', expected debug, info, warn or error.",
+            name
+        ))
+    })?;
+    Ok(Value::Nil)
+}
+
+// log.level(): 当前的最低输出级别 -> String
+pub fn level(interpreter: &mut Interpreter, _: Vec<Value>) -> Result<Value, RuntimeError> {
+    Ok(Value::String(interpreter.log_level.name().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Level, render};
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_render_line() {
+        assert_eq!(
+            render(Level::Info, 1_700_000_000.9, "ready"),
+            "2023-11-14T22:13:20Z INFO  ready"
+        );
+        assert_eq!(
+            render(Level::Error, 0.0, "x"),
+            "1970-01-01T00:00:00Z ERROR x"
+        );
+    }
+
+    #[test]
+    fn test_set_level() {
+        let code = r#"
+            var before = log.level();
+            log.setLevel("warn");
+            log.info("hidden");
+            log.warn("shown");
+            var res = [before, log.level()];
+        "#;
+        assert_eq!(eval_res(code).unwrap().to_string(), "[info, warn]");
+        assert!(eval_res(r#"log.setLevel("loud");"#).is_err());
+    }
+}
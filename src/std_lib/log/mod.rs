@@ -0,0 +1,48 @@
+mod methods;
+
+use crate::{
+    evaluate::value::{NativeFn, Value},
+    std_lib::utils,
+};
+
+/// 日志级别，低于当前级别（`log.setLevel`）的消息被丢弃
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Debug,
+    #[default]
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    const ALL: [Level; 4] = [Level::Debug, Level::Info, Level::Warn, Level::Error];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Level::Debug => "debug",
+            Level::Info => "info",
+            Level::Warn => "warn",
+            Level::Error => "error",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Level> {
+        Level::ALL.into_iter().find(|level| level.name() == name)
+    }
+}
+
+/// log 模块：带级别与 UTC 时间戳的日志，debug / info 写到 stdout，warn / error 写到 stderr
+const FUNCTIONS: &[(&str, usize, NativeFn)] = &[
+    ("debug", 1, methods::debug),
+    ("info", 1, methods::info),
+    ("warn", 1, methods::warn),
+    ("error", 1, methods::error),
+    ("setLevel", 1, methods::set_level),
+    ("level", 0, methods::level),
+];
+
+// 创建 log 模块对象
+pub fn create_module() -> Value {
+    utils::native_module("log", FUNCTIONS)
+}
@@ -90,23 +90,6 @@ macro_rules! math_unary_int {
     };
 }
 
-macro_rules! math_binary_int {
-    ($func_name:ident, $rust_fn:expr) => {
-        |_, args| {
-            if args.len() != 2 {
-                return Err(RuntimeError::Generic(format!(
-                    "'{}' expects 2 argument.",
-                    stringify!($func_name)
-                )));
-            }
-            let l_val = get_num(&args, 0)?;
-            let r_val = get_num(&args, 1)?;
-            let res = $rust_fn(l_val, r_val) as f64;
-            Ok(Value::Number(res))
-        }
-    };
-}
-
 // 模块构建
 
 pub fn create_module() -> Value {
@@ -128,6 +111,8 @@ pub fn create_module() -> Value {
     // 常量作为 Number 存入
     exports.insert("PI".to_string(), Value::Number(PI));
     exports.insert("E".to_string(), Value::Number(std::f64::consts::E));
+    exports.insert("INF".to_string(), Value::Number(f64::INFINITY));
+    exports.insert("NAN".to_string(), Value::Number(f64::NAN));
 
     register(
         &mut exports,
@@ -140,12 +125,27 @@ pub fn create_module() -> Value {
     register(&mut exports, "floor", 1, math_unary!(floor, f64::floor));
     register(&mut exports, "round", 1, math_unary!(round, f64::round));
     register(&mut exports, "sqrt", 1, math_unary!(sqrt, f64::sqrt));
+    register(&mut exports, "trunc", 1, math_unary!(trunc, f64::trunc)); // 向零取整
+    // 正数 1、负数 -1、零 0（f64::signum 对 0 返回 1，这里单独处理），NaN 保持 NaN
+    register(
+        &mut exports,
+        "sign",
+        1,
+        math_unary!(sign, |v: f64| if v == 0.0 { 0.0 } else { v.signum() }),
+    );
 
     register(&mut exports, "sin", 1, math_unary!(sin, f64::sin));
     register(&mut exports, "cos", 1, math_unary!(cos, f64::cos));
     register(&mut exports, "tan", 1, math_unary!(tan, f64::tan));
+    register(&mut exports, "asin", 1, math_unary!(asin, f64::asin));
+    register(&mut exports, "acos", 1, math_unary!(acos, f64::acos));
+    register(&mut exports, "atan", 1, math_unary!(atan, f64::atan));
+    // atan2(y, x)：点 (x, y) 的极角，结果在 [-PI, PI]
+    register(&mut exports, "atan2", 2, math_binary!(atan2, f64::atan2));
+    register(&mut exports, "hypot", 2, math_binary!(hypot, f64::hypot));
 
     register(&mut exports, "log", 1, math_unary!(log, f64::ln)); // 自然对数
+    register(&mut exports, "log2", 1, math_unary!(log2, f64::log2));
     register(&mut exports, "log10", 1, math_unary!(log10, f64::log10));
     register(&mut exports, "exp", 1, math_unary!(exp, f64::exp));
 
@@ -153,6 +153,16 @@ pub fn create_module() -> Value {
     register(&mut exports, "min", 2, math_binary!(min, f64::min));
     register(&mut exports, "max", 2, math_binary!(max, f64::max));
 
+    // clamp(x, min, max)：把 x 限制在 [min, max] 内
+    register(&mut exports, "clamp", 3, |_, args| {
+        let (v, min, max) = (get_num(&args, 0)?, get_num(&args, 1)?, get_num(&args, 2)?);
+        // f64::clamp 在 min > max 或边界为 NaN 时会 panic，先检查
+        if min.is_nan() || max.is_nan() || min > max {
+            return Err(RuntimeError::Generic("'clamp' expects min <= max.".into()));
+        }
+        Ok(Value::Number(v.clamp(min, max)))
+    });
+
     // 判断是否是整数
     register(&mut exports, "is_int", 1, |_, args| {
         let v = get_num(&args, 0)?;
@@ -168,14 +178,17 @@ pub fn create_module() -> Value {
         math_unary_int!(to_int, |v: f64| v as i64),
     );
 
-    register(
-        &mut exports,
-        "rand_int",
-        2,
-        math_binary_int!(rand_int, |min: f64, max: f64| rand_int(
-            min as i32, max as i32
-        )),
-    );
+    // [min, max) 为空区间（min >= max）时报错，而不是返回 min 或交换参数
+    register(&mut exports, "rand_int", 2, |_, args| {
+        let (min, max) = (get_num(&args, 0)? as i32, get_num(&args, 1)? as i32);
+        if min >= max {
+            return Err(RuntimeError::Generic(format!(
+                "rand_int() expects min < max, got {} and {}.",
+                min, max
+            )));
+        }
+        Ok(Value::Number(rand_int(min, max) as f64))
+    });
 
     #[allow(clippy::redundant_closure)]
     register(
@@ -193,3 +206,28 @@ pub fn create_module() -> Value {
 
     Value::Module(Rc::new(RefCell::new(module)))
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_extended_math_functions() {
+        let code = r#"
+            var res = [
+                math.clamp(5, 0, 3), math.clamp(-1, 0, 3), math.clamp(2, 0, 3),
+                math.trunc(-2.7), math.trunc(2.7),
+                math.sign(-4), math.sign(0), math.sign(9),
+                math.atan2(1, 1) == math.PI / 4, math.atan2(0, -1) == math.PI,
+                math.hypot(3, 4), math.asin(1) == math.PI / 2, math.acos(1), math.log2(8)
+            ];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[3, 0, 2, -2, 2, -1, 0, 1, true, true, 5, true, 0, 3]"
+        );
+        assert!(eval_res("var res = math.clamp(1, 3, 0);").is_err());
+        assert!(eval_res("var res = math.clamp(1, math.NAN, 0);").is_err());
+    }
+}
@@ -14,6 +14,11 @@ fn initial_seed() -> u32 {
     since_epoch.as_nanos() as u32 // 使用纳秒时间作为种子
 }
 
+/// 重新设定随机数种子，之后的随机序列完全由种子决定
+pub fn seed(seed: u32) {
+    RNG_STATE.with(|cell| cell.set(seed));
+}
+
 pub fn random() -> f64 {
     RNG_STATE.with(|cell| {
         let mut state = cell.get();
@@ -36,6 +41,7 @@ pub fn rand_range(min: f64, max: f64) -> f64 {
     }
     min + (max - min) * random()
 }
+/// [min, max) 中均匀分布的整数，min == max 时返回 min
 pub fn rand_int(min: i32, max: i32) -> i32 {
     if min > max {
         println!(
@@ -44,5 +50,7 @@ pub fn rand_int(min: i32, max: i32) -> i32 {
         return rand_int(max, min);
     }
 
-    min + (max - min) * random() as i32
+    // 先乘再取整：random() 本身在 [0, 1) 内，直接转整数恒为 0
+    let span = (max as i64 - min as i64) as f64;
+    (min as i64 + (span * random()) as i64) as i32
 }
@@ -1,25 +1,84 @@
+pub mod bytes;
+pub mod compress;
+pub mod config;
+pub mod csv;
+pub mod datetime;
 pub mod dict;
+pub mod encoding;
+pub mod env;
+pub mod gc;
 pub mod globals;
+pub mod http_server;
+pub mod i18n;
 pub mod io;
+pub mod iter;
+pub mod iterator;
+pub mod json;
 pub mod list;
+pub mod log;
 pub mod math;
+pub mod nd;
+pub mod os;
+pub mod path;
+pub mod process;
+pub mod random;
+pub mod regex;
+pub mod registry;
+pub mod set;
+pub mod signal;
+pub mod store;
 pub mod string;
+pub mod string_builder;
+pub mod term;
+pub mod time;
+pub mod tuple;
 pub mod utils;
+pub mod uuid;
 
 pub use crate::evaluate::*;
 
-/// 查找原生方法
+/// 可以通过 `extend` 声明扩展方法的内置类型
+pub const EXTENDABLE_TYPES: &[&str] = &[
+    "String", "List", "Dict", "Set", "Tuple", "Bytes", "DateTime", "Number", "Boolean",
+];
+
+/// 查找内置类型上的方法，并绑定到接收者
+///
+/// 原生方法优先；找不到时再查找用户通过 `extend` 声明的扩展方法。
 ///
 /// # 参数
+/// * `interpreter` - 解释器引用 (用于访问扩展方法表)
 /// * `target` - 调用方法的对象 (用于判断类型: String? List? ..)
 /// * `name` - 方法名 (如 "len", "push")
-pub fn lookup_method(target: &Value, name: &str) -> Option<Value> {
-    match target {
+pub fn lookup_method(interpreter: &Interpreter, target: &Value, name: &str) -> Option<Value> {
+    let native = match target {
         Value::String(_) => string::lookup(name),
         Value::List(_) => list::lookup(name),
         Value::Dict(_) => dict::lookup(name),
+        Value::Tuple(_) => tuple::lookup(name),
+        Value::Set(_) => set::lookup(name),
+        Value::Bytes(_) => bytes::lookup(name),
+        Value::DateTime(_) => datetime::lookup(name),
+        Value::Store(_) => store::lookup(name),
+        Value::StringBuilder(_) => string_builder::lookup(name),
+        Value::NdArray(_) => nd::lookup(name),
+        Value::Generator(_) | Value::Iterator(_) => iterator::lookup(name),
         _ => None,
+    };
+
+    if let Some(method) = native {
+        // 原生方法通过 BoundNativeMethod 携带接收者，调用时作为第一个参数传入
+        return Some(Value::BoundNativeMethod {
+            method: Box::new(method),
+            receiver: Box::new(target.clone()),
+        });
     }
+
+    interpreter
+        .extensions
+        .get(target.type_name())
+        .and_then(|methods| methods.get(name))
+        .map(|method| method.bind(target.clone()))
 }
 
 #[macro_export]
@@ -0,0 +1,219 @@
+use super::{NdArray, element_count, wrap};
+use crate::std_lib::{Interpreter, Value, error::RuntimeError};
+use std::{cell::RefCell, rc::Rc};
+
+fn ensure_array(val: &Value) -> Result<NdArray, RuntimeError> {
+    match val {
+        Value::NdArray(array) => Ok(array.borrow().clone()),
+        other => Err(RuntimeError::TypeError(format!(
+            "Expected NdArray, got {}.",
+            other.type_name()
+        ))),
+    }
+}
+
+/// 解析形状参数：Number（一维）或由 Number 组成的 List
+fn ensure_shape(val: &Value) -> Result<Vec<usize>, RuntimeError> {
+    let dims = match val {
+        Value::Number(_) => vec![val.clone()],
+        Value::List(list) => list.borrow().clone(),
+        Value::Tuple(items) => items.clone(),
+        other => {
+            return Err(RuntimeError::TypeError(format!(
+                "Shape must be a Number or a List of Numbers, got {}.",
+                other.type_name()
+            )));
+        }
+    };
+
+    dims.iter()
+        .map(|dim| match dim {
+            Value::Number(n) if *n >= 0.0 && n.fract() == 0.0 => Ok(*n as usize),
+            other => Err(RuntimeError::Generic(format!(
+                "Invalid dimension {} in shape.",
+                other
+            ))),
+        })
+        .collect()
+}
+
+fn filled(shape: &Value, value: f64) -> Result<Value, RuntimeError> {
+    let shape = ensure_shape(shape)?;
+    let len = element_count(&shape)?;
+    Ok(wrap(NdArray::new(vec![value; len], shape)?))
+}
+
+// nd.array(list) -> NdArray，嵌套列表决定形状
+pub fn array(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    match &args[0] {
+        Value::List(_) => Ok(wrap(NdArray::from_value(&args[0])?)),
+        Value::NdArray(array) => Ok(wrap(array.borrow().clone())),
+        other => Err(RuntimeError::TypeError(format!(
+            "nd.array() expects a List, got {}.",
+            other.type_name()
+        ))),
+    }
+}
+
+// nd.zeros(shape) -> NdArray
+pub fn zeros(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    filled(&args[0], 0.0)
+}
+
+// nd.ones(shape) -> NdArray
+pub fn ones(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    filled(&args[0], 1.0)
+}
+
+// nd.arange(n) -> NdArray，[0, 1, ..., n - 1]
+pub fn arange(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let len = ensure_shape(&args[0])?;
+    let [len] = len.as_slice() else {
+        return Err(RuntimeError::Generic(
+            "nd.arange() expects a single length.".into(),
+        ));
+    };
+    let data = (0..element_count(&[*len])?).map(|i| i as f64).collect();
+    Ok(wrap(NdArray::new(data, vec![*len])?))
+}
+
+// nd.dot(a, b) -> Number | NdArray
+// 向量·向量得到 Number；矩阵 × 矩阵 / 矩阵 × 向量按矩阵乘法计算
+pub fn dot(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let (a, b) = (ensure_array(&args[0])?, ensure_array(&args[1])?);
+    let mismatch = || {
+        RuntimeError::Generic(format!(
+            "Shapes {:?} and {:?} are not aligned for dot().",
+            a.shape, b.shape
+        ))
+    };
+
+    match (a.shape.as_slice(), b.shape.as_slice()) {
+        ([n], [m]) if n == m => Ok(Value::Number(
+            a.data.iter().zip(&b.data).map(|(x, y)| x * y).sum(),
+        )),
+        ([rows, inner], [k, rest @ ..]) if inner == k && rest.len() <= 1 => {
+            let cols = rest.first().copied().unwrap_or(1);
+            let mut data = vec![0.0; element_count(&[*rows, cols])?];
+            for i in 0..*rows {
+                for p in 0..*inner {
+                    let x = a.data[i * inner + p];
+                    for j in 0..cols {
+                        data[i * cols + j] += x * b.data[p * cols + j];
+                    }
+                }
+            }
+            let shape = if rest.is_empty() {
+                vec![*rows]
+            } else {
+                vec![*rows, cols]
+            };
+            Ok(wrap(NdArray::new(data, shape)?))
+        }
+        _ => Err(mismatch()),
+    }
+}
+
+// arr.dot(other) -> Number | NdArray
+pub fn dot_method(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    dot(interpreter, args)
+}
+
+// arr.shape() -> List
+pub fn shape(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let shape = ensure_array(&args[0])?
+        .shape
+        .iter()
+        .map(|&dim| Value::Number(dim as f64))
+        .collect();
+    Ok(Value::List(Rc::new(RefCell::new(shape))))
+}
+
+// arr.len() -> Number，元素总数
+pub fn len(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    Ok(Value::Number(ensure_array(&args[0])?.data.len() as f64))
+}
+
+// arr.reshape(shape) -> NdArray，返回新数组，元素总数必须不变
+pub fn reshape(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let array = ensure_array(&args[0])?;
+    let shape = ensure_shape(&args[1])?;
+    Ok(wrap(NdArray::new(array.data, shape)?))
+}
+
+// arr.get(index) -> Number，index 为扁平下标或每一维的下标列表
+pub fn get(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let array = ensure_array(&args[0])?;
+    let out_of_bounds = || RuntimeError::Generic(format!("Index {} out of bounds.", args[1]));
+
+    let offset = match &args[1] {
+        Value::Number(_) => ensure_shape(&args[1]).map_err(|_| out_of_bounds())?[0],
+        _ => {
+            let indices = ensure_shape(&args[1]).map_err(|_| out_of_bounds())?;
+            if indices.len() != array.shape.len() {
+                return Err(out_of_bounds());
+            }
+            let mut offset = 0;
+            for (index, dim) in indices.iter().zip(&array.shape) {
+                if index >= dim {
+                    return Err(out_of_bounds());
+                }
+                offset = offset * dim + index;
+            }
+            offset
+        }
+    };
+
+    array
+        .data
+        .get(offset)
+        .map(|&n| Value::Number(n))
+        .ok_or_else(out_of_bounds)
+}
+
+// arr.sum() -> Number
+pub fn sum(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    Ok(Value::Number(ensure_array(&args[0])?.data.iter().sum()))
+}
+
+// arr.toList() -> List，按形状生成嵌套列表
+pub fn to_list(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    Ok(ensure_array(&args[0])?.to_value())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_elementwise_and_dot() {
+        let code = r#"
+            var a = nd.array([[1, 2], [3, 4]]);
+            var v = nd.arange(2) + 1;
+            var res = [
+                a * 2 + a, 1 / nd.ones(2), a.dot(a), nd.dot(a, v), v.dot(v),
+                nd.arange(6).reshape([2, 3]).get([1, 2]), a.shape(), nd.zeros([2, 2]).sum()
+            ];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[array([[3, 6], [9, 12]]), array([1, 1]), array([[7, 10], [15, 22]]), \
+             array([5, 11]), 5, 5, [2, 2], 0]"
+        );
+    }
+
+    #[test]
+    fn test_shape_errors() {
+        assert!(eval_res("var res = nd.array([[1, 2], [3]]);").is_err());
+        assert!(eval_res("var res = nd.arange(3) + nd.arange(2);").is_err());
+        assert!(eval_res("var res = nd.arange(6).reshape([4, 2]);").is_err());
+        assert!(eval_res("var res = nd.zeros([1099511627776, 1099511627776]);").is_err());
+        assert!(
+            eval_res("var res = nd.ones([0, 4611686018427387904, 4611686018427387904]);").is_err()
+        );
+        assert!(eval_res("var res = nd.arange(1e300);").is_err());
+        assert!(eval_res("var res = nd.arange(6).reshape([0, 1099511627776]);").is_err());
+        assert!(eval_res(r#"var res = nd.arange(2) + true;"#).is_err());
+    }
+}
@@ -0,0 +1,228 @@
+mod methods;
+
+use crate::{
+    ast::Operator,
+    evaluate::{
+        error::RuntimeError,
+        value::{NativeFn, Value},
+    },
+    native_fn,
+    std_lib::utils,
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// 数值数组 (NdArray)
+///
+/// 连续存储的 f64 缓冲区加上形状 (shape)，按行优先 (row-major) 排列。
+/// 数值计算不再需要为每个元素分配一个 `Value`，逐元素运算直接在缓冲区上完成。
+#[derive(Debug, Clone, PartialEq)]
+pub struct NdArray {
+    pub data: Vec<f64>,
+    pub shape: Vec<usize>,
+}
+
+/// 单个数组的元素个数上限
+pub const MAX_ELEMENTS: usize = 1 << 28;
+
+/// 形状对应的元素个数，超过 `MAX_ELEMENTS`（或溢出 usize）时报错
+///
+/// 长度为 0 的维度按 1 参与上限检查，这样 `[0, 2^40, 2^40]` 之类的形状同样会被拒绝。
+pub fn element_count(shape: &[usize]) -> Result<usize, RuntimeError> {
+    shape
+        .iter()
+        .try_fold(1usize, |count, &dim| {
+            count
+                .checked_mul(dim.max(1))
+                .filter(|&count| count <= MAX_ELEMENTS)
+        })
+        .ok_or_else(|| {
+            RuntimeError::Generic(format!(
+                "Shape {:?} exceeds the maximum of {} elements.",
+                shape, MAX_ELEMENTS
+            ))
+        })?;
+    Ok(shape.iter().product())
+}
+
+impl NdArray {
+    /// 创建数组，数据长度必须与形状相符
+    pub fn new(data: Vec<f64>, shape: Vec<usize>) -> Result<Self, RuntimeError> {
+        let expected = element_count(&shape)?;
+        if data.len() != expected {
+            return Err(RuntimeError::Generic(format!(
+                "Cannot arrange {} elements into shape {:?}.",
+                data.len(),
+                shape
+            )));
+        }
+        Ok(NdArray { data, shape })
+    }
+
+    /// 由（嵌套的）数字列表创建数组，每一层的长度必须一致
+    pub fn from_value(value: &Value) -> Result<Self, RuntimeError> {
+        let mut data = Vec::new();
+        let mut shape = Vec::new();
+        Self::flatten(value, 0, &mut shape, &mut data)?;
+        Ok(NdArray { data, shape })
+    }
+
+    fn flatten(
+        value: &Value,
+        depth: usize,
+        shape: &mut Vec<usize>,
+        data: &mut Vec<f64>,
+    ) -> Result<(), RuntimeError> {
+        match value {
+            Value::Number(n) if depth == shape.len() => {
+                data.push(*n);
+                Ok(())
+            }
+            Value::List(list) => {
+                let list = list.borrow().clone();
+                match shape.get(depth) {
+                    Some(&len) if len != list.len() => Err(ragged()),
+                    Some(_) => Ok(()),
+                    // 第一次到达这一层：只有在此前的元素都还没有写入时才能确定这一维
+                    None if data.is_empty() => {
+                        shape.push(list.len());
+                        Ok(())
+                    }
+                    None => Err(ragged()),
+                }?;
+                list.iter()
+                    .try_for_each(|item| Self::flatten(item, depth + 1, shape, data))
+            }
+            Value::Number(_) => Err(ragged()),
+            other => Err(RuntimeError::TypeError(format!(
+                "NdArray elements must be Numbers, got {}.",
+                other.type_name()
+            ))),
+        }
+    }
+
+    /// 转换为嵌套的 List
+    pub fn to_value(&self) -> Value {
+        fn build(data: &[f64], shape: &[usize]) -> Value {
+            let Some((&len, rest)) = shape.split_first() else {
+                return Value::Number(data[0]);
+            };
+            // 每个子数组占据等长的一段，不需要重新计算形状的乘积
+            let stride = data.len().checked_div(len).unwrap_or(0);
+            let items = (0..len)
+                .map(|i| build(&data[i * stride..(i + 1) * stride], rest))
+                .collect();
+            Value::List(Rc::new(RefCell::new(items)))
+        }
+        build(&self.data, &self.shape)
+    }
+
+    /// 逐元素运算：形状相同的数组之间，或数组与标量之间
+    fn zip_with(
+        &self,
+        other: &Operand,
+        f: impl Fn(f64, f64) -> f64,
+        flipped: bool,
+    ) -> Result<Self, RuntimeError> {
+        let apply = |a: f64, b: f64| if flipped { f(b, a) } else { f(a, b) };
+        let data = match other {
+            Operand::Scalar(b) => self.data.iter().map(|&a| apply(a, *b)).collect(),
+            Operand::Array(other) => {
+                if other.shape != self.shape {
+                    return Err(RuntimeError::Generic(format!(
+                        "Shape mismatch: {:?} vs {:?}.",
+                        self.shape, other.shape
+                    )));
+                }
+                self.data
+                    .iter()
+                    .zip(&other.data)
+                    .map(|(&a, &b)| apply(a, b))
+                    .collect()
+            }
+        };
+        Ok(NdArray {
+            data,
+            shape: self.shape.clone(),
+        })
+    }
+}
+
+fn ragged() -> RuntimeError {
+    RuntimeError::Generic("NdArray requires a rectangular list of Numbers.".into())
+}
+
+enum Operand {
+    Scalar(f64),
+    Array(NdArray),
+}
+
+/// NdArray 的二元运算符：`+ - * / ~/` 逐元素计算，另一侧可以是同形状的数组或 Number
+///
+/// 与 Number 的 `/` 不同，数组按 IEEE 754 计算除以零（得到 Infinity / NaN），
+/// 避免一个元素为零就让整个批量运算失败。
+///
+/// # 返回值
+/// * `None` - 两侧都不是 NdArray，或者运算符不由这里处理
+pub fn binary(op: &Operator, left: &Value, right: &Value) -> Option<Result<Value, RuntimeError>> {
+    let f: fn(f64, f64) -> f64 = match op {
+        Operator::Add => |a, b| a + b,
+        Operator::Sub => |a, b| a - b,
+        Operator::Mul => |a, b| a * b,
+        Operator::Div => |a, b| a / b,
+        Operator::FloorDiv => |a, b| (a / b).floor(),
+        _ => return None,
+    };
+
+    let (array, other, flipped) = match (left, right) {
+        (Value::NdArray(a), Value::NdArray(b)) => (
+            a.borrow().clone(),
+            Operand::Array(b.borrow().clone()),
+            false,
+        ),
+        (Value::NdArray(a), Value::Number(n)) => (a.borrow().clone(), Operand::Scalar(*n), false),
+        (Value::Number(n), Value::NdArray(a)) => (a.borrow().clone(), Operand::Scalar(*n), true),
+        // 字符串拼接交给通用的加法处理
+        (_, Value::String(_)) | (Value::String(_), _) => return None,
+        (Value::NdArray(_), other) | (other, Value::NdArray(_)) => {
+            return Some(Err(RuntimeError::TypeError(format!(
+                "Cannot combine NdArray with {}.",
+                other.type_name()
+            ))));
+        }
+        _ => return None,
+    };
+
+    Some(array.zip_with(&other, f, flipped).map(wrap))
+}
+
+/// 包装为 Value
+pub fn wrap(array: NdArray) -> Value {
+    Value::NdArray(Rc::new(RefCell::new(array)))
+}
+
+/// 数组上的方法
+pub fn lookup(name: &str) -> Option<Value> {
+    match name {
+        "shape" => native_fn!("shape", 0, methods::shape),
+        "len" => native_fn!("len", 0, methods::len),
+        "reshape" => native_fn!("reshape", 1, methods::reshape),
+        "get" => native_fn!("get", 1, methods::get),
+        "sum" => native_fn!("sum", 0, methods::sum),
+        "dot" => native_fn!("dot", 1, methods::dot_method),
+        "toList" => native_fn!("toList", 0, methods::to_list),
+        _ => None,
+    }
+}
+
+// 创建 nd 模块对象
+pub fn create_module() -> Value {
+    let functions: &[(&str, usize, NativeFn)] = &[
+        ("array", 1, methods::array),
+        ("zeros", 1, methods::zeros),
+        ("ones", 1, methods::ones),
+        ("arange", 1, methods::arange),
+        ("dot", 2, methods::dot),
+    ];
+
+    utils::native_module("nd", functions)
+}
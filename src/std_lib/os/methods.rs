@@ -0,0 +1,93 @@
+use crate::evaluate::{Interpreter, error::RuntimeError, heap, value::Value};
+use crate::std_lib::{io::trace::traced, utils::ensure_string};
+use std::{cell::RefCell, env, rc::Rc};
+
+// os.getenv(name): 读取环境变量，不存在时返回 nil。os.getenv(String) -> String | Nil
+pub fn getenv(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let name = ensure_string(&args[0])?;
+    traced(interpreter, "os.getenv", &[name], || {
+        Ok(env::var(name).map(Value::String).unwrap_or(Value::Nil))
+    })
+}
+
+// os.setenv(name, value): 设置当前进程（及其子进程）的环境变量。os.setenv(String, String) -> Nil
+pub fn setenv(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let name = ensure_string(&args[0])?;
+    let value = ensure_string(&args[1])?;
+    if name.is_empty() || name.contains(['=', '\0']) || value.contains('\0') {
+        return Err(RuntimeError::Generic(format!(
+            "Invalid environment variable '{}'.",
+            name
+        )));
+    }
+
+    traced(interpreter, "os.setenv", &[name, value], || {
+        // SAFETY: 脚本只在解释器线程中执行，rox 不会在其他线程中读写环境变量
+        unsafe { env::set_var(name, value) };
+        Ok(Value::Nil)
+    })
+}
+
+// os.cwd(): 当前工作目录。os.cwd() -> String
+pub fn cwd(interpreter: &mut Interpreter, _: Vec<Value>) -> Result<Value, RuntimeError> {
+    traced(interpreter, "os.cwd", &[], || {
+        env::current_dir()
+            .map(|dir| Value::String(dir.display().to_string()))
+            .map_err(|e| RuntimeError::Generic(format!("Failed to get current directory: {}", e)))
+    })
+}
+
+// os.chdir(path): 切换工作目录，之后的相对路径（eg. fs.readFile）以它为基准。os.chdir(String) -> Nil
+pub fn chdir(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path = ensure_string(&args[0])?;
+    traced(interpreter, "os.chdir", &[path], || {
+        env::set_current_dir(path)
+            .map(|_| Value::Nil)
+            .map_err(|e| RuntimeError::Generic(format!("Failed to change directory: {}", e)))
+    })
+}
+
+// os.platform(): 操作系统名，eg. "linux"、"macos"、"windows"。os.platform() -> String
+pub fn platform(_: &mut Interpreter, _: Vec<Value>) -> Result<Value, RuntimeError> {
+    Ok(Value::String(env::consts::OS.to_string()))
+}
+
+// os.args(): 脚本路径之后的命令行参数（`rox script.rox a b` 得到 ["a", "b"]）。os.args() -> List
+pub fn args(interpreter: &mut Interpreter, _: Vec<Value>) -> Result<Value, RuntimeError> {
+    let list = Value::List(Rc::new(RefCell::new(
+        interpreter
+            .script_args
+            .iter()
+            .cloned()
+            .map(Value::String)
+            .collect(),
+    )));
+    heap::track(&list);
+    Ok(list)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_env_and_platform() {
+        let code = r#"
+            os.setenv("ROX_OS_TEST_VAR", "hello");
+            var res = [
+                os.getenv("ROX_OS_TEST_VAR"),
+                os.getenv("ROX_OS_TEST_MISSING"),
+                os.platform() == "linux" or os.platform() == "macos" or os.platform() == "windows",
+                os.cwd().len() > 0,
+                os.args()
+            ];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[hello, nil, true, true, []]"
+        );
+        assert!(eval_res(r#"var res = os.setenv("A=B", "x");"#).is_err());
+        assert!(eval_res(r#"var res = os.chdir("/no/such/rox/dir");"#).is_err());
+    }
+}
@@ -0,0 +1,21 @@
+mod methods;
+
+use crate::{
+    evaluate::value::{NativeFn, Value},
+    std_lib::utils,
+};
+
+/// os 模块：环境变量、工作目录、平台信息与脚本的命令行参数
+const FUNCTIONS: &[(&str, usize, NativeFn)] = &[
+    ("getenv", 1, methods::getenv),
+    ("setenv", 2, methods::setenv),
+    ("cwd", 0, methods::cwd),
+    ("chdir", 1, methods::chdir),
+    ("platform", 0, methods::platform),
+    ("args", 0, methods::args),
+];
+
+// 创建 os 模块对象
+pub fn create_module() -> Value {
+    utils::native_module("os", FUNCTIONS)
+}
@@ -0,0 +1,105 @@
+use crate::evaluate::{Interpreter, error::RuntimeError, value::Value};
+use crate::std_lib::{io::trace::traced, utils::ensure_string};
+use std::path::{self, Component, Path, PathBuf};
+
+fn path_value(path: &Path) -> Value {
+    Value::String(path.display().to_string())
+}
+
+/// 只按字面处理 `.` 与 `..`，不访问文件系统（因此不解析符号链接）
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match out.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    out.pop();
+                }
+                // 根目录的上级仍是根目录
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => out.push(".."),
+            },
+            other => out.push(other),
+        }
+    }
+    if out.as_os_str().is_empty() {
+        out.push(".");
+    }
+    out
+}
+
+// path.join(base, child): 用平台分隔符拼接，child 为绝对路径时直接返回 child
+// path.join(String, String) -> String
+pub fn join(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let base = ensure_string(&args[0])?;
+    let child = ensure_string(&args[1])?;
+    Ok(path_value(&Path::new(base).join(child)))
+}
+
+// path.basename(path): 最后一段的名称，eg. "a/b.txt" -> "b.txt"；没有时为 ""。path.basename(String) -> String
+pub fn basename(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path = ensure_string(&args[0])?;
+    let name = Path::new(path)
+        .file_name()
+        .map_or(String::new(), |name| name.to_string_lossy().into_owned());
+    Ok(Value::String(name))
+}
+
+// path.dirname(path): 去掉最后一段后的路径，eg. "a/b.txt" -> "a"；没有上级时为 ""。path.dirname(String) -> String
+pub fn dirname(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path = ensure_string(&args[0])?;
+    Ok(Path::new(path)
+        .parent()
+        .map_or(Value::String(String::new()), path_value))
+}
+
+// path.extension(path): 不带点的扩展名，eg. "a.tar.gz" -> "gz"；没有时返回 nil。path.extension(String) -> String | Nil
+pub fn extension(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path = ensure_string(&args[0])?;
+    Ok(Path::new(path).extension().map_or(Value::Nil, |ext| {
+        Value::String(ext.to_string_lossy().into_owned())
+    }))
+}
+
+// path.absolute(path): 以当前工作目录为基准转为绝对路径并规范化。path.absolute(String) -> String
+pub fn absolute(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path = ensure_string(&args[0])?;
+    // 结果依赖工作目录，需要经过追踪才能稳定回放
+    traced(interpreter, "path.absolute", &[path], || {
+        path::absolute(path)
+            .map(|abs| path_value(&normalize_path(&abs)))
+            .map_err(|e| RuntimeError::Generic(format!("Failed to resolve path: {}", e)))
+    })
+}
+
+// path.normalize(path): 去掉多余的分隔符、`.` 与可以抵消的 `..`。path.normalize(String) -> String
+pub fn normalize(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path = ensure_string(&args[0])?;
+    Ok(path_value(&normalize_path(Path::new(path))))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_path_functions() {
+        let code = r#"
+            var res = [
+                path.join("a/b", "c.txt"), path.join("a", "/etc"),
+                path.basename("a/b.txt"), path.basename("/"),
+                path.dirname("a/b.txt"), path.dirname("b.txt"),
+                path.extension("x/a.tar.gz"), path.extension("Makefile"),
+                path.normalize("./a//b/../c/."), path.normalize("../a/.."), path.normalize("/.."),
+                path.absolute("x/../y") == path.join(os.cwd(), "y")
+            ];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[a/b/c.txt, /etc, b.txt, , a, , gz, nil, a/c, .., /, true]"
+        );
+        assert!(eval_res("var res = path.basename(1);").is_err());
+    }
+}
@@ -0,0 +1,21 @@
+mod methods;
+
+use crate::{
+    evaluate::value::{NativeFn, Value},
+    std_lib::utils,
+};
+
+/// path 模块：按当前平台的分隔符拼接、拆分与规范化路径
+const FUNCTIONS: &[(&str, usize, NativeFn)] = &[
+    ("join", 2, methods::join),
+    ("basename", 1, methods::basename),
+    ("dirname", 1, methods::dirname),
+    ("extension", 1, methods::extension),
+    ("absolute", 1, methods::absolute),
+    ("normalize", 1, methods::normalize),
+];
+
+// 创建 path 模块对象
+pub fn create_module() -> Value {
+    utils::native_module("path", FUNCTIONS)
+}
@@ -0,0 +1,96 @@
+use crate::evaluate::{Interpreter, error::RuntimeError, value::Value};
+use crate::std_lib::{
+    io::trace::traced,
+    utils::{ensure_list, ensure_string, string_dict},
+};
+use std::{process::Command, thread};
+
+/// 取出命令名与参数列表，参数必须都是字符串
+fn command_line(args: &[Value]) -> Result<(String, Vec<String>), RuntimeError> {
+    let program = ensure_string(&args[0])?.clone();
+    let arguments = ensure_list(&args[1])?
+        .borrow()
+        .iter()
+        .map(|arg| match arg {
+            Value::String(s) => Ok(s.clone()),
+            other => Err(RuntimeError::TypeError(format!(
+                "Command arguments must be strings, got {}.",
+                other.type_name()
+            ))),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((program, arguments))
+}
+
+// process.run(cmd, args): 运行命令并等待结束，捕获输出。
+// 返回 {"code": 退出码（被信号终止时为 nil）, "stdout": String, "stderr": String}
+// process.run(String, List) -> Dict
+pub fn run(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let (program, arguments) = command_line(&args)?;
+    let trace_args: Vec<&str> = std::iter::once(program.as_str())
+        .chain(arguments.iter().map(String::as_str))
+        .collect();
+
+    traced(interpreter, "process.run", &trace_args, || {
+        let output = Command::new(&program)
+            .args(&arguments)
+            .output()
+            .map_err(|e| RuntimeError::Generic(format!("Failed to run '{}': {}", program, e)))?;
+
+        let code = output
+            .status
+            .code()
+            .map_or(Value::Nil, |code| Value::Number(code as f64));
+        Ok(string_dict([
+            ("code".to_string(), code),
+            (
+                "stdout".to_string(),
+                Value::String(String::from_utf8_lossy(&output.stdout).into_owned()),
+            ),
+            (
+                "stderr".to_string(),
+                Value::String(String::from_utf8_lossy(&output.stderr).into_owned()),
+            ),
+        ]))
+    })
+}
+
+// process.spawn(cmd, args): 启动命令后立即返回进程号，不等待结束，输出直接写到终端。
+// process.spawn(String, List) -> Number
+pub fn spawn(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let (program, arguments) = command_line(&args)?;
+    let trace_args: Vec<&str> = std::iter::once(program.as_str())
+        .chain(arguments.iter().map(String::as_str))
+        .collect();
+
+    traced(interpreter, "process.spawn", &trace_args, || {
+        let mut child = Command::new(&program)
+            .args(&arguments)
+            .spawn()
+            .map_err(|e| RuntimeError::Generic(format!("Failed to spawn '{}': {}", program, e)))?;
+        let pid = child.id();
+
+        // 在后台回收子进程，避免留下僵尸进程
+        thread::spawn(move || child.wait());
+
+        Ok(Value::Number(pid as f64))
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_run_captures_output() {
+        let code = r#"
+            var ok = process.run("sh", ["-c", "printf out; printf err >&2; exit 3"]);
+            var res = [ok["code"], ok["stdout"], ok["stderr"], process.spawn("true", []) > 0];
+        "#;
+        assert_eq!(eval_res(code).unwrap().to_string(), "[3, out, err, true]");
+
+        assert!(eval_res(r#"var res = process.run("rox-no-such-command", []);"#).is_err());
+        assert!(eval_res(r#"var res = process.run("echo", [1]);"#).is_err());
+    }
+}
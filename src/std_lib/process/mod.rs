@@ -0,0 +1,15 @@
+mod methods;
+
+use crate::{
+    evaluate::value::{NativeFn, Value},
+    std_lib::utils,
+};
+
+/// process 模块：运行外部命令
+const FUNCTIONS: &[(&str, usize, NativeFn)] =
+    &[("run", 2, methods::run), ("spawn", 2, methods::spawn)];
+
+// 创建 process 模块对象
+pub fn create_module() -> Value {
+    utils::native_module("process", FUNCTIONS)
+}
@@ -0,0 +1,113 @@
+use crate::evaluate::{Interpreter, error::RuntimeError, heap, value::Value};
+use crate::std_lib::{math, utils::ensure_list};
+use std::{cell::RefCell, rc::Rc};
+
+/// [0, len) 中的随机下标
+fn index_below(len: usize) -> usize {
+    math::rand_int(0, len as i32) as usize
+}
+
+// random.seed(n): 设定种子，之后的随机序列（包括 math.random）完全由 n 决定。random.seed(Number) -> Nil
+pub fn seed(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    match &args[0] {
+        Value::Number(n) if n.fract() == 0.0 && (0.0..=u32::MAX as f64).contains(n) => {
+            math::seed(*n as u32);
+            Ok(Value::Nil)
+        }
+        _ => Err(RuntimeError::TypeError(
+            "Seed must be an integer between 0 and 4294967295.".into(),
+        )),
+    }
+}
+
+// random.shuffle(list): 原地打乱列表顺序 (Fisher-Yates)。random.shuffle(List) -> Nil
+pub fn shuffle(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let mut list = ensure_list(&args[0])?.borrow_mut();
+    for i in (1..list.len()).rev() {
+        list.swap(i, index_below(i + 1));
+    }
+    Ok(Value::Nil)
+}
+
+// random.choice(list): 随机取一个元素，空列表报错。random.choice(List) -> Value
+pub fn choice(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let list = ensure_list(&args[0])?.borrow();
+    if list.is_empty() {
+        return Err(RuntimeError::Generic(
+            "Cannot choose from an empty list.".into(),
+        ));
+    }
+    Ok(list[index_below(list.len())].clone())
+}
+
+// random.sample(list, k): 不放回地随机取 k 个元素，返回新列表，原列表不变。random.sample(List, Number) -> List
+pub fn sample(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let mut pool = ensure_list(&args[0])?.borrow().clone();
+    let k = match &args[1] {
+        Value::Number(k) if k.fract() == 0.0 && *k >= 0.0 && *k <= pool.len() as f64 => *k as usize,
+        _ => {
+            return Err(RuntimeError::Generic(format!(
+                "Sample size must be an integer between 0 and {}.",
+                pool.len()
+            )));
+        }
+    };
+
+    // 只需洗出前 k 个位置
+    for i in 0..k {
+        let j = i + index_below(pool.len() - i);
+        pool.swap(i, j);
+    }
+    pool.truncate(k);
+
+    let list = Value::List(Rc::new(RefCell::new(pool)));
+    heap::track(&list);
+    Ok(list)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_seeded_sequences_repeat() {
+        let code = r#"
+            fun draw() {
+                var items = [1, 2, 3, 4, 5, 6, 7, 8];
+                random.shuffle(items);
+                return [items, random.choice(items), random.sample(items, 3), math.rand_int(0, 100)];
+            }
+            random.seed(42);
+            var first = draw();
+            random.seed(42);
+            var res = first == draw();
+        "#;
+        assert_eq!(eval_res(code).unwrap().to_string(), "true");
+    }
+
+    #[test]
+    fn test_shuffle_choice_sample() {
+        let code = r#"
+            random.seed(7);
+            var items = [1, 2, 3, 4, 5];
+            random.shuffle(items);
+            var picked = random.sample(items, 5);
+            var ints = [];
+            for (var i = 0; i < 50; i = i + 1) ints.push(math.rand_int(3, 6));
+            var res = [
+                set(items) == set([1, 2, 3, 4, 5]), set(picked).len(), random.sample(items, 0),
+                items.contains(random.choice(items)), set(ints) == set([3, 4, 5])
+            ];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[true, 5, [], true, true]"
+        );
+        assert!(eval_res("var res = random.choice([]);").is_err());
+        assert!(eval_res("var res = random.sample([1], 2);").is_err());
+        assert!(eval_res("var res = random.seed(-1);").is_err());
+        assert!(eval_res("var res = math.rand_int(5, 5);").is_err());
+        assert!(eval_res("var res = math.rand_int(6, 3);").is_err());
+    }
+}
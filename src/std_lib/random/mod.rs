@@ -0,0 +1,20 @@
+mod methods;
+
+use crate::{
+    evaluate::value::{NativeFn, Value},
+    std_lib::utils,
+};
+
+/// random 模块：可设定种子的随机数，以及基于它的洗牌与抽样。
+/// 与 `math.random` / `math.rand_int` 共用同一个生成器
+const FUNCTIONS: &[(&str, usize, NativeFn)] = &[
+    ("seed", 1, methods::seed),
+    ("shuffle", 1, methods::shuffle),
+    ("choice", 1, methods::choice),
+    ("sample", 2, methods::sample),
+];
+
+// 创建 random 模块对象
+pub fn create_module() -> Value {
+    utils::native_module("random", FUNCTIONS)
+}
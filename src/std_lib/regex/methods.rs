@@ -0,0 +1,97 @@
+use crate::evaluate::{Interpreter, error::RuntimeError, heap, value::Value};
+use crate::std_lib::utils::ensure_string;
+use ::regex::Regex;
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+/// 缓存的编译结果上限，超过后整体清空，避免动态拼接的模式无限增长
+const CACHE_LIMIT: usize = 64;
+
+thread_local! {
+    // 模式字符串 -> 编译好的正则，循环中反复使用同一个模式时只编译一次
+    static CACHE: RefCell<HashMap<String, Regex>> = RefCell::new(HashMap::new());
+}
+
+/// 取出（必要时编译并缓存）模式对应的正则
+fn compile(pattern: &Value) -> Result<Regex, RuntimeError> {
+    let pattern = ensure_string(pattern)?;
+    CACHE.with(|cache| {
+        if let Some(regex) = cache.borrow().get(pattern) {
+            return Ok(regex.clone());
+        }
+        let regex = Regex::new(pattern)
+            .map_err(|e| RuntimeError::Generic(format!("Invalid regex pattern: {}", e)))?;
+        let mut cache = cache.borrow_mut();
+        if cache.len() >= CACHE_LIMIT {
+            cache.clear();
+        }
+        cache.insert(pattern.clone(), regex.clone());
+        Ok(regex)
+    })
+}
+
+fn string_list(items: impl IntoIterator<Item = String>) -> Value {
+    let list = Value::List(Rc::new(RefCell::new(
+        items.into_iter().map(Value::String).collect(),
+    )));
+    heap::track(&list);
+    list
+}
+
+// regex.matches(pattern, text): 文本中是否存在匹配。regex.matches(String, String) -> Boolean
+pub fn matches(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let regex = compile(&args[0])?;
+    let text = ensure_string(&args[1])?;
+    Ok(Value::Boolean(regex.is_match(text)))
+}
+
+// regex.find_all(pattern, text): 所有不重叠的匹配文本。regex.find_all(String, String) -> List
+pub fn find_all(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let regex = compile(&args[0])?;
+    let text = ensure_string(&args[1])?;
+    Ok(string_list(
+        regex.find_iter(text).map(|m| m.as_str().to_string()),
+    ))
+}
+
+// regex.replace(pattern, text, replacement): 替换所有匹配，replacement 中可用 `$1` / `${name}` 引用分组
+// regex.replace(String, String, String) -> String
+pub fn replace(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let regex = compile(&args[0])?;
+    let text = ensure_string(&args[1])?;
+    let replacement = ensure_string(&args[2])?;
+    Ok(Value::String(
+        regex.replace_all(text, replacement.as_str()).into_owned(),
+    ))
+}
+
+// regex.split(pattern, text): 按匹配切分文本。regex.split(String, String) -> List
+pub fn split(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let regex = compile(&args[0])?;
+    let text = ensure_string(&args[1])?;
+    Ok(string_list(regex.split(text).map(str::to_string)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_regex_functions() {
+        let code = r#"
+            var res = [
+                regex.matches("^[a-z]+$", "rox"),
+                regex.matches("^[a-z]+$", "Rox"),
+                regex.find_all("[0-9]+", "a1 b22 c333"),
+                regex.replace("(\w+)@(\w+)", "me@home you@work", "$2:$1"),
+                regex.split("\s*,\s*", "a , b,c")
+            ];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[true, false, [1, 22, 333], home:me work:you, [a, b, c]]"
+        );
+        assert!(eval_res(r#"var res = regex.matches("(", "x");"#).is_err());
+        assert!(eval_res(r#"var res = regex.matches(1, "x");"#).is_err());
+    }
+}
@@ -0,0 +1,19 @@
+mod methods;
+
+use crate::{
+    evaluate::value::{NativeFn, Value},
+    std_lib::utils,
+};
+
+/// regex 模块：正则匹配、查找、替换与分割，参数顺序统一为 (pattern, text, ...)
+const FUNCTIONS: &[(&str, usize, NativeFn)] = &[
+    ("matches", 2, methods::matches),
+    ("find_all", 2, methods::find_all),
+    ("replace", 3, methods::replace),
+    ("split", 2, methods::split),
+];
+
+// 创建 regex 模块对象
+pub fn create_module() -> Value {
+    utils::native_module("regex", FUNCTIONS)
+}
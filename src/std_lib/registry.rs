@@ -0,0 +1,401 @@
+use crate::{
+    evaluate::{
+        environment::Environment,
+        value::{RoxModule, Value},
+    },
+    std_lib::{
+        compress, config, csv, datetime, encoding, env, gc, globals, http_server, i18n, io, iter,
+        json, log, math, nd, os, path, process, random, regex, signal, store, term, time, uuid,
+    },
+};
+use std::{
+    cell::{OnceCell, RefCell},
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+/// 原生模块描述
+///
+/// `create` 负责构造模块的导出表，只会在模块第一次被访问时调用。
+pub struct NativeModule {
+    pub name: &'static str,
+    pub create: fn() -> Value,
+}
+
+/// 所有内置原生模块的清单，新增模块只需要在这里登记
+pub const NATIVE_MODULES: &[NativeModule] = &[
+    NativeModule {
+        name: "base64",
+        create: encoding::base64::create_module,
+    },
+    NativeModule {
+        name: "csv",
+        create: csv::create_module,
+    },
+    NativeModule {
+        name: "datetime",
+        create: datetime::create_module,
+    },
+    NativeModule {
+        name: "deflate",
+        create: compress::deflate::create_module,
+    },
+    NativeModule {
+        name: "env",
+        create: env::create_module,
+    },
+    NativeModule {
+        name: "fs",
+        create: io::file_system::create_module,
+    },
+    NativeModule {
+        name: "gc",
+        create: gc::create_module,
+    },
+    NativeModule {
+        name: "gzip",
+        create: compress::gzip::create_module,
+    },
+    NativeModule {
+        name: "http",
+        create: http_server::create_module,
+    },
+    NativeModule {
+        name: "i18n",
+        create: i18n::create_module,
+    },
+    NativeModule {
+        name: "iter",
+        create: iter::create_module,
+    },
+    NativeModule {
+        name: "json",
+        create: json::create_module,
+    },
+    NativeModule {
+        name: "log",
+        create: log::create_module,
+    },
+    NativeModule {
+        name: "math",
+        create: math::create_module,
+    },
+    NativeModule {
+        name: "nd",
+        create: nd::create_module,
+    },
+    NativeModule {
+        name: "os",
+        create: os::create_module,
+    },
+    NativeModule {
+        name: "path",
+        create: path::create_module,
+    },
+    NativeModule {
+        name: "process",
+        create: process::create_module,
+    },
+    NativeModule {
+        name: "random",
+        create: random::create_module,
+    },
+    NativeModule {
+        name: "regex",
+        create: regex::create_module,
+    },
+    NativeModule {
+        name: "signal",
+        create: signal::create_module,
+    },
+    NativeModule {
+        name: "store",
+        create: store::create_module,
+    },
+    NativeModule {
+        name: "term",
+        create: term::create_module,
+    },
+    NativeModule {
+        name: "time",
+        create: time::create_module,
+    },
+    NativeModule {
+        name: "toml",
+        create: config::toml::create_module,
+    },
+    NativeModule {
+        name: "uuid",
+        create: uuid::create_module,
+    },
+];
+
+/// 内置模块注册表 (Builtins Registry)
+///
+/// 统一负责把原生模块安装到全局环境：
+/// - 所有启用的模块挂在 `std` 命名空间下，eg. `std.math.abs(-1)`。
+/// - 同时保留 `fs`、`math` 这类顶层全局名，兼容已有脚本。
+///
+/// 嵌入方可以在创建解释器前禁用某些模块（eg. 沙箱环境禁用 `fs`）。
+///
+/// 模块是惰性构建的：安装时只放入一个未初始化 (`is_initialized == false`) 的空模块，
+/// 第一次访问其成员或通过 `import("name")` 导入时才真正构建导出表。
+///
+/// 内置全局名（原生模块 + `clock`、`import` 等全局函数）只构建一次，保存在只读的
+/// 预置环境 (Prelude) 中；解释器的全局环境与每个文件模块的环境都以它为父环境，
+/// 创建时只需要增加一次引用计数。
+#[derive(Debug, Clone, Default)]
+pub struct Registry {
+    disabled: HashSet<String>,
+    /// 模块占位缓存：共享同一注册表的解释器（包括其加载的文件模块）共享同一份模块对象
+    modules: Rc<RefCell<ModuleSlots>>,
+    /// 预置环境快照，第一次使用时构建，配置变化后失效
+    prelude: OnceCell<Rc<RefCell<Environment>>>,
+}
+
+type ModuleSlots = HashMap<&'static str, Rc<RefCell<RoxModule>>>;
+
+thread_local! {
+    /// 默认配置的注册表快照，同一线程内的解释器共用
+    static SHARED: Registry = Registry::default();
+}
+
+#[allow(dead_code)] // 面向嵌入方的 API，CLI 本身使用默认配置
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 默认配置的注册表：同一线程内共享已构建的预置环境与模块
+    pub fn shared() -> Self {
+        SHARED.with(|registry| {
+            registry.prelude(); // 先在快照上构建，克隆出的注册表共享同一个预置环境
+            registry.clone()
+        })
+    }
+
+    /// 禁用模块，未知的模块名会被忽略
+    pub fn disable(&mut self, name: &str) -> &mut Self {
+        self.disabled.insert(name.to_string());
+        self.prelude = OnceCell::new();
+        self
+    }
+
+    /// 重新启用之前被禁用的模块
+    pub fn enable(&mut self, name: &str) -> &mut Self {
+        self.disabled.remove(name);
+        self.prelude = OnceCell::new();
+        self
+    }
+
+    pub fn is_enabled(&self, name: &str) -> bool {
+        NATIVE_MODULES.iter().any(|m| m.name == name) && !self.disabled.contains(name)
+    }
+
+    /// 列出所有已启用的模块名
+    pub fn list(&self) -> Vec<&'static str> {
+        NATIVE_MODULES
+            .iter()
+            .map(|m| m.name)
+            .filter(|name| self.is_enabled(name))
+            .collect()
+    }
+
+    /// 按名称获取一个已启用的模块（尚未构建时返回未初始化的占位模块）
+    pub fn module(&self, name: &str) -> Option<Value> {
+        let native = NATIVE_MODULES
+            .iter()
+            .find(|m| m.name == name && self.is_enabled(name))?;
+
+        let module = self
+            .modules
+            .borrow_mut()
+            .entry(native.name)
+            .or_insert_with(|| Rc::new(RefCell::new(RoxModule::new(native.name.to_string()))))
+            .clone();
+
+        Some(Value::Module(module))
+    }
+
+    /// 按名称获取模块，并确保其已经构建完成
+    pub fn import(&self, name: &str) -> Option<Value> {
+        let module = self.module(name)?;
+        if let Value::Module(module_rc) = &module {
+            self.load(module_rc);
+        }
+        Some(module)
+    }
+
+    /// 构建原生模块的导出表（已初始化或非原生模块不做处理）
+    pub fn load(&self, module: &Rc<RefCell<RoxModule>>) {
+        if module.borrow().is_initialized {
+            return;
+        }
+
+        let name = module.borrow().name.clone();
+        let Some(native) = NATIVE_MODULES
+            .iter()
+            .find(|m| m.name == name && self.is_enabled(&name))
+        else {
+            return; // 文件模块：未初始化意味着仍在加载中
+        };
+
+        // 只有注册表里的占位模块才会被填充，避免误填同名的文件模块
+        let is_registered = self
+            .modules
+            .borrow()
+            .get(native.name)
+            .is_some_and(|m| Rc::ptr_eq(m, module));
+        if !is_registered {
+            return;
+        }
+
+        if let Value::Module(built) = (native.create)() {
+            let built = built.borrow();
+            let mut module = module.borrow_mut();
+            module.exports = built.exports.clone();
+            module.is_initialized = true;
+        }
+    }
+
+    /// 将已启用的模块安装到环境中：`std` 命名空间 + 顶层全局名
+    pub fn install(&self, env: &mut Environment) {
+        let mut exports = HashMap::new();
+
+        for name in self.list() {
+            if let Some(module) = self.module(name) {
+                env.define(name.to_string(), module.clone());
+                exports.insert(name.to_string(), module);
+            }
+        }
+
+        let std = RoxModule {
+            name: "std".to_string(),
+            exports,
+            is_initialized: true,
+        };
+        env.define("std".to_string(), Value::Module(Rc::new(RefCell::new(std))));
+    }
+
+    /// 预置环境：已安装全部内置全局名的只读环境
+    ///
+    /// 用户代码不会直接修改它：对内置名赋值时，解释器会在当前全局环境中遮蔽该名字。
+    pub fn prelude(&self) -> Rc<RefCell<Environment>> {
+        self.prelude
+            .get_or_init(|| {
+                let mut env = Environment::new();
+                self.install(&mut env);
+                for &(name, arity, func) in globals::FUNCTIONS {
+                    env.define(
+                        name.to_string(),
+                        Value::NativeFunction {
+                            name: name.to_string(),
+                            arity,
+                            func,
+                        },
+                    );
+                }
+                Rc::new(RefCell::new(env))
+            })
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Registry;
+    use crate::{
+        evaluate::Interpreter,
+        parser::parse,
+        reader::Source,
+        resolver::Resolver,
+        std_lib::{Value, eval_res},
+        tokenizer::tokenize,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_std_namespace() {
+        let code = r#"
+            var res = std.math.abs(-2) + math.abs(-1);
+        "#;
+
+        assert_eq!(eval_res(code).unwrap(), Value::Number(3.0));
+    }
+
+    #[test]
+    fn test_lazy_module_loading() {
+        let mut interpreter = Interpreter::new();
+        let Some(Value::Module(math)) = interpreter.registry.module("math") else {
+            panic!("math module should be registered");
+        };
+        assert!(!math.borrow().is_initialized);
+
+        let tokens = tokenize(Source {
+            contents: r#"var m = import("math"); var res = m.abs(-1) + std.math.abs(-1);"#
+                .to_string(),
+        })
+        .unwrap();
+        let ast = parse(tokens).unwrap();
+        Resolver::new(&mut interpreter)
+            .resolve_stmts(&ast.body)
+            .unwrap();
+        interpreter.interpret(ast).unwrap();
+
+        assert!(math.borrow().is_initialized);
+        assert_eq!(
+            interpreter.get_global_value("res").unwrap(),
+            Value::Number(2.0)
+        );
+    }
+
+    #[test]
+    fn test_registry_disable_module() {
+        let mut registry = Registry::new();
+        registry.disable("fs");
+        assert_eq!(
+            registry.list(),
+            vec![
+                "base64", "csv", "datetime", "deflate", "env", "gc", "gzip", "http", "i18n",
+                "iter", "json", "log", "math", "nd", "os", "path", "process", "random", "regex",
+                "signal", "store", "term", "time", "toml", "uuid"
+            ]
+        );
+
+        let mut interpreter = Interpreter::with_registry(registry);
+        let tokens = tokenize(Source {
+            contents: "var res = fs;".to_string(),
+        })
+        .unwrap();
+        let ast = parse(tokens).unwrap();
+        Resolver::new(&mut interpreter)
+            .resolve_stmts(&ast.body)
+            .unwrap();
+
+        assert!(interpreter.interpret(ast).is_err());
+    }
+
+    #[test]
+    fn test_prelude_is_shared_and_read_only() {
+        let mut first = Interpreter::new();
+        let second = Interpreter::new();
+        assert!(std::rc::Rc::ptr_eq(
+            &first.registry.prelude(),
+            &second.registry.prelude()
+        ));
+
+        let tokens = tokenize(Source {
+            contents: "clock = 1; var res = clock;".to_string(),
+        })
+        .unwrap();
+        let ast = parse(tokens).unwrap();
+        Resolver::new(&mut first).resolve_stmts(&ast.body).unwrap();
+        first.interpret(ast).unwrap();
+
+        assert_eq!(first.get_global_value("res").unwrap(), Value::Number(1.0));
+        assert!(matches!(
+            second.get_global_value("clock"),
+            Some(Value::NativeFunction { .. })
+        ));
+    }
+}
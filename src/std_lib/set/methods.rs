@@ -0,0 +1,140 @@
+use crate::evaluate::value::DictMap;
+use crate::std_lib::{Interpreter, Value, error::RuntimeError, heap, utils::ensure_set};
+use std::{cell::RefCell, rc::Rc};
+
+fn new_set(set: RefCell<DictMap>) -> Value {
+    let set = Value::Set(Rc::new(set));
+    heap::track(&set);
+    set
+}
+
+fn elements(set: &RefCell<DictMap>) -> Vec<Value> {
+    set.borrow().values().map(|(k, _)| k.clone()).collect()
+}
+
+// set(items): 用 List / Tuple / Set 中的元素创建集合，重复元素只保留一个。set(List|Tuple|Set) -> Set
+pub fn new(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let items = match &args[0] {
+        Value::List(list) => list.borrow().clone(),
+        Value::Tuple(items) => items.to_vec(),
+        Value::Set(set) => elements(set),
+        other => {
+            return Err(RuntimeError::TypeError(format!(
+                "set() expects a List, Tuple or Set, got {}.",
+                other.type_name()
+            )));
+        }
+    };
+    let set = RefCell::new(DictMap::new());
+    for item in items {
+        interpreter.dict_insert(&set, item, Value::Nil)?;
+    }
+    Ok(new_set(set))
+}
+
+// s.add(item): 加入元素，已存在时不变
+pub fn add(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let set = ensure_set(&args[0])?;
+    interpreter.dict_insert(set, args[1].clone(), Value::Nil)?;
+    Ok(Value::Nil)
+}
+
+// s.has(item) -> Boolean
+pub fn has(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let set = ensure_set(&args[0])?;
+    Ok(Value::Boolean(interpreter.dict_contains(set, &args[1])?))
+}
+
+// s.remove(item): 删除元素，返回元素原本是否存在 -> Boolean
+pub fn remove(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let set = ensure_set(&args[0])?;
+    Ok(Value::Boolean(
+        interpreter.dict_remove(set, &args[1])?.is_some(),
+    ))
+}
+
+// s.union(other): 两个集合的并集，返回新集合 -> Set
+pub fn union(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let result = RefCell::new(ensure_set(&args[0])?.borrow().clone());
+    for item in elements(ensure_set(&args[1])?) {
+        interpreter.dict_insert(&result, item, Value::Nil)?;
+    }
+    Ok(new_set(result))
+}
+
+// s.intersect(other): 同时属于两个集合的元素，返回新集合 -> Set
+pub fn intersect(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    filter(interpreter, &args, true)
+}
+
+// s.difference(other): 属于 s 但不属于 other 的元素，返回新集合 -> Set
+pub fn difference(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    filter(interpreter, &args, false)
+}
+
+/// 保留 s 中“是否属于 other”等于 `keep_shared` 的元素，顺序与 s 一致
+fn filter(
+    interpreter: &mut Interpreter,
+    args: &[Value],
+    keep_shared: bool,
+) -> Result<Value, RuntimeError> {
+    let set = ensure_set(&args[0])?;
+    let other = ensure_set(&args[1])?;
+    let result = RefCell::new(DictMap::new());
+    for item in elements(set) {
+        if interpreter.dict_contains(other, &item)? == keep_shared {
+            interpreter.dict_insert(&result, item, Value::Nil)?;
+        }
+    }
+    Ok(new_set(result))
+}
+
+// s.len(): 元素个数 -> Number
+pub fn len(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let set = ensure_set(&args[0])?;
+    Ok(Value::Number(set.borrow().len() as f64))
+}
+
+// s.toList(): 按加入顺序列出元素 -> List
+pub fn to_list(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let list = Value::List(Rc::new(RefCell::new(elements(ensure_set(&args[0])?))));
+    heap::track(&list);
+    Ok(list)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_set_methods() {
+        let code = r#"
+            var s = set([1, 2, 2, 3, 1]);
+            s.add(4);
+            s.add(2);
+            var removed = [s.remove(1), s.remove(9)];
+            var other = set((3, 4, 5));
+            var res = [
+                s, s.len(), s.has(2), s.has(1), removed,
+                s.union(other), s.intersect(other), s.difference(other),
+                set([1, 2]) == set([2, 1]), set([1]) == set([1, 2]), type(s)
+            ];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[set([2, 3, 4]), 3, true, false, [true, false], set([2, 3, 4, 5]), set([3, 4]), set([2]), true, false, set]"
+        );
+
+        let code = r#"
+            var total = 0;
+            for (var x in set(["a", "b", "a"])) total = total + x.len();
+            var res = [total, set(("x", (1, 2))).toList()];
+        "#;
+        assert_eq!(eval_res(code).unwrap().to_string(), "[2, [x, (1, 2)]]");
+
+        assert!(eval_res("var res = set([[1]]);").is_err());
+        assert!(eval_res("var res = set(1);").is_err());
+        assert!(eval_res("var res = set([1]).union([2]);").is_err());
+    }
+}
@@ -0,0 +1,19 @@
+pub mod methods;
+use super::value::Value;
+use crate::{native_fn, std_lib::iterator};
+
+/// Set：无序不重复的元素集合，由 `set(items)` 创建，元素必须可哈希
+pub fn lookup(name: &str) -> Option<Value> {
+    match name {
+        "add" => native_fn!("add", 1, methods::add),
+        "has" => native_fn!("has", 1, methods::has),
+        "remove" => native_fn!("remove", 1, methods::remove),
+        "union" => native_fn!("union", 1, methods::union),
+        "intersect" => native_fn!("intersect", 1, methods::intersect),
+        "difference" => native_fn!("difference", 1, methods::difference),
+        "len" => native_fn!("len", 0, methods::len),
+        "toList" => native_fn!("toList", 0, methods::to_list),
+        "iter" => native_fn!("iter", 0, iterator::methods::collection_iter),
+        _ => None,
+    }
+}
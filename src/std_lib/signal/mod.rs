@@ -0,0 +1,79 @@
+mod state;
+
+pub use state::SignalState;
+
+use crate::evaluate::{
+    Interpreter,
+    error::RuntimeError,
+    value::{NativeFn, Value},
+};
+use crate::std_lib::utils::{self, ensure_string};
+
+/// signal 模块：在安全检查点把进程信号分发给 rox 回调，便于长时间运行的脚本优雅退出
+const FUNCTIONS: &[(&str, usize, NativeFn)] =
+    &[("on", 2, on), ("off", 1, off), ("raise", 1, raise)];
+
+// signal.on(name, handler) -> Nil，注册信号处理函数，handler 接收信号名
+pub fn on(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let name = ensure_string(&args[0])?;
+    if !matches!(
+        args[1],
+        Value::Function { .. } | Value::NativeFunction { .. } | Value::BoundNativeMethod { .. }
+    ) {
+        return Err(RuntimeError::TypeError(format!(
+            "signal.on() expects a function handler, got {}.",
+            args[1].type_name()
+        )));
+    }
+
+    interpreter.signals.on(name, args[1].clone())?;
+    Ok(Value::Nil)
+}
+
+// signal.off(name) -> Boolean，取消处理函数并恢复默认行为，返回之前是否注册过
+pub fn off(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let name = ensure_string(&args[0])?;
+    Ok(Value::Boolean(interpreter.signals.off(name)?))
+}
+
+// signal.raise(name) -> Nil，向当前进程发送信号（用于测试处理函数）
+pub fn raise(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let name = ensure_string(&args[0])?;
+    let signal = state::SIGNALS
+        .iter()
+        .find(|(signal, _)| signal == name)
+        .map(|(_, number)| *number)
+        .ok_or_else(|| RuntimeError::Generic(format!("Unsupported signal '{}'.", name)))?;
+
+    signal_hook::low_level::raise(signal)
+        .map_err(|e| RuntimeError::Generic(format!("Cannot raise {}: {}", name, e)))?;
+    Ok(Value::Nil)
+}
+
+// 创建 signal 模块对象
+pub fn create_module() -> Value {
+    utils::native_module("signal", FUNCTIONS)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_handler_runs_at_checkpoint() {
+        let code = r#"
+            var received = [];
+            signal.on("SIGUSR1", fun(name) { received.push(name); });
+            signal.raise("SIGUSR1");
+            var after = received.len();
+            var removed = signal.off("SIGUSR1");
+            var res = [received, after, removed, signal.off("SIGUSR1")];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[[SIGUSR1], 1, true, false]"
+        );
+        assert!(eval_res(r#"var res = signal.on("SIGKILL", fun() {});"#).is_err());
+    }
+}
@@ -0,0 +1,172 @@
+use crate::evaluate::{Interpreter, error::RuntimeError, value::Value};
+use signal_hook::{SigId, consts, flag};
+use std::{
+    collections::BTreeMap,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+/// 支持的信号：脚本中使用的名字 -> 信号编号
+#[cfg(unix)]
+pub const SIGNALS: &[(&str, i32)] = &[
+    ("SIGINT", consts::SIGINT),
+    ("SIGTERM", consts::SIGTERM),
+    ("SIGHUP", consts::SIGHUP),
+    ("SIGUSR1", consts::SIGUSR1),
+    ("SIGUSR2", consts::SIGUSR2),
+];
+#[cfg(not(unix))]
+pub const SIGNALS: &[(&str, i32)] = &[("SIGINT", consts::SIGINT), ("SIGTERM", consts::SIGTERM)];
+
+/// 一个已注册过处理函数的信号
+#[derive(Debug)]
+struct Handler {
+    /// 当前的处理函数，`off` 之后为 None
+    callback: Option<Value>,
+    /// 为 true 时脚本不再处理，信号按默认行为处理（eg. 终止进程）
+    unhandled: Arc<AtomicBool>,
+    /// 信号到达后置位，由检查点消费
+    received: Arc<AtomicBool>,
+    /// 注册到 signal-hook 的动作，解释器销毁时取消注册
+    ids: [SigId; 3],
+}
+
+/// 脚本注册的信号处理函数
+///
+/// 真正的信号处理程序只做两件事：标记这个信号已到达，并标记存在待处理的信号。
+/// 回调不会在信号处理上下文中执行，而是由解释器在执行下一条语句之前（安全检查点）调用，
+/// 因此回调可以像普通代码一样访问变量、分配内存、抛出异常。
+#[derive(Debug, Default)]
+pub struct SignalState {
+    handlers: BTreeMap<&'static str, Handler>,
+    pending: Arc<AtomicBool>,
+}
+
+impl SignalState {
+    /// 是否有尚未分发的信号（每条语句执行前检查，只是一次原子读取）
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Relaxed)
+    }
+
+    /// 注册（或替换）信号处理函数
+    pub fn on(&mut self, name: &str, callback: Value) -> Result<(), RuntimeError> {
+        let (name, signal) = lookup(name)?;
+        if let Some(handler) = self.handlers.get_mut(name) {
+            handler.callback = Some(callback);
+            handler.unhandled.store(false, Ordering::Relaxed);
+            return Ok(());
+        }
+
+        let error =
+            |e: std::io::Error| RuntimeError::Generic(format!("Cannot handle {}: {}", name, e));
+        let unhandled = Arc::new(AtomicBool::new(false));
+        let received = Arc::new(AtomicBool::new(false));
+        let ids = [
+            // 脚本不再处理时执行默认行为
+            flag::register_conditional_default(signal, unhandled.clone()).map_err(error)?,
+            flag::register(signal, received.clone()).map_err(error)?,
+            flag::register(signal, self.pending.clone()).map_err(error)?,
+        ];
+
+        self.handlers.insert(
+            name,
+            Handler {
+                callback: Some(callback),
+                unhandled,
+                received,
+                ids,
+            },
+        );
+        Ok(())
+    }
+
+    /// 取消信号处理函数，之后到达的信号按默认行为处理
+    ///
+    /// # 返回值
+    /// * `bool` - 之前是否注册过处理函数
+    pub fn off(&mut self, name: &str) -> Result<bool, RuntimeError> {
+        let (name, _) = lookup(name)?;
+        let Some(handler) = self.handlers.get_mut(name) else {
+            return Ok(false);
+        };
+        handler.unhandled.store(true, Ordering::Relaxed);
+        handler.received.store(false, Ordering::Relaxed);
+        Ok(handler.callback.take().is_some())
+    }
+
+    /// 当前注册的处理函数（信号名 -> 回调），供快照使用
+    pub fn callbacks(&self) -> BTreeMap<&'static str, Value> {
+        self.handlers
+            .iter()
+            .filter_map(|(name, handler)| Some((*name, handler.callback.clone()?)))
+            .collect()
+    }
+
+    /// 恢复为 `callbacks` 中的处理函数，其余信号按默认行为处理
+    ///
+    /// 快照中的信号一定注册过（`handlers` 只增不减），因此这里不会失败。
+    pub fn restore(&mut self, callbacks: &BTreeMap<&'static str, Value>) {
+        for (name, handler) in &mut self.handlers {
+            let callback = callbacks.get(name).cloned();
+            handler
+                .unhandled
+                .store(callback.is_none(), Ordering::Relaxed);
+            if callback.is_none() {
+                handler.received.store(false, Ordering::Relaxed);
+            }
+            handler.callback = callback;
+        }
+    }
+
+    /// 取出已到达的信号及其回调
+    fn take_received(&self) -> Vec<(&'static str, Value)> {
+        self.pending.store(false, Ordering::Relaxed);
+        self.handlers
+            .iter()
+            .filter(|(_, handler)| handler.received.swap(false, Ordering::Relaxed))
+            .filter_map(|(name, handler)| Some((*name, handler.callback.clone()?)))
+            .collect()
+    }
+}
+
+impl Drop for SignalState {
+    fn drop(&mut self) {
+        for handler in self.handlers.values() {
+            for id in handler.ids {
+                signal_hook::low_level::unregister(id);
+            }
+        }
+    }
+}
+
+fn lookup(name: &str) -> Result<(&'static str, i32), RuntimeError> {
+    SIGNALS
+        .iter()
+        .find(|(signal, _)| *signal == name)
+        .copied()
+        .ok_or_else(|| {
+            let supported = SIGNALS.iter().map(|(s, _)| *s).collect::<Vec<_>>();
+            RuntimeError::Generic(format!(
+                "Unsupported signal '{}'. Expected one of: {}.",
+                name,
+                supported.join(", ")
+            ))
+        })
+}
+
+impl Interpreter {
+    /// 安全检查点：调用已到达信号的处理函数，参数为信号名
+    pub fn dispatch_signals(&mut self) -> Result<(), RuntimeError> {
+        for (name, callback) in self.signals.take_received() {
+            // 不接收参数的处理函数也可以直接注册
+            let args = match &callback {
+                Value::Function { args, .. } if args.is_empty() => vec![],
+                _ => vec![Value::String(name.to_string())],
+            };
+            self.call_value(&callback, args)?;
+        }
+        Ok(())
+    }
+}
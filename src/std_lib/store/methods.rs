@@ -0,0 +1,155 @@
+use super::Store;
+use crate::evaluate::{Interpreter, error::RuntimeError, heap, value::Value};
+use crate::std_lib::{
+    io::trace::traced,
+    json,
+    utils::{ensure_string, string_dict},
+};
+use indexmap::IndexMap;
+use std::{cell::RefCell, fs, rc::Rc};
+
+fn ensure_store(value: &Value) -> Result<&RefCell<Store>, RuntimeError> {
+    match value {
+        Value::Store(store) => Ok(store),
+        _ => Err(RuntimeError::TypeError("Expected Store.".into())),
+    }
+}
+
+/// 把全部数据写回文件；值无法序列化为 JSON 时报错，文件保持不变
+fn save(interpreter: &mut Interpreter, store: &Store) -> Result<(), RuntimeError> {
+    let data = string_dict(store.data.clone());
+    let Value::String(text) = json::stringify(interpreter, vec![data, Value::Number(2.0)])? else {
+        unreachable!("json.stringify always returns a String");
+    };
+
+    let path = &store.path;
+    traced(interpreter, "store.save", &[path, &text], || {
+        let temp = format!("{}.tmp", path);
+        fs::write(&temp, &text)
+            .and_then(|_| fs::rename(&temp, path))
+            .map(|_| Value::Nil)
+            .map_err(|e| RuntimeError::Generic(format!("Failed to save store: {}", e)))
+    })?;
+    Ok(())
+}
+
+// store.open(path): 打开（不存在时创建）存储文件。store.open(String) -> Store
+pub fn open(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let path = ensure_string(&args[0])?.clone();
+
+    // 文件不存在时视为空存储，第一次修改时才创建
+    let text = traced(
+        interpreter,
+        "store.open",
+        &[&path],
+        || match fs::read_to_string(&path) {
+            Ok(text) => Ok(Value::String(text)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Value::Nil),
+            Err(e) => Err(RuntimeError::Generic(format!(
+                "Failed to open store: {}",
+                e
+            ))),
+        },
+    )?;
+
+    let mut data = IndexMap::new();
+    if let Value::String(_) = text {
+        let Value::Dict(dict) = json::parse(interpreter, vec![text])? else {
+            return Err(RuntimeError::Generic(format!(
+                "Store '{}' does not contain a JSON object.",
+                path
+            )));
+        };
+        for (key, value) in dict.borrow().values() {
+            if let Value::String(key) = key {
+                data.insert(key.clone(), value.clone());
+            }
+        }
+    }
+
+    Ok(Value::Store(Rc::new(RefCell::new(Store { path, data }))))
+}
+
+// s.get(key): 读取值，不存在时返回 nil。s.get(String) -> Value
+pub fn get(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let store = ensure_store(&args[0])?.borrow();
+    let key = ensure_string(&args[1])?;
+    Ok(store.data.get(key).map_or(Value::Nil, Value::deep_copy))
+}
+
+// s.set(key, value): 写入并立即保存，值必须能序列化为 JSON。s.set(String, Value) -> Nil
+pub fn set(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let store = ensure_store(&args[0])?;
+    let key = ensure_string(&args[1])?.clone();
+
+    let mut updated = store.borrow().clone();
+    updated.data.insert(key, args[2].deep_copy());
+    save(interpreter, &updated)?;
+    *store.borrow_mut() = updated;
+    Ok(Value::Nil)
+}
+
+// s.delete(key): 删除并立即保存，返回键是否存在。s.delete(String) -> Boolean
+pub fn delete(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let store = ensure_store(&args[0])?;
+    let key = ensure_string(&args[1])?;
+
+    let mut updated = store.borrow().clone();
+    if updated.data.shift_remove(key).is_none() {
+        return Ok(Value::Boolean(false));
+    }
+    save(interpreter, &updated)?;
+    *store.borrow_mut() = updated;
+    Ok(Value::Boolean(true))
+}
+
+// s.keys(): 所有键，按写入顺序。s.keys() -> List
+pub fn keys(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let store = ensure_store(&args[0])?.borrow();
+    let list = Value::List(Rc::new(RefCell::new(
+        store.data.keys().cloned().map(Value::String).collect(),
+    )));
+    heap::track(&list);
+    Ok(list)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+    use std::fs;
+
+    #[test]
+    fn test_values_survive_reopening() {
+        let file = std::env::temp_dir().join(format!("rox_store_{}.json", std::process::id()));
+        let _ = fs::remove_file(&file);
+        let code = format!(
+            r#"
+            var path = "{}";
+            var db = store.open(path);
+            var empty = db.keys();
+            var items = [1, 2];
+            db.set("count", 1);
+            db.set("items", items);
+            db.set("drop", true);
+            items.push(3);
+            var removed = [db.delete("drop"), db.delete("drop")];
+            var failed = false;
+            try {{ db.set("bad", fun() {{}}); }} catch (e) {{ failed = true; }}
+
+            var again = store.open(path);
+            var res = [empty, removed, failed, again.keys(), again.get("items"), again.get("missing"), type(db)];
+            "#,
+            file.display()
+        );
+        let result = eval_res(&code);
+        let saved = fs::read_to_string(&file);
+        let _ = fs::remove_file(&file);
+
+        assert_eq!(
+            result.unwrap().to_string(),
+            "[[], [true, false], true, [count, items], [1, 2], nil, store]"
+        );
+        assert!(saved.unwrap().contains("\"count\": 1"));
+    }
+}
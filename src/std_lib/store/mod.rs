@@ -0,0 +1,37 @@
+pub mod methods;
+
+use crate::{
+    evaluate::value::{NativeFn, Value},
+    native_fn,
+    std_lib::utils,
+};
+use indexmap::IndexMap;
+
+/// store 模块：持久化的键值存储，数据以 JSON 对象保存在单个文件中
+const FUNCTIONS: &[(&str, usize, NativeFn)] = &[("open", 1, methods::open)];
+
+/// `store.open` 返回的存储
+///
+/// 内存中保存完整数据，每次修改后整体写回文件（先写临时文件再改名，中途崩溃不会留下半个文件）。
+/// 值在写入与读取时都会深拷贝，之后修改取出的列表不会影响已存储的数据。
+#[derive(Debug, Clone, PartialEq)]
+pub struct Store {
+    pub path: String,
+    pub data: IndexMap<String, Value>,
+}
+
+/// Store 上的方法
+pub fn lookup(name: &str) -> Option<Value> {
+    match name {
+        "get" => native_fn!("get", 1, methods::get),
+        "set" => native_fn!("set", 2, methods::set),
+        "delete" => native_fn!("delete", 1, methods::delete),
+        "keys" => native_fn!("keys", 0, methods::keys),
+        _ => None,
+    }
+}
+
+// 创建 store 模块对象
+pub fn create_module() -> Value {
+    utils::native_module("store", FUNCTIONS)
+}
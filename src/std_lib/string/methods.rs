@@ -19,13 +19,31 @@ pub fn split(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeErro
     ))))
 }
 
-// str.substring(start, end)
+// str.substring(start, end): 按字符计的 [start, end)，下标为不超过长度的非负整数 -> String
 pub fn substring(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
     let s = ensure_string(&args[0])?;
-    let start = args[1].clone().to_string().parse::<usize>().unwrap();
-    let end = args[2].clone().to_string().parse::<usize>().unwrap();
-
-    Ok(Value::String(s[start..end].to_string()))
+    let len = s.chars().count();
+    let (start, end) = match (&args[1], &args[2]) {
+        (Value::Number(start), Value::Number(end))
+            if start.fract() == 0.0
+                && end.fract() == 0.0
+                && 0.0 <= *start
+                && start <= end
+                && *end <= len as f64 =>
+        {
+            (*start as usize, *end as usize)
+        }
+        _ => {
+            return Err(RuntimeError::IndexError(format!(
+                "substring() expects indices 0 <= start <= end <= {}, got ({}, {}).",
+                len, args[1], args[2]
+            )));
+        }
+    };
+
+    Ok(Value::String(
+        s.chars().skip(start).take(end - start).collect(),
+    ))
 }
 
 // str.replace(old, new)
@@ -37,6 +55,80 @@ pub fn replace(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeEr
     Ok(Value::String(s.replace(old, new)))
 }
 
+// str.trim(): 去掉首尾空白 -> String
+pub fn trim(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let s = ensure_string(&args[0])?;
+    Ok(Value::String(s.trim().to_string()))
+}
+
+// str.upper() -> String
+pub fn upper(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let s = ensure_string(&args[0])?;
+    Ok(Value::String(s.to_uppercase()))
+}
+
+// str.lower() -> String
+pub fn lower(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let s = ensure_string(&args[0])?;
+    Ok(Value::String(s.to_lowercase()))
+}
+
+// str.contains(sub) -> Boolean
+pub fn contains(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let s = ensure_string(&args[0])?;
+    let sub = ensure_string(&args[1])?;
+    Ok(Value::Boolean(s.contains(sub.as_str())))
+}
+
+// str.starts_with(prefix) -> Boolean
+pub fn starts_with(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let s = ensure_string(&args[0])?;
+    let prefix = ensure_string(&args[1])?;
+    Ok(Value::Boolean(s.starts_with(prefix.as_str())))
+}
+
+// str.ends_with(suffix) -> Boolean
+pub fn ends_with(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let s = ensure_string(&args[0])?;
+    let suffix = ensure_string(&args[1])?;
+    Ok(Value::Boolean(s.ends_with(suffix.as_str())))
+}
+
+// str.find(sub): 第一次出现的位置（按字符计，与下标访问一致），找不到返回 -1 -> Number
+pub fn find(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let s = ensure_string(&args[0])?;
+    let sub = ensure_string(&args[1])?;
+    let index = s
+        .find(sub.as_str())
+        .map_or(-1.0, |byte| s[..byte].chars().count() as f64);
+    Ok(Value::Number(index))
+}
+
+/// `repeat` 结果的最大字节数
+const MAX_REPEAT_BYTES: usize = 1 << 30;
+
+// str.repeat(n): 重复 n 次，n 为非负整数 -> String
+pub fn repeat(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let s = ensure_string(&args[0])?;
+    let count = match args[1] {
+        Value::Number(n) if n >= 0.0 && n.fract() == 0.0 => n as usize,
+        _ => {
+            return Err(RuntimeError::TypeError(
+                "repeat() expects a non-negative integer.".into(),
+            ));
+        }
+    };
+
+    // 结果过长时报错，而不是溢出或耗尽内存
+    match s.len().checked_mul(count) {
+        Some(len) if len <= MAX_REPEAT_BYTES => Ok(Value::String(s.repeat(count))),
+        _ => Err(RuntimeError::Generic(format!(
+            "repeat() result would exceed {} bytes.",
+            MAX_REPEAT_BYTES
+        ))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{cell::RefCell, rc::Rc};
@@ -75,6 +167,13 @@ mod tests {
 		  "#;
 
         assert_eq!(eval_res(ast).unwrap(), Value::String("ray".to_string()));
+        assert_eq!(
+            eval_res(r#"var res = "héllo".substring(1, 3);"#).unwrap(),
+            Value::String("él".to_string())
+        );
+        for bad in ["(-1, 2)", "(1, 9)", "(2, 1)", "(0.5, 1)"] {
+            assert!(eval_res(&format!(r#"var res = "abc".substring{};"#, bad)).is_err());
+        }
     }
 
     #[test]
@@ -86,4 +185,39 @@ mod tests {
         let expected = Value::String("hello ray".to_string());
         assert_eq!(eval_res(ast).unwrap(), expected);
     }
+
+    #[test]
+    fn test_string_case_and_trim_methods() {
+        let ast = r#"
+				var s = "  Hello Ray  ";
+				var res = [s.trim(), s.trim().upper(), s.trim().lower(), "ab".repeat(3), "x".repeat(0)];
+		  "#;
+        assert_eq!(
+            eval_res(ast).unwrap().to_string(),
+            "[Hello Ray, HELLO RAY, hello ray, ababab, ]"
+        );
+        assert!(eval_res(r#"var res = "a".repeat(-1);"#).is_err());
+        assert!(eval_res(r#"var res = "ab".repeat(1e19);"#).is_err());
+        assert!(eval_res(r#"var res = "a".repeat(2147483648);"#).is_err());
+        assert_eq!(
+            eval_res(r#"var res = "".repeat(1e19);"#).unwrap(),
+            Value::String("".into())
+        );
+    }
+
+    #[test]
+    fn test_string_search_methods() {
+        let ast = r#"
+				var s = "héllo ray";
+				var res = [
+					s.contains("llo"), s.contains("xyz"),
+					s.starts_with("hé"), s.ends_with("ray"), s.ends_with("he"),
+					s.find("ray"), s.find("nope")
+				];
+		  "#;
+        assert_eq!(
+            eval_res(ast).unwrap().to_string(),
+            "[true, false, true, true, false, 6, -1]"
+        );
+    }
 }
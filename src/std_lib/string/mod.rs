@@ -1,12 +1,21 @@
 pub mod methods;
 use super::value::Value;
-use crate::native_fn;
+use crate::{native_fn, std_lib::iterator};
 pub fn lookup(name: &str) -> Option<Value> {
     match name {
         "len" => native_fn!("len", 0, methods::len),
         "split" => native_fn!("split", 1, methods::split),
         "substring" => native_fn!("substring", 2, methods::substring),
         "replace" => native_fn!("replace", 2, methods::replace),
+        "trim" => native_fn!("trim", 0, methods::trim),
+        "upper" => native_fn!("upper", 0, methods::upper),
+        "lower" => native_fn!("lower", 0, methods::lower),
+        "contains" => native_fn!("contains", 1, methods::contains),
+        "starts_with" => native_fn!("starts_with", 1, methods::starts_with),
+        "ends_with" => native_fn!("ends_with", 1, methods::ends_with),
+        "find" => native_fn!("find", 1, methods::find),
+        "repeat" => native_fn!("repeat", 1, methods::repeat),
+        "iter" => native_fn!("iter", 0, iterator::methods::collection_iter),
         _ => None,
     }
 }
@@ -0,0 +1,66 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::std_lib::{Interpreter, Value, error::RuntimeError, utils::ensure_string_builder};
+
+// StringBuilder(): 创建空的字符串缓冲区。StringBuilder() -> StringBuilder
+pub fn new(_: &mut Interpreter, _: Vec<Value>) -> Result<Value, RuntimeError> {
+    Ok(Value::StringBuilder(Rc::new(RefCell::new(String::new()))))
+}
+
+// sb.append(value) -> StringBuilder，返回自身以便链式调用
+pub fn append(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let buffer = ensure_string_builder(&args[0])?;
+    let piece = interpreter.stringify(&args[1])?;
+    buffer.borrow_mut().push_str(&piece);
+    Ok(args[0].clone())
+}
+
+// sb.toString() -> String
+pub fn to_string(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let buffer = ensure_string_builder(&args[0])?;
+    Ok(Value::String(buffer.borrow().clone()))
+}
+
+// sb.len() -> Number，与 String.len() 一致按字节计
+pub fn len(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let buffer = ensure_string_builder(&args[0])?;
+    Ok(Value::Number(buffer.borrow().len() as f64))
+}
+
+// sb.clear() -> StringBuilder
+pub fn clear(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let buffer = ensure_string_builder(&args[0])?;
+    buffer.borrow_mut().clear();
+    Ok(args[0].clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::{Value, eval_res};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_append_in_loop() {
+        let ast = r#"
+		   var sb = StringBuilder();
+		   for (var i = 0; i < 3; i = i + 1) {
+		       sb.append(i).append(",");
+		   }
+		   var res = sb.toString();
+		"#;
+
+        assert_eq!(eval_res(ast).unwrap(), Value::String("0,1,2,".to_string()));
+    }
+
+    #[test]
+    fn test_len_and_clear() {
+        let ast = r#"
+		   var sb = StringBuilder();
+		   sb.append("abc");
+		   var before = sb.len();
+		   var res = before + sb.clear().len();
+		"#;
+
+        assert_eq!(eval_res(ast).unwrap(), Value::Number(3.0));
+    }
+}
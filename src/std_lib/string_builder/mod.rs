@@ -0,0 +1,17 @@
+pub mod methods;
+use super::value::Value;
+use crate::native_fn;
+
+/// StringBuilder：可变字符串缓冲区
+///
+/// 循环中 `s = s + piece` 每次都会重新分配整个字符串 (O(n²))，
+/// StringBuilder 原地追加，最后通过 `toString()` 一次性取出结果。
+pub fn lookup(name: &str) -> Option<Value> {
+    match name {
+        "append" => native_fn!("append", 1, methods::append),
+        "toString" => native_fn!("toString", 0, methods::to_string),
+        "len" => native_fn!("len", 0, methods::len),
+        "clear" => native_fn!("clear", 0, methods::clear),
+        _ => None,
+    }
+}
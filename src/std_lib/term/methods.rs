@@ -0,0 +1,148 @@
+use crate::evaluate::{Interpreter, error::RuntimeError, value::Value};
+use std::{
+    env,
+    io::{self, IsTerminal},
+};
+
+/// 样式名 -> SGR 代码
+pub const STYLES: &[(&str, u8)] = &[
+    ("bold", 1),
+    ("dim", 2),
+    ("italic", 3),
+    ("underline", 4),
+    ("inverse", 7),
+    ("black", 30),
+    ("red", 31),
+    ("green", 32),
+    ("yellow", 33),
+    ("blue", 34),
+    ("magenta", 35),
+    ("cyan", 36),
+    ("white", 37),
+    ("gray", 90),
+];
+
+/// 当前是否输出颜色
+///
+/// 优先使用脚本通过 `term.setColor()` 指定的值；否则遵循 `NO_COLOR` / `FORCE_COLOR` 约定，
+/// 最后根据 stdout 是否是终端决定，重定向到文件或管道时自动关闭。
+fn colors_enabled(interpreter: &Interpreter) -> bool {
+    if let Some(enabled) = interpreter.term_colors {
+        return enabled;
+    }
+    if env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+        return false;
+    }
+    if env::var_os("FORCE_COLOR").is_some_and(|v| !v.is_empty()) {
+        return true;
+    }
+    io::stdout().is_terminal()
+}
+
+/// 给文本加上样式，颜色关闭时原样返回
+pub fn apply(
+    interpreter: &mut Interpreter,
+    text: &Value,
+    styles: &[&str],
+) -> Result<Value, RuntimeError> {
+    let text = interpreter.stringify(text)?;
+    let codes = styles
+        .iter()
+        .map(|name| {
+            STYLES
+                .iter()
+                .find(|(style, _)| style == name)
+                .map(|(_, code)| code.to_string())
+                .ok_or_else(|| RuntimeError::Generic(format!("Unknown style '{}'.", name)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if codes.is_empty() || !colors_enabled(interpreter) {
+        return Ok(Value::String(text));
+    }
+    Ok(Value::String(format!(
+        "\x1b[{}m{}\x1b[0m",
+        codes.join(";"),
+        text
+    )))
+}
+
+// term.style(text, styles) -> String
+// styles 为空格分隔的样式名（"bold red"）或样式名列表（["bold", "red"]）
+pub fn style(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let names: Vec<String> = match &args[1] {
+        Value::String(s) => s.split_whitespace().map(str::to_string).collect(),
+        Value::List(list) => list.borrow().iter().map(Value::to_string).collect(),
+        other => {
+            return Err(RuntimeError::TypeError(format!(
+                "term.style() expects a String or List of style names, got {}.",
+                other.type_name()
+            )));
+        }
+    };
+    let names = names.iter().map(String::as_str).collect::<Vec<_>>();
+    apply(interpreter, &args[0], &names)
+}
+
+// term.strip(text) -> String，去掉文本中的 ANSI 转义序列
+pub fn strip(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let text = interpreter.stringify(&args[0])?;
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' && chars.peek() == Some(&'[') {
+            // CSI 序列以 0x40..=0x7E 范围内的字符结束
+            chars.next();
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    Ok(Value::String(out))
+}
+
+// term.isColor() -> Boolean，当前是否输出颜色
+pub fn is_color(interpreter: &mut Interpreter, _: Vec<Value>) -> Result<Value, RuntimeError> {
+    Ok(Value::Boolean(colors_enabled(interpreter)))
+}
+
+// term.setColor(flag) -> Nil，强制开启 / 关闭颜色，传入 nil 恢复自动检测
+pub fn set_color(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    interpreter.term_colors = match args[0] {
+        Value::Nil => None,
+        Value::Boolean(flag) => Some(flag),
+        ref other => {
+            return Err(RuntimeError::TypeError(format!(
+                "term.setColor() expects a Boolean or nil, got {}.",
+                other.type_name()
+            )));
+        }
+    };
+    Ok(Value::Nil)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_styles() {
+        let code = r#"
+            term.setColor(true);
+            var styled = [term.red("error"), term.style(42, "bold underline")];
+            var plain = term.strip(styled[0]);
+            term.setColor(false);
+            var res = [styled[0], styled[1], plain, term.bold("off"), term.isColor()];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[\x1b[31merror\x1b[0m, \x1b[1;4m42\x1b[0m, error, off, false]"
+        );
+        assert!(eval_res(r#"var res = term.style("x", "sparkly");"#).is_err());
+    }
+}
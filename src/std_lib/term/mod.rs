@@ -0,0 +1,44 @@
+mod methods;
+
+use crate::evaluate::{
+    Interpreter,
+    error::RuntimeError,
+    value::{NativeFn, Value},
+};
+use crate::std_lib::utils;
+
+/// 单一样式的快捷函数：term.red(text) 等价于 term.style(text, "red")
+macro_rules! shorthand {
+    ($($name:ident),* $(,)?) => {
+        $(
+            fn $name(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+                methods::apply(interpreter, &args[0], &[stringify!($name)])
+            }
+        )*
+
+        const SHORTHANDS: &[(&str, NativeFn)] = &[$((stringify!($name), $name)),*];
+    };
+}
+
+shorthand!(
+    bold, dim, italic, underline, inverse, black, red, green, yellow, blue, magenta, cyan, white,
+    gray,
+);
+
+/// term 模块：终端 ANSI 样式，stdout 不是终端时自动关闭
+const FUNCTIONS: &[(&str, usize, NativeFn)] = &[
+    ("style", 2, methods::style),
+    ("strip", 1, methods::strip),
+    ("isColor", 0, methods::is_color),
+    ("setColor", 1, methods::set_color),
+];
+
+// 创建 term 模块对象
+pub fn create_module() -> Value {
+    let functions = FUNCTIONS
+        .iter()
+        .copied()
+        .chain(SHORTHANDS.iter().map(|&(name, func)| (name, 1, func)));
+
+    utils::new_module("term", utils::native_exports(functions))
+}
@@ -0,0 +1,125 @@
+use crate::evaluate::{Interpreter, error::RuntimeError, value::Value};
+use std::sync::OnceLock;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+// time.now(): 当前时间戳（秒）。确定性模式下返回假时钟。time.now() -> Number
+pub fn now(interpreter: &mut Interpreter, _: Vec<Value>) -> Result<Value, RuntimeError> {
+    if let Some(fake) = interpreter.fake_clock {
+        return Ok(Value::Number(fake));
+    }
+
+    let since_the_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    Ok(Value::Number(since_the_epoch.as_secs_f64()))
+}
+
+// time.monotonic(): 单调时钟（秒），不受系统时间调整影响，只适合计算时间间隔。
+// 起点是第一次调用的时刻；确定性模式下同样返回假时钟。time.monotonic() -> Number
+pub fn monotonic(interpreter: &mut Interpreter, _: Vec<Value>) -> Result<Value, RuntimeError> {
+    static START: OnceLock<Instant> = OnceLock::new();
+
+    if let Some(fake) = interpreter.fake_clock {
+        return Ok(Value::Number(fake));
+    }
+
+    let start = START.get_or_init(Instant::now);
+    Ok(Value::Number(start.elapsed().as_secs_f64()))
+}
+
+// time.advance(seconds): 拨动假时钟，只在确定性模式下可用。time.advance(Number) -> Number
+pub fn advance(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let Value::Number(seconds) = args[0] else {
+        return Err(RuntimeError::TypeError(
+            "time.advance() expects a number of seconds.".into(),
+        ));
+    };
+
+    match interpreter.fake_clock.as_mut() {
+        Some(fake) => {
+            *fake += seconds;
+            Ok(Value::Number(*fake))
+        }
+        None => Err(RuntimeError::Generic(
+            "time.advance() is only available in deterministic mode.".into(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        evaluate::{Interpreter, Value},
+        parser::parse,
+        reader::Source,
+        resolver::Resolver,
+        std_lib::eval_res,
+        tokenizer::tokenize,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn run_deterministic(code: &str) -> Value {
+        let mut interpreter = Interpreter::new();
+        interpreter.set_deterministic(42);
+
+        let tokens = tokenize(Source {
+            contents: code.to_string(),
+        })
+        .unwrap();
+        let ast = parse(tokens).unwrap();
+        Resolver::new(&mut interpreter)
+            .resolve_stmts(&ast.body)
+            .unwrap();
+        interpreter.interpret(ast).unwrap();
+        interpreter.get_global_value("res").unwrap()
+    }
+
+    #[test]
+    fn test_fake_clock() {
+        let code = r#"
+            var start = time.now();
+            time.advance(2);
+            var res = [start, time.now(), clock(), time.clock(), time.monotonic()];
+        "#;
+
+        assert_eq!(run_deterministic(code).to_string(), "[0, 2, 2, 2, 2]");
+    }
+
+    #[test]
+    fn test_sleep() {
+        let code = "sleep(1.5); sleep(0); var res = [clock(), time.monotonic()];";
+        assert_eq!(run_deterministic(code).to_string(), "[1.5, 1.5]");
+
+        let code = r#"
+            var start = time.monotonic();
+            sleep(0.02);
+            var res = time.monotonic() - start >= 0.02;
+        "#;
+        assert_eq!(eval_res(code).unwrap(), Value::Boolean(true));
+        assert!(eval_res("sleep(-1);").is_err());
+        assert!(eval_res("sleep(math.INF);").is_err());
+        assert!(eval_res("sleep(1e300);").is_err());
+    }
+
+    #[test]
+    fn test_monotonic_never_goes_back() {
+        let code = r#"
+            var a = time.monotonic();
+            var b = time.monotonic();
+            var res = b >= a and a >= 0 and time.clock() > 0;
+        "#;
+        assert_eq!(eval_res(code).unwrap(), Value::Boolean(true));
+    }
+
+    #[test]
+    fn test_seeded_random_is_reproducible() {
+        let code = "var res = [math.random(), math.random()];";
+
+        assert_eq!(run_deterministic(code), run_deterministic(code));
+    }
+
+    #[test]
+    fn test_advance_requires_deterministic_mode() {
+        assert!(eval_res("var res = time.advance(1);").is_err());
+    }
+}
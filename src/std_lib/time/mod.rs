@@ -0,0 +1,18 @@
+mod methods;
+
+use crate::evaluate::value::{NativeFn, Value};
+use crate::std_lib::{globals, utils};
+
+/// time 模块：墙上时钟、单调时钟，以及确定性模式下的假时钟
+const FUNCTIONS: &[(&str, usize, NativeFn)] = &[
+    ("now", 0, methods::now),
+    ("monotonic", 0, methods::monotonic),
+    // 与全局的 clock() 相同
+    ("clock", 0, globals::clock),
+    ("advance", 1, methods::advance),
+];
+
+// 创建 time 模块对象
+pub fn create_module() -> Value {
+    utils::native_module("time", FUNCTIONS)
+}
@@ -0,0 +1,40 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::std_lib::{Interpreter, Value, error::RuntimeError, heap, utils::ensure_tuple};
+
+// t.len() -> Number
+pub fn len(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let items = ensure_tuple(&args[0])?;
+    Ok(Value::Number(items.len() as f64))
+}
+
+// t.toList() -> List，复制元素得到可变列表
+pub fn to_list(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let items = ensure_tuple(&args[0])?;
+    let list = Value::List(Rc::new(RefCell::new(items.to_vec())));
+    heap::track(&list);
+    Ok(list)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_tuple_indexing_and_methods() {
+        let code = r#"
+            var t = (1, "a", (2, 3));
+            var (n, s, inner) = t;
+            var res = [t[0], t[1], t[2][1], t.len(), (7,).len(), t.toList(), n, s, inner];
+        "#;
+        assert_eq!(
+            eval_res(code).unwrap().to_string(),
+            "[1, a, 3, 3, 1, [1, a, (2, 3)], 1, a, (2, 3)]"
+        );
+
+        assert!(eval_res("var t = (1, 2); var res = t[2];").is_err());
+        assert!(eval_res("var t = (1, 2); var res = t[-1];").is_err());
+        assert!(eval_res("var t = (1, 2); t[0] = 5;").is_err());
+    }
+}
@@ -0,0 +1,13 @@
+pub mod methods;
+use super::value::Value;
+use crate::{native_fn, std_lib::iterator};
+
+/// Tuple：不可变的定长序列，支持 `t[i]` 下标与解构
+pub fn lookup(name: &str) -> Option<Value> {
+    match name {
+        "len" => native_fn!("len", 0, methods::len),
+        "toList" => native_fn!("toList", 0, methods::to_list),
+        "iter" => native_fn!("iter", 0, iterator::methods::collection_iter),
+        _ => None,
+    }
+}
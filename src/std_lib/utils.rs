@@ -1,5 +1,9 @@
-use crate::std_lib::{Value, error::RuntimeError};
-use std::{cell::RefCell, collections::HashMap};
+use crate::std_lib::{
+    Value,
+    error::RuntimeError,
+    value::{DictMap, HashKey, NativeFn, RoxModule},
+};
+use std::{cell::RefCell, collections::HashMap, ops::Range, rc::Rc};
 
 pub fn ensure_string(val: &Value) -> Result<&String, RuntimeError> {
     if let Value::String(s) = val {
@@ -17,10 +21,118 @@ pub fn ensure_list(args: &Value) -> Result<&RefCell<Vec<Value>>, RuntimeError> {
     }
 }
 
-pub fn ensure_dict(val: &Value) -> Result<&RefCell<HashMap<String, Value>>, RuntimeError> {
+pub fn ensure_tuple(val: &Value) -> Result<&[Value], RuntimeError> {
+    if let Value::Tuple(items) = val {
+        Ok(items)
+    } else {
+        Err(RuntimeError::TypeError("Expected tuple.".into()))
+    }
+}
+
+pub fn ensure_dict(val: &Value) -> Result<&RefCell<DictMap>, RuntimeError> {
     if let Value::Dict(dict) = val {
         Ok(dict)
     } else {
         Err(RuntimeError::TypeError("Expected dict.".into()))
     }
 }
+
+pub fn ensure_set(val: &Value) -> Result<&RefCell<DictMap>, RuntimeError> {
+    if let Value::Set(set) = val {
+        Ok(set)
+    } else {
+        Err(RuntimeError::TypeError("Expected set.".into()))
+    }
+}
+
+pub fn ensure_bytes(val: &Value) -> Result<&[u8], RuntimeError> {
+    if let Value::Bytes(bytes) = val {
+        Ok(bytes)
+    } else {
+        Err(RuntimeError::TypeError("Expected bytes.".into()))
+    }
+}
+
+/// `slice(start, end)` 的下标区间：负数从末尾倒数，越界或 start > end 时报错
+pub fn slice_range(start: &Value, end: &Value, len: usize) -> Result<Range<usize>, RuntimeError> {
+    let bound = |value: &Value| -> Result<usize, RuntimeError> {
+        let Value::Number(n) = value else {
+            return Err(RuntimeError::TypeError(
+                "Index must be a number".to_string(),
+            ));
+        };
+        if n.fract() != 0.0 {
+            return Err(RuntimeError::IndexError(
+                "Index must be an integer".to_string(),
+            ));
+        }
+        let index = if *n < 0.0 { *n + len as f64 } else { *n };
+        if index < 0.0 || index > len as f64 {
+            return Err(RuntimeError::IndexError(format!(
+                "Index {} out of bounds (length {})",
+                n, len
+            )));
+        }
+        Ok(index as usize)
+    };
+    let start = bound(start)?;
+    let end = bound(end)?;
+    if start > end {
+        return Err(RuntimeError::IndexError(format!(
+            "Slice start {} is after end {}",
+            start, end
+        )));
+    }
+    Ok(start..end)
+}
+
+pub fn ensure_string_builder(val: &Value) -> Result<&RefCell<String>, RuntimeError> {
+    if let Value::StringBuilder(buffer) = val {
+        Ok(buffer)
+    } else {
+        Err(RuntimeError::TypeError("Expected StringBuilder.".into()))
+    }
+}
+
+/// 由字符串键构造字典（保持传入顺序）
+pub fn string_dict(entries: impl IntoIterator<Item = (String, Value)>) -> Value {
+    let dict = entries
+        .into_iter()
+        .map(|(k, v)| (HashKey::String(k.clone()), (Value::String(k), v)))
+        .collect::<DictMap>();
+    Value::Dict(Rc::new(RefCell::new(dict)))
+}
+
+/// 把 `(name, arity, func)` 形式的原生函数表转换为模块导出
+pub fn native_exports<'a>(
+    functions: impl IntoIterator<Item = (&'a str, usize, NativeFn)>,
+) -> HashMap<String, Value> {
+    functions
+        .into_iter()
+        .map(|(name, arity, func)| {
+            (
+                name.to_string(),
+                Value::NativeFunction {
+                    name: name.to_string(),
+                    arity,
+                    func,
+                },
+            )
+        })
+        .collect()
+}
+
+/// 由导出表创建已初始化的模块对象
+pub fn new_module(name: &str, exports: HashMap<String, Value>) -> Value {
+    let module = RoxModule {
+        name: name.to_string(),
+        exports,
+        is_initialized: true,
+    };
+    Value::Module(Rc::new(RefCell::new(module)))
+}
+
+/// 由原生函数表创建模块对象，eg. `native_module("json", FUNCTIONS)`
+pub fn native_module(name: &str, functions: &[(&str, usize, NativeFn)]) -> Value {
+    new_module(name, native_exports(functions.iter().copied()))
+}
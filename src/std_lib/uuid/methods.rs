@@ -0,0 +1,75 @@
+use crate::evaluate::{Interpreter, error::RuntimeError, value::Value};
+use crate::std_lib::math;
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// 每次调用都不同的计数器，与时间一起作为哈希输入
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// 128 位随机数
+///
+/// 确定性模式下取自可设定种子的 `math` 生成器，保证重放结果一致；
+/// 否则使用标准库 `RandomState` 的随机密钥（来自操作系统）对计数器与时间做哈希。
+fn random_bytes(interpreter: &Interpreter) -> [u8; 16] {
+    let mut bytes = [0u8; 16];
+    if interpreter.fake_clock.is_some() {
+        bytes.fill_with(|| (math::random() * 256.0) as u8);
+        return bytes;
+    }
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64);
+    for chunk in bytes.chunks_mut(8) {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+        hasher.write_u64(nanos);
+        chunk.copy_from_slice(&hasher.finish().to_le_bytes());
+    }
+    bytes
+}
+
+/// 按 8-4-4-4-12 分组的小写十六进制
+fn canonical(bytes: [u8; 16]) -> String {
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+// uuid.v4(): 随机 UUID，eg. "3f2b8c1e-9a4d-4e6f-b1c2-7d8e9f0a1b2c"。uuid.v4() -> String
+pub fn v4(interpreter: &mut Interpreter, _: Vec<Value>) -> Result<Value, RuntimeError> {
+    let mut bytes = random_bytes(interpreter);
+    // RFC 9562：版本号 4 写在第 7 字节高 4 位，变体 10 写在第 9 字节高 2 位
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    Ok(Value::String(canonical(bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::std_lib::eval_res;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_v4_format() {
+        let code = r#"
+            var ids = [];
+            for (var i = 0; i < 100; i = i + 1) ids.push(uuid.v4());
+            var id = ids[0];
+            var res = [
+                set(ids).len(), id.len(), regex.matches("^[0-9a-f]{8}-[0-9a-f]{4}-4[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}$", id)
+            ];
+        "#;
+        assert_eq!(eval_res(code).unwrap().to_string(), "[100, 36, true]");
+    }
+}
@@ -0,0 +1,14 @@
+mod methods;
+
+use crate::{
+    evaluate::value::{NativeFn, Value},
+    std_lib::utils,
+};
+
+/// uuid 模块：生成随机的（第 4 版）UUID
+const FUNCTIONS: &[(&str, usize, NativeFn)] = &[("v4", 0, methods::v4)];
+
+// 创建 uuid 模块对象
+pub fn create_module() -> Value {
+    utils::native_module("uuid", FUNCTIONS)
+}
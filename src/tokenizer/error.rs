@@ -4,6 +4,7 @@ use std::fmt;
 pub enum ScanError {
     UnexpectedCharacter { c: char, line: usize },
     UnterminatedString { line: usize },
+    InvalidNumber { lexeme: String, line: usize },
 }
 
 #[derive(Debug)]
@@ -1,10 +1,14 @@
+use serde::{Deserialize, Serialize};
+
 #[allow(dead_code)]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Literal {
     Nil,
     None,
     String(String),
     Number(f64),
+    /// 大整数的十进制数字
+    BigInt(String),
     Tuple(Vec<Literal>),
     List(Vec<Literal>),
     Dict(Vec<(Literal, Literal)>),
@@ -6,6 +6,6 @@ mod token_type;
 
 pub use error::{Error, ScanError};
 pub use literal::Literal;
-pub use scanner::tokenize;
-pub use token::{Token, Tokens};
+pub use scanner::{KEYWORDS, tokenize};
+pub use token::{Comment, Token, Tokens};
 pub use token_type::TokenType;
@@ -1,16 +1,53 @@
-use super::{Literal, Token, TokenType, Tokens};
+use super::{Comment, Literal, Token, TokenType, Tokens};
 use crate::tokenizer::error::{Error as ScannerError, ScanError};
 
+/// 全部关键字，与 `Scanner::is_identifier` 中的关键字表一致
+pub const KEYWORDS: &[&str] = &[
+    "and",
+    "break",
+    "catch",
+    "class",
+    "continue",
+    "defer",
+    "else",
+    "export",
+    "extend",
+    "false",
+    "finally",
+    "for",
+    "fun",
+    "if",
+    "implements",
+    "in",
+    "nil",
+    "or",
+    "print",
+    "return",
+    "static",
+    "super",
+    "this",
+    "throw",
+    "trait",
+    "true",
+    "try",
+    "var",
+    "while",
+    "yield",
+];
+
 pub struct Scanner {
     // 这里将输入的源文本转换为Vec<char>。
     // char' Rust类型表示0到0x10FFFFF范围内的Unicode码位。
     // 在内部，char是32位的，这样做的主要原因是扫描自然地与字符一起工作。
     source: Vec<char>,
     tokens: Vec<Token>,
+    comments: Vec<Comment>,
     start: usize,
     current: usize,
     line: usize,
     errors: Vec<ScanError>,
+    /// 尚未结束的字符串插值，每一项是插值表达式内部未闭合的 `{` 数量
+    interpolations: Vec<usize>,
 }
 
 impl Scanner {
@@ -19,10 +56,12 @@ impl Scanner {
         Self {
             source: source.chars().collect(),
             tokens: vec![],
+            comments: vec![],
             start: 0,
             current: 0,
             line: 1,
             errors: vec![],
+            interpolations: vec![],
         }
     }
 
@@ -35,6 +74,9 @@ impl Scanner {
             ScanError::UnterminatedString { line } => {
                 println!("[line {}] Unterminated string", line);
             }
+            ScanError::InvalidNumber { ref lexeme, line } => {
+                println!("[line {}] Invalid number: {}", line, lexeme);
+            }
         }
 
         self.errors.push(error);
@@ -111,12 +153,36 @@ impl Scanner {
         match c {
             '(' => self.add_token(TokenType::LeftParen),
             ')' => self.add_token(TokenType::RightParen),
-            '{' => self.add_token(TokenType::LeftBrace),
-            '}' => self.add_token(TokenType::RightBrace),
+            '{' => {
+                if let Some(depth) = self.interpolations.last_mut() {
+                    *depth += 1;
+                }
+                self.add_token(TokenType::LeftBrace)
+            }
+            '}' => match self.interpolations.last_mut() {
+                // 插值表达式结束，继续扫描字符串剩余部分
+                Some(0) => {
+                    self.interpolations.pop();
+                    self.is_string();
+                }
+                Some(depth) => {
+                    *depth -= 1;
+                    self.add_token(TokenType::RightBrace)
+                }
+                None => self.add_token(TokenType::RightBrace),
+            },
             '[' => self.add_token(TokenType::LeftBracket),
             ']' => self.add_token(TokenType::RightBracket),
             ',' => self.add_token(TokenType::Comma),
-            '.' => self.add_token(TokenType::Dot),
+            '.' => {
+                if self.peek() == '.' && self.peek_next() == '.' {
+                    self.advance();
+                    self.advance();
+                    self.add_token(TokenType::Ellipsis)
+                } else {
+                    self.add_token(TokenType::Dot)
+                }
+            }
             '-' => {
                 let token_type = if self.match_char('=') {
                     TokenType::MinusEqual
@@ -190,8 +256,11 @@ impl Scanner {
                     while self.peek() != '\n' && !self.is_at_end() {
                         self.advance();
                     }
+                    self.add_comment(self.line);
                 } else if self.match_char('*') {
+                    let line = self.line;
                     self.scan_block_comment();
+                    self.add_comment(line);
                 } else if self.match_char('=') {
                     self.add_token(TokenType::SlashEqual);
                 } else {
@@ -201,6 +270,10 @@ impl Scanner {
             '%' => {
                 self.add_token(TokenType::Percent);
             }
+            '?' if self.match_char('?') => self.add_token(TokenType::QuestionQuestion),
+            '?' if self.match_char('.') => self.add_token(TokenType::QuestionDot),
+            '?' if self.match_char('[') => self.add_token(TokenType::QuestionBracket),
+            '~' if self.match_char('/') => self.add_token(TokenType::TildeSlash),
             '&' => {
                 let toke_type = if self.match_char('&') {
                     TokenType::LogicalAnd
@@ -224,7 +297,8 @@ impl Scanner {
             // 字符串处理
             '"' => self.is_string(),
             _ => {
-                if c.is_numeric() {
+                // 只有 ASCII 数字开始数字字面值，`²`、`٣` 等其他 Unicode 数字按非法字符处理
+                if c.is_ascii_digit() {
                     self.is_digit();
                 } else if c.is_alphabetic() || c == '_' {
                     self.is_identifier();
@@ -236,9 +310,28 @@ impl Scanner {
     }
 
     /// 扫描字符串字面值
+    ///
+    /// 字符串中的 `${expr}` 会被拆分：`"a ${x} b"` 扫描为
+    /// `Interpolation("a ")`、`x` 的标记、`String(" b")`，由解析器脱糖为字符串拼接。
+    /// 写作 `\${` 时得到字面的 `${`，不开始插值。
     fn is_string(&mut self) {
-        // Note：进入此方法时，开头的 '"' 已经被 advance() 消耗了
+        // Note：进入此方法时，开头的 '"'（或结束插值的 '}'）已经被 advance() 消耗了
         while self.peek() != '"' && !self.is_at_end() {
+            if self.peek() == '\\'
+                && self.peek_next() == '$'
+                && self.source.get(self.current + 2) == Some(&'{')
+            {
+                self.current += 3;
+                continue;
+            }
+            if self.peek() == '$' && self.peek_next() == '{' {
+                let text = unescape_interpolation(&self.source[self.start + 1..self.current]);
+                self.advance(); // 消耗 '$'
+                self.advance(); // 消耗 '{'
+                self.add_token_with_literal(TokenType::Interpolation, Literal::String(text));
+                self.interpolations.push(0);
+                return;
+            }
             if self.peek() == '\n' {
                 self.line += 1;
             }
@@ -254,31 +347,65 @@ impl Scanner {
         // 消耗闭合的 '"'
         self.advance();
 
-        self.add_token_with_literal(TokenType::String, Literal::String(self.lexeme(true)));
+        let text = unescape_interpolation(&self.source[self.start + 1..self.current - 1]);
+        self.add_token_with_literal(TokenType::String, Literal::String(text));
     }
 
     /// 扫描数字字面值
+    ///
+    /// 支持数字分隔符 `1_000_000`（`_` 只能出现在两个数字之间）与科学计数法 `1e9`、`2.5e-3`。
     fn is_digit(&mut self) {
         // 只要 peek 依旧是数字就继续消耗
-        while self.peek().is_numeric() {
-            self.advance();
+        self.consume_digits();
+
+        // 大整数字面值：整数后紧跟 `n`，eg. `9007199254740993n`
+        if self.peek() == 'n' && !self.peek_next().is_alphanumeric() && self.peek_next() != '_' {
+            let digits = self.lexeme(false).replace('_', "");
+            self.advance(); // 消耗 'n'
+            self.add_token_with_literal(TokenType::BigInt, Literal::BigInt(digits));
+            return;
         }
 
-        // 小数部分
+        // 小数部分（允许 `5.` 这种省略小数位的写法）
         if self.peek() == '.' {
             self.advance(); // 消耗 '.'
+            self.consume_digits();
+        }
 
-            while self.peek_next().is_numeric() {
-                // 如果后续存在数字，则继续消耗
-                while self.peek().is_numeric() {
-                    self.advance();
-                }
+        // 指数部分：`e` / `E` 后必须有数字（可带符号），否则 `e` 留给后面的标识符
+        if matches!(self.peek(), 'e' | 'E') {
+            let digits_at = match self.peek_next() {
+                '+' | '-' => self.current + 2,
+                _ => self.current + 1,
+            };
+            if self.source.get(digits_at).is_some_and(char::is_ascii_digit) {
+                self.current = digits_at;
+                self.consume_digits();
             }
         }
 
-        // 存在解析失败的情况很少见，因为已经检查了字符，但在 Rust 中 unwrap 需要谨慎
-        let value = self.lexeme(false).parse::<f64>().unwrap_or(0.0);
-        self.add_token_with_literal(TokenType::Number, Literal::Number(value));
+        // 字符已经逐个检查过，解析失败的情况很少见；万一失败也报告为扫描错误，而不是静默变成 0
+        let lexeme = self.lexeme(false);
+        match lexeme.replace('_', "").parse::<f64>() {
+            Ok(value) => self.add_token_with_literal(TokenType::Number, Literal::Number(value)),
+            Err(_) => self.handle_error(ScanError::InvalidNumber {
+                lexeme,
+                line: self.line,
+            }),
+        }
+    }
+
+    /// 消耗连续的 ASCII 数字，以及两侧都是数字的分隔符 `_`（`1._5` 中的 `_` 不属于数字）
+    fn consume_digits(&mut self) {
+        loop {
+            let separator = self.peek() == '_'
+                && self.source[self.current - 1].is_ascii_digit()
+                && self.peek_next().is_ascii_digit();
+            if !self.peek().is_ascii_digit() && !separator {
+                break;
+            }
+            self.advance();
+        }
     }
 
     /// 扫描标识符或关键字
@@ -296,6 +423,7 @@ impl Scanner {
             "for" => TokenType::For,
             "fun" => TokenType::Fun,
             "if" => TokenType::If,
+            "in" => TokenType::In,
             "nil" => TokenType::Nil,
             "print" => TokenType::Print,
             "return" => TokenType::Return,
@@ -309,8 +437,15 @@ impl Scanner {
             "." => TokenType::Dot,
             "try" => TokenType::Try,
             "catch" => TokenType::Catch,
+            "finally" => TokenType::Finally,
             "throw" => TokenType::Throw,
             "export" => TokenType::Export,
+            "extend" => TokenType::Extend,
+            "static" => TokenType::Static,
+            "trait" => TokenType::Trait,
+            "implements" => TokenType::Implements,
+            "yield" => TokenType::Yield,
+            "defer" => TokenType::Defer,
             _ => TokenType::Identifier,
         };
 
@@ -324,11 +459,25 @@ impl Scanner {
 
     /// 扫描所有标记
     fn scan_tokens(&mut self) -> Result<Tokens, ScannerError> {
+        // 以 `#` 开头的首行视为注释，脚本可以带 shebang (eg. `#!/usr/bin/env rox`) 直接执行
+        if self.peek() == '#' {
+            while self.peek() != '\n' && !self.is_at_end() {
+                self.advance();
+            }
+            self.add_comment(self.line);
+        }
+
         while !self.is_at_end() {
             self.start = self.current;
             self.scan_token();
         }
 
+        // 插值表达式没有闭合，字符串也就没有结束
+        if !self.interpolations.is_empty() {
+            self.interpolations.clear();
+            self.handle_error(ScanError::UnterminatedString { line: self.line });
+        }
+
         self.tokens
             .push(Token::new(TokenType::Eof, "", self.line, Literal::None));
 
@@ -337,6 +486,7 @@ impl Scanner {
         } else {
             Ok(Tokens {
                 tokens: self.tokens.clone(),
+                comments: self.comments.clone(),
             })
         }
     }
@@ -357,6 +507,14 @@ impl Scanner {
         //   self.error_reporter.error(self.line, "Unterminated comment.");
     }
 
+    /// 记录从 `start` 到当前位置的注释
+    fn add_comment(&mut self, line: usize) {
+        self.comments.push(Comment {
+            line,
+            text: self.lexeme(false),
+        });
+    }
+
     /// 消耗一个字符并返回该字符
     fn advance(&mut self) -> char {
         let c = self.source[self.current];
@@ -365,6 +523,11 @@ impl Scanner {
     }
 }
 
+/// 字符串片段中的 `\${` 还原为字面的 `${`
+fn unescape_interpolation(chars: &[char]) -> String {
+    chars.iter().collect::<String>().replace("\\${", "${")
+}
+
 /// 将源码转换为标记序列
 pub fn tokenize(source: crate::reader::Source) -> Result<Tokens, ScannerError> {
     let mut scanner = Scanner::new(&source.contents);
@@ -1,4 +1,4 @@
-use crate::tokenizer::{Literal, Token, TokenType, scanner::Scanner};
+use crate::tokenizer::{Comment, Literal, Token, TokenType, scanner::Scanner};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -52,3 +52,45 @@ fn mixed_comments_and_code() {
         ]
     )
 }
+
+#[test]
+fn shebang_line() {
+    let mut scanner = Scanner::new("#!/usr/bin/env rox\nprint 1;");
+    let tokens = scanner.scan_tokens();
+    assert_eq!(
+        tokens.unwrap().tokens,
+        vec![
+            Token::new(TokenType::Print, "print", 2, Literal::None),
+            Token::new(TokenType::Number, "1", 2, Literal::Number(1.0)),
+            Token::new(TokenType::Semicolon, ";", 2, Literal::None),
+            Token::new(TokenType::Eof, "", 2, Literal::None),
+        ]
+    );
+
+    // 只有首行的 `#` 是注释
+    let mut scanner = Scanner::new("print 1;\n# not a comment");
+    assert!(scanner.scan_tokens().is_err());
+}
+
+#[test]
+fn comments_are_kept() {
+    let source = "#!/usr/bin/env rox\nvar x = 1; // one\n/* two\nlines */ print x;";
+    let comments = Scanner::new(source).scan_tokens().unwrap().comments;
+    assert_eq!(
+        comments,
+        vec![
+            Comment {
+                line: 1,
+                text: "#!/usr/bin/env rox".into()
+            },
+            Comment {
+                line: 2,
+                text: "// one".into()
+            },
+            Comment {
+                line: 3,
+                text: "/* two\nlines */".into()
+            },
+        ]
+    )
+}
@@ -1,4 +1,4 @@
-use crate::tokenizer::{Literal, Token, TokenType, scanner::Scanner};
+use crate::tokenizer::{KEYWORDS, Literal, Token, TokenType, scanner::Scanner};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -31,3 +31,11 @@ fn keywords() {
         ]
     )
 }
+
+#[test]
+fn keyword_table_matches_scanner() {
+    for keyword in KEYWORDS {
+        let tokens = Scanner::new(keyword).scan_tokens().unwrap().tokens;
+        assert_ne!(tokens[0].token_type, TokenType::Identifier, "{}", keyword);
+    }
+}
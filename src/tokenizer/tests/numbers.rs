@@ -71,3 +71,65 @@ fn zero_prefixed_number() {
         ]
     )
 }
+
+#[test]
+fn single_fraction_digit() {
+    let mut scanner = Scanner::new("1.5;");
+    let tokens = scanner.scan_tokens();
+    assert_eq!(
+        tokens.unwrap().tokens,
+        vec![
+            Token::new(TokenType::Number, "1.5", 1, Literal::Number(1.5)),
+            Token::new(TokenType::Semicolon, ";", 1, Literal::None),
+            Token::new(TokenType::Eof, "", 1, Literal::None),
+        ]
+    )
+}
+
+#[test]
+fn exponent_and_separators() {
+    let mut scanner = Scanner::new("1e9 2.5e-3 1_000_000 1_0n 3e x_1");
+    let tokens = scanner.scan_tokens();
+    assert_eq!(
+        tokens.unwrap().tokens,
+        vec![
+            Token::new(TokenType::Number, "1e9", 1, Literal::Number(1e9)),
+            Token::new(TokenType::Number, "2.5e-3", 1, Literal::Number(2.5e-3)),
+            Token::new(
+                TokenType::Number,
+                "1_000_000",
+                1,
+                Literal::Number(1_000_000.0)
+            ),
+            Token::new(TokenType::BigInt, "1_0n", 1, Literal::BigInt("10".into())),
+            Token::new(TokenType::Number, "3", 1, Literal::Number(3.0)),
+            Token::new(TokenType::Identifier, "e", 1, Literal::None),
+            Token::new(TokenType::Identifier, "x_1", 1, Literal::None),
+            Token::new(TokenType::Eof, "", 1, Literal::None),
+        ]
+    )
+}
+
+#[test]
+fn separator_needs_digits_on_both_sides() {
+    let mut scanner = Scanner::new("1._5 2e_3");
+    let tokens = scanner.scan_tokens();
+    assert_eq!(
+        tokens.unwrap().tokens,
+        vec![
+            Token::new(TokenType::Number, "1.", 1, Literal::Number(1.0)),
+            Token::new(TokenType::Identifier, "_5", 1, Literal::None),
+            Token::new(TokenType::Number, "2", 1, Literal::Number(2.0)),
+            Token::new(TokenType::Identifier, "e_3", 1, Literal::None),
+            Token::new(TokenType::Eof, "", 1, Literal::None),
+        ]
+    )
+}
+
+#[test]
+fn non_ascii_digits_are_rejected() {
+    for source in ["1²", "٣", "x = ٣;"] {
+        let mut scanner = Scanner::new(source);
+        assert!(scanner.scan_tokens().is_err(), "{}", source);
+    }
+}
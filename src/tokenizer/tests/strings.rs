@@ -61,3 +61,56 @@ fn string_with_newlines() {
         ]
     )
 }
+
+#[test]
+fn string_interpolation() {
+    let mut scanner = Scanner::new(r#""a${x}b""#);
+    let tokens = scanner.scan_tokens();
+    assert_eq!(
+        tokens.unwrap().tokens,
+        vec![
+            Token::new(
+                TokenType::Interpolation,
+                r#""a${"#,
+                1,
+                Literal::String("a".to_string())
+            ),
+            Token::new(TokenType::Identifier, "x", 1, Literal::None),
+            Token::new(
+                TokenType::String,
+                r#"}b""#,
+                1,
+                Literal::String("b".to_string())
+            ),
+            Token::new(TokenType::Eof, "", 1, Literal::None),
+        ]
+    );
+
+    let mut scanner = Scanner::new(r#""a${x"#);
+    assert!(scanner.scan_tokens().is_err());
+}
+
+#[test]
+fn escaped_interpolation() {
+    let mut scanner = Scanner::new(r#""cost: \${x} ${y}$""#);
+    let tokens = scanner.scan_tokens();
+    assert_eq!(
+        tokens.unwrap().tokens,
+        vec![
+            Token::new(
+                TokenType::Interpolation,
+                r#""cost: \${x} ${"#,
+                1,
+                Literal::String("cost: ${x} ".to_string())
+            ),
+            Token::new(TokenType::Identifier, "y", 1, Literal::None),
+            Token::new(
+                TokenType::String,
+                r#"}$""#,
+                1,
+                Literal::String("$".to_string())
+            ),
+            Token::new(TokenType::Eof, "", 1, Literal::None),
+        ]
+    );
+}
@@ -21,3 +21,19 @@ fn two_character() {
         ]
     )
 }
+
+#[test]
+fn floor_division_and_comment() {
+    let mut scanner = Scanner::new("7 ~/ 2 // 3");
+    let tokens = scanner.scan_tokens();
+    assert_eq!(
+        tokens.unwrap().tokens,
+        vec![
+            Token::new(TokenType::Number, "7", 1, Literal::Number(7.0)),
+            Token::new(TokenType::TildeSlash, "~/", 1, Literal::None),
+            Token::new(TokenType::Number, "2", 1, Literal::Number(2.0)),
+            Token::new(TokenType::Eof, "", 1, Literal::None)
+        ]
+    );
+    assert!(Scanner::new("~").scan_tokens().is_err());
+}
@@ -1,9 +1,10 @@
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 use super::Literal;
 use super::TokenType;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Token {
     pub token_type: TokenType,
     pub lexeme: String,
@@ -33,7 +34,17 @@ impl Token {
     }
 }
 
+/// 源码中的注释（含首行的 shebang），解析器不使用，供格式化工具 (`rox fmt`) 保留注释
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    /// 注释开始的行号
+    pub line: usize,
+    /// 注释原文，含 `//`、`/* */` 或 `#` 标记
+    pub text: String,
+}
+
 #[derive(Debug)]
 pub struct Tokens {
     pub tokens: Vec<Token>,
+    pub comments: Vec<Comment>,
 }
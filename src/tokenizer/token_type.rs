@@ -1,4 +1,6 @@
-#[derive(Debug, Clone, Copy, PartialEq)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum TokenType {
     // single character
     LeftParen,
@@ -10,6 +12,8 @@ pub enum TokenType {
     Colon,
     Comma,
     Dot,
+    /// 剩余参数标记 `...`
+    Ellipsis,
     Minus,
     Plus,
     PlusEqual,
@@ -18,9 +22,17 @@ pub enum TokenType {
     StarEqual,
     Semicolon,
     Slash,
+    /// 向下取整除法 `~/`（`//` 已用于行注释）
+    TildeSlash,
     Star,
     LogicalAnd, // &&
     LogicalOr,
+    /// 空值合并 `??`
+    QuestionQuestion,
+    /// 可选链 `?.`
+    QuestionDot,
+    /// 可选下标 `?[`
+    QuestionBracket,
     Ampersand, // &
     Pipe,
     Xor,
@@ -41,7 +53,11 @@ pub enum TokenType {
     // literals
     Identifier,
     String,
+    /// 字符串插值中 `${` 之前的文本，eg. `"a ${`
+    Interpolation,
     Number,
+    /// 大整数字面值，eg. `123n`
+    BigInt,
 
     // keywords
     Class,
@@ -49,6 +65,7 @@ pub enum TokenType {
     False,
     Fun,
     For,
+    In,
     If,
     Nil,
     Print,
@@ -62,8 +79,15 @@ pub enum TokenType {
     Break,
     Try,
     Catch,
+    Finally,
     Throw,
     Export,
+    Extend,
+    Static,
+    Trait,
+    Implements,
+    Yield,
+    Defer,
 
     Eof,
 }